@@ -17,6 +17,10 @@ use crate::sql::Idiom;
 // value = serialized table mutations
 type PreparedWrite = (Vec<u8>, Vec<u8>, Vec<u8>, crate::kvs::Val);
 
+/// The number of mutations which were buffered for each table at a point
+/// in a transaction, so that any later mutations can be discarded
+pub(crate) type Mark = HashMap<ChangeKey, usize>;
+
 #[non_exhaustive]
 pub struct Writer {
 	buf: Buffer,
@@ -27,7 +31,7 @@ pub struct Buffer {
 	pub b: HashMap<ChangeKey, TableMutations>,
 }
 
-#[derive(Hash, Eq, PartialEq, Debug)]
+#[derive(Clone, Hash, Eq, PartialEq, Debug)]
 #[non_exhaustive]
 pub struct ChangeKey {
 	pub ns: String,
@@ -112,6 +116,22 @@ impl Writer {
 		}
 	}
 
+	/// Mark the mutations which are currently buffered
+	pub(crate) fn mark(&self) -> Mark {
+		self.buf.b.iter().map(|(k, v)| (k.clone(), v.1.len())).collect()
+	}
+
+	/// Discard the mutations which were buffered after the mark
+	pub(crate) fn truncate(&mut self, mark: &Mark) {
+		self.buf.b.retain(|k, v| match mark.get(k) {
+			Some(len) => {
+				v.1.truncate(*len);
+				true
+			}
+			None => false,
+		});
+	}
+
 	pub(crate) fn define_table(&mut self, ns: &str, db: &str, tb: &str, dt: &DefineTableStatement) {
 		self.buf.push(
 			ns.to_string(),
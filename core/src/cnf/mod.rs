@@ -35,10 +35,39 @@ pub const SERVER_NAME: &str = "SurrealDB";
 /// Datastore processor batch size for scan operations
 pub const PROCESSOR_BATCH_SIZE: u32 = 50;
 
+/// Specifies how many selected rows are buffered before being inserted by an INSERT ... SELECT
+pub const INSERT_SELECT_BATCH_SIZE: usize = 50;
+
+/// Specifies how many index entries are counted when estimating how many records a condition matches
+pub static MAX_ESTIMATE_COUNT: Lazy<u32> =
+	lazy_env_parse!("SURREAL_MAX_ESTIMATE_COUNT", u32, 10_000);
+
+/// Specifies how many records an index must be estimated to match before the table is scanned instead,
+/// when most of the records in the table match.
+pub static INDEX_ESTIMATE_THRESHOLD: Lazy<usize> =
+	lazy_env_parse!("SURREAL_INDEX_ESTIMATE_THRESHOLD", usize, 1_000);
+
 /// Forward all signup/signin query errors to a client trying authenticate to a scope. Do not use in production.
 pub static INSECURE_FORWARD_SCOPE_ERRORS: Lazy<bool> =
 	lazy_env_parse!("SURREAL_INSECURE_FORWARD_SCOPE_ERRORS", bool, false);
 
+/// Specifies the maximum edit distance at which two strings are considered a fuzzy match
+pub static FUZZY_MATCH_MAX_DISTANCE: Lazy<usize> =
+	lazy_env_parse!("SURREAL_FUZZY_MATCH_MAX_DISTANCE", usize, 2);
+
+/// Specifies how many characters of each string are compared when calculating an edit distance
+pub static MAX_STRING_DISTANCE_LENGTH: Lazy<usize> =
+	lazy_env_parse!("SURREAL_MAX_STRING_DISTANCE_LENGTH", usize, 1_000);
+
+/// Specifies how many seconds after a scope token has expired it can still be refreshed
+pub static TOKEN_REFRESH_GRACE_PERIOD: Lazy<i64> =
+	lazy_env_parse!("SURREAL_TOKEN_REFRESH_GRACE_PERIOD", i64, 300);
+
+/// Specifies how many bytes a compressed import can be decompressed to, before it is rejected
+#[cfg(not(target_arch = "wasm32"))]
+pub static MAX_DECOMPRESSED_IMPORT_SIZE: Lazy<usize> =
+	lazy_env_parse!("SURREAL_MAX_DECOMPRESSED_IMPORT_SIZE", usize, 4 * 1024 * 1024 * 1024);
+
 #[cfg(any(
 	feature = "kv-surrealkv",
 	feature = "kv-file",
@@ -1,3 +1,4 @@
+use std::fmt;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 
@@ -5,7 +6,13 @@ use std::sync::Arc;
 #[non_exhaustive]
 pub struct Canceller {
 	/// A reference to the canceled value of a context.
-	cancelled: Arc<AtomicBool>,
+	pub(super) cancelled: Arc<AtomicBool>,
+}
+
+impl fmt::Debug for Canceller {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.debug_struct("Canceller").field("cancelled", &self.is_cancelled()).finish()
+	}
 }
 
 impl Canceller {
@@ -19,4 +26,8 @@ impl Canceller {
 	pub fn cancel(&self) {
 		self.cancelled.store(true, Ordering::Relaxed);
 	}
+	/// Check if the context has been cancelled.
+	pub fn is_cancelled(&self) -> bool {
+		self.cancelled.load(Ordering::Relaxed)
+	}
 }
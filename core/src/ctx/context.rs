@@ -3,7 +3,7 @@ use crate::ctx::reason::Reason;
 use crate::dbs::capabilities::FuncTarget;
 #[cfg(feature = "http")]
 use crate::dbs::capabilities::NetTarget;
-use crate::dbs::{Capabilities, Notification};
+use crate::dbs::{Capabilities, MetricsCollector, Notification, RunningQueries};
 use crate::err::Error;
 use crate::idx::planner::executor::QueryExecutor;
 use crate::idx::planner::{IterationStage, QueryPlanner};
@@ -60,8 +60,14 @@ pub struct Context<'a> {
 	cancelled: Arc<AtomicBool>,
 	// A collection of read only values stored in this context.
 	values: HashMap<Cow<'static, str>, Cow<'a, Value>>,
+	// A collection of common table expression results stored in this context.
+	ctes: HashMap<Cow<'static, str>, Cow<'a, Value>>,
 	// Stores the notification channel if available
 	notifications: Option<Sender<Notification>>,
+	// Stores the iterator metrics collector if available
+	metrics: Option<MetricsCollector>,
+	// Stores the running queries of the session if available
+	running_queries: Option<RunningQueries>,
 	// An optional query planner
 	query_planner: Option<&'a QueryPlanner<'a>>,
 	// An optional query executor
@@ -107,6 +113,7 @@ impl<'a> Debug for Context<'a> {
 			.field("deadline", &self.deadline)
 			.field("cancelled", &self.cancelled)
 			.field("values", &self.values)
+			.field("ctes", &self.ctes)
 			.finish()
 	}
 }
@@ -137,10 +144,13 @@ impl<'a> Context<'a> {
 	) -> Result<Context<'a>, Error> {
 		let mut ctx = Self {
 			values: HashMap::default(),
+			ctes: HashMap::default(),
 			parent: None,
 			deadline: None,
 			cancelled: Arc::new(AtomicBool::new(false)),
 			notifications: None,
+			metrics: None,
+			running_queries: None,
 			query_planner: None,
 			query_executor: None,
 			iteration_stage: None,
@@ -174,10 +184,13 @@ impl<'a> Context<'a> {
 	pub fn background() -> Self {
 		Self {
 			values: HashMap::default(),
+			ctes: HashMap::default(),
 			parent: None,
 			deadline: None,
 			cancelled: Arc::new(AtomicBool::new(false)),
 			notifications: None,
+			metrics: None,
+			running_queries: None,
 			query_planner: None,
 			query_executor: None,
 			iteration_stage: None,
@@ -208,10 +221,13 @@ impl<'a> Context<'a> {
 	pub fn new(parent: &'a Context) -> Self {
 		Context {
 			values: HashMap::default(),
+			ctes: HashMap::default(),
 			parent: Some(parent),
 			deadline: parent.deadline,
 			cancelled: Arc::new(AtomicBool::new(false)),
 			notifications: parent.notifications.clone(),
+			metrics: parent.metrics.clone(),
+			running_queries: parent.running_queries.clone(),
 			query_planner: parent.query_planner,
 			query_executor: parent.query_executor.clone(),
 			iteration_stage: parent.iteration_stage.clone(),
@@ -248,6 +264,16 @@ impl<'a> Context<'a> {
 		self.values.insert(key.into(), value.into());
 	}
 
+	/// Add the result of a common table expression to the context. It
+	/// overwrites any previously set result with the same name.
+	pub(crate) fn add_cte<K, V>(&mut self, name: K, value: V)
+	where
+		K: Into<Cow<'static, str>>,
+		V: Into<Cow<'a, Value>>,
+	{
+		self.ctes.insert(name.into(), value.into());
+	}
+
 	/// Add cancellation to the context. The value that is returned will cancel
 	/// the context and it's children once called.
 	pub fn add_cancel(&mut self) -> Canceller {
@@ -255,6 +281,12 @@ impl<'a> Context<'a> {
 		Canceller::new(cancelled)
 	}
 
+	/// Link the cancellation of the context to an existing canceller, so
+	/// that the context and it's children are cancelled along with it.
+	pub(crate) fn set_canceller(&mut self, canceller: &Canceller) {
+		self.cancelled = canceller.cancelled.clone();
+	}
+
 	/// Add a deadline to the context. If the current deadline is sooner than
 	/// the provided deadline, this method does nothing.
 	pub fn add_deadline(&mut self, deadline: Instant) {
@@ -283,6 +315,18 @@ impl<'a> Context<'a> {
 		self.notifications = chn.cloned()
 	}
 
+	/// Add an iterator metrics collector to the context, so that any
+	/// iterators can record their metrics once finished.
+	pub(crate) fn add_metrics(&mut self, metrics: MetricsCollector) {
+		self.metrics = Some(metrics)
+	}
+
+	/// Add the running queries of the session to the context, so
+	/// that statements can be registered and killed while they run.
+	pub(crate) fn set_running_queries(&mut self, queries: RunningQueries) {
+		self.running_queries = Some(queries)
+	}
+
 	pub(crate) fn set_query_planner(&mut self, qp: &'a QueryPlanner) {
 		self.query_planner = Some(qp);
 	}
@@ -305,6 +349,14 @@ impl<'a> Context<'a> {
 		self.notifications.clone()
 	}
 
+	pub(crate) fn get_metrics(&self) -> Option<&MetricsCollector> {
+		self.metrics.as_ref()
+	}
+
+	pub(crate) fn get_running_queries(&self) -> Option<&RunningQueries> {
+		self.running_queries.as_ref()
+	}
+
 	pub(crate) fn get_query_planner(&self) -> Option<&QueryPlanner> {
 		self.query_planner
 	}
@@ -350,6 +402,11 @@ impl<'a> Context<'a> {
 		matches!(self.done(), Some(Reason::Timedout))
 	}
 
+	/// Check if the context is not ok to continue, because it was cancelled.
+	pub fn is_cancelled(&self) -> bool {
+		matches!(self.done(), Some(Reason::Canceled))
+	}
+
 	#[cfg(any(
 		feature = "kv-surrealkv",
 		feature = "kv-file",
@@ -391,6 +448,21 @@ impl<'a> Context<'a> {
 		}
 	}
 
+	/// Get the result of a common table expression from the context. If no
+	/// result is stored under the provided name, then this will return None.
+	pub(crate) fn cte(&self, name: &str) -> Option<&Value> {
+		match self.ctes.get(name) {
+			Some(v) => match v {
+				Cow::Borrowed(v) => Some(*v),
+				Cow::Owned(v) => Some(v),
+			},
+			None => match self.parent {
+				Some(p) => p.cte(name),
+				_ => None,
+			},
+		}
+	}
+
 	/// Get a 'static view into the cancellation status.
 	#[cfg(feature = "scripting")]
 	pub fn cancellation(&self) -> crate::ctx::cancellation::Cancellation {
@@ -0,0 +1,107 @@
+use crate::cnf::TOKEN_REFRESH_GRACE_PERIOD;
+use crate::dbs::{Session, Transaction};
+use crate::err::Error;
+use crate::iam::token::{Claims, HEADER};
+use crate::kvs::Key;
+use crate::syn;
+use chrono::{Duration, Utc};
+use jsonwebtoken::{encode, EncodingKey};
+use uuid::Uuid;
+
+/// The maximum number of stale revoked tokens which are cleared on each refresh
+const CLEANUP_BATCH_SIZE: u32 = 1000;
+
+/// Issue a new token, with a fresh expiry, for a session which was
+/// authenticated with a scope signin or signup token. The token claims
+/// of the session must already have been verified. The existing token
+/// is revoked, so that it can not be used or refreshed again.
+pub(crate) async fn refresh_token(session: &Session, txn: &Transaction) -> Result<String, Error> {
+	// Get the claims of the token which authenticated the session
+	let claims = match &session.tk {
+		Some(v) => serde_json::from_value::<Claims>(v.clone().into_json())
+			.map_err(|_| Error::InvalidAuth)?,
+		None => return Err(Error::InvalidAuth),
+	};
+	// Only scope tokens issued by signin or signup can be refreshed
+	let (ns, db, sc, id) = match &claims {
+		Claims {
+			ns: Some(ns),
+			db: Some(db),
+			sc: Some(sc),
+			id: Some(id),
+			tk: None,
+			..
+		} => (ns.as_str(), db.as_str(), sc.as_str(), id.as_str()),
+		_ => return Err(Error::InvalidAuth),
+	};
+	// Tokens without an expiry can never be refreshed
+	let exp = claims.exp.ok_or_else(|| Error::MissingTokenClaim("exp".to_string()))?;
+	let iat = claims.iat.ok_or_else(|| Error::MissingTokenClaim("iat".to_string()))?;
+	let jti = claims.jti.as_ref().ok_or_else(|| Error::MissingTokenClaim("jti".to_string()))?;
+	// Claim transaction
+	let mut run = txn.lock().await;
+	// Check that the scope still exists
+	let sv = match run.get_sc(ns, db, sc).await {
+		Ok(sv) => sv,
+		Err(Error::ScNotFound {
+			..
+		}) => return Err(Error::NoScopeFound),
+		Err(e) => return Err(e),
+	};
+	// Check that the token has not expired beyond the grace period
+	let now = Utc::now().timestamp();
+	if exp.saturating_add(*TOKEN_REFRESH_GRACE_PERIOD) < now {
+		trace!("The authentication token expired too long ago to be refreshed");
+		return Err(Error::ExpiredToken);
+	}
+	// Check that the token lasts no longer than the scope SESSION clause
+	let duration = match sv.session {
+		Some(v) => Duration::from_std(v.0).map_err(|_| Error::InvalidSessionDuration)?,
+		None => Duration::hours(1),
+	};
+	if exp.saturating_sub(iat) > duration.num_seconds() {
+		trace!("The authentication token lasts longer than the scope session");
+		return Err(Error::InvalidAuth);
+	}
+	// Check that the token has not already been revoked
+	if run.exi(Key::from(crate::key::scope::rv::new(ns, db, sc, jti))).await? {
+		trace!("The authentication token has already been revoked");
+		return Err(Error::RevokedToken);
+	}
+	// Check that the scope record still exists
+	let rid = syn::thing(id)?;
+	if !run.exi(Key::from(crate::key::thing::new(ns, db, &rid.tb, &rid.id))).await? {
+		return Err(Error::NoRecordFound);
+	}
+	// Create the new expiry from the scope session duration
+	let Some(new_exp) = Utc::now().checked_add_signed(duration) else {
+		return Err(Error::InvalidSessionExpiration);
+	};
+	// Create the new authentication claim with a new identifier
+	let val = Claims {
+		iat: Some(now),
+		nbf: Some(now),
+		exp: Some(new_exp.timestamp()),
+		jti: Some(Uuid::new_v4().to_string()),
+		..claims.clone()
+	};
+	let enc = encode(&HEADER, &val, &EncodingKey::from_secret(sv.code.as_ref()))
+		.map_err(|_| Error::TokenMakingFailed)?;
+	// Revoke the old token until it can no longer be refreshed
+	let until = exp.saturating_add(*TOKEN_REFRESH_GRACE_PERIOD);
+	let key = crate::key::scope::rv::new(ns, db, sc, jti);
+	run.set(key, until.to_be_bytes().to_vec()).await?;
+	// Clear revoked tokens which can no longer be refreshed or used
+	let beg = crate::key::scope::rv::prefix(ns, db, sc);
+	let end = crate::key::scope::rv::suffix(ns, db, sc);
+	for (k, v) in run.getr(beg..end, CLEANUP_BATCH_SIZE).await? {
+		if let Ok(v) = <[u8; 8]>::try_from(v.as_slice()) {
+			if i64::from_be_bytes(v) < now {
+				run.del(k).await?;
+			}
+		}
+	}
+	// Log the success
+	debug!("Refreshed the authentication token for scope `{}`", sc);
+	Ok(enc)
+}
@@ -1,6 +1,8 @@
+use std::collections::HashMap;
+use std::mem;
 use std::sync::Arc;
 
-use channel::Receiver;
+use channel::{Receiver, Sender};
 use futures::lock::Mutex;
 use futures::StreamExt;
 use reblessive::TreeStack;
@@ -14,6 +16,7 @@ use wasm_bindgen_futures::spawn_local as spawn;
 use crate::ctx::Context;
 use crate::dbs::response::Response;
 use crate::dbs::Force;
+use crate::dbs::MetricsCollector;
 use crate::dbs::Notification;
 use crate::dbs::Options;
 use crate::dbs::QueryType;
@@ -35,6 +38,8 @@ pub(crate) struct Executor<'a> {
 	err: bool,
 	kvs: &'a Datastore,
 	txn: Option<Transaction>,
+	vars: HashMap<String, Value>,
+	pending: HashMap<String, Value>,
 }
 
 impl<'a> Executor<'a> {
@@ -43,9 +48,25 @@ impl<'a> Executor<'a> {
 			kvs,
 			txn: None,
 			err: false,
+			vars: HashMap::new(),
+			pending: HashMap::new(),
 		}
 	}
 
+	/// Take the variables which were set by LET statements
+	pub fn take_variables(&mut self) -> HashMap<String, Value> {
+		mem::take(&mut self.vars)
+	}
+
+	/// Remember a variable which was set by a LET statement, once the
+	/// transaction which it was set in has been committed successfully
+	fn set_variable(&mut self, local: bool, name: String, val: Value) {
+		match local {
+			true => self.vars.insert(name, val),
+			false => self.pending.insert(name, val),
+		};
+	}
+
 	fn txn(&self) -> Transaction {
 		self.txn.clone().expect("unreachable: txn was None after successful begin")
 	}
@@ -55,7 +76,12 @@ impl<'a> Executor<'a> {
 	/// - false if
 	///   - couldn't create transaction (sets err flag)
 	///   - a transaction has already begun
-	async fn begin(&mut self, write: TransactionType) -> bool {
+	async fn begin(&mut self, opt: &Options, write: TransactionType) -> bool {
+		// Read-only sessions never open writeable transactions
+		let write = match opt.read_only {
+			true => Read,
+			false => write,
+		};
 		match self.txn.as_ref() {
 			Some(_) => false,
 			None => match self.kvs.transaction(write, Optimistic).await {
@@ -86,6 +112,12 @@ impl<'a> Executor<'a> {
 					// Cancel and ignore any error because the error flag was
 					// already set
 					let _ = txn.cancel().await;
+				} else if !txn.writeable() {
+					// A read-only transaction has no changes to commit
+					if let Err(e) = txn.cancel().await {
+						self.err = true;
+						return Err(e);
+					}
 				} else {
 					let r = match txn.complete_changes(false).await {
 						Ok(_) => {
@@ -135,6 +167,7 @@ impl<'a> Executor<'a> {
 			time: v.time,
 			result: Err(Error::QueryCancelled),
 			query_type: QueryType::Other,
+			metrics: v.metrics,
 		}
 	}
 
@@ -152,6 +185,7 @@ impl<'a> Executor<'a> {
 					Err(e) => Err(e),
 				},
 				query_type: QueryType::Other,
+				metrics: v.metrics,
 			},
 			_ => v,
 		}
@@ -166,6 +200,20 @@ impl<'a> Executor<'a> {
 		});
 	}
 
+	/// Discard the notifications which were buffered after the first `len`
+	/// notifications, by draining the buffer channel and resending them
+	fn truncate(&self, snd: &Sender<Notification>, rcv: &Receiver<Notification>, len: usize) {
+		let mut buf = Vec::with_capacity(len);
+		while let Ok(notification) = rcv.try_recv() {
+			buf.push(notification);
+		}
+		buf.truncate(len);
+		for notification in buf {
+			// The buffer channel is unbounded, so this can not fail
+			let _ = snd.try_send(notification);
+		}
+	}
+
 	/// Flush notifications from a buffer channel (live queries) to the committed notification channel.
 	/// This is because we don't want to broadcast notifications to the user for failed transactions.
 	/// TODO we can delete this once we migrate to lq v2
@@ -220,7 +268,7 @@ impl<'a> Executor<'a> {
 		// Create a notification channel
 		let (send, recv) = channel::unbounded();
 		// Set the notification channel
-		let mut opt = opt.new_with_sender(send);
+		let mut opt = opt.new_with_sender(send.clone());
 		// Initialise buffer of responses
 		let mut buf: Vec<Response> = vec![];
 		// Initialise array of responses
@@ -242,6 +290,8 @@ impl<'a> Executor<'a> {
 			let is_stm_kill = matches!(stm, Statement::Kill(_));
 			// Check if this is a RETURN statement
 			let is_stm_output = matches!(stm, Statement::Output(_));
+			// The iterator metrics of the statement, if requested
+			let mut metrics = None;
 			// Process a single statement
 			let res = match stm {
 				// Specify runtime options
@@ -265,12 +315,13 @@ impl<'a> Executor<'a> {
 				}
 				// Begin a new transaction
 				Statement::Begin(_) => {
-					self.begin(Write).await;
+					self.begin(&opt, Write).await;
 					continue;
 				}
 				// Cancel a running transaction
 				Statement::Cancel(_) => {
 					self.cancel(true).await;
+					self.pending.clear();
 					self.clear(&ctx, recv.clone()).await;
 					buf = buf.into_iter().map(|v| self.buf_cancel(v)).collect();
 					out.append(&mut buf);
@@ -281,6 +332,11 @@ impl<'a> Executor<'a> {
 				// Commit a running transaction
 				Statement::Commit(_) => {
 					let commit_error = self.commit(true).await.err();
+					// Remember the variables set within the transaction
+					match commit_error.is_none() && !self.err {
+						true => self.vars.extend(self.pending.drain()),
+						false => self.pending.clear(),
+					}
 					buf = buf.into_iter().map(|v| self.buf_commit(v, &commit_error)).collect();
 					self.flush(&ctx, recv.clone()).await;
 					if let Some(lqs) = self.consume_committed_live_query_registrations().await {
@@ -291,6 +347,36 @@ impl<'a> Executor<'a> {
 					self.txn = None;
 					continue;
 				}
+				// Create a savepoint in a running transaction
+				Statement::Savepoint(stm) => match (self.txn.as_ref(), self.err) {
+					(None, _) => Err(Error::SavepointNoTransaction),
+					(Some(_), true) => Err(Error::QueryNotExecuted),
+					(Some(txn), false) => {
+						txn.lock().await.savepoint(&stm.name, recv.len()).await.map(|_| Value::None)
+					}
+				},
+				// Roll back a running transaction to a savepoint
+				Statement::Rollback(stm) => match self.txn.as_ref() {
+					None => Err(Error::SavepointNoTransaction),
+					Some(txn) => match txn.lock().await.rollback_to(&stm.name).await {
+						Ok(len) => {
+							// Discard notifications sent after the savepoint
+							self.truncate(&send, &recv, len);
+							// Statements after the savepoint have been undone
+							self.err = false;
+							Ok(Value::None)
+						}
+						Err(e) => Err(e),
+					},
+				},
+				// Release a savepoint in a running transaction
+				Statement::Release(stm) => match (self.txn.as_ref(), self.err) {
+					(None, _) => Err(Error::SavepointNoTransaction),
+					(Some(_), true) => Err(Error::QueryNotExecuted),
+					(Some(txn), false) => {
+						txn.lock().await.release(&stm.name).await.map(|_| Value::None)
+					}
+				},
 				// Switch to a different NS or DB
 				Statement::Use(stm) => {
 					if let Some(ref ns) = stm.ns {
@@ -304,7 +390,7 @@ impl<'a> Executor<'a> {
 				// Process param definition statements
 				Statement::Set(stm) => {
 					// Create a transaction
-					let loc = self.begin(stm.writeable().into()).await;
+					let loc = self.begin(&opt, stm.writeable().into()).await;
 					// Check the transaction
 					match self.err {
 						// We failed to create a transaction
@@ -321,8 +407,9 @@ impl<'a> Executor<'a> {
 								Ok(val) => {
 									// Check if writeable
 									let writeable = stm.writeable();
+									let name = stm.name.clone();
 									// Set the parameter
-									ctx.add_value(stm.name, val);
+									ctx.add_value(stm.name, val.clone());
 									// Finalise transaction, returning nothing unless it couldn't commit
 									if writeable {
 										match self.commit(loc).await {
@@ -334,6 +421,8 @@ impl<'a> Executor<'a> {
 												})
 											}
 											Ok(_) => {
+												// Remember the parameter for the session
+												self.set_variable(loc, name, val);
 												// Flush live query notifications
 												self.flush(&ctx, recv.clone()).await;
 												if let Some(lqs) = self
@@ -348,6 +437,8 @@ impl<'a> Executor<'a> {
 									} else {
 										self.cancel(loc).await;
 										self.clear(&ctx, recv.clone()).await;
+										// Remember the parameter for the session
+										self.set_variable(loc, name, val);
 										Ok(Value::None)
 									}
 								}
@@ -367,78 +458,131 @@ impl<'a> Executor<'a> {
 					true => Err(Error::QueryNotExecuted),
 					// Compute the statement normally
 					false => {
-						// Create a transaction
-						let loc = self.begin(stm.writeable().into()).await;
-						// Check the transaction
-						match self.err {
-							// We failed to create a transaction
-							true => Err(Error::TxFailure),
-							// The transaction began successfully
-							false => {
-								let mut ctx = Context::new(&ctx);
-								// Process the statement
-								let res = match stm.timeout() {
-									// There is a timeout clause
-									Some(timeout) => {
-										// Set statement timeout or propagate the error
-										if let Err(err) = ctx.add_timeout(timeout) {
-											Err(err)
-										} else {
+						// Register the statement, so that it can be killed while it runs
+						let mut ctx = Context::new(&ctx);
+						let canceller = ctx.add_cancel();
+						let query = ctx
+							.get_running_queries()
+							.map(|q| q.register(opt.auth.clone(), canceller));
+						if let Some(query) = &query {
+							trace!("Running the statement with query id {}", query.id());
+						}
+						// Count the attempts at running this statement
+						let mut attempts = 0;
+						loop {
+							attempts += 1;
+							// The delay before retrying the statement
+							let mut retry = None;
+							// Create a transaction
+							let loc = self.begin(&opt, stm.writeable().into()).await;
+							// Check the transaction
+							let res = match self.err {
+								// We failed to create a transaction
+								true => Err(Error::TxFailure),
+								// The transaction began successfully
+								false => {
+									let mut ctx = Context::new(&ctx);
+									// Collect the iterator metrics if requested
+									let collector =
+										opt.collect_metrics.then(MetricsCollector::default);
+									if let Some(collector) = &collector {
+										ctx.add_metrics(collector.clone());
+									}
+									// Process the statement
+									let res = match stm.timeout().or(opt.timeout) {
+										// There is a timeout clause, or a default timeout
+										Some(timeout) => {
+											// Set statement timeout or propagate the error
+											if let Err(err) = ctx.add_timeout(timeout) {
+												Err(err)
+											} else {
+												let txn = self.txn();
+												// Process the statement
+												let res = stack
+													.enter(|stk| {
+														stm.compute(stk, &ctx, &opt, &txn, None)
+													})
+													.finish()
+													.await;
+												// Catch statement timeout
+												match ctx.is_timedout() {
+													true => Err(Error::QueryTimedout),
+													false => res,
+												}
+											}
+										}
+										// There is no timeout clause
+										None => {
 											let txn = self.txn();
-											// Process the statement
-											let res = stack
+											stack
 												.enter(|stk| {
 													stm.compute(stk, &ctx, &opt, &txn, None)
 												})
 												.finish()
-												.await;
-											// Catch statement timeout
-											match ctx.is_timedout() {
-												true => Err(Error::QueryTimedout),
-												false => res,
-											}
+												.await
 										}
+									};
+									// Catch global timeout
+									let res = match ctx.is_timedout() {
+										true => Err(Error::QueryTimedout),
+										false => res,
+									};
+									// Catch session cancellation, or a KILL statement
+									let res = match ctx.is_cancelled() {
+										true => Err(Error::QueryCancelled),
+										false => res,
+									};
+									// Output the collected iterator metrics
+									metrics = collector.map(|c| c.get());
+									// Check if the statement should be retried
+									if let Err(e) = &res {
+										retry =
+											opt.retry_policy.and_then(|p| p.backoff(attempts, e));
 									}
-									// There is no timeout clause
-									None => {
-										let txn = self.txn();
-										stack
-											.enter(|stk| stm.compute(stk, &ctx, &opt, &txn, None))
-											.finish()
-											.await
-									}
-								};
-								// Catch global timeout
-								let res = match ctx.is_timedout() {
-									true => Err(Error::QueryTimedout),
-									false => res,
-								};
-								// Finalise transaction and return the result.
-								if res.is_ok() && stm.writeable() {
-									if let Err(e) = self.commit(loc).await {
+									// Finalise transaction and return the result.
+									if res.is_ok() && stm.writeable() {
+										if let Err(e) = self.commit(loc).await {
+											// Check if the statement should be retried
+											retry = opt
+												.retry_policy
+												.and_then(|p| p.backoff(attempts, &e));
+											// Clear live query notification details
+											self.clear(&ctx, recv.clone()).await;
+											// The commit failed
+											Err(Error::QueryNotExecutedDetail {
+												message: e.to_string(),
+											})
+										} else {
+											// Flush the live query change notifications
+											self.flush(&ctx, recv.clone()).await;
+											if let Some(lqs) = self
+												.consume_committed_live_query_registrations()
+												.await
+											{
+												live_queries.extend(lqs);
+											}
+											res
+										}
+									} else {
+										self.cancel(loc).await;
 										// Clear live query notification details
 										self.clear(&ctx, recv.clone()).await;
-										// The commit failed
-										Err(Error::QueryNotExecutedDetail {
-											message: e.to_string(),
-										})
-									} else {
-										// Flush the live query change notifications
-										self.flush(&ctx, recv.clone()).await;
-										if let Some(lqs) =
-											self.consume_committed_live_query_registrations().await
-										{
-											live_queries.extend(lqs);
-										}
+										// Return an error
 										res
 									}
-								} else {
-									self.cancel(loc).await;
-									// Clear live query notification details
-									self.clear(&ctx, recv.clone()).await;
-									// Return an error
-									res
 								}
+							};
+							// Only retry statements which run in their own transaction
+							match (loc, retry) {
+								(true, Some(delay)) => {
+									// Reset the error marked by a failed commit
+									self.err = false;
+									#[cfg(target_arch = "wasm32")]
+									wasmtimer::tokio::sleep(delay).await;
+									#[cfg(not(target_arch = "wasm32"))]
+									tokio::time::sleep(delay).await;
+								}
+								_ => break res,
 							}
 						}
 					}
@@ -469,6 +613,7 @@ impl<'a> Executor<'a> {
 					}
 					_ => QueryType::Other,
 				},
+				metrics,
 			};
 			// Output the response
 			if self.txn.is_some() {
@@ -487,7 +632,15 @@ impl<'a> Executor<'a> {
 
 #[cfg(test)]
 mod tests {
-	use crate::{dbs::Session, iam::Role, kvs::Datastore};
+	use crate::{
+		dbs::{RetryPolicy, Session},
+		err::Error,
+		iam::Role,
+		kvs::Datastore,
+		sql::Value,
+		syn::Parse,
+	};
+	use std::time::{Duration, Instant};
 
 	#[tokio::test]
 	async fn check_execute_option_permissions() {
@@ -597,4 +750,44 @@ mod tests {
 			);
 		}
 	}
+
+	#[tokio::test]
+	async fn check_execute_retry() {
+		let policy = RetryPolicy::new(3, Duration::from_millis(40));
+		let ds = Datastore::new("memory").await.unwrap().with_retry_policy(Some(policy));
+		let ses = Session::owner().with_ns("NS").with_db("DB");
+		// A statement is retried until its commit succeeds
+		ds.set_conflicts(2);
+		let now = Instant::now();
+		let res = ds.execute("CREATE person:one", &ses, None).await.unwrap();
+		assert!(res[0].result.is_ok(), "Expected the retried statement to succeed: {:?}", res);
+		assert_eq!(ds.conflicts(), 0);
+		// Each retry waits for at least half of its doubling delay
+		assert!(now.elapsed() >= Duration::from_millis(20 + 40));
+		// A statement fails once all of its attempts conflict
+		ds.set_conflicts(5);
+		let res = ds.execute("CREATE person:two", &ses, None).await.unwrap();
+		assert!(
+			matches!(res[0].result, Err(Error::QueryNotExecutedDetail { .. })),
+			"Expected the statement to fail after every attempt: {:?}",
+			res
+		);
+		assert_eq!(ds.conflicts(), 2);
+		// Statements within an explicit transaction are not retried
+		ds.set_conflicts(1);
+		let res = ds.execute("BEGIN; CREATE person:three; COMMIT;", &ses, None).await.unwrap();
+		assert!(res[0].result.is_err(), "Expected the transaction to fail: {:?}", res);
+		assert_eq!(ds.conflicts(), 0);
+		// Only the statement which was retried was committed
+		ds.set_conflicts(0);
+		let res = ds.execute("SELECT VALUE id FROM person", &ses, None).await.unwrap();
+		let val = Value::parse("[person:one]");
+		assert_eq!(res[0].result.as_ref().unwrap(), &val);
+		// Without a retry policy the conflict is returned straight away
+		let ds = Datastore::new("memory").await.unwrap();
+		ds.set_conflicts(2);
+		let res = ds.execute("CREATE person:one", &ses, None).await.unwrap();
+		assert!(res[0].result.is_err(), "Expected the statement to fail: {:?}", res);
+		assert_eq!(ds.conflicts(), 1);
+	}
 }
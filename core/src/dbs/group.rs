@@ -1,19 +1,67 @@
 use crate::ctx::Context;
 use crate::dbs::plan::Explanation;
+#[cfg(any(
+	feature = "kv-surrealkv",
+	feature = "kv-file",
+	feature = "kv-rocksdb",
+	feature = "kv-fdb",
+	feature = "kv-tikv",
+	feature = "kv-speedb"
+))]
+use crate::dbs::store::file_store::FileCollector;
 use crate::dbs::store::MemoryCollector;
 use crate::dbs::{Options, Statement, Transaction};
 use crate::err::Error;
 use crate::sql::function::OptimisedAggregate;
 use crate::sql::value::{TryAdd, TryDiv, Value};
-use crate::sql::{Array, Field, Function, Idiom};
+use crate::sql::{Array, Field, Fields, Function, Idiom};
 use reblessive::tree::Stk;
 use std::borrow::Cow;
-use std::collections::{BTreeMap, HashMap};
+use std::collections::{BTreeMap, HashMap, HashSet};
+#[cfg(any(
+	feature = "kv-surrealkv",
+	feature = "kv-file",
+	feature = "kv-rocksdb",
+	feature = "kv-fdb",
+	feature = "kv-tikv",
+	feature = "kv-speedb"
+))]
+use std::mem;
 
 pub(super) struct GroupsCollector {
 	base: Vec<Aggregator>,
 	idioms: Vec<Idiom>,
 	grp: BTreeMap<Array, Vec<Aggregator>>,
+	#[cfg(any(
+		feature = "kv-surrealkv",
+		feature = "kv-file",
+		feature = "kv-rocksdb",
+		feature = "kv-fdb",
+		feature = "kv-tikv",
+		feature = "kv-speedb"
+	))]
+	spill: Option<GroupsSpill>,
+}
+
+/// Once more records than the threshold have been grouped in memory,
+/// any further records are spilled to temporary files along with their
+/// group. When the groups are output, the spilled records are sorted by
+/// their group, and merged with the groups which are held in memory.
+#[cfg(any(
+	feature = "kv-surrealkv",
+	feature = "kv-file",
+	feature = "kv-rocksdb",
+	feature = "kv-fdb",
+	feature = "kv-tikv",
+	feature = "kv-speedb"
+))]
+struct GroupsSpill {
+	/// How many records are grouped in memory
+	threshold: usize,
+	/// How many records have been pushed
+	count: usize,
+	/// The records which have been spilled
+	file: Option<FileCollector>,
 }
 
 #[derive(Default)]
@@ -22,14 +70,42 @@ struct Aggregator {
 	first_val: Option<Value>,
 	count: Option<usize>,
 	count_function: Option<(Box<Function>, usize)>,
+	count_distinct: Option<HashSet<Value>>,
 	math_max: Option<Value>,
 	math_min: Option<Value>,
 	math_sum: Option<Value>,
 	math_mean: Option<(Value, usize)>,
+	math_variance: Option<Welford>,
 	time_max: Option<Value>,
 	time_min: Option<Value>,
 }
 
+/// Accumulates the variance of a series of numbers in a single
+/// pass, using Welford's algorithm to remain numerically stable
+#[derive(Clone, Copy, Default)]
+struct Welford {
+	count: usize,
+	mean: f64,
+	m2: f64,
+}
+
+impl Welford {
+	fn push(&mut self, val: f64) {
+		self.count += 1;
+		let delta = val - self.mean;
+		self.mean += delta / self.count as f64;
+		self.m2 += delta * (val - self.mean);
+	}
+
+	fn variance(&self, sample: bool) -> Option<f64> {
+		match self.count {
+			0 => None,
+			1 => Some(0.0),
+			len => Some(self.m2 / (len - sample as usize) as f64),
+		}
+	}
+}
+
 impl GroupsCollector {
 	pub(super) fn new(stm: &Statement<'_>) -> Self {
 		let mut idioms_agr: HashMap<Idiom, Aggregator> = HashMap::new();
@@ -55,9 +131,36 @@ impl GroupsCollector {
 			base,
 			idioms,
 			grp: Default::default(),
+			#[cfg(any(
+				feature = "kv-surrealkv",
+				feature = "kv-file",
+				feature = "kv-rocksdb",
+				feature = "kv-fdb",
+				feature = "kv-tikv",
+				feature = "kv-speedb"
+			))]
+			spill: None,
 		}
 	}
 
+	/// Spill any records which exceed the threshold to temporary files
+	#[cfg(any(
+		feature = "kv-surrealkv",
+		feature = "kv-file",
+		feature = "kv-rocksdb",
+		feature = "kv-fdb",
+		feature = "kv-tikv",
+		feature = "kv-speedb"
+	))]
+	pub(super) fn with_spill_threshold(mut self, threshold: Option<usize>) -> Self {
+		self.spill = threshold.map(|threshold| GroupsSpill {
+			threshold,
+			count: 0,
+			file: None,
+		});
+		self
+	}
+
 	pub(super) async fn push(
 		&mut self,
 		stk: &mut Stk,
@@ -77,6 +180,28 @@ impl GroupsCollector {
 				// Set the value at the path
 				arr.push(val);
 			}
+			// Spill the record once the threshold has been reached
+			#[cfg(any(
+				feature = "kv-surrealkv",
+				feature = "kv-file",
+				feature = "kv-rocksdb",
+				feature = "kv-fdb",
+				feature = "kv-tikv",
+				feature = "kv-speedb"
+			))]
+			if let Some(spill) = &mut self.spill {
+				spill.count += 1;
+				if spill.count > spill.threshold {
+					if spill.file.is_none() {
+						spill.file = Some(FileCollector::new(ctx.temporary_directory())?);
+					}
+					if let Some(file) = &mut spill.file {
+						// The position keeps the records of each group in order
+						file.push(Value::from(vec![Value::from(arr), spill.count.into(), obj]))?;
+					}
+					return Ok(());
+				}
+			}
 			// Add to grouped collection
 			let agr = self
 				.grp
@@ -116,56 +241,149 @@ impl GroupsCollector {
 		stm: &Statement<'_>,
 	) -> Result<MemoryCollector, Error> {
 		let mut results = MemoryCollector::default();
+		// GROUP ALL outputs a single row, even when no records were pushed
+		if self.grp.is_empty() && stm.group().is_some_and(|g| g.is_empty()) {
+			self.grp.insert(Array::new(), self.base.iter().map(|a| a.new_instance()).collect());
+		}
 		if let Some(fields) = stm.expr() {
+			// Merge any spilled records with the groups in memory
+			#[cfg(any(
+				feature = "kv-surrealkv",
+				feature = "kv-file",
+				feature = "kv-rocksdb",
+				feature = "kv-fdb",
+				feature = "kv-tikv",
+				feature = "kv-speedb"
+			))]
+			if let Some(file) = self.spill.as_mut().and_then(|s| s.file.take()) {
+				return self.output_spilled(stk, ctx, opt, txn, fields, file).await;
+			}
 			// Loop over each grouped collection
 			for aggregator in self.grp.values_mut() {
-				// Create a new value
-				let mut obj = Value::base();
-				// Loop over each group clause
-				for field in fields.other() {
-					// Process the field
-					if let Field::Single {
-						expr,
-						alias,
-					} = field
-					{
-						let idiom = alias
-							.as_ref()
-							.map(Cow::Borrowed)
-							.unwrap_or_else(|| Cow::Owned(expr.to_idiom()));
-						if let Some(idioms_pos) =
-							self.idioms.iter().position(|i| i.eq(idiom.as_ref()))
-						{
-							if let Some(agr) = aggregator.get_mut(idioms_pos) {
-								match expr {
-									Value::Function(f) if f.is_aggregate() => {
-										let a = f.get_optimised_aggregate();
-										let x = if matches!(a, OptimisedAggregate::None) {
-											// The aggregation is not optimised, let's compute it with the values
-											let vals = agr.take();
-											f.aggregate(vals)
-												.compute(stk, ctx, opt, txn, None)
-												.await?
-										} else {
-											// The aggregation is optimised, just get the value
-											agr.compute(a)?
-										};
-										obj.set(stk, ctx, opt, txn, idiom.as_ref(), x).await?;
-									}
-									_ => {
-										let x = agr.take().first();
-										obj.set(stk, ctx, opt, txn, idiom.as_ref(), x).await?;
-									}
-								}
+				let idioms = &self.idioms;
+				let obj = Self::output_group(stk, ctx, opt, txn, fields, idioms, aggregator);
+				results.push(obj.await?);
+			}
+		}
+		Ok(results)
+	}
+
+	/// Outputs the groups in memory along with the spilled records, which
+	/// are sorted by their group so that only one group of spilled records
+	/// is aggregated at a time.
+	#[cfg(any(
+		feature = "kv-surrealkv",
+		feature = "kv-file",
+		feature = "kv-rocksdb",
+		feature = "kv-fdb",
+		feature = "kv-tikv",
+		feature = "kv-speedb"
+	))]
+	async fn output_spilled(
+		&mut self,
+		stk: &mut Stk,
+		ctx: &Context<'_>,
+		opt: &Options,
+		txn: &Transaction,
+		fields: &Fields,
+		mut file: FileCollector,
+	) -> Result<MemoryCollector, Error> {
+		let mut results = MemoryCollector::default();
+		let idioms = &self.idioms;
+		let mut grp = mem::take(&mut self.grp).into_iter().peekable();
+		let mut current: Option<(Array, Vec<Aggregator>)> = None;
+		// Records are sorted by their group, and then by their position
+		for val in file.take_sorted_by(|a: &Value, b: &Value| a.cmp(b))? {
+			// Each spilled record holds its group, position, and value
+			let mut val = match val? {
+				Value::Array(v) => v.0.into_iter(),
+				_ => return Err(Error::Internal("Invalid spilled group record".to_string())),
+			};
+			let (key, obj) = match (val.next(), val.nth(1)) {
+				(Some(Value::Array(key)), Some(obj)) => (key, obj),
+				_ => return Err(Error::Internal("Invalid spilled group record".to_string())),
+			};
+			// Output the current group once all of its records have been pushed
+			if current.as_ref().is_some_and(|(k, _)| *k != key) {
+				if let Some((_, mut agr)) = current.take() {
+					let obj = Self::output_group(stk, ctx, opt, txn, fields, idioms, &mut agr);
+					results.push(obj.await?);
+				}
+			}
+			if current.is_none() {
+				// Output any groups in memory which are ordered before this group
+				while let Some((_, mut agr)) = grp.next_if(|(k, _)| *k < key) {
+					let obj = Self::output_group(stk, ctx, opt, txn, fields, idioms, &mut agr);
+					results.push(obj.await?);
+				}
+				// Continue the group in memory, or start a new group
+				let agr = match grp.next_if(|(k, _)| *k == key) {
+					Some((_, agr)) => agr,
+					None => self.base.iter().map(|a| a.new_instance()).collect(),
+				};
+				current = Some((key, agr));
+			}
+			if let Some((_, agr)) = &mut current {
+				Self::pushes(stk, ctx, opt, txn, agr, idioms, obj).await?;
+			}
+		}
+		// Output the last group, and any remaining groups in memory
+		for (_, mut agr) in current.into_iter().chain(grp) {
+			let obj = Self::output_group(stk, ctx, opt, txn, fields, idioms, &mut agr);
+			results.push(obj.await?);
+		}
+		Ok(results)
+	}
+
+	/// Computes the output of a single group from its aggregators
+	async fn output_group(
+		stk: &mut Stk,
+		ctx: &Context<'_>,
+		opt: &Options,
+		txn: &Transaction,
+		fields: &Fields,
+		idioms: &[Idiom],
+		aggregator: &mut [Aggregator],
+	) -> Result<Value, Error> {
+		// Create a new value
+		let mut obj = Value::base();
+		// Loop over each group clause
+		for field in fields.other() {
+			// Process the field
+			if let Field::Single {
+				expr,
+				alias,
+			} = field
+			{
+				let idiom = alias
+					.as_ref()
+					.map(Cow::Borrowed)
+					.unwrap_or_else(|| Cow::Owned(expr.to_idiom()));
+				if let Some(idioms_pos) = idioms.iter().position(|i| i.eq(idiom.as_ref())) {
+					if let Some(agr) = aggregator.get_mut(idioms_pos) {
+						match expr {
+							Value::Function(f) if f.is_aggregate() => {
+								let a = f.get_optimised_aggregate();
+								let x = if matches!(a, OptimisedAggregate::None) {
+									// The aggregation is not optimised, let's compute it with the values
+									let vals = agr.take();
+									f.aggregate(vals).compute(stk, ctx, opt, txn, None).await?
+								} else {
+									// The aggregation is optimised, just get the value
+									agr.compute(a)?
+								};
+								obj.set(stk, ctx, opt, txn, idiom.as_ref(), x).await?;
+							}
+							_ => {
+								let x = agr.take().first();
+								obj.set(stk, ctx, opt, txn, idiom.as_ref(), x).await?;
 							}
 						}
 					}
 				}
-				// Add the object to the results
-				results.push(obj);
 			}
 		}
-		Ok(results)
+		Ok(obj)
 	}
 
 	pub(super) fn explain(&self, exp: &mut Explanation) {
@@ -210,6 +428,11 @@ impl Aggregator {
 					self.count_function = Some((f.unwrap().clone(), 0));
 				}
 			}
+			OptimisedAggregate::CountDistinct => {
+				if self.count_distinct.is_none() {
+					self.count_distinct = Some(HashSet::new());
+				}
+			}
 			OptimisedAggregate::MathMax => {
 				if self.math_max.is_none() {
 					self.math_max = Some(Value::None);
@@ -230,6 +453,14 @@ impl Aggregator {
 					self.math_mean = Some((0.into(), 0));
 				}
 			}
+			OptimisedAggregate::MathStddev
+			| OptimisedAggregate::MathStddevPop
+			| OptimisedAggregate::MathVariance
+			| OptimisedAggregate::MathVariancePop => {
+				if self.math_variance.is_none() {
+					self.math_variance = Some(Welford::default());
+				}
+			}
 			OptimisedAggregate::TimeMax => {
 				if self.time_max.is_none() {
 					self.time_max = Some(Value::None);
@@ -249,10 +480,12 @@ impl Aggregator {
 			first_val: self.first_val.as_ref().map(|_| Value::None),
 			count: self.count.as_ref().map(|_| 0),
 			count_function: self.count_function.as_ref().map(|(f, _)| (f.clone(), 0)),
+			count_distinct: self.count_distinct.as_ref().map(|_| HashSet::new()),
 			math_max: self.math_max.as_ref().map(|_| Value::None),
 			math_min: self.math_min.as_ref().map(|_| Value::None),
 			math_sum: self.math_sum.as_ref().map(|_| 0.into()),
 			math_mean: self.math_mean.as_ref().map(|_| (0.into(), 0)),
+			math_variance: self.math_variance.as_ref().map(|_| Welford::default()),
 			time_max: self.time_max.as_ref().map(|_| Value::None),
 			time_min: self.time_min.as_ref().map(|_| Value::None),
 		}
@@ -274,6 +507,12 @@ impl Aggregator {
 				*c += 1;
 			}
 		}
+		// Missing values are not counted, as with count(value)
+		if let Some(ref mut s) = self.count_distinct {
+			if !val.is_none_or_null() {
+				s.insert(val.clone());
+			}
+		}
 		if val.is_number() {
 			if let Some(s) = self.math_sum.take() {
 				self.math_sum = Some(s.try_add(val.clone())?);
@@ -282,6 +521,9 @@ impl Aggregator {
 				let s = s.try_add(val.clone())?;
 				self.math_mean = Some((s, i + 1));
 			}
+			if let (Some(w), Value::Number(n)) = (self.math_variance.as_mut(), &val) {
+				w.push(n.to_float());
+			}
 			if let Some(m) = self.math_min.take() {
 				self.math_min = Some(if m.is_none() {
 					val.clone()
@@ -330,21 +572,38 @@ impl Aggregator {
 			OptimisedAggregate::CountFunction => {
 				self.count_function.take().map(|(_, v)| v.into()).unwrap_or(Value::None)
 			}
+			OptimisedAggregate::CountDistinct => {
+				self.count_distinct.take().map(|s| s.len().into()).unwrap_or(Value::None)
+			}
 			OptimisedAggregate::MathMax => self.math_max.take().unwrap_or(Value::None),
 			OptimisedAggregate::MathMin => self.math_min.take().unwrap_or(Value::None),
 			OptimisedAggregate::MathSum => self.math_sum.take().unwrap_or(Value::None),
-			OptimisedAggregate::MathMean => {
-				if let Some((v, i)) = self.math_mean.take() {
-					v.try_div(i.into())?
-				} else {
-					Value::None
-				}
+			OptimisedAggregate::MathMean => match self.math_mean.take() {
+				// An empty group has no mean
+				Some((v, i)) if i > 0 => v.try_div(i.into())?,
+				_ => Value::None,
+			},
+			OptimisedAggregate::MathStddev => {
+				self.variance(true).map(f64::sqrt).map(Value::from).unwrap_or(Value::None)
+			}
+			OptimisedAggregate::MathStddevPop => {
+				self.variance(false).map(f64::sqrt).map(Value::from).unwrap_or(Value::None)
+			}
+			OptimisedAggregate::MathVariance => {
+				self.variance(true).map(Value::from).unwrap_or(Value::None)
+			}
+			OptimisedAggregate::MathVariancePop => {
+				self.variance(false).map(Value::from).unwrap_or(Value::None)
 			}
 			OptimisedAggregate::TimeMax => self.time_max.take().unwrap_or(Value::None),
 			OptimisedAggregate::TimeMin => self.time_min.take().unwrap_or(Value::None),
 		})
 	}
 
+	fn variance(&self, sample: bool) -> Option<f64> {
+		self.math_variance.as_ref().and_then(|w| w.variance(sample))
+	}
+
 	fn take(&mut self) -> Value {
 		// We return a clone because the same value may be returned for different groups
 		if let Some(v) = self.first_val.as_ref().cloned() {
@@ -370,6 +629,9 @@ impl Aggregator {
 		if self.count_function.is_some() {
 			collections.push("count+func".into());
 		}
+		if self.count_distinct.is_some() {
+			collections.push("count+distinct".into());
+		}
 		if self.math_mean.is_some() {
 			collections.push("math::mean".into());
 		}
@@ -382,6 +644,9 @@ impl Aggregator {
 		if self.math_sum.is_some() {
 			collections.push("math::sum".into());
 		}
+		if self.math_variance.is_some() {
+			collections.push("math::variance".into());
+		}
 		if self.time_max.is_some() {
 			collections.push("time::max".into());
 		}
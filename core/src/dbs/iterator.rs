@@ -1,3 +1,4 @@
+use crate::cnf::MAX_ESTIMATE_COUNT;
 use crate::ctx::Canceller;
 use crate::ctx::Context;
 #[cfg(not(target_arch = "wasm32"))]
@@ -5,20 +6,31 @@ use crate::dbs::distinct::AsyncDistinct;
 use crate::dbs::distinct::SyncDistinct;
 use crate::dbs::plan::Plan;
 use crate::dbs::result::Results;
+use crate::dbs::store::{SampleCollector, StreamCollector};
 use crate::dbs::Statement;
-use crate::dbs::{Options, Transaction};
+use crate::dbs::{IteratorMetrics, Options, Transaction};
+use crate::doc::CursorDoc;
 use crate::doc::Document;
 use crate::err::Error;
 use crate::idx::docids::DocId;
 use crate::idx::planner::executor::IteratorRef;
 use crate::idx::planner::IterationStage;
+use crate::key;
 use crate::sql::edges::Edges;
+use crate::sql::limit::Limit;
+use crate::sql::order::Comparator;
 use crate::sql::range::Range;
+use crate::sql::statements::DefineIndexStatement;
 use crate::sql::table::Table;
 use crate::sql::thing::Thing;
 use crate::sql::value::Value;
+use crate::sql::{Array, Cond, Expression, Index, Operator};
+use channel::Sender;
+use futures::StreamExt;
 use reblessive::{tree::Stk, TreeStack};
+use std::collections::HashSet;
 use std::mem;
+use trice::Instant;
 
 #[derive(Clone)]
 pub(crate) enum Iterable {
@@ -58,14 +70,20 @@ pub(crate) struct Iterator {
 	run: Canceller,
 	// Iterator limit value
 	limit: Option<usize>,
+	// Iterator limit percentage
+	percentage: Option<usize>,
 	// Iterator start value
 	start: Option<usize>,
+	// Iterator cursor position
+	after: Option<(Value, Comparator)>,
 	// Iterator runtime error
 	error: Option<Error>,
 	// Iterator output results
 	results: Results,
 	// Iterator input values
 	entries: Vec<Iterable>,
+	// Iterator processing metrics
+	metrics: IteratorMetrics,
 }
 
 impl Clone for Iterator {
@@ -73,10 +91,13 @@ impl Clone for Iterator {
 		Self {
 			run: self.run.clone(),
 			limit: self.limit,
+			percentage: self.percentage,
 			start: self.start,
+			after: self.after.clone(),
 			error: None,
 			results: Results::default(),
 			entries: self.entries.clone(),
+			metrics: IteratorMetrics::default(),
 		}
 	}
 }
@@ -92,6 +113,109 @@ impl Iterator {
 		self.entries.push(val)
 	}
 
+	/// Only outputs the records which are ordered after the cursor position
+	pub(crate) fn start_after(&mut self, cursor: Value, cmp: Comparator) {
+		self.after = Some((cursor, cmp))
+	}
+
+	/// Estimates how many records in a table match a condition, by
+	/// counting the index entries for each indexed equality check.
+	/// Returns `None` when the indexes can not be used for an estimate.
+	pub(crate) async fn estimate(
+		opt: &Options,
+		txn: &Transaction,
+		tb: &Table,
+		cond: &Cond,
+	) -> Result<Option<usize>, Error> {
+		let ixs = txn.lock().await.all_tb_indexes(opt.ns(), opt.db(), tb).await?;
+		Self::estimate_value(opt, txn, &ixs, &cond.0).await
+	}
+
+	async fn estimate_value(
+		opt: &Options,
+		txn: &Transaction,
+		ixs: &[DefineIndexStatement],
+		val: &Value,
+	) -> Result<Option<usize>, Error> {
+		let Value::Expression(e) = val else {
+			return Ok(None);
+		};
+		let Expression::Binary {
+			l,
+			o,
+			r,
+		} = e.as_ref()
+		else {
+			return Ok(None);
+		};
+		match o {
+			Operator::And => {
+				let l = Box::pin(Self::estimate_value(opt, txn, ixs, l)).await?;
+				let r = Box::pin(Self::estimate_value(opt, txn, ixs, r)).await?;
+				// Both sides must match, so the smaller estimate is used
+				Ok(match (l, r) {
+					(Some(l), Some(r)) => Some(l.min(r)),
+					(l, r) => l.or(r),
+				})
+			}
+			Operator::Or => {
+				let l = Box::pin(Self::estimate_value(opt, txn, ixs, l)).await?;
+				let r = Box::pin(Self::estimate_value(opt, txn, ixs, r)).await?;
+				// Either side can match, so both sides must be estimated
+				Ok(match (l, r) {
+					(Some(l), Some(r)) => Some(l + r),
+					_ => None,
+				})
+			}
+			Operator::Equal => {
+				let (id, v) = match (l, r) {
+					(Value::Idiom(id), v) | (v, Value::Idiom(id)) if v.is_static() => (id, v),
+					_ => return Ok(None),
+				};
+				// Find an index on this field alone
+				let Some(ix) = ixs.iter().find(|ix| matches!(ix.cols.0.as_slice(), [c] if c == id))
+				else {
+					return Ok(None);
+				};
+				let fd = Array::from(v.clone());
+				let mut run = txn.lock().await;
+				match ix.index {
+					Index::Idx => {
+						let beg = key::index::Index::prefix_ids_beg(
+							opt.ns(),
+							opt.db(),
+							&ix.what,
+							&ix.name,
+							&fd,
+						);
+						let end = key::index::Index::prefix_ids_end(
+							opt.ns(),
+							opt.db(),
+							&ix.what,
+							&ix.name,
+							&fd,
+						);
+						Ok(Some(run.count(beg..end, *MAX_ESTIMATE_COUNT).await?))
+					}
+					Index::Uniq => {
+						let key: Vec<u8> = key::index::Index::new(
+							opt.ns(),
+							opt.db(),
+							&ix.what,
+							&ix.name,
+							&fd,
+							None,
+						)
+						.into();
+						Ok(Some(run.exi(key).await? as usize))
+					}
+					_ => Ok(None),
+				}
+			}
+			_ => Ok(None),
+		}
+	}
+
 	/// Prepares a value for processing
 	pub async fn prepare(
 		&mut self,
@@ -287,11 +411,137 @@ impl Iterator {
 		txn: &Transaction,
 		stm: &Statement<'_>,
 	) -> Result<Value, Error> {
+		// Check if the full result set is needed
+		if !Self::can_stream(stm) {
+			return Ok(self.output_buffered(stk, ctx, opt, txn, stm).await?.into());
+		}
+		// Otherwise collect the records as they are streamed
+		let (chn, rcv) = channel::unbounded();
+		let (res, values) =
+			futures::join!(self.stream(stk, ctx, opt, txn, stm, chn), rcv.collect::<Vec<_>>());
+		res?;
+		// Output the results
+		Ok(values.into())
+	}
+
+	/// Process the records and send each one to the channel as soon as it is
+	/// iterated. Any clause which needs the full result set, such as GROUP BY,
+	/// or ORDER BY, falls back to buffering the records before sending them.
+	pub async fn stream(
+		&mut self,
+		stk: &mut Stk,
+		ctx: &Context<'_>,
+		opt: &Options,
+		txn: &Transaction,
+		stm: &Statement<'_>,
+		chn: Sender<Value>,
+	) -> Result<(), Error> {
+		// Check if the full result set is needed
+		if !Self::can_stream(stm) {
+			for v in self.output_buffered(stk, ctx, opt, txn, stm).await? {
+				chn.send(v).await.map_err(|_| Error::QueryCancelled)?;
+			}
+			return Ok(());
+		}
+		// Iterate the records into the channel
+		let res = self.output_stream(stk, ctx, opt, txn, stm, chn).await;
+		// Close the channel, as the collector owns the sender
+		self.results = Results::None;
+		res
+	}
+
+	/// Check if records can be output as they are iterated, as
+	/// no clause needs to see the full result set beforehand
+	fn can_stream(stm: &Statement<'_>) -> bool {
+		stm.explain().is_none()
+			&& stm.split().is_none()
+			&& stm.group().is_none()
+			&& stm.having().is_none()
+			&& stm.sample().is_none()
+			&& stm.order().is_none()
+			&& !stm.distinct()
+			&& !stm.limit().is_some_and(Limit::is_percentage)
+			&& stm.fetch().is_none()
+	}
+
+	#[inline]
+	async fn output_stream(
+		&mut self,
+		stk: &mut Stk,
+		ctx: &Context<'_>,
+		opt: &Options,
+		txn: &Transaction,
+		stm: &Statement<'_>,
+		chn: Sender<Value>,
+	) -> Result<(), Error> {
+		// Log the statement
+		trace!("Streaming: {}", stm);
+		// Measure the time spent iterating
+		let now = Instant::now();
+		// Enable context override
+		let mut cancel_ctx = Context::new(ctx);
+		self.run = cancel_ctx.add_cancel();
+		// Stop iterating once the statement times out
+		if let Some(timeout) = stm.timeout().or(opt.timeout) {
+			cancel_ctx.add_timeout(timeout)?;
+		}
+		// Process the query LIMIT clause
+		self.setup_limit(stk, &cancel_ctx, opt, txn, stm).await?;
+		// Process the query START clause
+		self.setup_start(stk, &cancel_ctx, opt, txn, stm).await?;
+		// Send the records on as they are collected
+		self.results = Results::Stream(StreamCollector::new(chn, self.start, self.limit));
+		// Process prepared values
+		if let Some(qp) = ctx.get_query_planner() {
+			while let Some(s) = qp.next_iteration_stage().await {
+				let is_last = matches!(s, IterationStage::Iterate(_));
+				cancel_ctx.set_iteration_stage(s);
+				if !is_last {
+					self.clone().iterate(stk, &cancel_ctx, opt, txn, stm).await?;
+				};
+			}
+		}
+		self.iterate(stk, &cancel_ctx, opt, txn, stm).await?;
+		// Stop if the query was cancelled part way through
+		if ctx.is_cancelled() {
+			return Err(Error::QueryCancelled);
+		}
+		// Stop if the statement timed out part way through
+		if cancel_ctx.is_timedout() {
+			return Err(Error::QueryTimedout);
+		}
+		// Return any document errors
+		if let Some(e) = self.error.take() {
+			return Err(e);
+		}
+		// Record the iterator metrics
+		if let Results::Stream(s) = &self.results {
+			self.metrics.records_returned = s.sent() as u64;
+		}
+		self.record_metrics(ctx, now);
+		Ok(())
+	}
+
+	#[inline]
+	async fn output_buffered(
+		&mut self,
+		stk: &mut Stk,
+		ctx: &Context<'_>,
+		opt: &Options,
+		txn: &Transaction,
+		stm: &Statement<'_>,
+	) -> Result<Vec<Value>, Error> {
 		// Log the statement
 		trace!("Iterating: {}", stm);
+		// Measure the time spent iterating
+		let now = Instant::now();
 		// Enable context override
 		let mut cancel_ctx = Context::new(ctx);
 		self.run = cancel_ctx.add_cancel();
+		// Stop iterating once the statement times out
+		if let Some(timeout) = stm.timeout().or(opt.timeout) {
+			cancel_ctx.add_timeout(timeout)?;
+		}
 		// Process the query LIMIT clause
 		self.setup_limit(stk, &cancel_ctx, opt, txn, stm).await?;
 		// Process the query START clause
@@ -307,7 +557,17 @@ impl Iterator {
 				feature = "kv-speedb"
 			))]
 			ctx,
+			#[cfg(any(
+				feature = "kv-surrealkv",
+				feature = "kv-file",
+				feature = "kv-rocksdb",
+				feature = "kv-fdb",
+				feature = "kv-tikv",
+				feature = "kv-speedb"
+			))]
+			opt,
 			stm,
+			self.random_sample(stm),
 		)?;
 		// Extract the expected behaviour depending on the presence of EXPLAIN with or without FULL
 		let mut plan = Plan::new(ctx, stm, &self.entries, &self.results);
@@ -323,6 +583,14 @@ impl Iterator {
 				}
 			}
 			self.iterate(stk, &cancel_ctx, opt, txn, stm).await?;
+			// Stop if the query was cancelled part way through
+			if ctx.is_cancelled() {
+				return Err(Error::QueryCancelled);
+			}
+			// Stop if the statement timed out part way through
+			if cancel_ctx.is_timedout() {
+				return Err(Error::QueryTimedout);
+			}
 			// Return any document errors
 			if let Some(e) = self.error.take() {
 				return Err(e);
@@ -333,12 +601,23 @@ impl Iterator {
 			if let Results::Groups(g) = &mut self.results {
 				self.results = Results::Memory(g.output(stk, ctx, opt, txn, stm).await?);
 			}
+			// Process any HAVING clause
+			self.output_having(stk, ctx, opt, txn, stm).await?;
+			// Process any SAMPLE clause
+			self.output_sample(stm)?;
 
 			// Process any ORDER clause
 			if let Some(orders) = stm.order() {
 				self.results.sort(orders);
 			}
+			// Process any DISTINCT clause
+			self.output_distinct(stm)?;
 
+			// Process any percentage LIMIT clause
+			if let Some(p) = self.percentage {
+				let len = self.results.len().saturating_sub(self.start.unwrap_or(0));
+				self.limit = Some(Limit::rows(p, len));
+			}
 			// Process any START & LIMIT clause
 			self.results.start_limit(self.start.as_ref(), self.limit.as_ref());
 
@@ -353,8 +632,17 @@ impl Iterator {
 		// Extract the output from the result
 		let mut results = self.results.take()?;
 
+		// Record the iterator metrics
+		self.metrics.records_returned = results.len() as u64;
+		self.record_metrics(ctx, now);
+
 		// Output the explanation if any
-		if let Some(e) = plan.explanation {
+		if let Some(mut e) = plan.explanation {
+			// Report the runtime statistics for EXPLAIN ANALYZE
+			if stm.explain().is_some_and(|e| e.analyze) {
+				self.metrics.elapsed_us = now.elapsed().as_micros() as u64;
+				e.add_statistics(&self.metrics);
+			}
 			results.clear();
 			for v in e.output() {
 				results.push(v)
@@ -362,7 +650,16 @@ impl Iterator {
 		}
 
 		// Output the results
-		Ok(results.into())
+		Ok(results)
+	}
+
+	/// Pass the metrics of the finished iteration on to the statement, if
+	/// the metrics were requested
+	fn record_metrics(&mut self, ctx: &Context<'_>, now: Instant) {
+		if let Some(m) = ctx.get_metrics() {
+			self.metrics.elapsed_us = now.elapsed().as_micros() as u64;
+			m.record(&self.metrics);
+		}
 	}
 
 	#[inline]
@@ -375,7 +672,12 @@ impl Iterator {
 		stm: &Statement<'_>,
 	) -> Result<(), Error> {
 		if let Some(v) = stm.limit() {
-			self.limit = Some(v.process(stk, ctx, opt, txn, None).await?);
+			let limit = v.process(stk, ctx, opt, txn, None).await?;
+			// A percentage depends on the number of results
+			match v.is_percentage() {
+				true => self.percentage = Some(limit),
+				false => self.limit = Some(limit),
+			}
 		}
 		Ok(())
 	}
@@ -395,6 +697,10 @@ impl Iterator {
 		Ok(())
 	}
 
+	/// Applies each SPLIT idiom in sequence, so that splitting on several
+	/// fields yields every combination of their array elements. A value
+	/// which is not an array is kept unchanged, while a row with an empty
+	/// array at the split path is dropped from the results.
 	#[inline]
 	async fn output_split(
 		&mut self,
@@ -440,6 +746,95 @@ impl Iterator {
 		Ok(())
 	}
 
+	/// Check if the records are only ordered randomly before being limited, in
+	/// which case the output can be chosen from a random sample of the records,
+	/// rather than ordering every record. Returns the size of the sample.
+	fn random_sample(&self, stm: &Statement<'_>) -> Option<usize> {
+		let limit = self.limit?;
+		match stm.order() {
+			Some(orders) if orders.is_unseeded_random() => {}
+			_ => return None,
+		}
+		// Any clause which changes the records after
+		// they are collected needs every record
+		if stm.split().is_some() || stm.having().is_some() || stm.distinct() {
+			return None;
+		}
+		Some(self.start.unwrap_or(0).saturating_add(limit))
+	}
+
+	#[inline]
+	fn output_sample(&mut self, stm: &Statement<'_>) -> Result<(), Error> {
+		if let Results::Sample(s) = &mut self.results {
+			// The records were sampled for a random ORDER BY,
+			// and are still shuffled by the ORDER clause
+			if stm.sample().is_none() {
+				self.results = s.take_vec().into();
+				return Ok(());
+			}
+		}
+		if let Some(sample) = stm.sample() {
+			let values = match &mut self.results {
+				// The records were sampled as they were collected
+				Results::Sample(s) => s.take_vec(),
+				// Grouped or filtered results are sampled now
+				results => {
+					let mut s = SampleCollector::new(sample.0 as usize);
+					for v in results.take()? {
+						s.push(v);
+					}
+					s.take_vec()
+				}
+			};
+			self.results = values.into();
+		}
+		Ok(())
+	}
+
+	#[inline]
+	async fn output_having(
+		&mut self,
+		stk: &mut Stk,
+		ctx: &Context<'_>,
+		opt: &Options,
+		txn: &Transaction,
+		stm: &Statement<'_>,
+	) -> Result<(), Error> {
+		if let Some(having) = stm.having() {
+			// Point any projected expressions at their output fields
+			let cond = match stm.expr() {
+				Some(fields) => having.resolve(fields),
+				None => having.0.to_owned(),
+			};
+			// Get the query result
+			let values = self.results.take()?;
+			// Loop over each result value
+			let mut results = Vec::with_capacity(values.len());
+			for obj in values {
+				// Check the condition against the output row
+				let doc = CursorDoc::from(&obj);
+				if cond.compute(stk, ctx, opt, txn, Some(&doc)).await?.is_truthy() {
+					results.push(obj);
+				}
+			}
+			self.results = results.into();
+		}
+		Ok(())
+	}
+
+	#[inline]
+	fn output_distinct(&mut self, stm: &Statement<'_>) -> Result<(), Error> {
+		if stm.distinct() {
+			// Get the query result
+			let mut values = self.results.take()?;
+			// Keep only the first occurrence of each value
+			let mut seen = HashSet::with_capacity(values.len());
+			values.retain(|v| seen.insert(v.to_owned()));
+			self.results = values.into();
+		}
+		Ok(())
+	}
+
 	#[inline]
 	async fn output_fetch(
 		&mut self,
@@ -546,8 +941,13 @@ impl Iterator {
 				let (chn, vals) = channel::bounded(crate::cnf::MAX_CONCURRENT_TASKS);
 				// Create an async closure for received values
 				let avals = async {
+					// Count the records fetched from an index
+					let mut index_hits = 0;
 					// Process all received values
 					while let Ok(pro) = docs.recv().await {
+						if pro.ir.is_some() {
+							index_hits += 1;
+						}
 						let chn_clone = chn.clone();
 						e.spawn(async move {
 							let mut stack = TreeStack::new();
@@ -563,6 +963,8 @@ impl Iterator {
 					}
 					// Drop the uncloned channel instance
 					drop(chn);
+					// Return the index hits
+					index_hits
 				};
 				// Create an async closure to process results
 				let aproc = async {
@@ -579,6 +981,8 @@ impl Iterator {
 				let res = futures::join!(adocs, avals, aproc, fut);
 				// Consume executor error
 				let _ = res.3;
+				// Count the records fetched from an index
+				self.metrics.index_hits += res.1;
 				// Everything processed ok
 				Ok(())
 			}
@@ -595,6 +999,14 @@ impl Iterator {
 		stm: &Statement<'_>,
 		pro: Processed,
 	) {
+		// Stop processing records once the statement is killed
+		if ctx.is_cancelled() {
+			return;
+		}
+		// Count the records fetched from an index
+		if pro.ir.is_some() {
+			self.metrics.index_hits += 1;
+		}
 		// Process the document
 		let res = stk.run(|stk| Document::process(stk, ctx, opt, txn, stm, pro)).await;
 		// Process the result
@@ -611,9 +1023,12 @@ impl Iterator {
 		stm: &Statement<'_>,
 		res: Result<Value, Error>,
 	) {
+		// Count the processed record
+		self.metrics.records_scanned += 1;
 		// Process the result
 		match res {
 			Err(Error::Ignore) => {
+				self.metrics.records_filtered += 1;
 				return;
 			}
 			Err(e) => {
@@ -622,6 +1037,13 @@ impl Iterator {
 				return;
 			}
 			Ok(v) => {
+				// Skip any records ordered before the cursor
+				if let Some((after, cmp)) = &self.after {
+					if !cmp.is_after(&v, after) {
+						self.metrics.records_filtered += 1;
+						return;
+					}
+				}
 				if let Err(e) = self.results.push(stk, ctx, opt, txn, stm, v).await {
 					self.error = Some(e);
 					self.run.cancel();
@@ -630,7 +1052,12 @@ impl Iterator {
 			}
 		}
 		// Check if we can exit
-		if stm.group().is_none() && stm.order().is_none() {
+		if stm.group().is_none()
+			&& stm.having().is_none()
+			&& stm.order().is_none()
+			&& stm.sample().is_none()
+			&& !stm.distinct()
+		{
 			if let Some(l) = self.limit {
 				if let Some(s) = self.start {
 					if self.results.len() == l + s {
@@ -0,0 +1,65 @@
+use std::sync::{Arc, Mutex};
+
+/// Counts the records which were processed by the iterators of a statement
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct IteratorMetrics {
+	/// The number of records which were processed
+	pub records_scanned: u64,
+	/// The number of processed records which were not output, because they
+	/// did not match the WHERE clause or could not be accessed
+	pub records_filtered: u64,
+	/// The number of records which were output
+	pub records_returned: u64,
+	/// The number of processed records which were fetched from an index
+	pub index_hits: u64,
+	/// The number of records which an INSERT statement created
+	pub records_inserted: u64,
+	/// The number of existing records which an INSERT statement updated
+	pub records_updated: u64,
+	/// The time spent iterating, in microseconds
+	pub elapsed_us: u64,
+}
+
+impl IteratorMetrics {
+	/// Add the metrics of another iterator. Any nested iterator runs within
+	/// the iterator which encloses it, so the longest time is kept.
+	fn add(&mut self, other: &IteratorMetrics) {
+		self.records_scanned += other.records_scanned;
+		self.records_filtered += other.records_filtered;
+		self.records_returned += other.records_returned;
+		self.index_hits += other.index_hits;
+		self.records_inserted += other.records_inserted;
+		self.records_updated += other.records_updated;
+		self.elapsed_us = self.elapsed_us.max(other.elapsed_us);
+	}
+}
+
+/// Collects the metrics of every iterator which a statement runs,
+/// including the iterators of any subqueries
+#[derive(Clone, Debug, Default)]
+pub(crate) struct MetricsCollector(Arc<Mutex<IteratorMetrics>>);
+
+impl MetricsCollector {
+	/// Record the metrics of a finished iterator
+	pub(crate) fn record(&self, metrics: &IteratorMetrics) {
+		if let Ok(mut m) = self.0.lock() {
+			m.add(metrics);
+		}
+	}
+
+	/// Record a record which was created or updated by an INSERT statement
+	pub(crate) fn record_insert(&self, updated: bool) {
+		if let Ok(mut m) = self.0.lock() {
+			match updated {
+				true => m.records_updated += 1,
+				false => m.records_inserted += 1,
+			}
+		}
+	}
+
+	/// Retrieve the metrics collected so far
+	pub(crate) fn get(&self) -> IteratorMetrics {
+		self.0.lock().map(|m| *m).unwrap_or_default()
+	}
+}
@@ -2,16 +2,19 @@
 //! In this module we essentially manage the entire lifecycle of a database request acting as the
 //! glue between the API and the response. In this module we use channels as a transport layer
 //! and executors to process the operations. This module also gives a `context` to the transaction.
+mod auth;
 mod distinct;
 mod executor;
 mod group;
 mod iterator;
+mod metrics;
 mod notification;
 mod options;
 mod plan;
 mod processor;
 mod response;
 mod result;
+mod running;
 mod session;
 mod statement;
 mod store;
@@ -24,13 +27,17 @@ pub mod node;
 
 pub use self::capabilities::Capabilities;
 pub use self::lifecycle::*;
+pub use self::metrics::IteratorMetrics;
 pub use self::notification::*;
 pub use self::options::*;
 pub use self::response::*;
 pub use self::session::*;
 
+pub(crate) use self::auth::*;
 pub(crate) use self::executor::*;
 pub(crate) use self::iterator::*;
+pub(crate) use self::metrics::MetricsCollector;
+pub(crate) use self::running::*;
 pub(crate) use self::statement::*;
 pub(crate) use self::transaction::*;
 pub(crate) use self::variables::*;
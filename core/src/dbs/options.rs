@@ -2,12 +2,14 @@ use super::capabilities::Capabilities;
 use crate::cnf::MAX_COMPUTATION_DEPTH;
 use crate::dbs::Notification;
 use crate::err::Error;
-use crate::iam::{Action, Auth, ResourceKind, Role};
+use crate::iam::{Action, Auth, Level, ResourceKind, Role};
 use crate::sql::{
 	statements::define::DefineIndexStatement, statements::define::DefineTableStatement, Base,
 };
 use channel::Sender;
+use rand::Rng;
 use std::sync::Arc;
+use std::time::Duration;
 use uuid::Uuid;
 
 /// An Options is passed around when processing a set of query
@@ -42,9 +44,19 @@ pub struct Options {
 	/// Should we process field queries?
 	pub import: bool,
 	/// Should we process function futures?
-	pub futures: bool,
+	pub futures: Futures,
 	/// Should we process variable field projections?
 	pub projections: bool,
+	/// Should transactions be opened as read-only?
+	pub read_only: bool,
+	/// Should statements be retried if their transaction conflicts?
+	pub retry_policy: Option<RetryPolicy>,
+	/// Should iterator metrics be attached to the responses?
+	pub collect_metrics: bool,
+	/// How many results are collected in memory before spilling to temporary files?
+	pub spill_threshold: Option<usize>,
+	/// How long can each statement run before timing out?
+	pub timeout: Option<Duration>,
 	/// The channel over which we send notifications
 	pub sender: Option<Sender<Notification>>,
 	/// Datastore capabilities
@@ -70,12 +82,214 @@ impl Force {
 	}
 }
 
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum Futures {
+	/// Futures are stored without being processed
+	Disabled,
+	/// Futures are processed
+	Enabled,
+	/// Futures are never processed, and are output as-is
+	Never,
+}
+
+impl Futures {
+	fn new(futures: bool) -> Self {
+		match futures {
+			true => Futures::Enabled,
+			false => Futures::Disabled,
+		}
+	}
+}
+
+/// How a statement is retried when its transaction conflicts
+/// with another concurrent transaction
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct RetryPolicy {
+	/// The maximum number of times a statement is attempted
+	pub max_attempts: u32,
+	/// The delay before the first retry, which doubles on each attempt
+	pub base_delay: Duration,
+}
+
+impl RetryPolicy {
+	/// Create a new RetryPolicy object
+	pub fn new(max_attempts: u32, base_delay: Duration) -> Self {
+		RetryPolicy {
+			max_attempts,
+			base_delay,
+		}
+	}
+	/// Check if a statement should be retried after the specified
+	/// number of attempts, returning how long to wait beforehand
+	pub(crate) fn backoff(&self, attempts: u32, err: &Error) -> Option<Duration> {
+		// Only transaction conflicts are retried
+		if !matches!(err, Error::TxConflict) || attempts >= self.max_attempts {
+			return None;
+		}
+		// The delay doubles for each failed attempt
+		let exp = attempts.saturating_sub(1);
+		let delay = self.base_delay.saturating_mul(2u32.saturating_pow(exp));
+		// Randomise the second half of the delay
+		let half = delay / 2;
+		Some(half + rand::thread_rng().gen_range(Duration::ZERO..=half))
+	}
+}
+
 impl Default for Options {
 	fn default() -> Self {
 		Options::new()
 	}
 }
 
+/// Builds an [`Options`] object, checking that the
+/// combination of settings is valid before it is used
+#[derive(Clone, Debug, Default)]
+#[non_exhaustive]
+pub struct OptionsBuilder {
+	auth: Option<(Level, Role)>,
+	ns: Option<String>,
+	db: Option<String>,
+	futures: bool,
+	read_only: bool,
+	timeout: Option<Duration>,
+	collect_metrics: bool,
+	retry_policy: Option<RetryPolicy>,
+	spill_threshold: Option<usize>,
+}
+
+impl OptionsBuilder {
+	/// Create a new OptionsBuilder object
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Authenticate as a system user with a role at a level
+	pub fn auth(mut self, level: Level, role: Role) -> Self {
+		self.auth = Some((level, role));
+		self
+	}
+
+	/// Specify which Namespace should be used
+	pub fn ns(mut self, ns: impl Into<String>) -> Self {
+		self.ns = Some(ns.into());
+		self
+	}
+
+	/// Specify which Database should be used
+	pub fn db(mut self, db: impl Into<String>) -> Self {
+		self.db = Some(db.into());
+		self
+	}
+
+	/// Specify if futures should be processed
+	pub fn futures(mut self, futures: bool) -> Self {
+		self.futures = futures;
+		self
+	}
+
+	/// Specify if transactions should be opened as read-only
+	pub fn read_only(mut self, read_only: bool) -> Self {
+		self.read_only = read_only;
+		self
+	}
+
+	/// Specify how long each statement can run before timing out
+	pub fn timeout(mut self, timeout: Duration) -> Self {
+		self.timeout = Some(timeout);
+		self
+	}
+
+	/// Specify if iterator metrics should be attached to the responses
+	pub fn collect_metrics(mut self, collect_metrics: bool) -> Self {
+		self.collect_metrics = collect_metrics;
+		self
+	}
+
+	/// Specify how statements are retried on transaction conflicts
+	pub fn retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+		self.retry_policy = Some(retry_policy);
+		self
+	}
+
+	/// Specify how many results are collected in memory before
+	/// sorting and grouping spills them to temporary files
+	pub fn spill_threshold(mut self, spill_threshold: usize) -> Self {
+		self.spill_threshold = Some(spill_threshold);
+		self
+	}
+
+	/// Check the settings, and build the Options object
+	pub fn build(self) -> Result<Options, Error> {
+		// A database can only be selected within a namespace
+		if self.db.is_some() && self.ns.is_none() {
+			return Err(Error::InvalidOptions {
+				message: "A namespace must be specified when a database is specified".to_owned(),
+			});
+		}
+		// A timeout of zero would stop every statement
+		if self.timeout.is_some_and(|t| t.is_zero()) {
+			return Err(Error::InvalidOptions {
+				message: "The timeout must be greater than zero".to_owned(),
+			});
+		}
+		// A retry policy must allow the statement to run at least once
+		if self.retry_policy.is_some_and(|p| p.max_attempts == 0) {
+			return Err(Error::InvalidOptions {
+				message: "The retry policy must allow at least one attempt".to_owned(),
+			});
+		}
+		// The selected namespace and database must be within the auth level
+		let auth = match self.auth {
+			None => Auth::default(),
+			Some((level, role)) => {
+				let conflict = match &level {
+					Level::Namespace(ns) => self.ns.as_ref().is_some_and(|v| v != ns),
+					Level::Database(ns, db) => {
+						self.ns.as_ref().is_some_and(|v| v != ns)
+							|| self.db.as_ref().is_some_and(|v| v != db)
+					}
+					_ => false,
+				};
+				if conflict {
+					return Err(Error::InvalidOptions {
+						message: format!(
+							"The selected namespace and database must be within the auth level: {level}"
+						),
+					});
+				}
+				match level {
+					Level::No => Auth::default(),
+					Level::Root => Auth::for_root(role),
+					Level::Namespace(ns) => Auth::for_ns(role, &ns),
+					Level::Database(ns, db) => Auth::for_db(role, &ns, &db),
+					Level::Scope(..) => {
+						return Err(Error::InvalidOptions {
+							message: "Scope users can only be authenticated with a record"
+								.to_owned(),
+						})
+					}
+				}
+			}
+		};
+		// Build the options
+		let opt = Options::new()
+			.with_ns(self.ns.map(Into::into))
+			.with_db(self.db.map(Into::into))
+			.with_auth(Arc::new(auth))
+			.with_futures(self.futures)
+			.with_read_only(self.read_only)
+			.with_collect_metrics(self.collect_metrics)
+			.with_retry_policy(self.retry_policy)
+			.with_spill_threshold(self.spill_threshold);
+		Ok(match self.timeout {
+			Some(timeout) => opt.with_timeout(timeout),
+			None => opt,
+		})
+	}
+}
+
 impl Options {
 	/// Create a new Options object
 	pub fn new() -> Options {
@@ -89,8 +303,13 @@ impl Options {
 			force: Force::None,
 			strict: false,
 			import: false,
-			futures: false,
+			futures: Futures::Disabled,
 			projections: false,
+			read_only: false,
+			retry_policy: None,
+			collect_metrics: false,
+			spill_threshold: None,
+			timeout: None,
 			auth_enabled: true,
 			sender: None,
 			auth: Arc::new(Auth::default()),
@@ -199,7 +418,16 @@ impl Options {
 
 	/// Specify if we should process futures
 	pub fn with_futures(mut self, futures: bool) -> Self {
-		self.futures = futures;
+		self.futures = Futures::new(futures);
+		self
+	}
+
+	/// Specify if futures should never be processed
+	pub fn with_futures_never(mut self, never: bool) -> Self {
+		self.futures = match never {
+			true => Futures::Never,
+			false => Futures::Disabled,
+		};
 		self
 	}
 
@@ -209,6 +437,37 @@ impl Options {
 		self
 	}
 
+	/// Specify if transactions should be opened as read-only
+	pub fn with_read_only(mut self, read_only: bool) -> Self {
+		self.read_only = read_only;
+		self
+	}
+
+	/// Specify how statements should be retried on transaction conflicts
+	pub fn with_retry_policy(mut self, retry_policy: Option<RetryPolicy>) -> Self {
+		self.retry_policy = retry_policy;
+		self
+	}
+
+	/// Specify how long each statement can run before timing out
+	pub fn with_timeout(mut self, timeout: Duration) -> Self {
+		self.timeout = Some(timeout);
+		self
+	}
+
+	/// Specify if iterator metrics should be attached to the responses
+	pub fn with_collect_metrics(mut self, collect_metrics: bool) -> Self {
+		self.collect_metrics = collect_metrics;
+		self
+	}
+
+	/// Specify how many results are collected in memory before
+	/// sorting and grouping spills them to temporary files
+	pub fn with_spill_threshold(mut self, spill_threshold: Option<usize>) -> Self {
+		self.spill_threshold = spill_threshold;
+		self
+	}
+
 	/// Create a new Options object with auth enabled
 	pub fn with_auth_enabled(mut self, auth_enabled: bool) -> Self {
 		self.auth_enabled = auth_enabled;
@@ -287,7 +546,10 @@ impl Options {
 			ns: self.ns.clone(),
 			db: self.db.clone(),
 			force: self.force.clone(),
-			futures,
+			futures: match self.futures {
+				Futures::Never => Futures::Never,
+				_ => Futures::new(futures),
+			},
 			..*self
 		}
 	}
@@ -527,4 +789,115 @@ mod tests {
 				.unwrap();
 		}
 	}
+
+	#[test]
+	fn retry_policy_backoff() {
+		let policy = RetryPolicy::new(4, Duration::from_millis(100));
+		// Transaction conflicts are retried with an increasing delay
+		for (attempts, max) in [(1, 100), (2, 200), (3, 400)] {
+			let delay = policy.backoff(attempts, &Error::TxConflict).unwrap();
+			assert!(delay >= Duration::from_millis(max / 2));
+			assert!(delay <= Duration::from_millis(max));
+		}
+		// The statement is not retried once all attempts are used
+		assert_eq!(policy.backoff(4, &Error::TxConflict), None);
+		assert_eq!(policy.backoff(5, &Error::TxConflict), None);
+		// Other errors are never retried
+		assert_eq!(policy.backoff(1, &Error::TxFailure), None);
+		assert_eq!(policy.backoff(1, &Error::QueryTimedout), None);
+		// A single attempt is never retried
+		let policy = RetryPolicy::new(1, Duration::from_millis(100));
+		assert_eq!(policy.backoff(1, &Error::TxConflict), None);
+	}
+
+	#[test]
+	fn options_builder() {
+		// The defaults match a new Options object
+		let opt = OptionsBuilder::new().build().unwrap();
+		assert!(opt.ns.is_none());
+		assert!(opt.db.is_none());
+		assert!(opt.auth.is_anon());
+		assert_eq!(opt.futures, Futures::Disabled);
+		assert!(!opt.read_only);
+		assert_eq!(opt.timeout, None);
+		assert!(!opt.collect_metrics);
+		assert_eq!(opt.retry_policy, None);
+		assert_eq!(opt.spill_threshold, None);
+		// Every setting is passed on to the Options object
+		let opt = OptionsBuilder::new()
+			.auth(Level::Database("ns".to_owned(), "db".to_owned()), Role::Editor)
+			.ns("ns")
+			.db("db")
+			.futures(true)
+			.read_only(true)
+			.timeout(Duration::from_secs(5))
+			.collect_metrics(true)
+			.retry_policy(RetryPolicy::new(3, Duration::from_millis(50)))
+			.spill_threshold(10_000)
+			.build()
+			.unwrap();
+		assert_eq!(opt.ns(), "ns");
+		assert_eq!(opt.db(), "db");
+		assert!(opt.auth.is_db());
+		assert!(opt.auth.has_role(&Role::Editor));
+		assert_eq!(opt.futures, Futures::Enabled);
+		assert!(opt.read_only);
+		assert_eq!(opt.timeout, Some(Duration::from_secs(5)));
+		assert!(opt.collect_metrics);
+		assert_eq!(opt.retry_policy, Some(RetryPolicy::new(3, Duration::from_millis(50))));
+		assert_eq!(opt.spill_threshold, Some(10_000));
+		// Root and namespace users can select any database within their level
+		let opt = OptionsBuilder::new().auth(Level::Root, Role::Owner).ns("ns").build().unwrap();
+		assert!(opt.auth.is_root());
+		assert_eq!(opt.ns(), "ns");
+		let opt = OptionsBuilder::new()
+			.auth(Level::Namespace("ns".to_owned()), Role::Viewer)
+			.ns("ns")
+			.db("db")
+			.build()
+			.unwrap();
+		assert!(opt.auth.is_ns());
+		assert_eq!(opt.db(), "db");
+	}
+
+	#[test]
+	fn options_builder_invalid() {
+		// A database requires a namespace
+		let err = OptionsBuilder::new().db("db").build().unwrap_err();
+		assert_eq!(
+			err.to_string(),
+			"Invalid options: A namespace must be specified when a database is specified"
+		);
+		// A timeout must not be zero
+		let err = OptionsBuilder::new().timeout(Duration::ZERO).build().unwrap_err();
+		assert_eq!(err.to_string(), "Invalid options: The timeout must be greater than zero");
+		// A retry policy must allow at least one attempt
+		let err = OptionsBuilder::new()
+			.retry_policy(RetryPolicy::new(0, Duration::from_millis(50)))
+			.build()
+			.unwrap_err();
+		assert_eq!(
+			err.to_string(),
+			"Invalid options: The retry policy must allow at least one attempt"
+		);
+		// The namespace must be within the auth level
+		let err = OptionsBuilder::new()
+			.auth(Level::Namespace("ns".to_owned()), Role::Owner)
+			.ns("other")
+			.build()
+			.unwrap_err();
+		assert!(matches!(err, Error::InvalidOptions { .. }));
+		// The database must be within the auth level
+		let err = OptionsBuilder::new()
+			.auth(Level::Database("ns".to_owned(), "db".to_owned()), Role::Owner)
+			.ns("ns")
+			.db("other")
+			.build()
+			.unwrap_err();
+		assert!(matches!(err, Error::InvalidOptions { .. }));
+		// Scope users can not be built without a record
+		let level = Level::Scope("ns".to_owned(), "db".to_owned(), "sc".to_owned());
+		let err = OptionsBuilder::new().auth(level, Role::Viewer).build().unwrap_err();
+		assert!(matches!(err, Error::InvalidOptions { .. }));
+	}
 }
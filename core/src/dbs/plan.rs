@@ -1,6 +1,6 @@
 use crate::ctx::Context;
 use crate::dbs::result::Results;
-use crate::dbs::{Iterable, Statement};
+use crate::dbs::{Iterable, IteratorMetrics, Statement};
 use crate::sql::{Object, Value};
 use std::collections::HashMap;
 
@@ -29,7 +29,7 @@ impl Plan {
 					}
 				}
 				results.explain(&mut exp);
-				(e.0, Some(exp))
+				(e.full || e.analyze, Some(exp))
 			}
 		};
 		Self {
@@ -62,6 +62,10 @@ impl Explanation {
 		self.0.push(ExplainItem::new_fallback(reason));
 	}
 
+	pub(super) fn add_statistics(&mut self, metrics: &IteratorMetrics) {
+		self.0.push(ExplainItem::new_statistics(metrics));
+	}
+
 	pub(super) fn output(self) -> Vec<Value> {
 		self.0.into_iter().map(|e| e.into()).collect()
 	}
@@ -80,6 +84,19 @@ impl ExplainItem {
 		}
 	}
 
+	fn new_statistics(metrics: &IteratorMetrics) -> Self {
+		Self {
+			name: "Statistics".into(),
+			details: vec![
+				("records_scanned", metrics.records_scanned.into()),
+				("records_filtered", metrics.records_filtered.into()),
+				("records_returned", metrics.records_returned.into()),
+				("index_hits", metrics.index_hits.into()),
+				("elapsed_us", metrics.elapsed_us.into()),
+			],
+		}
+	}
+
 	fn new_fallback(reason: String) -> Self {
 		Self {
 			name: "Fallback".into(),
@@ -1,3 +1,4 @@
+use crate::dbs::IteratorMetrics;
 use crate::err::Error;
 use crate::sql::value::Value;
 use revision::revisioned;
@@ -28,6 +29,8 @@ pub struct Response {
 	pub result: Result<Value, Error>,
 	// Record the query type in case processing the response is necessary (such as tracking live queries).
 	pub query_type: QueryType,
+	// The iterator metrics of the query, if they were requested.
+	pub metrics: Option<IteratorMetrics>,
 }
 
 impl Response {
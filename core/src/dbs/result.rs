@@ -10,7 +10,7 @@ use crate::dbs::plan::Explanation;
 	feature = "kv-speedb"
 ))]
 use crate::dbs::store::file_store::FileCollector;
-use crate::dbs::store::MemoryCollector;
+use crate::dbs::store::{MemoryCollector, SampleCollector, StreamCollector};
 use crate::dbs::{Options, Statement, Transaction};
 use crate::err::Error;
 use crate::sql::{Orders, Value};
@@ -29,6 +29,8 @@ pub(super) enum Results {
 	))]
 	File(Box<FileCollector>),
 	Groups(GroupsCollector),
+	Sample(SampleCollector),
+	Stream(StreamCollector),
 }
 
 impl Results {
@@ -43,10 +45,43 @@ impl Results {
 			feature = "kv-speedb"
 		))]
 		ctx: &Context<'_>,
+		#[cfg(any(
+			feature = "kv-surrealkv",
+			feature = "kv-file",
+			feature = "kv-rocksdb",
+			feature = "kv-fdb",
+			feature = "kv-tikv",
+			feature = "kv-speedb"
+		))]
+		opt: &Options,
 		stm: &Statement<'_>,
+		random: Option<usize>,
 	) -> Result<Self, Error> {
 		if stm.expr().is_some() && stm.group().is_some() {
-			return Ok(Self::Groups(GroupsCollector::new(stm)));
+			let grp = GroupsCollector::new(stm);
+			// Grouped records are spilled once they exceed the threshold
+			#[cfg(any(
+				feature = "kv-surrealkv",
+				feature = "kv-file",
+				feature = "kv-rocksdb",
+				feature = "kv-fdb",
+				feature = "kv-tikv",
+				feature = "kv-speedb"
+			))]
+			let grp = grp.with_spill_threshold(opt.spill_threshold);
+			return Ok(Self::Groups(grp));
+		}
+		// Records can only be sampled as they are collected
+		// when they are not filtered again by a HAVING clause
+		if let Some(sample) = stm.sample() {
+			if stm.having().is_none() {
+				return Ok(Self::Sample(SampleCollector::new(sample.0 as usize)));
+			}
+		}
+		// Randomly ordered and limited records only need a
+		// random sample of as many records as will be output
+		if let Some(size) = random {
+			return Ok(Self::Sample(SampleCollector::new(size)));
 		}
 		#[cfg(any(
 			feature = "kv-surrealkv",
@@ -56,7 +91,9 @@ impl Results {
 			feature = "kv-tikv",
 			feature = "kv-speedb"
 		))]
-		if !ctx.is_memory() {
+		// Without a threshold, results are only ever stored in
+		// temporary files when the datastore is not in memory
+		if !ctx.is_memory() && opt.spill_threshold.is_none() {
 			return Ok(Self::File(Box::new(FileCollector::new(ctx.temporary_directory())?)));
 		}
 		Ok(Self::Memory(Default::default()))
@@ -75,6 +112,22 @@ impl Results {
 			Self::None => {}
 			Self::Memory(s) => {
 				s.push(val);
+				// Spill the results to temporary files once they exceed the threshold
+				#[cfg(any(
+					feature = "kv-surrealkv",
+					feature = "kv-file",
+					feature = "kv-rocksdb",
+					feature = "kv-fdb",
+					feature = "kv-tikv",
+					feature = "kv-speedb"
+				))]
+				if opt.spill_threshold.is_some_and(|t| s.len() > t) {
+					let mut file = FileCollector::new(ctx.temporary_directory())?;
+					for v in s.take_vec() {
+						file.push(v)?;
+					}
+					*self = Self::File(Box::new(file));
+				}
 			}
 			#[cfg(any(
 				feature = "kv-surrealkv",
@@ -90,6 +143,12 @@ impl Results {
 			Self::Groups(g) => {
 				g.push(stk, ctx, opt, txn, stm, val).await?;
 			}
+			Self::Sample(s) => {
+				s.push(val);
+			}
+			Self::Stream(s) => {
+				s.push(val).await?;
+			}
 		}
 		Ok(())
 	}
@@ -124,6 +183,8 @@ impl Results {
 			))]
 			Self::File(f) => f.start_limit(start, limit),
 			Self::Groups(_) => {}
+			Self::Sample(_) => {}
+			Self::Stream(_) => {}
 		}
 	}
 
@@ -141,6 +202,8 @@ impl Results {
 			))]
 			Self::File(e) => e.len(),
 			Self::Groups(g) => g.len(),
+			Self::Sample(s) => s.len(),
+			Self::Stream(s) => s.len(),
 		}
 	}
 
@@ -156,6 +219,7 @@ impl Results {
 				feature = "kv-speedb"
 			))]
 			Self::File(f) => f.take_vec()?,
+			Self::Sample(s) => s.take_vec(),
 			_ => vec![],
 		})
 	}
@@ -180,6 +244,12 @@ impl Results {
 			Self::Groups(g) => {
 				g.explain(exp);
 			}
+			Self::Sample(s) => {
+				s.explain(exp);
+			}
+			Self::Stream(s) => {
+				s.explain(exp);
+			}
 		}
 	}
 }
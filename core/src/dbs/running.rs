@@ -0,0 +1,116 @@
+use crate::ctx::Canceller;
+use crate::iam::Auth;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use uuid::Uuid;
+
+/// The statements which are currently running within a
+/// session, so that they can be cancelled with KILL
+#[derive(Clone, Debug, Default)]
+pub(crate) struct RunningQueries {
+	queries: Arc<Mutex<HashMap<Uuid, RunningQuery>>>,
+}
+
+#[derive(Debug)]
+pub(crate) struct RunningQuery {
+	/// The authentication of the user running the statement
+	pub(crate) auth: Arc<Auth>,
+	/// Cancels the context which the statement runs within
+	canceller: Canceller,
+}
+
+impl RunningQuery {
+	/// Cancel the statement
+	pub(crate) fn cancel(&self) {
+		self.canceller.cancel();
+	}
+}
+
+/// Removes a statement from the running queries once it finishes
+pub(crate) struct Registration {
+	id: Uuid,
+	queries: RunningQueries,
+}
+
+impl Registration {
+	/// The query id of the running statement
+	pub(crate) fn id(&self) -> Uuid {
+		self.id
+	}
+}
+
+impl Drop for Registration {
+	fn drop(&mut self) {
+		if let Ok(mut queries) = self.queries.queries.lock() {
+			queries.remove(&self.id);
+		}
+	}
+}
+
+impl RunningQueries {
+	/// Register a running statement with a new query id. The statement
+	/// is removed again when the returned registration is dropped.
+	pub(crate) fn register(&self, auth: Arc<Auth>, canceller: Canceller) -> Registration {
+		let id = Uuid::new_v4();
+		if let Ok(mut queries) = self.queries.lock() {
+			queries.insert(
+				id,
+				RunningQuery {
+					auth,
+					canceller,
+				},
+			);
+		}
+		Registration {
+			id,
+			queries: self.clone(),
+		}
+	}
+
+	/// Cancel a running statement if the check passes, returning
+	/// `None` if there is no statement running with the query id
+	pub(crate) fn cancel<F>(&self, id: &Uuid, check: F) -> Option<bool>
+	where
+		F: FnOnce(&RunningQuery) -> bool,
+	{
+		let queries = self.queries.lock().ok()?;
+		let query = queries.get(id)?;
+		if !check(query) {
+			return Some(false);
+		}
+		query.cancel();
+		Some(true)
+	}
+
+	/// The query ids of the statements which are running
+	pub(crate) fn ids(&self) -> Vec<Uuid> {
+		match self.queries.lock() {
+			Ok(queries) => queries.keys().copied().collect(),
+			Err(_) => vec![],
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn register_and_cancel() {
+		let queries = RunningQueries::default();
+		let canceller = Canceller::default();
+		let reg = queries.register(Arc::new(Auth::default()), canceller.clone());
+		assert_eq!(queries.ids(), vec![reg.id()]);
+		// Unknown query ids are not found
+		assert_eq!(queries.cancel(&Uuid::new_v4(), |_| true), None);
+		// A failed check leaves the statement running
+		assert_eq!(queries.cancel(&reg.id(), |_| false), Some(false));
+		assert!(!canceller.is_cancelled());
+		// Otherwise the statement is cancelled
+		assert_eq!(queries.cancel(&reg.id(), |_| true), Some(true));
+		assert!(canceller.is_cancelled());
+		// The statement is removed once it finishes
+		drop(reg);
+		assert!(queries.ids().is_empty());
+	}
+}
@@ -1,12 +1,15 @@
+use crate::ctx::Canceller;
 use crate::ctx::Context;
+use crate::dbs::{RetryPolicy, RunningQueries};
 use crate::iam::Auth;
 use crate::iam::{Level, Role};
 use crate::sql::value::Value;
 use chrono::Utc;
+use std::collections::HashMap;
 use std::sync::Arc;
 
 /// Specifies the current session information when processing a query.
-#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[derive(Clone, Debug, Default)]
 #[non_exhaustive]
 pub struct Session {
 	/// The current session [`Auth`] information
@@ -31,8 +34,67 @@ pub struct Session {
 	pub sd: Option<Value>,
 	/// The current expiration time of the session
 	pub exp: Option<i64>,
+	/// Whether future values are output without being processed
+	pub defer_futures: bool,
+	/// Whether queries are run within read-only transactions
+	pub read_only: bool,
+	/// Whether iterator metrics are attached to the query responses
+	pub collect_metrics: bool,
+	/// How statements are retried on transaction conflicts, overriding the datastore
+	pub retry_policy: Option<RetryPolicy>,
+	/// The variables which persist across the queries of the session
+	pub variables: HashMap<String, Value>,
+	/// Cancels any queries running within the session
+	pub(crate) canceller: Canceller,
+	/// The statements which are running within the session
+	pub(crate) queries: RunningQueries,
 }
 
+/// Sessions are compared without their canceller and running queries,
+/// which only track the queries currently running within the session.
+impl PartialEq for Session {
+	fn eq(&self, other: &Self) -> bool {
+		let Session {
+			au,
+			rt,
+			ip,
+			or,
+			id,
+			ns,
+			db,
+			sc,
+			tk,
+			sd,
+			exp,
+			defer_futures,
+			read_only,
+			collect_metrics,
+			retry_policy,
+			variables,
+			canceller: _,
+			queries: _,
+		} = self;
+		*au == other.au
+			&& *rt == other.rt
+			&& *ip == other.ip
+			&& *or == other.or
+			&& *id == other.id
+			&& *ns == other.ns
+			&& *db == other.db
+			&& *sc == other.sc
+			&& *tk == other.tk
+			&& *sd == other.sd
+			&& *exp == other.exp
+			&& *defer_futures == other.defer_futures
+			&& *read_only == other.read_only
+			&& *collect_metrics == other.collect_metrics
+			&& *retry_policy == other.retry_policy
+			&& *variables == other.variables
+	}
+}
+
+impl Eq for Session {}
+
 impl Session {
 	/// Set the selected namespace for the session
 	pub fn with_ns(mut self, ns: &str) -> Session {
@@ -58,6 +120,52 @@ impl Session {
 		self
 	}
 
+	/// Set whether future values are output without being processed
+	pub fn with_deferred_futures(mut self, defer: bool) -> Session {
+		self.defer_futures = defer;
+		self
+	}
+
+	/// Set whether queries are run within read-only transactions
+	pub fn with_read_only(mut self, read_only: bool) -> Session {
+		self.read_only = read_only;
+		self
+	}
+
+	/// Set whether iterator metrics are attached to the query responses
+	pub fn with_collect_metrics(mut self, collect_metrics: bool) -> Session {
+		self.collect_metrics = collect_metrics;
+		self
+	}
+
+	/// Set how statements are retried on transaction conflicts
+	pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Session {
+		self.retry_policy = Some(retry_policy);
+		self
+	}
+
+	/// Set a variable which persists across the queries of the session
+	pub fn set_variable(&mut self, name: &str, val: Value) {
+		self.variables.insert(name.to_owned(), val);
+	}
+
+	/// Retrieve a variable which was set in the session
+	pub fn get_variable(&self, name: &str) -> Option<&Value> {
+		self.variables.get(name)
+	}
+
+	/// Terminate the session, cancelling any queries which are running
+	/// within it, and any which are run with it afterwards
+	pub fn cancel(&self) {
+		self.canceller.cancel();
+	}
+
+	/// Retrieves the query ids of the statements running within the
+	/// session, any of which can be cancelled with a KILL statement
+	pub fn running_queries(&self) -> Vec<uuid::Uuid> {
+		self.queries.ids()
+	}
+
 	/// Retrieves the selected namespace
 	pub(crate) fn ns(&self) -> Option<Arc<str>> {
 		self.ns.as_deref().map(Into::into)
@@ -73,8 +181,23 @@ impl Session {
 		self.rt
 	}
 
+	/// Checks if futures should be output without being processed
+	pub(crate) fn defer_futures(&self) -> bool {
+		self.defer_futures
+	}
+
+	/// Checks if queries should be run within read-only transactions
+	pub(crate) fn read_only(&self) -> bool {
+		self.read_only
+	}
+
+	/// Checks if iterator metrics should be attached to the query responses
+	pub(crate) fn collect_metrics(&self) -> bool {
+		self.collect_metrics
+	}
+
 	/// Checks if the session has expired
-	pub(crate) fn expired(&self) -> bool {
+	pub fn is_expired(&self) -> bool {
 		match self.exp {
 			Some(exp) => Utc::now().timestamp() > exp,
 			// It is currently possible to have sessions without expiration.
@@ -82,8 +205,21 @@ impl Session {
 		}
 	}
 
+	/// Retrieves the retry policy for transaction conflicts
+	pub(crate) fn retry_policy(&self) -> Option<RetryPolicy> {
+		self.retry_policy
+	}
+
 	/// Convert a session into a runtime
 	pub(crate) fn context<'a>(&self, mut ctx: Context<'a>) -> Context<'a> {
+		// Cancel the context when the session is cancelled
+		ctx.set_canceller(&self.canceller);
+		// Register running statements with the session
+		ctx.set_running_queries(self.queries.clone());
+		// Add session variables, which can not replace the values below
+		for (key, val) in self.variables.iter() {
+			ctx.add_value(key.to_owned(), val.to_owned());
+		}
 		// Add scope auth data
 		let val: Value = self.sd.to_owned().into();
 		ctx.add_value("auth", val);
@@ -93,6 +229,10 @@ impl Session {
 		// Add token data
 		let val: Value = self.tk.to_owned().into();
 		ctx.add_value("token", val);
+		// Add the roles of the session actor, so that
+		// permissions clauses can be checked against them
+		let rl: Vec<String> =
+			self.au.roles().iter().map(|r| r.to_string().to_ascii_lowercase()).collect();
 		// Add session value
 		let val: Value = Value::from(map! {
 			"db".to_string() => self.db.to_owned().into(),
@@ -104,6 +244,7 @@ impl Session {
 			"sd".to_string() => self.sd.to_owned().into(),
 			"tk".to_string() => self.tk.to_owned().into(),
 			"exp".to_string() => self.exp.to_owned().into(),
+			"rl".to_string() => rl.into(),
 		});
 		ctx.add_value("session", val);
 		// Output context
@@ -147,6 +288,13 @@ impl Session {
 			tk: None,
 			sd: Some(rid),
 			exp: None,
+			defer_futures: false,
+			read_only: false,
+			collect_metrics: false,
+			retry_policy: None,
+			variables: HashMap::new(),
+			canceller: Canceller::default(),
+			queries: RunningQueries::default(),
 		}
 	}
 
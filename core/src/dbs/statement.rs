@@ -3,10 +3,13 @@ use crate::sql::data::Data;
 use crate::sql::fetch::Fetchs;
 use crate::sql::field::Fields;
 use crate::sql::group::Groups;
+use crate::sql::having::Having;
 use crate::sql::idiom::Idioms;
 use crate::sql::limit::Limit;
+use crate::sql::lock::LockMode;
 use crate::sql::order::Orders;
 use crate::sql::output::Output;
+use crate::sql::sample::Sample;
 use crate::sql::split::Splits;
 use crate::sql::start::Start;
 use crate::sql::statements::create::CreateStatement;
@@ -17,8 +20,10 @@ use crate::sql::statements::relate::RelateStatement;
 use crate::sql::statements::select::SelectStatement;
 use crate::sql::statements::show::ShowStatement;
 use crate::sql::statements::update::UpdateStatement;
+use crate::sql::statements::upsert::UpsertStatement;
 use crate::sql::Explain;
 use std::fmt;
+use std::time::Duration;
 
 #[derive(Clone, Debug)]
 pub(crate) enum Statement<'a> {
@@ -27,6 +32,7 @@ pub(crate) enum Statement<'a> {
 	Select(&'a SelectStatement),
 	Create(&'a CreateStatement),
 	Update(&'a UpdateStatement),
+	Upsert(&'a UpsertStatement),
 	Relate(&'a RelateStatement),
 	Delete(&'a DeleteStatement),
 	Insert(&'a InsertStatement),
@@ -62,6 +68,12 @@ impl<'a> From<&'a UpdateStatement> for Statement<'a> {
 	}
 }
 
+impl<'a> From<&'a UpsertStatement> for Statement<'a> {
+	fn from(v: &'a UpsertStatement) -> Self {
+		Statement::Upsert(v)
+	}
+}
+
 impl<'a> From<&'a RelateStatement> for Statement<'a> {
 	fn from(v: &'a RelateStatement) -> Self {
 		Statement::Relate(v)
@@ -88,6 +100,7 @@ impl<'a> fmt::Display for Statement<'a> {
 			Statement::Select(v) => write!(f, "{v}"),
 			Statement::Create(v) => write!(f, "{v}"),
 			Statement::Update(v) => write!(f, "{v}"),
+			Statement::Upsert(v) => write!(f, "{v}"),
 			Statement::Relate(v) => write!(f, "{v}"),
 			Statement::Delete(v) => write!(f, "{v}"),
 			Statement::Insert(v) => write!(f, "{v}"),
@@ -106,6 +119,14 @@ impl<'a> Statement<'a> {
 	pub fn is_delete(&self) -> bool {
 		matches!(self, Statement::Delete(_))
 	}
+	/// Returns if the DISTINCT keyword is specified
+	#[inline]
+	pub fn distinct(&self) -> bool {
+		match self {
+			Statement::Select(v) => v.distinct,
+			_ => false,
+		}
+	}
 	/// Returns any query fields if specified
 	#[inline]
 	pub fn expr(&self) -> Option<&Fields> {
@@ -129,11 +150,28 @@ impl<'a> Statement<'a> {
 		match self {
 			Statement::Create(v) => v.data.as_ref(),
 			Statement::Update(v) => v.data.as_ref(),
+			Statement::Upsert(v) => v.data.as_ref(),
 			Statement::Relate(v) => v.data.as_ref(),
 			Statement::Insert(v) => v.update.as_ref(),
 			_ => None,
 		}
 	}
+	/// Returns any ON DUPLICATE KEY UPDATE clause of an UPSERT if specified
+	#[inline]
+	pub fn update(&self) -> Option<&Data> {
+		match self {
+			Statement::Upsert(v) => v.update.as_ref(),
+			_ => None,
+		}
+	}
+	/// Returns if the IGNORE keyword is specified
+	#[inline]
+	pub fn ignore(&self) -> bool {
+		match self {
+			Statement::Insert(v) => v.ignore,
+			_ => false,
+		}
+	}
 	/// Returns any WHERE clause if specified
 	#[inline]
 	pub fn conds(&self) -> Option<&Cond> {
@@ -161,6 +199,14 @@ impl<'a> Statement<'a> {
 			_ => None,
 		}
 	}
+	/// Returns any HAVING clause if specified
+	#[inline]
+	pub fn having(&self) -> Option<&Having> {
+		match self {
+			Statement::Select(v) => v.having.as_ref(),
+			_ => None,
+		}
+	}
 	/// Returns any ORDER clause if specified
 	#[inline]
 	pub fn order(&self) -> Option<&Orders> {
@@ -177,6 +223,14 @@ impl<'a> Statement<'a> {
 			_ => None,
 		}
 	}
+	/// Returns any SAMPLE clause if specified
+	#[inline]
+	pub fn sample(&self) -> Option<&Sample> {
+		match self {
+			Statement::Select(v) => v.sample.as_ref(),
+			_ => None,
+		}
+	}
 	/// Returns any START clause if specified
 	#[inline]
 	pub fn start(&self) -> Option<&Start> {
@@ -199,12 +253,27 @@ impl<'a> Statement<'a> {
 		match self {
 			Statement::Create(v) => v.output.as_ref(),
 			Statement::Update(v) => v.output.as_ref(),
+			Statement::Upsert(v) => v.output.as_ref(),
 			Statement::Relate(v) => v.output.as_ref(),
 			Statement::Delete(v) => v.output.as_ref(),
 			Statement::Insert(v) => v.output.as_ref(),
 			_ => None,
 		}
 	}
+	/// Returns any TIMEOUT clause if specified
+	#[inline]
+	pub fn timeout(&self) -> Option<Duration> {
+		match self {
+			Statement::Select(v) => v.timeout.as_ref().map(|v| *v.0),
+			Statement::Create(v) => v.timeout.as_ref().map(|v| *v.0),
+			Statement::Update(v) => v.timeout.as_ref().map(|v| *v.0),
+			Statement::Upsert(v) => v.timeout.as_ref().map(|v| *v.0),
+			Statement::Relate(v) => v.timeout.as_ref().map(|v| *v.0),
+			Statement::Delete(v) => v.timeout.as_ref().map(|v| *v.0),
+			Statement::Insert(v) => v.timeout.as_ref().map(|v| *v.0),
+			_ => None,
+		}
+	}
 	/// Returns any PARALLEL clause if specified
 	#[inline]
 	#[allow(dead_code)]
@@ -213,12 +282,21 @@ impl<'a> Statement<'a> {
 			Statement::Select(v) => v.parallel,
 			Statement::Create(v) => v.parallel,
 			Statement::Update(v) => v.parallel,
+			Statement::Upsert(v) => v.parallel,
 			Statement::Relate(v) => v.parallel,
 			Statement::Delete(v) => v.parallel,
 			Statement::Insert(v) => v.parallel,
 			_ => false,
 		}
 	}
+	/// Returns any FOR UPDATE or FOR SHARE clause if specified
+	#[inline]
+	pub fn lock(&self) -> Option<LockMode> {
+		match self {
+			Statement::Select(v) => v.lock,
+			_ => None,
+		}
+	}
 	/// Returns any EXPLAIN clause if specified
 	#[inline]
 	pub fn explain(&self) -> Option<&Explain> {
@@ -1,6 +1,9 @@
 use crate::dbs::plan::Explanation;
+use crate::err::Error;
 use crate::sql::value::Value;
 use crate::sql::Orders;
+use channel::Sender;
+use rand::Rng;
 use std::mem;
 
 #[derive(Default)]
@@ -12,7 +15,8 @@ impl MemoryCollector {
 	}
 
 	pub(super) fn sort(&mut self, orders: &Orders) {
-		self.0.sort_by(|a, b| orders.compare(a, b));
+		let cmp = orders.comparator();
+		self.0.sort_by(|a, b| cmp.compare(a, b));
 	}
 
 	pub(super) fn len(&self) -> usize {
@@ -49,6 +53,99 @@ impl From<Vec<Value>> for MemoryCollector {
 	}
 }
 
+/// Collects a uniformly random sample of a fixed size, without keeping
+/// every value in memory, using reservoir sampling (Vitter's Algorithm R)
+pub(super) struct SampleCollector {
+	size: usize,
+	seen: usize,
+	values: Vec<Value>,
+}
+
+impl SampleCollector {
+	pub(super) fn new(size: usize) -> Self {
+		Self {
+			size,
+			seen: 0,
+			values: Vec::with_capacity(size.min(1024)),
+		}
+	}
+
+	pub(super) fn push(&mut self, val: Value) {
+		self.seen += 1;
+		// Fill the reservoir first
+		if self.values.len() < self.size {
+			self.values.push(val);
+			return;
+		}
+		// Then replace a random value, so that every value
+		// seen so far has the same chance of being sampled
+		let i = rand::thread_rng().gen_range(0..self.seen);
+		if i < self.size {
+			self.values[i] = val;
+		}
+	}
+
+	pub(super) fn len(&self) -> usize {
+		self.values.len()
+	}
+
+	pub(super) fn take_vec(&mut self) -> Vec<Value> {
+		mem::take(&mut self.values)
+	}
+
+	pub(super) fn explain(&self, exp: &mut Explanation) {
+		exp.add_collector("Sample", vec![]);
+	}
+}
+
+/// Sends each value on to a channel as soon as it is collected, applying
+/// any START and LIMIT clauses without keeping the values in memory
+pub(super) struct StreamCollector {
+	chn: Sender<Value>,
+	start: usize,
+	limit: Option<usize>,
+	seen: usize,
+}
+
+impl StreamCollector {
+	pub(super) fn new(chn: Sender<Value>, start: Option<usize>, limit: Option<usize>) -> Self {
+		Self {
+			chn,
+			start: start.unwrap_or(0),
+			limit,
+			seen: 0,
+		}
+	}
+
+	pub(super) async fn push(&mut self, val: Value) -> Result<(), Error> {
+		self.seen += 1;
+		// Skip any values before the START clause
+		if self.seen <= self.start {
+			return Ok(());
+		}
+		// Drop any values after the LIMIT clause
+		if self.limit.is_some_and(|l| self.seen > self.start + l) {
+			return Ok(());
+		}
+		// The receiver has gone away, so stop iterating
+		self.chn.send(val).await.map_err(|_| Error::QueryCancelled)
+	}
+
+	pub(super) fn len(&self) -> usize {
+		self.seen
+	}
+
+	/// The number of values which were sent on to the channel
+	pub(super) fn sent(&self) -> usize {
+		let sent = self.seen.saturating_sub(self.start);
+		self.limit.map_or(sent, |l| sent.min(l))
+	}
+
+	pub(super) fn explain(&self, exp: &mut Explanation) {
+		exp.add_collector("Stream", vec![]);
+	}
+}
+
 #[cfg(any(
 	feature = "kv-surrealkv",
 	feature = "kv-file",
@@ -64,17 +161,21 @@ pub(super) mod file_store {
 	use crate::sql::{Orders, Value};
 	use ext_sort::{ExternalChunk, ExternalSorter, ExternalSorterBuilder, LimitedBufferBuilder};
 	use revision::Revisioned;
+	use std::cmp::Ordering;
 	use std::fs::{File, OpenOptions};
 	use std::io::{BufReader, BufWriter, Read, Seek, SeekFrom, Take, Write};
 	use std::path::{Path, PathBuf};
 	use std::{fs, io, mem};
 	use tempfile::{Builder, TempDir};
 
+	type ValueSorter = ExternalSorter<Value, Error, LimitedBufferBuilder, ValueExternalChunk>;
+
 	pub(in crate::dbs) struct FileCollector {
 		dir: TempDir,
 		len: usize,
 		writer: Option<FileWriter>,
 		reader: Option<FileReader>,
+		sorter: Option<ValueSorter>,
 		orders: Option<Orders>,
 		paging: FilePaging,
 	}
@@ -93,6 +194,7 @@ pub(super) mod file_store {
 				len: 0,
 				writer: Some(FileWriter::new(&dir)?),
 				reader: None,
+				sorter: None,
 				orders: None,
 				paging: Default::default(),
 				dir,
@@ -143,6 +245,22 @@ pub(super) mod file_store {
 			Ok(vec![])
 		}
 
+		/// Sorts all of the collected records with an external merge
+		/// sort, returning an iterator over the sorted records
+		pub(in crate::dbs) fn take_sorted_by<F>(
+			&mut self,
+			cmp: F,
+		) -> Result<impl Iterator<Item = Result<Value, Error>> + '_, Error>
+		where
+			F: Fn(&Value, &Value) -> Ordering + Copy + Send + Sync,
+		{
+			self.check_reader()?;
+			match self.reader.take() {
+				Some(reader) => self.sort_by(reader, cmp),
+				None => Err(Error::Internal("No FileReader available.".to_string())),
+			}
+		}
+
 		fn sort_and_take_vec(
 			&mut self,
 			reader: FileReader,
@@ -150,19 +268,29 @@ pub(super) mod file_store {
 			start: usize,
 			num: usize,
 		) -> Result<Vec<Value>, Error> {
+			let cmp = orders.comparator();
+			let sorted = self.sort_by(reader, |a: &Value, b: &Value| cmp.compare(a, b))?;
+			sorted.skip(start).take(num).collect()
+		}
+
+		fn sort_by<F>(
+			&mut self,
+			reader: FileReader,
+			cmp: F,
+		) -> Result<impl Iterator<Item = Result<Value, Error>> + '_, Error>
+		where
+			F: Fn(&Value, &Value) -> Ordering + Copy + Send + Sync,
+		{
 			let sort_dir = self.dir.path().join(Self::SORT_DIRECTORY_NAME);
 			fs::create_dir(&sort_dir)?;
-
-			let sorter: ExternalSorter<Value, Error, LimitedBufferBuilder, ValueExternalChunk> =
+			// The sorter is kept until the sorted records have been read
+			let sorter: &mut ValueSorter = self.sorter.insert(
 				ExternalSorterBuilder::new()
 					.with_tmp_dir(&sort_dir)
 					.with_buffer(LimitedBufferBuilder::new(*EXTERNAL_SORTING_BUFFER_LIMIT, true))
-					.build()?;
-
-			let sorted = sorter.sort_by(reader, |a, b| orders.compare(a, b))?;
-			let iter = sorted.map(Result::unwrap);
-			let r: Vec<Value> = iter.skip(start).take(num).collect();
-			Ok(r)
+					.build()?,
+			);
+			Ok(sorter.sort_by(reader, cmp)?.map(|v| v.map_err(Error::from)))
 		}
 		pub(in crate::dbs) fn explain(&self, exp: &mut Explanation) {
 			exp.add_collector("TempFiles", vec![]);
@@ -418,3 +546,105 @@ pub(super) mod file_store {
 		}
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[tokio::test]
+	async fn stream_collector_start_limit() {
+		let (chn, rcv) = channel::unbounded();
+		let mut s = StreamCollector::new(chn, Some(2), Some(3));
+		for i in 0..10 {
+			s.push(Value::from(i)).await.unwrap();
+		}
+		assert_eq!(s.len(), 10);
+		drop(s);
+		let mut values = vec![];
+		while let Ok(v) = rcv.recv().await {
+			values.push(v);
+		}
+		assert_eq!(values, (2..5).map(Value::from).collect::<Vec<_>>());
+	}
+
+	#[tokio::test]
+	async fn stream_collector_closed() {
+		let (chn, rcv) = channel::unbounded();
+		let mut s = StreamCollector::new(chn, None, None);
+		s.push(Value::from(1)).await.unwrap();
+		drop(rcv);
+		assert!(matches!(s.push(Value::from(2)).await, Err(Error::QueryCancelled)));
+	}
+
+	#[tokio::test]
+	async fn stream_collector_backpressure() {
+		let (chn, rcv) = channel::bounded(1);
+		let mut s = StreamCollector::new(chn, None, None);
+		// The first value fills the channel
+		s.push(Value::from(1)).await.unwrap();
+		// The next value waits until the first has been received
+		let mut next = Box::pin(s.push(Value::from(2)));
+		assert!(futures::poll!(&mut next).is_pending());
+		assert_eq!(rcv.recv().await.unwrap(), Value::from(1));
+		next.await.unwrap();
+		assert_eq!(rcv.recv().await.unwrap(), Value::from(2));
+	}
+
+	#[test]
+	fn sample_collector_size() {
+		let mut s = SampleCollector::new(5);
+		(0..3).for_each(|i| s.push(Value::from(i)));
+		assert_eq!(s.len(), 3);
+		(3..100).for_each(|i| s.push(Value::from(i)));
+		assert_eq!(s.len(), 5);
+		let mut values = s.take_vec();
+		values.sort();
+		values.dedup();
+		assert_eq!(values.len(), 5);
+		assert!(values.iter().all(|v| (0..100).any(|i| *v == Value::from(i))));
+	}
+
+	#[test]
+	fn sample_collector_distribution() {
+		const VALUES: usize = 20;
+		const SIZE: usize = 5;
+		const TRIALS: usize = 20_000;
+		// Count how often each value is sampled
+		let mut buckets = [0usize; VALUES];
+		for _ in 0..TRIALS {
+			let mut s = SampleCollector::new(SIZE);
+			(0..VALUES).for_each(|i| s.push(Value::from(i)));
+			for v in s.take_vec() {
+				if let Value::Number(v) = v {
+					buckets[v.as_usize()] += 1;
+				}
+			}
+		}
+		// Every value should be sampled equally often
+		let expected = (TRIALS * SIZE / VALUES) as f64;
+		let chi_squared: f64 =
+			buckets.iter().map(|&o| (o as f64 - expected).powi(2) / expected).sum();
+		// The critical value for 19 degrees of freedom at p = 0.001
+		assert!(chi_squared < 43.82, "chi-squared of {chi_squared} for {buckets:?}");
+	}
+
+	#[cfg(any(
+		feature = "kv-surrealkv",
+		feature = "kv-file",
+		feature = "kv-rocksdb",
+		feature = "kv-fdb",
+		feature = "kv-tikv",
+		feature = "kv-speedb"
+	))]
+	#[test]
+	fn file_collector_sorted_by() {
+		use super::file_store::FileCollector;
+		let mut f = FileCollector::new(&std::env::temp_dir()).unwrap();
+		(0..100).rev().for_each(|i| f.push(Value::from(i % 10)).unwrap());
+		assert_eq!(f.len(), 100);
+		let values = f.take_sorted_by(|a: &Value, b: &Value| a.cmp(b)).unwrap();
+		let values = values.collect::<Result<Vec<_>, _>>().unwrap();
+		let expected = (0..10).flat_map(|i| vec![Value::from(i); 10]).collect::<Vec<_>>();
+		assert_eq!(values, expected);
+	}
+}
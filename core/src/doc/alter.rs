@@ -22,8 +22,14 @@ impl<'a> Document<'a> {
 		let rid = self.id.as_ref().unwrap();
 		// Set default field values
 		self.current.doc.to_mut().def(rid);
+		// An existing record is changed by any
+		// ON DUPLICATE KEY UPDATE clause instead
+		let data = match self.initial.doc.is_some() {
+			true => stm.update().or(stm.data()),
+			false => stm.data(),
+		};
 		// The statement has a data clause
-		if let Some(v) = stm.data() {
+		if let Some(v) = data {
 			match v {
 				Data::PatchExpression(data) => {
 					let data = data.compute(stk, ctx, opt, txn, Some(&self.current)).await?;
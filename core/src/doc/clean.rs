@@ -15,6 +15,10 @@ impl<'a> Document<'a> {
 		txn: &Transaction,
 		_stm: &Statement<'_>,
 	) -> Result<(), Error> {
+		// Check import
+		if opt.import {
+			return Ok(());
+		}
 		// Get the table
 		let tb = self.tb(opt, txn).await?;
 		// This table is schemafull
@@ -47,6 +51,13 @@ impl<'a> Document<'a> {
 						fd if fd.is_in() => continue,
 						fd if fd.is_out() => continue,
 						fd if fd.is_meta() => continue,
+						// Undefined fields are rejected in strict mode
+						fd if opt.strict => {
+							return Err(Error::SchemaViolation {
+								thing: self.id.as_ref().unwrap().to_string(),
+								field: fd.to_owned(),
+							})
+						}
 						fd => self.current.doc.to_mut().del(stk, ctx, opt, txn, fd).await?,
 					}
 				}
@@ -39,6 +39,7 @@ impl<'a> Document<'a> {
 				Statement::Select(_) => doc.select(stk, ctx, opt, txn, stm).await,
 				Statement::Create(_) => doc.create(stk, ctx, opt, txn, stm).await,
 				Statement::Update(_) => doc.update(stk, ctx, opt, txn, stm).await,
+				Statement::Upsert(_) => doc.upsert(stk, ctx, opt, txn, stm).await,
 				Statement::Relate(_) => doc.relate(stk, ctx, opt, txn, stm).await,
 				Statement::Delete(_) => doc.delete(stk, ctx, opt, txn, stm).await,
 				Statement::Insert(_) => doc.insert(stk, ctx, opt, txn, stm).await,
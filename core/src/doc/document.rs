@@ -6,6 +6,8 @@ use crate::iam::Action;
 use crate::iam::ResourceKind;
 use crate::idx::docids::DocId;
 use crate::idx::planner::executor::IteratorRef;
+use crate::sql::idiom::Idiom;
+use crate::sql::operation::Operation;
 use crate::sql::statements::define::DefineEventStatement;
 use crate::sql::statements::define::DefineFieldStatement;
 use crate::sql::statements::define::DefineIndexStatement;
@@ -131,6 +133,27 @@ impl<'a> Document<'a> {
 	pub(crate) fn initial_doc(&self) -> &Value {
 		self.initial.doc.as_ref()
 	}
+
+	/// Get the RFC 6902 JSON patch which turns the initial document into the current document
+	pub(crate) fn diff(&self) -> Value {
+		self.initial
+			.doc
+			.diff(&self.current.doc, Idiom::default())
+			.into_iter()
+			.map(|op| match op {
+				// Text changes are not part of RFC 6902, so the text is replaced
+				Operation::Change {
+					path,
+					..
+				} => Operation::Replace {
+					value: self.current.doc.pick(&path),
+					path,
+				},
+				op => op,
+			})
+			.collect::<Vec<_>>()
+			.into()
+	}
 }
 
 impl<'a> Document<'a> {
@@ -238,3 +261,63 @@ impl<'a> Document<'a> {
 		txn.clone().lock().await.all_tb_lives(opt.ns(), opt.db(), &id.tb).await
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::syn::Parse;
+
+	fn diff(initial: &str, current: &str) -> Value {
+		let initial = Value::parse(initial);
+		let current = Value::parse(current);
+		let doc = Document::new_artificial(
+			None,
+			None,
+			None,
+			Cow::Borrowed(&current),
+			Cow::Borrowed(&initial),
+			Workable::Normal,
+		);
+		let ops = doc.diff();
+		// Applying the diff to the initial document gives the current document
+		let mut patched = initial.clone();
+		patched.patch(ops.clone()).unwrap();
+		assert_eq!(patched, current);
+		ops
+	}
+
+	#[test]
+	fn diff_round_trip() {
+		for (initial, current) in [
+			("{ name: 'Tobie' }", "{ name: 'Tobie' }"),
+			("{ name: 'Tobie' }", "{ name: 'Jaime', age: 30 }"),
+			("{ a: { b: { c: 1, d: 2 } } }", "{ a: { b: { c: 3 }, e: [] } }"),
+			("{ tags: [1, 2, 3] }", "{ tags: [0, 1, 2, 3, 4] }"),
+			("{ tags: [1, 2, 3, 4, 5] }", "{ tags: [2, 4] }"),
+			("{ tags: ['a', 'b', 'c'] }", "{ tags: ['c', 'b', 'a', 'd'] }"),
+			(
+				"{ list: [{ id: 1 }, { id: 2, v: [1] }] }",
+				"{ list: [{ id: 1 }, { id: 2, v: [1, 2] }] }",
+			),
+			("{ list: [[1, 2], [3]] }", "{ list: [[1], [2], [3, 4]] }"),
+			("{ tags: [1, 2] }", "{ tags: 'none' }"),
+		] {
+			diff(initial, current);
+		}
+	}
+
+	#[test]
+	fn diff_text_is_replaced() {
+		let ops = diff("{ name: 'Mary' }", "{ name: 'Marie' }");
+		assert_eq!(ops, Value::parse("[{ op: 'replace', path: '/name', value: 'Marie' }]"));
+	}
+
+	#[test]
+	fn diff_array_element() {
+		let ops = diff("{ tags: [1, 2, 3, 4] }", "{ tags: [1, 2, 5, 4] }");
+		assert_eq!(ops, Value::parse("[{ op: 'replace', path: '/tags/2', value: 5 }]"));
+		//
+		let ops = diff("{ tags: [1, 2, 3, 4] }", "{ tags: [1, 3, 4] }");
+		assert_eq!(ops, Value::parse("[{ op: 'remove', path: '/tags/1' }]"));
+	}
+}
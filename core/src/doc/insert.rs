@@ -16,7 +16,13 @@ impl<'a> Document<'a> {
 		stm: &Statement<'_>,
 	) -> Result<Value, Error> {
 		// Check whether current record exists
-		match self.current.doc.is_some() {
+		let exists = self.current.doc.is_some();
+		let res = match exists {
+			// We attempted to INSERT IGNORE a document with
+			// an ID, and this ID already exists in the database,
+			// so without an ON DUPLICATE KEY UPDATE clause we
+			// leave the existing record unchanged.
+			true if stm.ignore() && stm.data().is_none() => Err(Error::Ignore),
 			// We attempted to INSERT a document with an ID,
 			// and this ID already exists in the database,
 			// so we need to update the record instead.
@@ -42,7 +48,12 @@ impl<'a> Document<'a> {
 					Ok(v) => Ok(v),
 				}
 			}
+		};
+		// Count the created or updated record
+		if let (Ok(_), Some(m)) = (&res, ctx.get_metrics()) {
+			m.record_insert(exists);
 		}
+		res
 	}
 	// Attempt to run an INSERT clause
 	async fn insert_create(
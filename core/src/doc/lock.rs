@@ -0,0 +1,50 @@
+use crate::ctx::Context;
+use crate::dbs::Statement;
+use crate::dbs::{Options, Transaction};
+use crate::doc::Document;
+use crate::err::Error;
+use crate::sql::LockMode;
+
+impl<'a> Document<'a> {
+	/// Lock the record for the remainder of the transaction when the
+	/// statement has a FOR UPDATE clause. Records are locked by joining
+	/// the write set of the transaction, so a concurrent write conflicts
+	/// on optimistic stores, and waits on stores which serialize writes.
+	/// Within a read-only transaction the record is read without a lock.
+	pub async fn lock(
+		&self,
+		_ctx: &Context<'_>,
+		opt: &Options,
+		txn: &Transaction,
+		stm: &Statement<'_>,
+	) -> Result<(), Error> {
+		// Check if the record should be locked
+		let Some(mode) = stm.lock() else {
+			return Ok(());
+		};
+		// Check if this is a stored record
+		let Some(rid) = self.id.as_ref() else {
+			return Ok(());
+		};
+		// A shared lock is held by the writeable
+		// transaction itself, without any further writes
+		if mode == LockMode::Share {
+			return Ok(());
+		}
+		// Claim transaction
+		let mut run = txn.lock().await;
+		// A read-only transaction can not hold the record in its
+		// write set, so the record is read without being locked
+		if !run.writeable() {
+			warn!("Unable to lock record {rid} {mode} within a read-only transaction");
+			return Ok(());
+		}
+		// Write the unchanged record back, so that it is
+		// in the write set of this transaction, and any
+		// concurrent write to this record will conflict
+		let key = crate::key::thing::new(opt.ns(), opt.db(), &rid.tb, &rid.id);
+		run.set(key, self).await?;
+		// Carry on
+		Ok(())
+	}
+}
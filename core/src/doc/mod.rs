@@ -18,6 +18,7 @@ mod insert; // Processes a INSERT statement for this document
 mod relate; // Processes a RELATE statement for this document
 mod select; // Processes a SELECT statement for this document
 mod update; // Processes a UPDATE statement for this document
+mod upsert; // Processes a UPSERT statement for this document
 
 mod allow; // Checks whether the query can access this document
 mod alter; // Modifies and updates the fields in this document
@@ -31,6 +32,7 @@ mod event; // Processes any table events relevant for this document
 mod field; // Processes any schema-defined fields for this document
 mod index; // Attempts to store the index data for this document
 mod lives; // Processes any live queries relevant for this document
+mod lock; // Locks this document for the rest of the transaction
 mod merge; // Merges any field changes for an INSERT statement
 mod pluck; // Pulls the projected expressions from the document
 mod purge; // Deletes this document, and any edges or indexes
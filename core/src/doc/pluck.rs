@@ -50,6 +50,10 @@ impl<'a> Document<'a> {
 					// Output the specified fields
 					v.compute(stk, &ctx, opt, txn, Some(&self.current), false).await
 				}
+				Output::Patch => {
+					// Output an RFC 6902 JSON patch of any changes applied to the document
+					Ok(self.diff())
+				}
 			},
 			None => match stm {
 				Statement::Live(s) => match s.expr.len() {
@@ -65,6 +69,9 @@ impl<'a> Document<'a> {
 				Statement::Update(_) => {
 					self.current.doc.compute(stk, ctx, opt, txn, Some(&self.current)).await
 				}
+				Statement::Upsert(_) => {
+					self.current.doc.compute(stk, ctx, opt, txn, Some(&self.current)).await
+				}
 				Statement::Relate(_) => {
 					self.current.doc.compute(stk, ctx, opt, txn, Some(&self.current)).await
 				}
@@ -32,6 +32,7 @@ impl<'a> Document<'a> {
 			let res = match stm {
 				Statement::Select(_) => doc.select(stk, ctx, opt, txn, stm).await,
 				Statement::Update(_) => doc.update(stk, ctx, opt, txn, stm).await,
+				Statement::Upsert(_) => doc.upsert(stk, ctx, opt, txn, stm).await,
 				Statement::Create(_) => doc.create(stk, ctx, opt, txn, stm).await,
 				Statement::Relate(_) => doc.relate(stk, ctx, opt, txn, stm).await,
 				Statement::Delete(_) => doc.delete(stk, ctx, opt, txn, stm).await,
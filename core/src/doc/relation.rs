@@ -16,7 +16,7 @@ impl<'a> Document<'a> {
 
 		let rid = self.id.as_ref().unwrap();
 		match stm {
-			Statement::Create(_) | Statement::Insert(_) => {
+			Statement::Create(_) | Statement::Insert(_) | Statement::Upsert(_) => {
 				if !tb.allows_normal() {
 					return Err(Error::TableCheck {
 						thing: rid.to_string(),
@@ -21,6 +21,8 @@ impl<'a> Document<'a> {
 		self.check(stk, ctx, opt, txn, stm).await?;
 		// Check if allowed
 		self.allow(stk, ctx, opt, txn, stm).await?;
+		// Lock the record
+		self.lock(ctx, opt, txn, stm).await?;
 		// Yield document
 		self.pluck(stk, ctx, opt, txn, stm).await
 	}
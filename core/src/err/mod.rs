@@ -108,6 +108,39 @@ pub enum Error {
 	#[error("Transaction is too large")]
 	TxTooLarge,
 
+	/// The transaction conflicted with another concurrent transaction
+	#[error("Transaction conflicted with another transaction, and can be retried")]
+	TxConflict,
+
+	/// The requested savepoint does not exist
+	#[error("The savepoint '{value}' does not exist")]
+	SavepointNotFound {
+		value: String,
+	},
+
+	/// A savepoint was used outside of a transaction
+	#[error("Savepoints can only be used within a transaction")]
+	SavepointNoTransaction,
+
+	/// A line of an import is not valid
+	#[error("Invalid import line: {message}")]
+	InvalidImport {
+		message: String,
+	},
+
+	/// The changes since the time of an incremental export have been removed from the change feeds
+	#[error("The changes since {since} are no longer retained by the change feeds")]
+	ExportChangesExpired {
+		since: String,
+	},
+
+	/// A line of an import could not be imported
+	#[error("Unable to import line {line}: {message}")]
+	ImportFailed {
+		line: usize,
+		message: String,
+	},
+
 	/// No namespace has been selected
 	#[error("Specify a namespace to use")]
 	NsEmpty,
@@ -169,6 +202,13 @@ pub enum Error {
 		field: String,
 	},
 
+	/// A projected field references an alias which is defined by a later field
+	#[error("Found '{field}' in SELECT clause, but it references the alias `{alias}` before it is defined")]
+	InvalidFieldReference {
+		field: String,
+		alias: Idiom,
+	},
+
 	#[error("Found '{field}' in SPLIT ON clause on line {line}, but field is not present in SELECT expression")]
 	InvalidSplit {
 		line: usize,
@@ -193,12 +233,46 @@ pub enum Error {
 		value: String,
 	},
 
+	/// A percentage LIMIT clause must evaluate to an integer between 0 and 100
+	#[error("Found {value} but the LIMIT clause must evaluate to a percentage between 0 and 100")]
+	InvalidLimitPercentage {
+		value: String,
+	},
+
 	/// The START clause must evaluate to a positive integer
 	#[error("Found {value} but the START clause must evaluate to a positive integer")]
 	InvalidStart {
 		value: String,
 	},
 
+	/// The LIMIT clause evaluated to an integer which is too large for this platform
+	#[error("Found {value} but the LIMIT clause must evaluate to an integer no larger than {max}", max = usize::MAX)]
+	LimitOverflow {
+		value: String,
+	},
+
+	/// The START clause evaluated to an integer which is too large for this platform
+	#[error("Found {value} but the START clause must evaluate to an integer no larger than {max}", max = usize::MAX)]
+	StartOverflow {
+		value: String,
+	},
+
+	/// The AFTER clause must evaluate to a record id or a cursor token
+	#[error("Found {value} but the AFTER clause must evaluate to a record id or a cursor token")]
+	InvalidCursor {
+		value: String,
+	},
+
+	/// The AFTER clause can only be used when records are ordered by id
+	#[error("The AFTER clause can only be used when ordering the records by id")]
+	InvalidCursorOrder,
+
+	/// The ORDER BY values of an AFTER cursor do not match the ORDER BY clause
+	#[error("Unable to continue from the AFTER cursor, as {message}")]
+	InvalidCursorKeys {
+		message: String,
+	},
+
 	/// There was an error with the provided JavaScript code
 	#[error("Problem with embedded script function. {message}")]
 	InvalidScript {
@@ -263,6 +337,12 @@ pub enum Error {
 	#[error("Invalid timeout: {0:?} seconds")]
 	InvalidTimeout(u64),
 
+	/// The options could not be built from an invalid combination of settings
+	#[error("Invalid options: {message}")]
+	InvalidOptions {
+		message: String,
+	},
+
 	/// The query timedout
 	#[error("The query was not executed because it exceeded the timeout")]
 	QueryTimedout,
@@ -463,6 +543,12 @@ pub enum Error {
 		value: String,
 	},
 
+	/// Can not execute UPSERT statement using the specified value
+	#[error("Can not execute UPSERT statement using value '{value}'")]
+	UpsertStatement {
+		value: String,
+	},
+
 	/// Can not execute RELATE statement using the specified value
 	#[error("Can not execute RELATE statement using value '{value}'")]
 	RelateStatement {
@@ -561,6 +647,13 @@ pub enum Error {
 		check: String,
 	},
 
+	/// A field which is not defined was found on a SCHEMAFULL table in strict mode
+	#[error("Found field `{field}`, with record `{thing}`, but the field is not defined on the SCHEMAFULL table")]
+	SchemaViolation {
+		thing: String,
+		field: Idiom,
+	},
+
 	/// The specified field did not conform to the field ASSERT clause
 	#[error(
 		"Found changed value for field `{field}`, with record `{thing}`, but field is readonly"
@@ -794,6 +887,14 @@ pub enum Error {
 	#[error("Auth was expected to be set but was unknown")]
 	UnknownAuth,
 
+	/// The token was revoked when it was refreshed
+	#[error("The authentication token has been revoked")]
+	RevokedToken,
+
+	/// The token expired too long ago to be refreshed
+	#[error("The authentication token expired too long ago to be refreshed")]
+	ExpiredToken,
+
 	/// Auth requires a token header which is missing
 	#[error("Auth token is missing the '{0}' header")]
 	MissingTokenHeader(String),
@@ -1009,21 +1110,30 @@ impl From<tikv::Error> for Error {
 #[cfg(feature = "kv-speedb")]
 impl From<speedb::Error> for Error {
 	fn from(e: speedb::Error) -> Error {
-		Error::Tx(e.to_string())
+		match e.kind() {
+			speedb::ErrorKind::Busy | speedb::ErrorKind::TryAgain => Error::TxConflict,
+			_ => Error::Tx(e.to_string()),
+		}
 	}
 }
 
 #[cfg(feature = "kv-rocksdb")]
 impl From<rocksdb::Error> for Error {
 	fn from(e: rocksdb::Error) -> Error {
-		Error::Tx(e.to_string())
+		match e.kind() {
+			rocksdb::ErrorKind::Busy | rocksdb::ErrorKind::TryAgain => Error::TxConflict,
+			_ => Error::Tx(e.to_string()),
+		}
 	}
 }
 
 #[cfg(feature = "kv-surrealkv")]
 impl From<surrealkv::Error> for Error {
 	fn from(e: surrealkv::Error) -> Error {
-		Error::Tx(e.to_string())
+		match e {
+			surrealkv::Error::TransactionReadConflict => Error::TxConflict,
+			_ => Error::Tx(e.to_string()),
+		}
 	}
 }
 
@@ -1,6 +1,50 @@
 use crate::err::Error;
 use crate::sql::{Bytes, Value};
+use base64::alphabet::STANDARD;
+use base64::engine::general_purpose::{GeneralPurpose, GeneralPurposeConfig};
+use base64::engine::DecodePaddingMode;
+use base64::Engine;
+
+/// Encodes without padding, and decodes with or without padding
+const BASE64: GeneralPurpose = GeneralPurpose::new(
+	&STANDARD,
+	GeneralPurposeConfig::new()
+		.with_encode_padding(false)
+		.with_decode_padding_mode(DecodePaddingMode::Indifferent),
+);
+
+pub fn from_base64((arg,): (String,)) -> Result<Value, Error> {
+	Ok(Value::from(Bytes(BASE64.decode(arg).map_err(|_| Error::InvalidArguments {
+		name: "bytes::from_base64".to_owned(),
+		message: "invalid base64".to_owned(),
+	})?)))
+}
 
 pub fn len((bytes,): (Bytes,)) -> Result<Value, Error> {
 	Ok(bytes.len().into())
 }
+
+pub fn slice((bytes, beg, lim): (Bytes, Option<isize>, Option<isize>)) -> Result<Value, Error> {
+	let skip = match beg {
+		Some(v) if v < 0 => bytes.len().saturating_sub(v.unsigned_abs()),
+		Some(v) => v as usize,
+		None => 0,
+	};
+
+	let take = match lim {
+		Some(v) if v < 0 => bytes.len().saturating_sub(skip).saturating_sub(v.unsigned_abs()),
+		Some(v) => v as usize,
+		None => usize::MAX,
+	};
+
+	Ok(if skip > 0 || take < usize::MAX {
+		Bytes(bytes.iter().skip(skip).take(take).copied().collect())
+	} else {
+		bytes
+	}
+	.into())
+}
+
+pub fn to_base64((bytes,): (Bytes,)) -> Result<Value, Error> {
+	Ok(Value::from(BASE64.encode(&*bytes)))
+}
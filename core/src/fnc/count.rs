@@ -1,5 +1,6 @@
 use crate::err::Error;
 use crate::sql::value::Value;
+use std::collections::HashSet;
 
 pub fn count((arg,): (Option<Value>,)) -> Result<Value, Error> {
 	Ok(arg
@@ -9,3 +10,12 @@ pub fn count((arg,): (Option<Value>,)) -> Result<Value, Error> {
 		})
 		.unwrap_or_else(|| 1.into()))
 }
+
+pub fn distinct((arg,): (Value,)) -> Result<Value, Error> {
+	Ok(match arg {
+		Value::Array(v) => {
+			v.iter().filter(|v| !v.is_none_or_null()).collect::<HashSet<_>>().len().into()
+		}
+		v => (!v.is_none_or_null() as i64).into(),
+	})
+}
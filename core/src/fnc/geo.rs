@@ -59,6 +59,20 @@ pub fn distance(points: (Value, Value)) -> Result<Value, Error> {
 	})
 }
 
+pub fn intersects(geometries: (Value, Value)) -> Result<Value, Error> {
+	Ok(match geometries {
+		(Value::Geometry(v), Value::Geometry(w)) => v.intersects(&w).into(),
+		_ => Value::None,
+	})
+}
+
+pub fn within(geometries: (Value, Value)) -> Result<Value, Error> {
+	Ok(match geometries {
+		(Value::Geometry(v), Value::Geometry(w)) => w.contains(&v).into(),
+		_ => Value::None,
+	})
+}
+
 pub mod hash {
 
 	use crate::err::Error;
@@ -1,6 +1,5 @@
 use crate::err::Error;
 use crate::fnc::util::math::bottom::Bottom;
-use crate::fnc::util::math::deviation::Deviation;
 use crate::fnc::util::math::interquartile::Interquartile;
 use crate::fnc::util::math::mean::Mean;
 use crate::fnc::util::math::median::Median;
@@ -11,9 +10,9 @@ use crate::fnc::util::math::percentile::Percentile;
 use crate::fnc::util::math::spread::Spread;
 use crate::fnc::util::math::top::Top;
 use crate::fnc::util::math::trimean::Trimean;
-use crate::fnc::util::math::variance::Variance;
 use crate::sql::number::{Number, Sort};
 use crate::sql::value::{TryPow, Value};
+use crate::sql::Array;
 
 pub fn abs((arg,): (Number,)) -> Result<Value, Error> {
 	Ok(arg.abs().into())
@@ -64,7 +63,8 @@ pub fn mean((array,): (Vec<Number>,)) -> Result<Value, Error> {
 	Ok(array.mean().into())
 }
 
-pub fn median((mut array,): (Vec<Number>,)) -> Result<Value, Error> {
+pub fn median((array,): (Array,)) -> Result<Value, Error> {
+	let mut array = numbers(array)?;
 	Ok(match array.is_empty() {
 		true => Value::None,
 		false => array.sorted().median().into(),
@@ -90,7 +90,8 @@ pub fn nearestrank((mut array, n): (Vec<Number>, Number)) -> Result<Value, Error
 	Ok(array.sorted().nearestrank(n).into())
 }
 
-pub fn percentile((mut array, n): (Vec<Number>, Number)) -> Result<Value, Error> {
+pub fn percentile((array, n): (Array, Number)) -> Result<Value, Error> {
+	let mut array = numbers(array)?;
 	Ok(array.sorted().percentile(n).into())
 }
 
@@ -117,8 +118,32 @@ pub fn sqrt((arg,): (Number,)) -> Result<Value, Error> {
 	})
 }
 
-pub fn stddev((array,): (Vec<Number>,)) -> Result<Value, Error> {
-	Ok(array.deviation(true).into())
+pub fn stddev((array,): (Array,)) -> Result<Value, Error> {
+	stddev::samp((array,))
+}
+
+pub mod stddev {
+
+	use super::numbers;
+	use crate::err::Error;
+	use crate::fnc::util::math::deviation::Deviation;
+	use crate::sql::{Array, Value};
+
+	pub fn pop((array,): (Array,)) -> Result<Value, Error> {
+		let array = numbers(array)?;
+		Ok(match array.is_empty() {
+			true => Value::None,
+			false => array.deviation(false).into(),
+		})
+	}
+
+	pub fn samp((array,): (Array,)) -> Result<Value, Error> {
+		let array = numbers(array)?;
+		Ok(match array.is_empty() {
+			true => Value::None,
+			false => array.deviation(true).into(),
+		})
+	}
 }
 
 pub fn sum((array,): (Vec<Number>,)) -> Result<Value, Error> {
@@ -140,6 +165,106 @@ pub fn trimean((mut array,): (Vec<Number>,)) -> Result<Value, Error> {
 	Ok(array.sorted().trimean().into())
 }
 
-pub fn variance((array,): (Vec<Number>,)) -> Result<Value, Error> {
-	Ok(array.variance(true).into())
+pub fn variance((array,): (Array,)) -> Result<Value, Error> {
+	variance::samp((array,))
+}
+
+pub mod variance {
+
+	use super::numbers;
+	use crate::err::Error;
+	use crate::fnc::util::math::variance::Variance;
+	use crate::sql::{Array, Value};
+
+	pub fn pop((array,): (Array,)) -> Result<Value, Error> {
+		let array = numbers(array)?;
+		Ok(match array.is_empty() {
+			true => Value::None,
+			false => array.variance(false).into(),
+		})
+	}
+
+	pub fn samp((array,): (Array,)) -> Result<Value, Error> {
+		let array = numbers(array)?;
+		Ok(match array.is_empty() {
+			true => Value::None,
+			false => array.variance(true).into(),
+		})
+	}
+}
+
+/// Collects the numbers in an array, ignoring any NONE or NULL values
+fn numbers(array: Array) -> Result<Vec<Number>, Error> {
+	array.into_iter().filter(|v| !v.is_none_or_null()).map(Value::coerce_to_number).collect()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{median, percentile, stddev, variance};
+	use crate::sql::{Array, Value};
+
+	#[test]
+	fn math_median() {
+		fn test(arr: Array, expected: Value) {
+			assert_eq!(median((arr,)).unwrap(), expected);
+		}
+
+		test(Vec::<Value>::new().into(), Value::None);
+		test(vec![Value::Null, Value::None].into(), Value::None);
+		test(vec![7].into(), 7.0.into());
+		test(vec![3, 1, 2].into(), 2.0.into());
+		test(vec![4, 1, 3, 2].into(), 2.5.into());
+		test(vec![Value::from(3), Value::Null, Value::from(1), Value::None].into(), 2.0.into());
+	}
+
+	#[test]
+	fn math_percentile() {
+		fn test(arr: Array, n: f64, expected: f64) {
+			assert_eq!(percentile((arr, n.into())).unwrap(), expected.into());
+		}
+
+		test(vec![7].into(), 0.0, 7.0);
+		test(vec![7].into(), 95.0, 7.0);
+		test(vec![4, 1, 3, 2].into(), 0.0, 1.0);
+		test(vec![4, 1, 3, 2].into(), 100.0, 4.0);
+		test(vec![4, 1, 3, 2].into(), 50.0, 2.5);
+		test(vec![4, 1, 3, 2].into(), 90.0, 3.7);
+		test(vec![Value::from(1), Value::Null, Value::from(3), Value::None].into(), 25.0, 1.5);
+		assert!(percentile((Vec::<Value>::new().into(), 50.into())).unwrap().is_nan());
+		assert!(percentile((vec![4, 1].into(), 101.into())).unwrap().is_nan());
+		assert!(percentile((vec![Value::from(1), Value::from("a")].into(), 50.into())).is_err());
+	}
+
+	#[test]
+	fn math_stddev() {
+		fn test(arr: Array, pop: Value, samp: Value) {
+			assert_eq!(stddev::pop((arr.clone(),)).unwrap(), pop);
+			assert_eq!(stddev::samp((arr,)).unwrap(), samp);
+		}
+
+		test(Vec::<Value>::new().into(), Value::None, Value::None);
+		test(vec![Value::Null].into(), Value::None, Value::None);
+		test(vec![5].into(), 0.0.into(), 0.0.into());
+		test(vec![2, 4, 4, 4, 5, 5, 7, 9].into(), 2.0.into(), 2.138089935299395.into());
+		test(
+			vec![Value::from(1), Value::Null, Value::from(3), Value::None].into(),
+			1.0.into(),
+			std::f64::consts::SQRT_2.into(),
+		);
+		assert!(stddev::pop((vec!["a"].into(),)).is_err());
+	}
+
+	#[test]
+	fn math_variance() {
+		fn test(arr: Array, pop: Value, samp: Value) {
+			assert_eq!(variance::pop((arr.clone(),)).unwrap(), pop);
+			assert_eq!(variance::samp((arr,)).unwrap(), samp);
+		}
+
+		test(Vec::<Value>::new().into(), Value::None, Value::None);
+		test(vec![Value::None].into(), Value::None, Value::None);
+		test(vec![5].into(), 0.0.into(), 0.0.into());
+		test(vec![2, 4, 4, 4, 5, 5, 7, 9].into(), 4.0.into(), 4.571428571428571.into());
+		test(vec![Value::from(1), Value::Null, Value::from(3)].into(), 1.0.into(), 2.0.into());
+	}
 }
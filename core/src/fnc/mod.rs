@@ -133,9 +133,13 @@ pub fn synchronous(ctx: &Context<'_>, name: &str, args: Vec<Value>) -> Result<Va
 		"array::sort::asc" => array::sort::asc,
 		"array::sort::desc" => array::sort::desc,
 		//
+		"bytes::from_base64" => bytes::from_base64,
 		"bytes::len" => bytes::len,
+		"bytes::slice" => bytes::slice,
+		"bytes::to_base64" => bytes::to_base64,
 		//
 		"count" => count::count,
+		"count::distinct" => count::distinct,
 		//
 		"crypto::md5" => crypto::md5,
 		"crypto::sha1" => crypto::sha1,
@@ -169,6 +173,8 @@ pub fn synchronous(ctx: &Context<'_>, name: &str, args: Vec<Value>) -> Result<Va
 		"geo::distance" => geo::distance,
 		"geo::hash::decode" => geo::hash::decode,
 		"geo::hash::encode" => geo::hash::encode,
+		"geo::intersects" => geo::intersects,
+		"geo::within" => geo::within,
 		//
 		"math::abs" => math::abs,
 		"math::bottom" => math::bottom,
@@ -190,10 +196,14 @@ pub fn synchronous(ctx: &Context<'_>, name: &str, args: Vec<Value>) -> Result<Va
 		"math::spread" => math::spread,
 		"math::sqrt" => math::sqrt,
 		"math::stddev" => math::stddev,
+		"math::stddev::pop" => math::stddev::pop,
+		"math::stddev::samp" => math::stddev::samp,
 		"math::sum" => math::sum,
 		"math::top" => math::top,
 		"math::trimean" => math::trimean,
 		"math::variance" => math::variance,
+		"math::variance::pop" => math::variance::pop,
+		"math::variance::samp" => math::variance::samp,
 		//
 		"meta::id" => meta::id,
 		"meta::table" => meta::tb,
@@ -249,6 +259,7 @@ pub fn synchronous(ctx: &Context<'_>, name: &str, args: Vec<Value>) -> Result<Va
 		"string::repeat" => string::repeat,
 		"string::replace" => string::replace,
 		"string::reverse" => string::reverse,
+		"string::similarity" => string::similarity,
 		"string::slice" => string::slice,
 		"string::slug" => string::slug,
 		"string::split" => string::split,
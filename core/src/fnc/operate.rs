@@ -224,7 +224,7 @@ pub(crate) async fn matches(
 ) -> Result<Value, Error> {
 	let res = match get_executor_option(ctx, doc, exp) {
 		ExecutorOption::PreMatch => true,
-		ExecutorOption::None => false,
+		ExecutorOption::None => l.matches_terms(&r),
 		ExecutorOption::Execute(exe, thg) => {
 			exe.matches(stk, ctx, opt, txn, thg, exp, l, r).await?
 		}
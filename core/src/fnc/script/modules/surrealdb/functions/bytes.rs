@@ -6,6 +6,9 @@ pub struct Package;
 
 impl_module_def!(
 	Package,
-	"array",
-	"len" => run
+	"bytes",
+	"from_base64" => run,
+	"len" => run,
+	"slice" => run,
+	"to_base64" => run
 );
@@ -13,5 +13,7 @@ impl_module_def!(
 	"bearing" => run,
 	"centroid" => run,
 	"distance" => run,
-	"hash" => (hash::Package)
+	"hash" => (hash::Package),
+	"intersects" => run,
+	"within" => run
 );
@@ -1,6 +1,9 @@
 use super::run;
 use crate::fnc::script::modules::impl_module_def;
 
+mod stddev;
+mod variance;
+
 #[non_exhaustive]
 pub struct Package;
 
@@ -26,9 +29,9 @@ impl_module_def!(
 	"round" => run,
 	"spread" => run,
 	"sqrt" => run,
-	"stddev" => run,
+	"stddev" => (stddev::Package),
 	"sum" => run,
 	"top" => run,
 	"trimean" => run,
-	"variance" => run
+	"variance" => (variance::Package)
 );
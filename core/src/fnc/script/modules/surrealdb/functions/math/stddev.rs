@@ -0,0 +1,31 @@
+use js::{prelude::Rest, Ctx};
+
+use super::super::run;
+use crate::sql::value::Value;
+
+#[non_exhaustive]
+pub struct Package;
+
+impl js::module::ModuleDef for Package {
+	fn declare(decls: &js::module::Declarations) -> js::Result<()> {
+		decls.declare("default")?;
+		decls.declare("pop")?;
+		decls.declare("samp")?;
+		Ok(())
+	}
+	fn evaluate<'js>(ctx: &js::Ctx<'js>, exports: &js::module::Exports<'js>) -> js::Result<()> {
+		let default = js::Function::new(ctx.clone(), |ctx: Ctx<'js>, args: Rest<Value>| {
+			run(ctx, "math::stddev", args.0)
+		})?
+		.with_name("stddev")?;
+		let value = crate::fnc::script::modules::impl_module_def!(ctx, "math::stddev", "pop", run,);
+		exports.export("pop", value.clone())?;
+		default.set("pop", value)?;
+		let value =
+			crate::fnc::script::modules::impl_module_def!(ctx, "math::stddev", "samp", run,);
+		exports.export("samp", value.clone())?;
+		default.set("samp", value)?;
+		exports.export("default", default)?;
+		Ok(())
+	}
+}
@@ -10,6 +10,7 @@ use super::query::{QueryContext, QUERY_DATA_PROP_NAME};
 
 mod array;
 mod bytes;
+mod count;
 mod crypto;
 mod duration;
 mod encoding;
@@ -35,7 +36,7 @@ impl_module_def!(
 	"", // root path
 	"array" => (array::Package),
 	"bytes" => (bytes::Package),
-	"count" => run,
+	"count" => (count::Package),
 	"crypto" => (crypto::Package),
 	"duration" => (duration::Package),
 	"encoding" => (encoding::Package),
@@ -125,6 +125,10 @@ pub fn slice((val, beg, lim): (String, Option<isize>, Option<isize>)) -> Result<
 	.into())
 }
 
+pub fn similarity((a, b): (String, String)) -> Result<Value, Error> {
+	Ok(string::distance::similarity(&a, &b).into())
+}
+
 pub fn slug((string,): (String,)) -> Result<Value, Error> {
 	Ok(string::slug::slug(string).into())
 }
@@ -152,6 +156,7 @@ pub fn words((string,): (String,)) -> Result<Value, Error> {
 pub mod distance {
 
 	use crate::err::Error;
+	use crate::fnc::util::string;
 	use crate::sql::Value;
 
 	pub fn hamming((_, _): (String, String)) -> Result<Value, Error> {
@@ -160,10 +165,8 @@ pub mod distance {
 		})
 	}
 
-	pub fn levenshtein((_, _): (String, String)) -> Result<Value, Error> {
-		Err(Error::FeatureNotYetImplemented {
-			feature: "string::distance::levenshtein() function".to_string(),
-		})
+	pub fn levenshtein((a, b): (String, String)) -> Result<Value, Error> {
+		Ok(string::distance::levenshtein(&a, &b).into())
 	}
 }
 
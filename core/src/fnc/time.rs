@@ -72,8 +72,54 @@ pub fn format((val, format): (Datetime, String)) -> Result<Value, Error> {
 	Ok(val.format(&format).to_string().into())
 }
 
-pub fn group((val, group): (Datetime, String)) -> Result<Value, Error> {
-	match group.as_str() {
+pub fn group((val, group): (Datetime, Value)) -> Result<Value, Error> {
+	match group {
+		Value::Duration(d) => group_duration(val, d),
+		Value::Strand(s) => group_unit(val, s.as_str()),
+		_ => Err(Error::InvalidArguments {
+			name: String::from("time::group"),
+			message: String::from("The second argument must be a duration, or a string which can be one of 'year', 'month', 'day', 'hour', 'minute', or 'second'."),
+		}),
+	}
+}
+
+/// Floors a datetime to the start of its bucket, where buckets are
+/// multiples of the duration from the Unix epoch. Buckets of whole
+/// weeks start on a Monday instead. Datetimes are stored in UTC, so
+/// the buckets are not affected by daylight saving time.
+fn group_duration(val: Datetime, duration: Duration) -> Result<Value, Error> {
+	const NANOS_PER_SEC: i128 = 1_000_000_000;
+	const NANOS_PER_WEEK: i128 = 7 * 86_400 * NANOS_PER_SEC;
+	// The first Monday after the Unix epoch
+	const FIRST_MONDAY: i128 = 4 * 86_400 * NANOS_PER_SEC;
+	let err = || {
+		Error::InvalidArguments {
+		name: String::from("time::group"),
+		message: String::from("The second argument must be a duration, and must be able to be represented as nanoseconds."),
+	}
+	};
+	let size = i128::try_from(duration.as_nanos()).map_err(|_| err())?;
+	// Check for zero duration
+	if size == 0 {
+		return Ok(Value::Datetime(val));
+	}
+	// Align the buckets of whole weeks to Mondays
+	let origin = match size % NANOS_PER_WEEK {
+		0 => FIRST_MONDAY,
+		_ => 0,
+	};
+	let nanos = val.timestamp() as i128 * NANOS_PER_SEC + val.timestamp_subsec_nanos() as i128;
+	let bucket = (nanos - origin).div_euclid(size) * size + origin;
+	let secs = i64::try_from(bucket.div_euclid(NANOS_PER_SEC)).map_err(|_| err())?;
+	let nsecs = bucket.rem_euclid(NANOS_PER_SEC) as u32;
+	match Utc.timestamp_opt(secs, nsecs).single() {
+		Some(v) => Ok(v.into()),
+		None => Err(err()),
+	}
+}
+
+fn group_unit(val: Datetime, group: &str) -> Result<Value, Error> {
+	match group {
 		"year" => Ok(Utc
 			.with_ymd_and_hms(val.year(), 1, 1, 0,0,0)
 			.earliest()
@@ -0,0 +1,68 @@
+use crate::cnf::MAX_STRING_DISTANCE_LENGTH;
+
+/// Calculate the Levenshtein edit distance between two strings.
+///
+/// Only the first [`MAX_STRING_DISTANCE_LENGTH`] characters of each string
+/// are compared, so that comparing huge strings remains bounded.
+pub fn levenshtein(a: &str, b: &str) -> usize {
+	// Limit the number of compared characters
+	let a: Vec<char> = a.chars().take(*MAX_STRING_DISTANCE_LENGTH).collect();
+	let b: Vec<char> = b.chars().take(*MAX_STRING_DISTANCE_LENGTH).collect();
+	// Keep the shortest string in the inner loop
+	let (a, b) = if a.len() < b.len() {
+		(b, a)
+	} else {
+		(a, b)
+	};
+	// Only the previous row of the matrix is needed
+	let mut row: Vec<usize> = (0..=b.len()).collect();
+	for (i, ca) in a.iter().enumerate() {
+		let mut prev = row[0];
+		row[0] = i + 1;
+		for (j, cb) in b.iter().enumerate() {
+			let cost = if ca == cb {
+				prev
+			} else {
+				prev + 1
+			};
+			prev = row[j + 1];
+			row[j + 1] = cost.min(row[j] + 1).min(prev + 1);
+		}
+	}
+	row[b.len()]
+}
+
+/// Calculate the normalised Levenshtein similarity between two strings,
+/// where 1 means the strings are identical and 0 means they share nothing.
+pub fn similarity(a: &str, b: &str) -> f64 {
+	let len = a.chars().count().max(b.chars().count()).min(*MAX_STRING_DISTANCE_LENGTH);
+	match len {
+		0 => 1.0,
+		len => 1.0 - levenshtein(a, b) as f64 / len as f64,
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{levenshtein, similarity};
+
+	#[test]
+	fn levenshtein_distance() {
+		assert_eq!(levenshtein("", ""), 0);
+		assert_eq!(levenshtein("apple", ""), 5);
+		assert_eq!(levenshtein("", "apple"), 5);
+		assert_eq!(levenshtein("apple", "apple"), 0);
+		assert_eq!(levenshtein("apple", "aple"), 1);
+		assert_eq!(levenshtein("aple", "appel"), 2);
+		assert_eq!(levenshtein("kitten", "sitting"), 3);
+		assert_eq!(levenshtein("你好世界", "你好"), 2);
+	}
+
+	#[test]
+	fn levenshtein_similarity() {
+		assert_eq!(similarity("", ""), 1.0);
+		assert_eq!(similarity("apple", "apple"), 1.0);
+		assert_eq!(similarity("apple", "aple"), 0.8);
+		assert_eq!(similarity("abc", "xyz"), 0.0);
+	}
+}
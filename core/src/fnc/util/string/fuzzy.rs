@@ -1,3 +1,5 @@
+use super::distance::levenshtein;
+use crate::cnf::FUZZY_MATCH_MAX_DISTANCE;
 use fuzzy_matcher::skim::SkimMatcherV2;
 use fuzzy_matcher::FuzzyMatcher;
 use once_cell::sync::Lazy;
@@ -9,15 +11,26 @@ pub trait Fuzzy {
 	fn fuzzy_match(&self, other: &str) -> bool;
 	/// Check if this &str matches another &str using a fuzzy algorithm
 	fn fuzzy_score(&self, other: &str) -> i64;
+	/// Check if this &str is within the maximum edit distance of another &str
+	fn fuzzy_distance(&self, other: &str) -> bool;
 }
 
 impl Fuzzy for str {
 	/// Retrieve the fuzzy similarity score of this &str compared to another &str
 	fn fuzzy_match(&self, other: &str) -> bool {
-		MATCHER.fuzzy_match(self, other).is_some()
+		MATCHER.fuzzy_match(self, other).is_some() || self.fuzzy_distance(other)
 	}
 	/// Check if this &str matches another &str using a fuzzy algorithm
 	fn fuzzy_score(&self, other: &str) -> i64 {
 		MATCHER.fuzzy_match(self, other).unwrap_or(0)
 	}
+	/// Check if this &str is within the maximum edit distance of another &str
+	fn fuzzy_distance(&self, other: &str) -> bool {
+		let max = *FUZZY_MATCH_MAX_DISTANCE;
+		// Skip the comparison when the lengths alone exceed the distance
+		if self.chars().count().abs_diff(other.chars().count()) > max {
+			return false;
+		}
+		levenshtein(&self.to_lowercase(), &other.to_lowercase()) <= max
+	}
 }
@@ -1,2 +1,4 @@
+pub mod distance;
 pub mod fuzzy;
 pub mod slug;
+pub mod terms;
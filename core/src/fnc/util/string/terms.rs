@@ -0,0 +1,18 @@
+/// Split a string into normalised terms, by splitting on
+/// whitespace and converting each term to lowercase.
+pub fn terms(s: &str) -> impl Iterator<Item = String> + '_ {
+	s.split_whitespace().map(str::to_lowercase)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::terms;
+
+	#[test]
+	fn terms_are_normalised() {
+		let res: Vec<String> = terms("  Rust\tis a DATABASE\nlanguage ").collect();
+		assert_eq!(res, vec!["rust", "is", "a", "database", "language"]);
+		assert_eq!(terms("").count(), 0);
+		assert_eq!(terms(" \t\n").count(), 0);
+	}
+}
@@ -87,6 +87,11 @@ impl Auth {
 	pub fn has_role(&self, role: &Role) -> bool {
 		self.actor.has_role(role)
 	}
+
+	/// Retrieves the roles of the current actor
+	pub fn roles(&self) -> &[Role] {
+		self.actor.roles()
+	}
 }
 
 impl std::convert::From<(&DefineUserStatement, Level)> for Auth {
@@ -50,6 +50,7 @@ impl From<&Statement<'_>> for Action {
 			Statement::Show(_) => Action::View,
 			Statement::Create(_) => Action::Edit,
 			Statement::Update(_) => Action::Edit,
+			Statement::Upsert(_) => Action::Edit,
 			Statement::Relate(_) => Action::Edit,
 			Statement::Delete(_) => Action::Edit,
 			Statement::Insert(_) => Action::Edit,
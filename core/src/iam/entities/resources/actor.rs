@@ -61,6 +61,11 @@ impl Actor {
 		self.roles.contains(role)
 	}
 
+	/// Retrieves the roles of the actor.
+	pub fn roles(&self) -> &[Role] {
+		&self.roles
+	}
+
 	// Cedar policy helpers
 	pub fn cedar_attrs(&self) -> HashMap<String, RestrictedExpression> {
 		[
@@ -10,6 +10,7 @@ pub mod entities;
 #[cfg(feature = "jwks")]
 pub mod jwks;
 pub mod policies;
+pub mod refresh;
 pub mod signin;
 pub mod signup;
 pub mod token;
@@ -0,0 +1,220 @@
+use super::verify::{config_alg, token as verify_token, DUD, KEY};
+use crate::dbs::{refresh_token, Session, Transaction};
+use crate::err::Error;
+use crate::iam::token::Claims;
+use crate::kvs::{Datastore, LockType::*, TransactionType::*};
+use crate::sql::Algorithm;
+use futures::lock::Mutex;
+use jsonwebtoken::decode;
+use std::sync::Arc;
+
+/// Refresh a scope authentication token, returning a new token with
+/// a fresh expiry. The existing token is revoked, so that it can not
+/// be used again, and tokens which expired within the grace period can
+/// still be refreshed. The session is authenticated with the new token.
+pub async fn refresh(kvs: &Datastore, session: &mut Session, token: &str) -> Result<String, Error> {
+	// Log the authentication type
+	trace!("Attempting to refresh an authentication token");
+	// Create a new writeable transaction
+	let txn: Transaction = Arc::new(Mutex::new(kvs.transaction(Write, Optimistic).await?));
+	// Refresh the token within the transaction
+	let res = match verify_signature(&txn, token).await {
+		Ok(claims) => {
+			let sess = Session {
+				tk: Some(claims.into()),
+				..Session::default()
+			};
+			refresh_token(&sess, &txn).await
+		}
+		Err(e) => Err(e),
+	};
+	let mut run = txn.lock().await;
+	let tk = match res {
+		Ok(tk) => {
+			run.commit().await?;
+			tk
+		}
+		Err(e) => {
+			run.cancel().await?;
+			return Err(e);
+		}
+	};
+	// Authenticate the session with the new token
+	verify_token(kvs, session, &tk).await?;
+	Ok(tk)
+}
+
+/// Verify the signature of a scope token, allowing it to have expired
+async fn verify_signature(txn: &Transaction, token: &str) -> Result<Claims, Error> {
+	// Decode the token without verifying, leaving any
+	// missing expiry to be reported when refreshing
+	let mut dud = DUD.clone();
+	dud.required_spec_claims.clear();
+	let claims = decode::<Claims>(token, &KEY, &dud)?.claims;
+	// Only scope tokens issued by signin or signup can be refreshed
+	let (ns, db, sc) = match &claims {
+		Claims {
+			ns: Some(ns),
+			db: Some(db),
+			sc: Some(sc),
+			tk: None,
+			..
+		} => (ns, db, sc),
+		_ => return Err(Error::InvalidAuth),
+	};
+	// Check that the scope still exists
+	let sv = match txn.lock().await.get_sc(ns, db, sc).await {
+		Ok(sv) => sv,
+		Err(Error::ScNotFound {
+			..
+		}) => return Err(Error::NoScopeFound),
+		Err(e) => return Err(e),
+	};
+	// Verify the token signature, allowing expired tokens
+	let cf = config_alg(Algorithm::Hs512, sv.code)?;
+	let mut validation = cf.1;
+	validation.validate_exp = false;
+	validation.required_spec_claims.clear();
+	decode::<Claims>(token, &cf.0, &validation)?;
+	Ok(claims)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::cnf::TOKEN_REFRESH_GRACE_PERIOD;
+	use crate::iam::signin::sc;
+	use crate::iam::token::HEADER;
+	use crate::iam::verify::token;
+	use crate::sql::Value;
+	use chrono::{Duration, Utc};
+	use jsonwebtoken::{encode, EncodingKey};
+	use std::collections::HashMap;
+
+	async fn setup() -> (Datastore, String) {
+		let ds = Datastore::new("memory").await.unwrap();
+		let sess = Session::owner().with_ns("test").with_db("test");
+		ds.execute(
+			r#"
+			DEFINE SCOPE user SESSION 1h
+				SIGNIN (
+					SELECT * FROM user WHERE name = $user AND crypto::argon2::compare(pass, $pass)
+				);
+
+			CREATE user:test CONTENT {
+				name: 'user',
+				pass: crypto::argon2::generate('pass')
+			}
+			"#,
+			&sess,
+			None,
+		)
+		.await
+		.unwrap();
+		// Signin with the user
+		let mut sess = Session::default();
+		let mut vars: HashMap<&str, Value> = HashMap::new();
+		vars.insert("user", "user".into());
+		vars.insert("pass", "pass".into());
+		let tk = sc(
+			&ds,
+			&mut sess,
+			"test".to_string(),
+			"test".to_string(),
+			"user".to_string(),
+			vars.into(),
+		)
+		.await
+		.unwrap()
+		.unwrap();
+		(ds, tk)
+	}
+
+	/// Sign the claims of a token again with a different expiry
+	async fn with_exp(ds: &Datastore, tk: &str, exp: Option<i64>) -> String {
+		let mut tx = ds.transaction(Read, Optimistic).await.unwrap();
+		let sv = tx.get_sc("test", "test", "user").await.unwrap();
+		tx.cancel().await.unwrap();
+		let claims = Claims {
+			exp,
+			..decode::<Claims>(tk, &KEY, &DUD).unwrap().claims
+		};
+		encode(&HEADER, &claims, &EncodingKey::from_secret(sv.code.as_ref())).unwrap()
+	}
+
+	#[tokio::test]
+	async fn test_refresh_token() {
+		let (ds, old) = setup().await;
+		// Refresh the token
+		let mut sess = Session::default();
+		let new = refresh(&ds, &mut sess, &old).await.unwrap();
+		assert_ne!(old, new);
+		assert_eq!(sess.au.id(), "user:test");
+		assert!(sess.au.is_scope());
+		// The new token has a new identifier and a fresh expiry
+		let old_claims = decode::<Claims>(&old, &KEY, &DUD).unwrap().claims;
+		let new_claims = decode::<Claims>(&new, &KEY, &DUD).unwrap().claims;
+		assert_ne!(old_claims.jti, new_claims.jti);
+		assert_eq!(new_claims.id, Some("user:test".to_string()));
+		let min_exp = (Utc::now() + Duration::hours(1) - Duration::seconds(10)).timestamp();
+		assert!(new_claims.exp.unwrap() > min_exp);
+		assert_eq!(sess.exp, new_claims.exp);
+		// The new token can be used to authenticate
+		let mut sess = Session::default();
+		token(&ds, &mut sess, &new).await.unwrap();
+		assert_eq!(sess.au.id(), "user:test");
+		// A token which expired within the grace period can be refreshed
+		let exp = Utc::now().timestamp() - 60;
+		let tk = with_exp(&ds, &new, Some(exp)).await;
+		let mut sess = Session::default();
+		assert!(refresh(&ds, &mut sess, &tk).await.is_ok());
+	}
+
+	#[tokio::test]
+	async fn test_refresh_token_expired() {
+		let (ds, tk) = setup().await;
+		// A token which expired beyond the grace period can not be refreshed
+		let exp = Utc::now().timestamp() - *TOKEN_REFRESH_GRACE_PERIOD - 60;
+		let tk = with_exp(&ds, &tk, Some(exp)).await;
+		let mut sess = Session::default();
+		let res = refresh(&ds, &mut sess, &tk).await;
+		assert!(matches!(res, Err(Error::ExpiredToken)), "Unexpected result: {:?}", res);
+		assert!(sess.au.is_anon());
+	}
+
+	#[tokio::test]
+	async fn test_refresh_token_invalid_expiry() {
+		let (ds, old) = setup().await;
+		// A token without an expiry can not be refreshed
+		let tk = with_exp(&ds, &old, None).await;
+		let mut sess = Session::default();
+		let res = refresh(&ds, &mut sess, &tk).await;
+		assert!(matches!(res, Err(Error::MissingTokenClaim(_))), "Unexpected result: {:?}", res);
+		// A token which lasts longer than the scope session can not be refreshed
+		let exp = (Utc::now() + Duration::hours(2)).timestamp();
+		let tk = with_exp(&ds, &old, Some(exp)).await;
+		let res = refresh(&ds, &mut sess, &tk).await;
+		assert!(matches!(res, Err(Error::InvalidAuth)), "Unexpected result: {:?}", res);
+		assert!(sess.au.is_anon());
+	}
+
+	#[tokio::test]
+	async fn test_refresh_token_revoked() {
+		let (ds, old) = setup().await;
+		let mut sess = Session::default();
+		refresh(&ds, &mut sess, &old).await.unwrap();
+		// The old token can not be refreshed again
+		let mut sess = Session::default();
+		let res = refresh(&ds, &mut sess, &old).await;
+		assert!(matches!(res, Err(Error::RevokedToken)), "Unexpected result: {:?}", res);
+		// The old token can not be used to authenticate
+		let mut sess = Session::default();
+		let res = token(&ds, &mut sess, &old).await;
+		assert!(matches!(res, Err(Error::RevokedToken)), "Unexpected result: {:?}", res);
+		// A token with an invalid signature can not be refreshed
+		let claims = decode::<Claims>(&old, &KEY, &DUD).unwrap().claims;
+		let tk = encode(&HEADER, &claims, &EncodingKey::from_secret(b"invalid")).unwrap();
+		let res = refresh(&ds, &mut sess, &tk).await;
+		assert!(res.is_err());
+	}
+}
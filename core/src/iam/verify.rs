@@ -3,7 +3,7 @@ use crate::err::Error;
 #[cfg(feature = "jwks")]
 use crate::iam::jwks;
 use crate::iam::{token::Claims, Actor, Auth, Level, Role};
-use crate::kvs::{Datastore, LockType::*, TransactionType::*};
+use crate::kvs::{Datastore, Key, LockType::*, TransactionType::*};
 use crate::sql::{statements::DefineUserStatement, Algorithm, Value};
 use crate::syn;
 use argon2::{Argon2, PasswordHash, PasswordVerifier};
@@ -37,7 +37,10 @@ async fn config(
 	}
 }
 
-fn config_alg(algo: Algorithm, code: String) -> Result<(DecodingKey, Validation), Error> {
+pub(super) fn config_alg(
+	algo: Algorithm,
+	code: String,
+) -> Result<(DecodingKey, Validation), Error> {
 	match algo {
 		Algorithm::Hs256 => Ok((
 			DecodingKey::from_secret(code.as_ref()),
@@ -95,9 +98,9 @@ fn config_alg(algo: Algorithm, code: String) -> Result<(DecodingKey, Validation)
 	}
 }
 
-static KEY: Lazy<DecodingKey> = Lazy::new(|| DecodingKey::from_secret(&[]));
+pub(super) static KEY: Lazy<DecodingKey> = Lazy::new(|| DecodingKey::from_secret(&[]));
 
-static DUD: Lazy<Validation> = Lazy::new(|| {
+pub(super) static DUD: Lazy<Validation> = Lazy::new(|| {
 	let mut validation = Validation::new(jsonwebtoken::Algorithm::HS256);
 	validation.insecure_disable_signature_validation();
 	validation.validate_nbf = false;
@@ -261,6 +264,7 @@ pub async fn token(kvs: &Datastore, session: &mut Session, token: &str) -> Resul
 			db: Some(db),
 			sc: Some(sc),
 			id: Some(id),
+			jti,
 			..
 		} => {
 			// Log the decoded authentication claims
@@ -274,6 +278,13 @@ pub async fn token(kvs: &Datastore, session: &mut Session, token: &str) -> Resul
 			let cf = config_alg(Algorithm::Hs512, de.code)?;
 			// Verify the token
 			decode::<Claims>(token, &cf.0, &cf.1)?;
+			// Check that the token has not been revoked by a refresh
+			if let Some(jti) = jti {
+				if tx.exi(Key::from(crate::key::scope::rv::new(&ns, &db, &sc, &jti))).await? {
+					trace!("The authentication token has been revoked");
+					return Err(Error::RevokedToken);
+				}
+			}
 			// Log the success
 			debug!("Authenticated to scope `{}`", sc);
 			// Set the session
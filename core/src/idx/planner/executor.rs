@@ -492,10 +492,8 @@ impl QueryExecutor {
 			return self.matches_with_value(stk, ctx, opt, txn, ft, l, r).await;
 		}
 
-		// If no previous case were successful, we end up with a user error
-		Err(Error::NoIndexFoundForMatch {
-			value: exp.to_string(),
-		})
+		// If no full-text index supports the expression, match the terms directly
+		Ok(l.matches_terms(&r))
 	}
 
 	async fn matches_with_doc_id(
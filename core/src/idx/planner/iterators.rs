@@ -150,6 +150,11 @@ impl RangeScan {
 		}
 	}
 
+	/// Checks if the bounds are contradictory, so that no key can match
+	fn is_empty(&self) -> bool {
+		self.beg > self.end
+	}
+
 	fn matches(&mut self, k: &Key) -> bool {
 		if let Some(b) = &self.beg_excl {
 			if b.eq(k) {
@@ -229,6 +234,9 @@ impl IndexRangeThingIterator {
 		limit: u32,
 		collector: &mut T,
 	) -> Result<usize, Error> {
+		if self.r.is_empty() {
+			return Ok(0);
+		}
 		let min = self.r.beg.clone();
 		let max = self.r.end.clone();
 		let res = txn
@@ -514,7 +522,7 @@ impl UniqueRangeThingIterator {
 		mut limit: u32,
 		collector: &mut T,
 	) -> Result<usize, Error> {
-		if self.done {
+		if self.done || self.r.is_empty() {
 			return Ok(0);
 		}
 		let min = self.r.beg.clone();
@@ -4,6 +4,7 @@ pub(in crate::idx) mod knn;
 pub(crate) mod plan;
 mod tree;
 
+use crate::cnf::INDEX_ESTIMATE_THRESHOLD;
 use crate::ctx::Context;
 use crate::dbs::{Iterable, Iterator, Options, Transaction};
 use crate::err::Error;
@@ -12,6 +13,7 @@ use crate::idx::planner::executor::{
 };
 use crate::idx::planner::plan::{Plan, PlanBuilder};
 use crate::idx::planner::tree::Tree;
+use crate::key::thing;
 use crate::sql::with::With;
 use crate::sql::{Cond, Expression, Table, Thing};
 use reblessive::tree::Stk;
@@ -68,7 +70,8 @@ impl<'a> QueryPlanner<'a> {
 					tree.knn_expressions,
 				)
 				.await?;
-				match PlanBuilder::build(tree.root, self.with, tree.with_indexes)? {
+				let plan = PlanBuilder::build(tree.root, self.with, tree.with_indexes)?;
+				match self.check_estimate(txn, &t, plan).await? {
 					Plan::SingleIndex(exp, io) => {
 						if io.require_distinct() {
 							self.requires_distinct = true;
@@ -117,6 +120,33 @@ impl<'a> QueryPlanner<'a> {
 		Ok(())
 	}
 
+	/// Scans the table instead of a single index when the index is
+	/// estimated to match more than half of the records in the table
+	async fn check_estimate(
+		&self,
+		txn: &Transaction,
+		t: &Table,
+		plan: Plan,
+	) -> Result<Plan, Error> {
+		let (Plan::SingleIndex(..), Some(cond), None) = (&plan, self.cond, self.with) else {
+			return Ok(plan);
+		};
+		// Small estimates are always served from the index
+		let n = match Iterator::estimate(self.opt, txn, t, cond).await? {
+			Some(n) if n >= *INDEX_ESTIMATE_THRESHOLD => n,
+			_ => return Ok(plan),
+		};
+		// Count the records in the table, up to twice the estimate
+		let beg = thing::prefix(self.opt.ns(), self.opt.db(), t);
+		let end = thing::suffix(self.opt.ns(), self.opt.db(), t);
+		let limit = u32::try_from(n * 2).unwrap_or(u32::MAX);
+		let rows = txn.lock().await.count(beg..end, limit).await?;
+		if rows < limit as usize {
+			return Ok(Plan::TableIterator(Some("ESTIMATED TO MATCH MOST RECORDS".to_string())));
+		}
+		Ok(plan)
+	}
+
 	fn add(
 		&mut self,
 		tb: Table,
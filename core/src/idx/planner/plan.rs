@@ -159,8 +159,8 @@ impl PlanBuilder {
 pub(super) enum Plan {
 	TableIterator(Option<String>),
 	SingleIndex(Arc<Expression>, IndexOption),
-	MultiIndex(Vec<(Arc<Expression>, IndexOption)>, Vec<(IndexRef, UnionRangeQueryBuilder)>),
-	SingleIndexRange(IndexRef, UnionRangeQueryBuilder),
+	MultiIndex(Vec<(Arc<Expression>, IndexOption)>, Vec<(IndexRef, RangeQueryBuilder)>),
+	SingleIndexRange(IndexRef, RangeQueryBuilder),
 }
 
 #[derive(Debug, Eq, PartialEq, Hash, Clone)]
@@ -278,55 +278,37 @@ pub(super) struct RangeValue {
 }
 
 impl RangeValue {
+	/// Narrow the upper bound to an exclusive value
 	fn set_to(&mut self, v: &Value) {
-		if self.value.is_none() {
-			self.value = v.clone();
-			return;
-		}
-		if self.value.lt(v) {
+		if self.value.is_none() || self.value.gt(v) {
 			self.value = v.clone();
 			self.inclusive = false;
+		} else if self.value.eq(v) {
+			self.inclusive = false;
 		}
 	}
 
+	/// Narrow the upper bound to an inclusive value
 	fn set_to_inclusive(&mut self, v: &Value) {
-		if self.value.is_none() {
-			self.value = v.clone();
-			self.inclusive = true;
-			return;
-		}
-		if self.inclusive {
-			if self.value.lt(v) {
-				self.value = v.clone();
-			}
-		} else if self.value.le(v) {
+		if self.value.is_none() || self.value.gt(v) {
 			self.value = v.clone();
 			self.inclusive = true;
 		}
 	}
 
+	/// Narrow the lower bound to an exclusive value
 	fn set_from(&mut self, v: &Value) {
-		if self.value.is_none() {
-			self.value = v.clone();
-			return;
-		}
-		if self.value.gt(v) {
+		if self.value.is_none() || self.value.lt(v) {
 			self.value = v.clone();
 			self.inclusive = false;
+		} else if self.value.eq(v) {
+			self.inclusive = false;
 		}
 	}
 
+	/// Narrow the lower bound to an inclusive value
 	fn set_from_inclusive(&mut self, v: &Value) {
-		if self.value.is_none() {
-			self.value = v.clone();
-			self.inclusive = true;
-			return;
-		}
-		if self.inclusive {
-			if self.value.gt(v) {
-				self.value = v.clone();
-			}
-		} else if self.value.ge(v) {
+		if self.value.is_none() || self.value.lt(v) {
 			self.value = v.clone();
 			self.inclusive = true;
 		}
@@ -348,26 +330,26 @@ pub(super) struct Group {
 }
 
 impl Group {
-	fn take_first_range(self) -> Option<(IndexRef, UnionRangeQueryBuilder)> {
+	fn take_first_range(self) -> Option<(IndexRef, RangeQueryBuilder)> {
 		if let Some((ir, ri)) = self.ranges.into_iter().take(1).next() {
-			UnionRangeQueryBuilder::new_aggregate(ri).map(|rb| (ir, rb))
+			RangeQueryBuilder::new_aggregate(ri).map(|rb| (ir, rb))
 		} else {
 			None
 		}
 	}
 
-	fn take_union_ranges(self, r: &mut Vec<(IndexRef, UnionRangeQueryBuilder)>) {
+	fn take_union_ranges(self, r: &mut Vec<(IndexRef, RangeQueryBuilder)>) {
 		for (ir, ri) in self.ranges {
-			if let Some(rb) = UnionRangeQueryBuilder::new_aggregate(ri) {
+			if let Some(rb) = RangeQueryBuilder::new_aggregate(ri) {
 				r.push((ir, rb));
 			}
 		}
 	}
 
-	fn take_intersect_ranges(self, r: &mut Vec<(IndexRef, UnionRangeQueryBuilder)>) {
+	fn take_intersect_ranges(self, r: &mut Vec<(IndexRef, RangeQueryBuilder)>) {
 		for (ir, ri) in self.ranges {
 			for (exp, io) in ri {
-				if let Some(rb) = UnionRangeQueryBuilder::new(exp, io) {
+				if let Some(rb) = RangeQueryBuilder::new(exp, io) {
 					r.push((ir, rb));
 				}
 			}
@@ -375,14 +357,16 @@ impl Group {
 	}
 }
 
+/// The bounds of a range scan on an index, narrowed
+/// by each comparison within a group of AND conditions
 #[derive(Default, Debug)]
-pub(super) struct UnionRangeQueryBuilder {
+pub(super) struct RangeQueryBuilder {
 	pub(super) exps: HashSet<Arc<Expression>>,
 	pub(super) from: RangeValue,
 	pub(super) to: RangeValue,
 }
 
-impl UnionRangeQueryBuilder {
+impl RangeQueryBuilder {
 	fn new_aggregate(exp_ios: Vec<(Arc<Expression>, IndexOption)>) -> Option<Self> {
 		if exp_ios.is_empty() {
 			return None;
@@ -459,84 +443,84 @@ mod tests {
 	#[test]
 	fn test_range_value_from_inclusive() {
 		let mut r = RangeValue::default();
-		r.set_from_inclusive(&20.into());
-		assert_eq!(r.value, 20.into());
-		assert!(r.inclusive);
 		r.set_from_inclusive(&10.into());
 		assert_eq!(r.value, 10.into());
 		assert!(r.inclusive);
 		r.set_from_inclusive(&20.into());
-		assert_eq!(r.value, 10.into());
+		assert_eq!(r.value, 20.into());
+		assert!(r.inclusive);
+		r.set_from_inclusive(&10.into());
+		assert_eq!(r.value, 20.into());
 		assert!(r.inclusive);
 	}
 
 	#[test]
 	fn test_range_value_from() {
 		let mut r = RangeValue::default();
-		r.set_from(&20.into());
-		assert_eq!(r.value, 20.into());
-		assert!(!r.inclusive);
 		r.set_from(&10.into());
 		assert_eq!(r.value, 10.into());
 		assert!(!r.inclusive);
 		r.set_from(&20.into());
-		assert_eq!(r.value, 10.into());
+		assert_eq!(r.value, 20.into());
+		assert!(!r.inclusive);
+		r.set_from(&10.into());
+		assert_eq!(r.value, 20.into());
 		assert!(!r.inclusive);
 	}
 
 	#[test]
 	fn test_range_value_to_inclusive() {
 		let mut r = RangeValue::default();
-		r.set_to_inclusive(&10.into());
-		assert_eq!(r.value, 10.into());
-		assert!(r.inclusive);
 		r.set_to_inclusive(&20.into());
 		assert_eq!(r.value, 20.into());
 		assert!(r.inclusive);
 		r.set_to_inclusive(&10.into());
-		assert_eq!(r.value, 20.into());
+		assert_eq!(r.value, 10.into());
+		assert!(r.inclusive);
+		r.set_to_inclusive(&20.into());
+		assert_eq!(r.value, 10.into());
 		assert!(r.inclusive);
 	}
 
 	#[test]
 	fn test_range_value_to() {
 		let mut r = RangeValue::default();
-		r.set_to(&10.into());
-		assert_eq!(r.value, 10.into());
-		assert!(!r.inclusive);
 		r.set_to(&20.into());
 		assert_eq!(r.value, 20.into());
 		assert!(!r.inclusive);
 		r.set_to(&10.into());
-		assert_eq!(r.value, 20.into());
+		assert_eq!(r.value, 10.into());
+		assert!(!r.inclusive);
+		r.set_to(&20.into());
+		assert_eq!(r.value, 10.into());
 		assert!(!r.inclusive);
 	}
 
 	#[test]
 	fn test_range_value_to_switch_inclusive() {
 		let mut r = RangeValue::default();
-		r.set_to(&20.into());
-		assert_eq!(r.value, 20.into());
-		assert!(!r.inclusive);
 		r.set_to_inclusive(&20.into());
 		assert_eq!(r.value, 20.into());
 		assert!(r.inclusive);
 		r.set_to(&20.into());
 		assert_eq!(r.value, 20.into());
-		assert!(r.inclusive);
+		assert!(!r.inclusive);
+		r.set_to_inclusive(&20.into());
+		assert_eq!(r.value, 20.into());
+		assert!(!r.inclusive);
 	}
 
 	#[test]
 	fn test_range_value_from_switch_inclusive() {
 		let mut r = RangeValue::default();
-		r.set_from(&20.into());
-		assert_eq!(r.value, 20.into());
-		assert!(!r.inclusive);
 		r.set_from_inclusive(&20.into());
 		assert_eq!(r.value, 20.into());
 		assert!(r.inclusive);
 		r.set_from(&20.into());
 		assert_eq!(r.value, 20.into());
-		assert!(r.inclusive);
+		assert!(!r.inclusive);
+		r.set_from_inclusive(&20.into());
+		assert_eq!(r.value, 20.into());
+		assert!(!r.inclusive);
 	}
 }
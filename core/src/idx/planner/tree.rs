@@ -33,6 +33,7 @@ impl Tree {
 		with: &'a Option<With>,
 	) -> Result<Option<Self>, Error> {
 		let mut b = TreeBuilder::new(ctx, opt, txn, table, with);
+		b.check_with_indexes().await?;
 		if let Some(cond) = cond {
 			let root = b.eval_value(stk, 0, &cond.0).await?;
 			Ok(Some(Self {
@@ -118,6 +119,24 @@ impl<'a> TreeBuilder<'a> {
 		Ok(())
 	}
 
+	/// Ensure that every index of a WITH INDEX clause is defined on the table
+	async fn check_with_indexes(&mut self) -> Result<(), Error> {
+		if let Some(With::Index(ixs)) = self.with {
+			let mut tx = self.txn.lock().await;
+			self.lazy_load_schema_resolver(&mut tx, self.table).await?;
+			if let Some(schema) = self.schemas.get(self.table) {
+				for ix in ixs {
+					if !schema.indexes.iter().any(|i| i.name.0.eq(ix)) {
+						return Err(Error::IxNotFound {
+							value: ix.to_owned(),
+						});
+					}
+				}
+			}
+		}
+		Ok(())
+	}
+
 	/// Was marked recursive
 	async fn eval_value(
 		&mut self,
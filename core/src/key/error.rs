@@ -66,6 +66,8 @@ pub enum KeyCategory {
 	///
 	/// crate::key::scope::all               /*{ns}*{db}±{sc}
 	ScopeRoot,
+	/// crate::key::scope::rv                /*{ns}*{db}±{sc}!rv{rv}
+	ScopeRevokedToken,
 	/// crate::key::scope::tk                /*{ns}*{db}±{sc}!tk{tk}
 	ScopeToken,
 	///
@@ -151,6 +153,7 @@ impl Display for KeyCategory {
 			KeyCategory::DatabaseUser => "DatabaseUser",
 			KeyCategory::DatabaseVersionstamp => "DatabaseVersionstamp",
 			KeyCategory::ScopeRoot => "ScopeRoot",
+			KeyCategory::ScopeRevokedToken => "ScopeRevokedToken",
 			KeyCategory::ScopeToken => "ScopeToken",
 			KeyCategory::TableRoot => "TableRoot",
 			KeyCategory::TableEvent => "TableEvent",
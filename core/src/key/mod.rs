@@ -31,6 +31,7 @@
 /// crate::key::database::vs             /*{ns}*{db}!vs
 ///
 /// crate::key::scope::all               /*{ns}*{db}±{sc}
+/// crate::key::scope::rv                /*{ns}*{db}±{sc}!rv{rv}
 /// crate::key::scope::tk                /*{ns}*{db}±{sc}!tk{tk}
 ///
 /// crate::key::table::all               /*{ns}*{db}*{tb}
@@ -1,4 +1,5 @@
 pub mod all;
+pub mod rv;
 pub mod tk;
 
 const CHAR: u8 = 0xb1; // ±
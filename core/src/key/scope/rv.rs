@@ -0,0 +1,81 @@
+//! Stores a revoked scope authentication token
+use crate::key::error::KeyCategory;
+use crate::key::key_req::KeyRequirements;
+use derive::Key;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Key)]
+#[non_exhaustive]
+pub struct Rv<'a> {
+	__: u8,
+	_a: u8,
+	pub ns: &'a str,
+	_b: u8,
+	pub db: &'a str,
+	_c: u8,
+	pub sc: &'a str,
+	_d: u8,
+	_e: u8,
+	_f: u8,
+	pub rv: &'a str,
+}
+
+pub fn new<'a>(ns: &'a str, db: &'a str, sc: &'a str, rv: &'a str) -> Rv<'a> {
+	Rv::new(ns, db, sc, rv)
+}
+
+pub fn prefix(ns: &str, db: &str, sc: &str) -> Vec<u8> {
+	let mut k = super::all::new(ns, db, sc).encode().unwrap();
+	k.extend_from_slice(&[b'!', b'r', b'v', 0x00]);
+	k
+}
+
+pub fn suffix(ns: &str, db: &str, sc: &str) -> Vec<u8> {
+	let mut k = super::all::new(ns, db, sc).encode().unwrap();
+	k.extend_from_slice(&[b'!', b'r', b'v', 0xff]);
+	k
+}
+
+impl KeyRequirements for Rv<'_> {
+	fn key_category(&self) -> KeyCategory {
+		KeyCategory::ScopeRevokedToken
+	}
+}
+
+impl<'a> Rv<'a> {
+	pub fn new(ns: &'a str, db: &'a str, sc: &'a str, rv: &'a str) -> Self {
+		Self {
+			__: b'/',
+			_a: b'*',
+			ns,
+			_b: b'*',
+			db,
+			_c: super::CHAR,
+			sc,
+			_d: b'!',
+			_e: b'r',
+			_f: b'v',
+			rv,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	#[test]
+	fn key() {
+		use super::*;
+		#[rustfmt::skip]
+		let val = Rv::new(
+			"testns",
+			"testdb",
+			"testsc",
+			"testrv",
+		);
+		let enc = Rv::encode(&val).unwrap();
+		assert_eq!(enc, b"/*testns\0*testdb\0\xb1testsc\0!rvtestrv\0");
+
+		let dec = Rv::decode(&enc).unwrap();
+		assert_eq!(val, dec);
+	}
+}
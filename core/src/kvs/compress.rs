@@ -0,0 +1,197 @@
+#[cfg(not(target_arch = "wasm32"))]
+use crate::cnf::MAX_DECOMPRESSED_IMPORT_SIZE;
+use crate::err::Error;
+#[cfg(not(target_arch = "wasm32"))]
+use async_compression::tokio::{
+	bufread::{GzipDecoder, ZstdDecoder},
+	write::{GzipEncoder, ZstdEncoder},
+};
+use channel::{Receiver, Sender};
+use futures::Future;
+use std::borrow::Cow;
+#[cfg(not(target_arch = "wasm32"))]
+use tokio::io::{AsyncReadExt, AsyncWrite, AsyncWriteExt};
+
+/// The magic bytes at the start of a gzip stream
+const GZIP_MAGIC: &[u8] = &[0x1f, 0x8b];
+/// The magic bytes at the start of a zstd frame
+const ZSTD_MAGIC: &[u8] = &[0x28, 0xb5, 0x2f, 0xfd];
+
+/// How the output of an export is compressed
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum Compression {
+	/// The export is not compressed
+	#[default]
+	None,
+	/// The export is compressed using gzip
+	Gzip,
+	/// The export is compressed using zstd
+	Zstd,
+}
+
+impl Compression {
+	/// Detects how an import is compressed from its leading magic bytes
+	pub fn detect(bytes: &[u8]) -> Self {
+		if bytes.starts_with(GZIP_MAGIC) {
+			Self::Gzip
+		} else if bytes.starts_with(ZSTD_MAGIC) {
+			Self::Zstd
+		} else {
+			Self::None
+		}
+	}
+}
+
+/// Returns the channel which an export is written to, along with the task
+/// which compresses everything written to it into the specified channel.
+/// Uncompressed exports are written directly to the specified channel.
+pub(super) fn compressor(
+	compression: Compression,
+	chn: Sender<Vec<u8>>,
+) -> (Sender<Vec<u8>>, impl Future<Output = Result<(), Error>>) {
+	let (snd, pipe) = match compression {
+		Compression::None => (chn, None),
+		_ => {
+			let (snd, rcv) = channel::bounded(1);
+			(snd, Some((rcv, chn)))
+		}
+	};
+	let task = async move {
+		match pipe {
+			Some((rcv, chn)) => compress(compression, rcv, chn).await,
+			None => Ok(()),
+		}
+	};
+	(snd, task)
+}
+
+/// Compresses each chunk which is received, sending on any compressed
+/// output, until every sender of the export channel has been dropped
+#[cfg(not(target_arch = "wasm32"))]
+async fn compress(
+	compression: Compression,
+	rcv: Receiver<Vec<u8>>,
+	chn: Sender<Vec<u8>>,
+) -> Result<(), Error> {
+	match compression {
+		Compression::Gzip => {
+			encode(GzipEncoder::new(Vec::new()), GzipEncoder::get_mut, rcv, chn).await
+		}
+		Compression::Zstd => {
+			encode(ZstdEncoder::new(Vec::new()), ZstdEncoder::get_mut, rcv, chn).await
+		}
+		Compression::None => Ok(()),
+	}
+}
+
+#[cfg(target_arch = "wasm32")]
+async fn compress(
+	_compression: Compression,
+	_rcv: Receiver<Vec<u8>>,
+	_chn: Sender<Vec<u8>>,
+) -> Result<(), Error> {
+	Err(Error::Unimplemented("Compressed exports are not supported on this platform".to_owned()))
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+async fn encode<E>(
+	mut enc: E,
+	output: fn(&mut E) -> &mut Vec<u8>,
+	rcv: Receiver<Vec<u8>>,
+	chn: Sender<Vec<u8>>,
+) -> Result<(), Error>
+where
+	E: AsyncWrite + Unpin,
+{
+	while let Ok(chunk) = rcv.recv().await {
+		enc.write_all(&chunk).await?;
+		// Send on anything which has been compressed so far
+		let out = std::mem::take(output(&mut enc));
+		if !out.is_empty() {
+			chn.send(out).await?;
+		}
+	}
+	// Finish the compressed stream
+	enc.shutdown().await?;
+	let out = std::mem::take(output(&mut enc));
+	if !out.is_empty() {
+		chn.send(out).await?;
+	}
+	Ok(())
+}
+
+/// Decompresses an import, detecting how it is compressed from its magic bytes
+#[cfg(not(target_arch = "wasm32"))]
+pub(super) async fn decompress(bytes: &[u8]) -> Result<Cow<'_, [u8]>, Error> {
+	decompress_limited(bytes, *MAX_DECOMPRESSED_IMPORT_SIZE).await
+}
+
+/// Decompresses an import, rejecting it once it decompresses to more than
+/// the limit, so that a small compressed import can not exhaust the memory
+#[cfg(not(target_arch = "wasm32"))]
+async fn decompress_limited(bytes: &[u8], limit: usize) -> Result<Cow<'_, [u8]>, Error> {
+	let mut out = Vec::new();
+	// Read one byte past the limit, to detect when it is exceeded
+	let max = (limit as u64).saturating_add(1);
+	match Compression::detect(bytes) {
+		Compression::Gzip => GzipDecoder::new(bytes).take(max).read_to_end(&mut out).await?,
+		Compression::Zstd => ZstdDecoder::new(bytes).take(max).read_to_end(&mut out).await?,
+		Compression::None => return Ok(Cow::Borrowed(bytes)),
+	};
+	if out.len() > limit {
+		return Err(Error::InvalidImport {
+			message: format!("The import decompresses to more than {limit} bytes"),
+		});
+	}
+	Ok(Cow::Owned(out))
+}
+
+#[cfg(target_arch = "wasm32")]
+pub(super) async fn decompress(bytes: &[u8]) -> Result<Cow<'_, [u8]>, Error> {
+	match Compression::detect(bytes) {
+		Compression::None => Ok(Cow::Borrowed(bytes)),
+		_ => Err(Error::Unimplemented(
+			"Compressed imports are not supported on this platform".to_owned(),
+		)),
+	}
+}
+
+/// Decompresses an import, and checks that it is valid UTF-8
+pub(super) async fn decompress_str(bytes: &[u8]) -> Result<Cow<'_, str>, Error> {
+	let invalid = || Error::InvalidImport {
+		message: "The import is not valid UTF-8".to_owned(),
+	};
+	Ok(match decompress(bytes).await? {
+		Cow::Borrowed(v) => Cow::Borrowed(std::str::from_utf8(v).map_err(|_| invalid())?),
+		Cow::Owned(v) => Cow::Owned(String::from_utf8(v).map_err(|_| invalid())?),
+	})
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn compression_detect() {
+		assert_eq!(Compression::detect(b""), Compression::None);
+		assert_eq!(Compression::detect(b"DEFINE TABLE person;"), Compression::None);
+		assert_eq!(Compression::detect(&[0x1f, 0x8b, 0x08, 0x00]), Compression::Gzip);
+		assert_eq!(Compression::detect(&[0x28, 0xb5, 0x2f, 0xfd, 0x00]), Compression::Zstd);
+	}
+
+	#[cfg(not(target_arch = "wasm32"))]
+	#[tokio::test]
+	async fn decompress_limited_size() {
+		let mut enc = GzipEncoder::new(Vec::new());
+		enc.write_all(&[b'a'; 1024]).await.unwrap();
+		enc.shutdown().await.unwrap();
+		let bytes = enc.into_inner();
+		// An import which decompresses to within the limit is accepted
+		let out = decompress_limited(&bytes, 1024).await.unwrap();
+		assert_eq!(out.len(), 1024);
+		// An import which decompresses to more than the limit is rejected
+		let res = decompress_limited(&bytes, 1023).await;
+		assert!(matches!(res, Err(Error::InvalidImport { .. })));
+	}
+}
@@ -0,0 +1,110 @@
+use crate::sql::Value;
+use serde_json::Value as Json;
+use std::collections::BTreeMap;
+
+/// Flattens a record into its columns, where the fields of nested
+/// objects are named using dot notation, such as `address.city`.
+pub(super) fn flatten(val: &Value) -> BTreeMap<String, &Value> {
+	fn walk<'a>(prefix: Option<&str>, val: &'a Value, out: &mut BTreeMap<String, &'a Value>) {
+		match val {
+			Value::Object(v) => {
+				for (k, v) in v.iter() {
+					let key = match prefix {
+						Some(p) => format!("{p}.{k}"),
+						None => k.to_owned(),
+					};
+					walk(Some(&key), v, out);
+				}
+			}
+			v => {
+				if let Some(p) = prefix {
+					out.insert(p.to_owned(), v);
+				}
+			}
+		}
+	}
+	let mut out = BTreeMap::new();
+	walk(None, val, &mut out);
+	out
+}
+
+/// Checks if a column belongs to the specified field
+pub(super) fn is_column_of(column: &str, field: &str) -> bool {
+	match column.strip_prefix(field) {
+		Some(rest) => rest.is_empty() || rest.starts_with('.'),
+		None => false,
+	}
+}
+
+/// Formats a value as a single CSV cell. Empty values produce empty
+/// cells, and arrays are written as JSON within a quoted cell.
+pub(super) fn cell(val: Option<&Value>) -> String {
+	let (text, quote) = match val {
+		None | Some(Value::None) | Some(Value::Null) => return String::new(),
+		Some(v @ Value::Array(_)) => (v.clone().into_json().to_string(), true),
+		Some(v) => match v.clone().into_json() {
+			Json::String(s) => (s, false),
+			Json::Null => return String::new(),
+			v => (v.to_string(), false),
+		},
+	};
+	escape(text, quote)
+}
+
+/// Formats a row of cells as a line of CSV
+pub(super) fn row<I: IntoIterator<Item = String>>(cells: I) -> Vec<u8> {
+	let mut line = cells.into_iter().collect::<Vec<_>>().join(",");
+	line.push_str("\r\n");
+	line.into_bytes()
+}
+
+/// Quotes a cell according to RFC 4180 if it contains a comma,
+/// a quote, or a line break, escaping any quotes by doubling them.
+pub(super) fn escape(text: String, quote: bool) -> String {
+	if quote || text.contains([',', '"', '\r', '\n']) {
+		format!("\"{}\"", text.replace('"', "\"\""))
+	} else {
+		text
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::syn::value;
+
+	#[test]
+	fn escape_cells() {
+		assert_eq!(escape("plain".to_owned(), false), "plain");
+		assert_eq!(escape("a,b".to_owned(), false), "\"a,b\"");
+		assert_eq!(escape("say \"hi\"".to_owned(), false), "\"say \"\"hi\"\"\"");
+		assert_eq!(escape("two\nlines".to_owned(), false), "\"two\nlines\"");
+		assert_eq!(escape("crlf\r".to_owned(), false), "\"crlf\r\"");
+		assert_eq!(escape("东京".to_owned(), false), "东京");
+		assert_eq!(escape(String::new(), true), "\"\"");
+	}
+
+	#[test]
+	fn format_cells() {
+		assert_eq!(cell(None), "");
+		assert_eq!(cell(Some(&Value::None)), "");
+		assert_eq!(cell(Some(&Value::Null)), "");
+		assert_eq!(cell(Some(&value("'text'").unwrap())), "text");
+		assert_eq!(cell(Some(&value("1.5").unwrap())), "1.5");
+		assert_eq!(cell(Some(&value("true").unwrap())), "true");
+		assert_eq!(cell(Some(&value("person:tobie").unwrap())), "person:tobie");
+		assert_eq!(cell(Some(&value("[1, 'a']").unwrap())), "\"[1,\"\"a\"\"]\"");
+		assert_eq!(cell(Some(&value("[]").unwrap())), "\"[]\"");
+	}
+
+	#[test]
+	fn flatten_objects() {
+		let val = value("{ name: 'Tobie', address: { city: 'London', geo: { lat: 1 } } }").unwrap();
+		let out = flatten(&val);
+		let keys = out.keys().map(String::as_str).collect::<Vec<_>>();
+		assert_eq!(keys, vec!["address.city", "address.geo.lat", "name"]);
+		assert!(is_column_of("address.city", "address"));
+		assert!(is_column_of("address", "address"));
+		assert!(!is_column_of("addresses", "address"));
+	}
+}
@@ -1,4 +1,4 @@
-use std::collections::{BTreeMap, BTreeSet};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 #[cfg(any(
 	feature = "kv-surrealkv",
 	feature = "kv-file",
@@ -18,6 +18,8 @@ use std::fmt;
 	feature = "kv-speedb"
 ))]
 use std::path::PathBuf;
+#[cfg(any(test, feature = "test-hooks"))]
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
 use std::time::Duration;
 #[cfg(not(target_arch = "wasm32"))]
@@ -29,18 +31,21 @@ use reblessive::{tree::Stk, TreeStack};
 use tokio::sync::RwLock;
 use tracing::instrument;
 use tracing::trace;
+use trice::Instant;
 
 #[cfg(target_arch = "wasm32")]
 use wasmtimer::std::{SystemTime, UNIX_EPOCH};
 
+use super::compress::{self, Compression};
+use super::import::{self, ImportOptions, ImportStats};
 use super::tx::Transaction;
 use crate::cf;
 use crate::ctx::Context;
 #[cfg(feature = "jwks")]
 use crate::dbs::capabilities::NetTarget;
 use crate::dbs::{
-	node::Timestamp, Attach, Capabilities, Executor, Notification, Options, Response, Session,
-	Variables,
+	node::Timestamp, Attach, Capabilities, Executor, Notification, Options, Response, RetryPolicy,
+	Session, Variables,
 };
 use crate::err::Error;
 #[cfg(feature = "jwks")]
@@ -56,7 +61,9 @@ use crate::kvs::lq_structs::{LqValue, TrackedResult, UnreachableLqType};
 use crate::kvs::lq_v2_fut::process_lq_notifications;
 use crate::kvs::{LockType, LockType::*, TransactionType, TransactionType::*};
 use crate::options::EngineOptions;
-use crate::sql::{self, statements::DefineUserStatement, Base, Query, Uuid, Value};
+use crate::sql::{
+	self, statements::DefineUserStatement, Base, Limit, LimitKind, Query, Start, Uuid, Value,
+};
 use crate::syn;
 use crate::vs::{conv, Oracle, Versionstamp};
 
@@ -86,6 +93,10 @@ pub struct Datastore {
 	query_timeout: Option<Duration>,
 	// The maximum duration timeout for running multiple statements in a transaction
 	transaction_timeout: Option<Duration>,
+	// How statements are retried when their transaction conflicts
+	retry_policy: Option<RetryPolicy>,
+	// How many results are collected in memory before spilling to temporary files
+	spill_threshold: Option<usize>,
 	// Capabilities for this datastore
 	capabilities: Capabilities,
 	pub(super) engine_options: EngineOptions,
@@ -112,6 +123,9 @@ pub struct Datastore {
 	// The temporary directory
 	temporary_directory: Arc<PathBuf>,
 	pub(crate) lq_cf_store: Arc<RwLock<LiveQueryTracker>>,
+	// The number of commits which fail with a simulated transaction conflict
+	#[cfg(any(test, feature = "test-hooks"))]
+	conflicts: Arc<AtomicUsize>,
 }
 
 /// We always want to be circulating the live query information
@@ -376,6 +390,8 @@ impl Datastore {
 			auth_level_enabled: false,
 			query_timeout: None,
 			transaction_timeout: None,
+			retry_policy: None,
+			spill_threshold: None,
 			notification_channel: None,
 			capabilities: Capabilities::default(),
 			engine_options: EngineOptions::default(),
@@ -394,6 +410,8 @@ impl Datastore {
 			))]
 			temporary_directory: Arc::new(env::temp_dir()),
 			lq_cf_store: Arc::new(RwLock::new(LiveQueryTracker::new())),
+			#[cfg(any(test, feature = "test-hooks"))]
+			conflicts: Arc::new(AtomicUsize::new(0)),
 		})
 	}
 
@@ -427,6 +445,35 @@ impl Datastore {
 		self
 	}
 
+	/// Set how statements are retried when their transaction conflicts
+	pub fn with_retry_policy(mut self, policy: Option<RetryPolicy>) -> Self {
+		self.retry_policy = policy;
+		self
+	}
+
+	/// Make the next commits on this Datastore fail with a transaction
+	/// conflict, so that retried statements can be tested without any
+	/// concurrent transactions
+	#[cfg(any(test, feature = "test-hooks"))]
+	#[doc(hidden)]
+	pub fn set_conflicts(&self, count: usize) {
+		self.conflicts.store(count, Ordering::SeqCst);
+	}
+
+	/// The number of simulated transaction conflicts which remain
+	#[cfg(any(test, feature = "test-hooks"))]
+	#[doc(hidden)]
+	pub fn conflicts(&self) -> usize {
+		self.conflicts.load(Ordering::SeqCst)
+	}
+
+	/// Set how many results are collected in memory before sorting
+	/// and grouping spills them to temporary files
+	pub fn with_spill_threshold(mut self, threshold: Option<usize>) -> Self {
+		self.spill_threshold = threshold;
+		self
+	}
+
 	/// Set whether authentication is enabled for this Datastore
 	pub fn with_auth_enabled(mut self, enabled: bool) -> Self {
 		self.auth_enabled = enabled;
@@ -1104,12 +1151,16 @@ impl Datastore {
 		#[allow(unreachable_code)]
 		Ok(Transaction {
 			inner,
+			write,
 			cache: super::cache::Cache::default(),
+			savepoints: super::savepoint::Savepoints::default(),
 			cf: cf::Writer::new(),
 			vso: self.versionstamp_oracle.clone(),
 			clock: self.clock.clone(),
 			prepared_async_events: (Arc::new(send), Arc::new(recv)),
 			engine_options: self.engine_options,
+			#[cfg(any(test, feature = "test-hooks"))]
+			conflicts: self.conflicts.clone(),
 		})
 	}
 
@@ -1142,6 +1193,52 @@ impl Datastore {
 		self.process(ast, sess, vars).await
 	}
 
+	/// Parse and execute an SQL query within a session, keeping any
+	/// variables set by LET statements for the next queries
+	///
+	/// ```rust,no_run
+	/// use surrealdb_core::kvs::Datastore;
+	/// use surrealdb_core::err::Error;
+	/// use surrealdb_core::dbs::Session;
+	///
+	/// #[tokio::main]
+	/// async fn main() -> Result<(), Error> {
+	///     let ds = Datastore::new("memory").await?;
+	///     let mut ses = Session::owner().with_ns("test").with_db("test");
+	///     ds.execute_in_session("LET $name = 'Tobie';", &mut ses, None).await?;
+	///     let res = ds.execute_in_session("RETURN $name;", &mut ses, None).await?;
+	///     Ok(())
+	/// }
+	/// ```
+	#[instrument(level = "debug", skip_all)]
+	pub async fn execute_in_session(
+		&self,
+		txt: &str,
+		sess: &mut Session,
+		vars: Variables,
+	) -> Result<Vec<Response>, Error> {
+		// Parse the SQL query text
+		let ast = syn::parse(txt)?;
+		// Process the AST
+		self.process_in_session(ast, sess, vars).await
+	}
+
+	/// Execute a pre-parsed SQL query within a session, keeping any
+	/// variables set by LET statements for the next queries
+	#[instrument(level = "debug", skip_all)]
+	pub async fn process_in_session(
+		&self,
+		ast: Query,
+		sess: &mut Session,
+		vars: Variables,
+	) -> Result<Vec<Response>, Error> {
+		// Process the query
+		let (res, vars) = self.process_query(ast, sess, vars).await?;
+		// Store the variables in the session
+		sess.variables.extend(vars);
+		Ok(res)
+	}
+
 	/// Execute a pre-parsed SQL query
 	///
 	/// ```rust,no_run
@@ -1166,8 +1263,19 @@ impl Datastore {
 		sess: &Session,
 		vars: Variables,
 	) -> Result<Vec<Response>, Error> {
+		self.process_query(ast, sess, vars).await.map(|(res, _)| res)
+	}
+
+	/// Execute a pre-parsed SQL query, returning the
+	/// responses and any variables set by LET statements
+	async fn process_query(
+		&self,
+		ast: Query,
+		sess: &Session,
+		vars: Variables,
+	) -> Result<(Vec<Response>, HashMap<String, Value>), Error> {
 		// Check if the session has expired
-		if sess.expired() {
+		if sess.is_expired() {
 			return Err(Error::ExpiredSession);
 		}
 		// Check if anonymous actors can execute queries when auth is enabled
@@ -1188,7 +1296,12 @@ impl Datastore {
 			.with_live(sess.live())
 			.with_auth(sess.au.clone())
 			.with_strict(self.strict)
-			.with_auth_enabled(self.auth_enabled);
+			.with_auth_enabled(self.auth_enabled)
+			.with_futures_never(sess.defer_futures())
+			.with_read_only(sess.read_only())
+			.with_collect_metrics(sess.collect_metrics())
+			.with_retry_policy(sess.retry_policy().or(self.retry_policy))
+			.with_spill_threshold(self.spill_threshold);
 		// Create a new query executor
 		let mut exe = Executor::new(self);
 		// Create a default context
@@ -1229,7 +1342,7 @@ impl Datastore {
 			Ok((responses, lives)) => {
 				// Register live queries
 				self.handle_postprocessing_of_statements(&lives).await?;
-				Ok(responses)
+				Ok((responses, exe.take_variables()))
 			}
 			Err(e) => Err(e),
 		}
@@ -1261,7 +1374,7 @@ impl Datastore {
 		vars: Variables,
 	) -> Result<Value, Error> {
 		// Check if the session has expired
-		if sess.expired() {
+		if sess.is_expired() {
 			return Err(Error::ExpiredSession);
 		}
 
@@ -1285,7 +1398,9 @@ impl Datastore {
 			.with_live(sess.live())
 			.with_auth(sess.au.clone())
 			.with_strict(self.strict)
-			.with_auth_enabled(self.auth_enabled);
+			.with_auth_enabled(self.auth_enabled)
+			.with_futures_never(sess.defer_futures())
+			.with_read_only(sess.read_only());
 		// Create a default context
 		let mut ctx = Context::default();
 		// Set context capabilities
@@ -1302,12 +1417,14 @@ impl Datastore {
 		let ctx = sess.context(ctx);
 		// Store the query variables
 		let ctx = vars.attach(ctx)?;
+		// Read-only sessions never open writeable transactions
+		let write = val.writeable() && !opt.read_only;
 		// Start a new transaction
-		let txn = self.transaction(val.writeable().into(), Optimistic).await?.enclose();
+		let txn = self.transaction(write.into(), Optimistic).await?.enclose();
 		// Compute the value
 		let res = stack.enter(|stk| val.compute(stk, &ctx, &opt, &txn, None)).finish().await;
 		// Store any data
-		match (res.is_ok(), val.writeable()) {
+		match (res.is_ok(), write) {
 			// If the compute was successful, then commit if writeable
 			(true, true) => txn.lock().await.commit().await?,
 			// Cancel if the compute was an error, or if readonly
@@ -1347,7 +1464,7 @@ impl Datastore {
 		vars: Variables,
 	) -> Result<Value, Error> {
 		// Check if the session has expired
-		if sess.expired() {
+		if sess.is_expired() {
 			return Err(Error::ExpiredSession);
 		}
 
@@ -1360,7 +1477,9 @@ impl Datastore {
 			.with_live(sess.live())
 			.with_auth(sess.au.clone())
 			.with_strict(self.strict)
-			.with_auth_enabled(self.auth_enabled);
+			.with_auth_enabled(self.auth_enabled)
+			.with_futures_never(sess.defer_futures())
+			.with_read_only(sess.read_only());
 		// Create a default context
 		let mut ctx = Context::default();
 		// Set context capabilities
@@ -1377,12 +1496,14 @@ impl Datastore {
 		let ctx = sess.context(ctx);
 		// Store the query variables
 		let ctx = vars.attach(ctx)?;
+		// Read-only sessions never open writeable transactions
+		let write = val.writeable() && !opt.read_only;
 		// Start a new transaction
-		let txn = self.transaction(val.writeable().into(), Optimistic).await?.enclose();
+		let txn = self.transaction(write.into(), Optimistic).await?.enclose();
 		// Compute the value
 		let res = stack.enter(|stk| val.compute(stk, &ctx, &opt, &txn, None)).finish().await;
 		// Store any data
-		match (res.is_ok(), val.writeable()) {
+		match (res.is_ok(), write) {
 			// If the compute was successful, then commit if writeable
 			(true, true) => txn.lock().await.commit().await?,
 			// Cancel if the compute was an error, or if readonly
@@ -1416,22 +1537,292 @@ impl Datastore {
 		self.notification_channel.as_ref().map(|v| v.1.clone())
 	}
 
-	/// Performs a database import from SQL
+	/// Performs a database import from SQL, which is decompressed
+	/// if it was exported using any [`Compression`]
 	#[instrument(level = "debug", skip(self, sess, sql))]
-	pub async fn import(&self, sql: &str, sess: &Session) -> Result<Vec<Response>, Error> {
+	pub async fn import(
+		&self,
+		sql: impl AsRef<[u8]>,
+		sess: &Session,
+	) -> Result<Vec<Response>, Error> {
+		// Decompress the import if necessary
+		let sql = compress::decompress_str(sql.as_ref()).await?;
 		// Execute the SQL import
-		self.execute(sql, sess, None).await
+		self.execute(&sql, sess, None).await
+	}
+
+	/// Performs a database import from newline-delimited JSON, as written by
+	/// [`Datastore::export_ndjson`]. Lines are imported in batches, each within
+	/// its own transaction, and a dry run validates every line within a single
+	/// transaction which is then cancelled. The import is decompressed if it
+	/// was exported using any [`Compression`].
+	#[instrument(level = "debug", skip_all)]
+	pub async fn import_ndjson(
+		&self,
+		ndjson: impl AsRef<[u8]>,
+		sess: &Session,
+		opts: ImportOptions,
+	) -> Result<ImportStats, Error> {
+		// Check if the session has expired
+		if sess.is_expired() {
+			return Err(Error::ExpiredSession);
+		}
+		// Check that a NS and DB have been provided
+		crate::iam::check::check_ns_db(sess)?;
+		// Start the import timer
+		let start = Instant::now();
+		let mut stats = ImportStats::default();
+		// Decompress the import if necessary
+		let ndjson = compress::decompress_str(ndjson.as_ref()).await?;
+		// Create a new query options
+		let opt = Options::default()
+			.with_id(self.id.0)
+			.with_ns(sess.ns())
+			.with_db(sess.db())
+			.with_auth(sess.au.clone())
+			.with_strict(self.strict)
+			.with_auth_enabled(self.auth_enabled);
+		// Create a default context
+		let mut ctx = Context::default();
+		// Set context capabilities
+		ctx.add_capabilities(self.capabilities.clone());
+		// Start an execution context
+		let ctx = sess.context(ctx);
+		// Number each of the non-empty lines
+		let lines: Vec<(usize, &str)> = ndjson
+			.lines()
+			.enumerate()
+			.filter(|(_, line)| !line.trim().is_empty())
+			.map(|(i, line)| (i + 1, line))
+			.collect();
+		// A dry run validates every line in one transaction
+		let size = match opts.dry_run {
+			true => lines.len(),
+			false => opts.batch_size,
+		};
+		let mut stack = TreeStack::new();
+		for batch in lines.chunks(size.max(1)) {
+			// Start a new transaction
+			let txn = self.transaction(Write, Optimistic).await?.enclose();
+			// Import the batch of lines
+			let res = stack
+				.enter(|stk| import::import_batch(stk, &ctx, &opt, &txn, batch, &opts, &mut stats))
+				.finish()
+				.await;
+			// Store any data
+			match (res, opts.dry_run) {
+				(Ok(_), false) => {
+					let mut txn = txn.lock().await;
+					// Record the changes in any change feeds
+					txn.complete_changes(false).await?;
+					txn.commit().await?
+				}
+				(Ok(_), true) => txn.lock().await.cancel().await?,
+				(Err(e), _) => {
+					txn.lock().await.cancel().await?;
+					return Err(e);
+				}
+			}
+		}
+		// Everything ok
+		stats.elapsed = start.elapsed();
+		Ok(stats)
 	}
 
-	/// Performs a full database export as SQL
+	/// Performs a full database export as SQL, using the specified compression
 	#[instrument(level = "debug", skip(self, sess, chn))]
 	pub async fn export(
 		&self,
 		sess: &Session,
+		compression: Compression,
+		chn: Sender<Vec<u8>>,
+	) -> Result<impl Future<Output = Result<(), Error>>, Error> {
+		// Check if the session has expired
+		if sess.is_expired() {
+			return Err(Error::ExpiredSession);
+		}
+		// Retrieve the provided NS and DB
+		let (ns, db) = crate::iam::check::check_ns_db(sess)?;
+		// Create a new readonly transaction
+		let mut txn = self.transaction(Read, Optimistic).await?;
+		// Return an async export job
+		Ok(async move {
+			// Compress the export if necessary
+			let (snd, compressor) = compress::compressor(compression, chn);
+			// Process the export
+			futures::try_join!(txn.export(&ns, &db, snd), compressor)?;
+			// Everything ok
+			Ok(())
+		})
+	}
+
+	/// Performs a database export as newline-delimited JSON, using the
+	/// specified compression
+	#[instrument(level = "debug", skip(self, sess, chn))]
+	pub async fn export_ndjson(
+		&self,
+		sess: &Session,
+		tables: Vec<String>,
+		compression: Compression,
+		chn: Sender<Vec<u8>>,
+	) -> Result<impl Future<Output = Result<(), Error>>, Error> {
+		// Check if the session has expired
+		if sess.is_expired() {
+			return Err(Error::ExpiredSession);
+		}
+		// Retrieve the provided NS and DB
+		let (ns, db) = crate::iam::check::check_ns_db(sess)?;
+		// Create a new readonly transaction
+		let mut txn = self.transaction(Read, Optimistic).await?;
+		// Return an async export job
+		Ok(async move {
+			// Fetch the specified tables
+			let tables: Vec<&str> = tables.iter().map(String::as_str).collect();
+			// Compress the export if necessary
+			let (snd, compressor) = compress::compressor(compression, chn);
+			// Process the export
+			futures::try_join!(txn.export_ndjson(&ns, &db, &tables, snd), compressor)?;
+			// Everything ok
+			Ok(())
+		})
+	}
+
+	/// Performs an export of the records of a table in batches as
+	/// newline-delimited JSON, using the specified compression. The START
+	/// and LIMIT of each batch are computed with the `$cursor` parameter set
+	/// to the number of the batch, counting from zero, so that a table can be
+	/// paged through with expressions such as `START $cursor * 100 LIMIT 100`.
+	/// The export ends after a batch which has fewer records than its LIMIT,
+	/// or before a batch which has a LIMIT of zero, or which does not start
+	/// after the previous batch. A START or LIMIT which is too large for this
+	/// platform returns an error, rather than being truncated.
+	#[instrument(level = "debug", skip(self, sess, chn))]
+	pub async fn export_paged(
+		&self,
+		sess: &Session,
+		table: String,
+		start: Value,
+		limit: Value,
+		compression: Compression,
+		chn: Sender<Vec<u8>>,
+	) -> Result<impl Future<Output = Result<(), Error>>, Error> {
+		// Check if the session has expired
+		if sess.is_expired() {
+			return Err(Error::ExpiredSession);
+		}
+		// Each batch selects a number of rows
+		let start = Start(start);
+		let limit = Limit(limit, LimitKind::Rows);
+		// Retrieve the provided NS and DB
+		let (ns, db) = crate::iam::check::check_ns_db(sess)?;
+		// Create a new query options
+		let opt = Options::default()
+			.with_id(self.id.0)
+			.with_ns(sess.ns())
+			.with_db(sess.db())
+			.with_auth(sess.au.clone())
+			.with_strict(self.strict)
+			.with_auth_enabled(self.auth_enabled)
+			.with_futures_never(sess.defer_futures())
+			.with_read_only(true);
+		// Create a default context
+		let mut ctx = Context::default();
+		// Set context capabilities
+		ctx.add_capabilities(self.capabilities.clone());
+		// Start an execution context
+		let ctx = sess.context(ctx);
+		// Create a new readonly transaction
+		let txn = self.transaction(Read, Optimistic).await?.enclose();
+		// Return an async export job
+		Ok(async move {
+			// Compress the export if necessary
+			let (snd, compressor) = compress::compressor(compression, chn);
+			// Process the export
+			let export = async move {
+				let mut stack = TreeStack::new();
+				let mut prev: Option<usize> = None;
+				let mut resume = None;
+				for cursor in 0i64.. {
+					// Compute the START and LIMIT of the batch
+					let mut ctx = Context::new(&ctx);
+					ctx.add_value("cursor", Value::from(cursor));
+					let beg = stack
+						.enter(|stk| start.process(stk, &ctx, &opt, &txn, None))
+						.finish()
+						.await?;
+					let len = stack
+						.enter(|stk| limit.process(stk, &ctx, &opt, &txn, None))
+						.finish()
+						.await?;
+					// Stop once a batch does not move forward
+					if len == 0 || prev.is_some_and(|prev| beg <= prev) {
+						break;
+					}
+					prev = Some(beg);
+					// Output the records of the batch
+					let (count, next) = txn
+						.lock()
+						.await
+						.export_ndjson_batch(&ns, &db, &table, beg, len, resume, snd.clone())
+						.await?;
+					// Stop once the table has been exhausted
+					if count < len || next.is_none() {
+						break;
+					}
+					resume = next;
+				}
+				Ok::<(), Error>(())
+			};
+			futures::try_join!(export, compressor)?;
+			// Everything ok
+			Ok(())
+		})
+	}
+
+	/// Performs an export of the records which have changed since the
+	/// specified time as newline-delimited JSON, using the specified
+	/// compression. Changes are read from the change feeds of the database,
+	/// and should be imported using [`ImportMode::Replace`](super::ImportMode).
+	#[instrument(level = "debug", skip(self, sess, chn))]
+	pub async fn export_incremental(
+		&self,
+		sess: &Session,
+		since: sql::Datetime,
+		compression: Compression,
+		chn: Sender<Vec<u8>>,
+	) -> Result<impl Future<Output = Result<(), Error>>, Error> {
+		// Check if the session has expired
+		if sess.is_expired() {
+			return Err(Error::ExpiredSession);
+		}
+		// Retrieve the provided NS and DB
+		let (ns, db) = crate::iam::check::check_ns_db(sess)?;
+		// Create a new readonly transaction
+		let mut txn = self.transaction(Read, Optimistic).await?;
+		// Return an async export job
+		Ok(async move {
+			// Compress the export if necessary
+			let (snd, compressor) = compress::compressor(compression, chn);
+			// Process the export
+			futures::try_join!(txn.export_incremental(&ns, &db, &since, snd), compressor)?;
+			// Everything ok
+			Ok(())
+		})
+	}
+
+	/// Performs an export of the records of a table as CSV, using the
+	/// specified compression
+	#[instrument(level = "debug", skip(self, sess, chn))]
+	pub async fn export_csv(
+		&self,
+		sess: &Session,
+		table: String,
+		fields: Vec<String>,
+		compression: Compression,
 		chn: Sender<Vec<u8>>,
 	) -> Result<impl Future<Output = Result<(), Error>>, Error> {
 		// Check if the session has expired
-		if sess.expired() {
+		if sess.is_expired() {
 			return Err(Error::ExpiredSession);
 		}
 		// Retrieve the provided NS and DB
@@ -1440,8 +1831,12 @@ impl Datastore {
 		let mut txn = self.transaction(Read, Optimistic).await?;
 		// Return an async export job
 		Ok(async move {
+			// Fetch the specified fields
+			let fields: Vec<&str> = fields.iter().map(String::as_str).collect();
+			// Compress the export if necessary
+			let (snd, compressor) = compress::compressor(compression, chn);
 			// Process the export
-			txn.export(&ns, &db, chn).await?;
+			futures::try_join!(txn.export_csv(&ns, &db, &table, &fields, snd), compressor)?;
 			// Everything ok
 			Ok(())
 		})
@@ -1451,7 +1846,7 @@ impl Datastore {
 	#[instrument(level = "debug", skip(self, sess))]
 	pub fn check(&self, sess: &Session, action: Action, resource: Resource) -> Result<(), Error> {
 		// Check if the session has expired
-		if sess.expired() {
+		if sess.is_expired() {
 			return Err(Error::ExpiredSession);
 		}
 		// Skip auth for Anonymous users if auth is disabled
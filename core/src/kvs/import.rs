@@ -0,0 +1,270 @@
+use crate::ctx::Context;
+use crate::dbs::{Options, Transaction};
+use crate::err::Error;
+use crate::sql::statements::{CreateStatement, DeleteStatement, UpdateStatement};
+use crate::sql::{Data, Output, Statement, Table, Value, Values};
+use crate::syn;
+use reblessive::tree::Stk;
+use std::time::Duration;
+
+/// The savepoint which each line is imported within
+const SAVEPOINT: &str = "import";
+
+/// How records are written when they are imported
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum ImportMode {
+	/// Create each record, failing if it already exists
+	#[default]
+	Insert,
+	/// Merge each record into any existing record
+	Upsert,
+	/// Replace the contents of any existing record
+	Replace,
+}
+
+/// Specifies how newline-delimited JSON is imported
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub struct ImportOptions {
+	/// How records are written
+	pub mode: ImportMode,
+	/// Whether the import is validated without writing any data
+	pub dry_run: bool,
+	/// Whether lines which fail to import are skipped
+	pub skip_errors: bool,
+	/// How many lines are imported within each transaction
+	pub batch_size: usize,
+}
+
+impl Default for ImportOptions {
+	fn default() -> Self {
+		ImportOptions {
+			mode: ImportMode::default(),
+			dry_run: false,
+			skip_errors: false,
+			batch_size: 1_000,
+		}
+	}
+}
+
+impl ImportOptions {
+	/// Set how records are written
+	pub fn with_mode(mut self, mode: ImportMode) -> Self {
+		self.mode = mode;
+		self
+	}
+	/// Set whether the import is validated without writing any data
+	pub fn with_dry_run(mut self, dry_run: bool) -> Self {
+		self.dry_run = dry_run;
+		self
+	}
+	/// Set whether lines which fail to import are skipped
+	pub fn with_skip_errors(mut self, skip_errors: bool) -> Self {
+		self.skip_errors = skip_errors;
+		self
+	}
+	/// Set how many lines are imported within each transaction
+	pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+		self.batch_size = batch_size;
+		self
+	}
+}
+
+/// The outcome of an import
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct ImportStats {
+	/// The number of non-empty lines which were processed
+	pub lines: usize,
+	/// The number of records which were written or deleted, or would be in a dry run
+	pub written: usize,
+	/// The number of lines which failed and were skipped
+	pub skipped: usize,
+	/// How long the import took
+	pub elapsed: Duration,
+}
+
+/// Converts a line of newline-delimited JSON, as written by an export,
+/// into the statement which imports it. Definitions are imported using
+/// their SQL, records are imported according to the import mode, and
+/// deleted records from an incremental export are deleted. Tables which
+/// an incremental export skipped are rejected, as their changes are missing.
+pub(super) fn statement(line: &str, mode: ImportMode) -> Result<Statement, Error> {
+	let val = syn::json(line)?;
+	let invalid = |message: &str| Error::InvalidImport {
+		message: message.to_owned(),
+	};
+	let kind = match val.pick(&["type".into()]) {
+		Value::Strand(v) => v.0,
+		_ => return Err(invalid("Expected a `type` field")),
+	};
+	match kind.as_str() {
+		"table" | "field" | "index" | "event" => {
+			let sql = match val.pick(&["sql".into()]) {
+				Value::Strand(v) => v.0,
+				_ => return Err(invalid("Expected a `sql` field")),
+			};
+			let mut query = syn::parse(&sql)?;
+			match (query.0 .0.pop(), query.0 .0.is_empty()) {
+				(Some(stm @ Statement::Define(_)), true) => Ok(stm),
+				_ => Err(invalid("Expected a single DEFINE statement")),
+			}
+		}
+		"record" => {
+			let tb = match val.pick(&["table".into()]) {
+				Value::Strand(v) => v.0,
+				_ => return Err(invalid("Expected a `table` field")),
+			};
+			let mut data = match val.pick(&["data".into()]) {
+				v @ Value::Object(_) => v,
+				_ => return Err(invalid("Expected a `data` object")),
+			};
+			// Records without an id are created with a new id
+			let what = match data.pick(&["id".into()]) {
+				Value::Strand(v) => {
+					let rid = syn::thing(&v)?;
+					if rid.tb != tb {
+						return Err(invalid("The record id does not belong to the table"));
+					}
+					data.put(&["id".into()], Value::Thing(rid.clone()));
+					Value::Thing(rid)
+				}
+				Value::None => Value::Table(Table(tb)),
+				_ => return Err(invalid("Expected the record id to be a string")),
+			};
+			let output = Some(Output::None);
+			Ok(match (mode, what.is_thing()) {
+				(ImportMode::Upsert, true) => Statement::Update(UpdateStatement {
+					what: Values(vec![what]),
+					data: Some(Data::MergeExpression(data)),
+					output,
+					..Default::default()
+				}),
+				(ImportMode::Replace, true) => Statement::Update(UpdateStatement {
+					what: Values(vec![what]),
+					data: Some(Data::ContentExpression(data)),
+					output,
+					..Default::default()
+				}),
+				_ => Statement::Create(CreateStatement {
+					what: Values(vec![what]),
+					data: Some(Data::ContentExpression(data)),
+					output,
+					..Default::default()
+				}),
+			})
+		}
+		"delete" => {
+			let tb = match val.pick(&["table".into()]) {
+				Value::Strand(v) => v.0,
+				_ => return Err(invalid("Expected a `table` field")),
+			};
+			let rid = match val.pick(&["id".into()]) {
+				Value::Strand(v) => syn::thing(&v)?,
+				_ => return Err(invalid("Expected an `id` field")),
+			};
+			if rid.tb != tb {
+				return Err(invalid("The record id does not belong to the table"));
+			}
+			Ok(Statement::Delete(DeleteStatement {
+				what: Values(vec![Value::Thing(rid)]),
+				output: Some(Output::None),
+				..Default::default()
+			}))
+		}
+		// The changes of a table without a change feed were not exported
+		"skipped" => match val.pick(&["table".into()]) {
+			Value::Strand(v) => {
+				Err(invalid(&format!("The changes to table `{}` were not exported", v.as_str())))
+			}
+			_ => Err(invalid("Expected a `table` field")),
+		},
+		_ => Err(invalid("Unknown line type")),
+	}
+}
+
+/// Imports a batch of numbered lines within a transaction. Each line is
+/// imported within its own savepoint, so that a failed line is rolled
+/// back without affecting the other lines in the batch.
+pub(super) async fn import_batch(
+	stk: &mut Stk,
+	ctx: &Context<'_>,
+	opt: &Options,
+	txn: &Transaction,
+	lines: &[(usize, &str)],
+	opts: &ImportOptions,
+	stats: &mut ImportStats,
+) -> Result<(), Error> {
+	for (num, line) in lines {
+		stats.lines += 1;
+		// Imports do not buffer any notifications
+		txn.lock().await.savepoint(SAVEPOINT, 0).await?;
+		// Import the line
+		let res = match statement(line, opts.mode) {
+			Ok(stm) => stm.compute(stk, ctx, opt, txn, None).await.map(|_| stm),
+			Err(e) => Err(e),
+		};
+		match res {
+			Ok(stm) => {
+				txn.lock().await.release(SAVEPOINT).await?;
+				if matches!(stm, Statement::Create(_) | Statement::Update(_) | Statement::Delete(_))
+				{
+					stats.written += 1;
+				}
+			}
+			Err(e) => {
+				txn.lock().await.rollback_to(SAVEPOINT).await?;
+				if !opts.skip_errors {
+					return Err(Error::ImportFailed {
+						line: *num,
+						message: e.to_string(),
+					});
+				}
+				stats.skipped += 1;
+			}
+		}
+	}
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn import_statements() {
+		let line = r#"{"type":"table","table":"person","sql":"DEFINE TABLE person SCHEMALESS"}"#;
+		let stm = statement(line, ImportMode::Insert).unwrap();
+		assert!(matches!(stm, Statement::Define(_)));
+		//
+		let line = r#"{"type":"record","table":"person","data":{"id":"person:1","name":"Tobie"}}"#;
+		let stm = statement(line, ImportMode::Insert).unwrap();
+		let data = "{ id: person:1, name: 'Tobie' }";
+		assert_eq!(stm.to_string(), format!("CREATE person:1 CONTENT {data} RETURN NONE"));
+		let stm = statement(line, ImportMode::Upsert).unwrap();
+		assert_eq!(stm.to_string(), format!("UPDATE person:1 MERGE {data} RETURN NONE"));
+		let stm = statement(line, ImportMode::Replace).unwrap();
+		assert_eq!(stm.to_string(), format!("UPDATE person:1 CONTENT {data} RETURN NONE"));
+		// Records without an id are always created
+		let line = r#"{"type":"record","table":"person","data":{"name":"Tobie"}}"#;
+		let stm = statement(line, ImportMode::Upsert).unwrap();
+		assert_eq!(stm.to_string(), "CREATE person CONTENT { name: 'Tobie' } RETURN NONE");
+		// Deleted records are deleted in every mode
+		let line = r#"{"type":"delete","table":"person","id":"person:1"}"#;
+		let stm = statement(line, ImportMode::Insert).unwrap();
+		assert_eq!(stm.to_string(), "DELETE person:1 RETURN NONE");
+		let line = r#"{"type":"delete","table":"animal","id":"person:1"}"#;
+		assert!(statement(line, ImportMode::Insert).is_err());
+		// Tables which were skipped by an incremental export are rejected
+		let line = r#"{"type":"skipped","table":"person"}"#;
+		assert!(statement(line, ImportMode::Replace).is_err());
+		// Invalid lines are rejected
+		assert!(statement("{", ImportMode::Insert).is_err());
+		assert!(statement(r#"{"type":"other"}"#, ImportMode::Insert).is_err());
+		let line = r#"{"type":"record","table":"animal","data":{"id":"person:1"}}"#;
+		assert!(statement(line, ImportMode::Insert).is_err());
+		let line = r#"{"type":"table","table":"person","sql":"SELECT * FROM person"}"#;
+		assert!(statement(line, ImportMode::Insert).is_err());
+	}
+}
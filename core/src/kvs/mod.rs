@@ -14,12 +14,16 @@
 //! - `mem`: in-memory database
 mod cache;
 mod clock;
+mod compress;
+mod csv;
 mod ds;
 mod fdb;
+mod import;
 mod indxdb;
 mod kv;
 mod mem;
 mod rocksdb;
+mod savepoint;
 mod speedb;
 mod surrealkv;
 mod tikv;
@@ -33,6 +37,8 @@ mod lq_v2_fut;
 #[cfg(test)]
 mod tests;
 
+pub use self::compress::Compression;
 pub use self::ds::*;
+pub use self::import::{ImportMode, ImportOptions, ImportStats};
 pub use self::kv::*;
 pub use self::tx::*;
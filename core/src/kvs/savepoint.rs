@@ -0,0 +1,96 @@
+use crate::cf::writer::Mark;
+use crate::kvs::kv::Key;
+use crate::kvs::kv::Val;
+use std::collections::HashMap;
+use std::mem;
+
+/// The values which were changed since a savepoint was created
+struct Savepoint {
+	name: String,
+	/// The value each key held before it was first changed
+	undo: HashMap<Key, Option<Val>>,
+	/// The change feed mutations buffered when the savepoint was created
+	changes: Mark,
+	/// The number of notifications buffered when the savepoint was created
+	notifications: usize,
+}
+
+/// The state to restore when rolling back to a savepoint
+#[non_exhaustive]
+pub struct Rollback {
+	/// The value each key held before it was first changed
+	pub undo: HashMap<Key, Option<Val>>,
+	/// The change feed mutations to keep
+	pub changes: Mark,
+	/// The number of notifications to keep
+	pub notifications: usize,
+}
+
+/// A stack of named savepoints within a transaction
+#[derive(Default)]
+#[non_exhaustive]
+pub struct Savepoints(Vec<Savepoint>);
+
+impl Savepoints {
+	/// Check if no savepoints have been created
+	#[allow(unused)]
+	pub fn is_empty(&self) -> bool {
+		self.0.is_empty()
+	}
+	/// Check if the previous value of a key still needs to be recorded
+	pub fn needs(&self, key: &Key) -> bool {
+		self.0.last().is_some_and(|s| !s.undo.contains_key(key))
+	}
+	/// Record the previous value of a key in the latest savepoint
+	pub fn record(&mut self, key: Key, val: Option<Val>) {
+		if let Some(s) = self.0.last_mut() {
+			s.undo.entry(key).or_insert(val);
+		}
+	}
+	/// Create a new savepoint
+	pub fn push(&mut self, name: &str, changes: Mark, notifications: usize) {
+		self.0.push(Savepoint {
+			name: name.to_owned(),
+			undo: HashMap::new(),
+			changes,
+			notifications,
+		});
+	}
+	/// Remove all savepoints created after the named savepoint,
+	/// returning the state which needs to be restored
+	pub fn rollback(&mut self, name: &str) -> Option<Rollback> {
+		let pos = self.position(name)?;
+		let mut undo = mem::take(&mut self.0[pos].undo);
+		// Older savepoints recorded older values
+		for s in self.0.drain(pos + 1..) {
+			for (k, v) in s.undo {
+				undo.entry(k).or_insert(v);
+			}
+		}
+		Some(Rollback {
+			undo,
+			changes: self.0[pos].changes.clone(),
+			notifications: self.0[pos].notifications,
+		})
+	}
+	/// Remove the named savepoint, and all savepoints created after it,
+	/// keeping their changes in the savepoint which was created before
+	pub fn release(&mut self, name: &str) -> bool {
+		let Some(pos) = self.position(name) else {
+			return false;
+		};
+		let released: Vec<_> = self.0.drain(pos..).collect();
+		if let Some(s) = self.0.last_mut() {
+			for r in released {
+				for (k, v) in r.undo {
+					s.undo.entry(k).or_insert(v);
+				}
+			}
+		}
+		true
+	}
+	/// Find the latest savepoint with the specified name
+	fn position(&self, name: &str) -> Option<usize> {
+		self.0.iter().rposition(|s| s.name == name)
+	}
+}
@@ -65,6 +65,7 @@ mod mem {
 	include!("lq.rs");
 	include!("nq.rs");
 	include!("raw.rs");
+	include!("savepoint.rs");
 	include!("snapshot.rs");
 	include!("tb.rs");
 	include!("multireader.rs");
@@ -112,6 +113,7 @@ mod rocksdb {
 	include!("lq.rs");
 	include!("nq.rs");
 	include!("raw.rs");
+	include!("savepoint.rs");
 	include!("snapshot.rs");
 	include!("tb.rs");
 	include!("multireader.rs");
@@ -159,6 +161,7 @@ mod speedb {
 	include!("lq.rs");
 	include!("nq.rs");
 	include!("raw.rs");
+	include!("savepoint.rs");
 	include!("snapshot.rs");
 	include!("tb.rs");
 	include!("multireader.rs");
@@ -207,6 +210,7 @@ mod tikv {
 	include!("lq.rs");
 	include!("nq.rs");
 	include!("raw.rs");
+	include!("savepoint.rs");
 	include!("snapshot.rs");
 	include!("tb.rs");
 	include!("multireader.rs");
@@ -255,6 +259,7 @@ mod fdb {
 	include!("lq.rs");
 	include!("nq.rs");
 	include!("raw.rs");
+	include!("savepoint.rs");
 	include!("snapshot.rs");
 	include!("tb.rs");
 	include!("multireader.rs");
@@ -300,6 +305,7 @@ mod surrealkv {
 	}
 
 	include!("raw.rs");
+	include!("savepoint.rs");
 	include!("cluster_init.rs");
 	include!("hb.rs");
 	include!("helper.rs");
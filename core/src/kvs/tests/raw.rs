@@ -192,6 +192,36 @@ async fn delc() {
 	tx.cancel().await.unwrap();
 }
 
+#[tokio::test]
+#[serial]
+async fn readonly() {
+	// Create a new datastore
+	let node_id = Uuid::parse_str("42d91cae-0d58-4457-b606-de12a8903fe4").unwrap();
+	let clock = Arc::new(SizedClock::Fake(FakeClock::new(Timestamp::default())));
+	let (ds, _) = new_ds(node_id, clock).await;
+	// Create a writeable transaction
+	let mut tx = ds.transaction(Write, Optimistic).await.unwrap();
+	assert!(tx.writeable());
+	assert!(tx.put(Unknown, "test", "one").await.is_ok());
+	tx.commit().await.unwrap();
+	// Create a readonly transaction
+	let mut tx = ds.transaction(Read, Optimistic).await.unwrap();
+	assert!(!tx.writeable());
+	assert!(matches!(tx.set("test", "two").await, Err(crate::err::Error::TxReadonly)));
+	assert!(matches!(tx.put(Unknown, "other", "two").await, Err(crate::err::Error::TxReadonly)));
+	assert!(matches!(
+		tx.putc("test", "two", Some("one")).await,
+		Err(crate::err::Error::TxReadonly)
+	));
+	assert!(matches!(tx.del("test").await, Err(crate::err::Error::TxReadonly)));
+	assert!(matches!(tx.delc("test", Some("one")).await, Err(crate::err::Error::TxReadonly)));
+	assert!(matches!(tx.delr("a".."z", 10).await, Err(crate::err::Error::TxReadonly)));
+	// The data can still be read
+	let val = tx.get("test").await.unwrap();
+	assert!(matches!(val.as_deref(), Some(b"one")));
+	tx.cancel().await.unwrap();
+}
+
 #[tokio::test]
 #[serial]
 async fn scan() {
@@ -242,6 +272,28 @@ async fn scan() {
 	tx.cancel().await.unwrap();
 }
 
+#[tokio::test]
+#[serial]
+async fn count() {
+	// Create a new datastore
+	let node_id = Uuid::parse_str("5d4a2a7c-5b8e-4c67-9d3b-2a6f0e1c8b91").unwrap();
+	let clock = Arc::new(SizedClock::Fake(FakeClock::new(Timestamp::default())));
+	let (ds, _) = new_ds(node_id, clock).await;
+	// Create a writeable transaction
+	let mut tx = ds.transaction(Write, Optimistic).await.unwrap();
+	for i in 0..2500 {
+		assert!(tx.put(Unknown, format!("test{i:04}"), "v").await.is_ok());
+	}
+	tx.commit().await.unwrap();
+	// Create a readonly transaction
+	let mut tx = ds.transaction(Read, Optimistic).await.unwrap();
+	assert_eq!(tx.count("test".."tesu", u32::MAX).await.unwrap(), 2500);
+	assert_eq!(tx.count("test".."tesu", 1500).await.unwrap(), 1500);
+	assert_eq!(tx.count("test0100".."test0200", u32::MAX).await.unwrap(), 100);
+	assert_eq!(tx.count("test9".."tesu", u32::MAX).await.unwrap(), 0);
+	tx.cancel().await.unwrap();
+}
+
 #[tokio::test]
 #[serial]
 async fn scan_paged() {
@@ -0,0 +1,88 @@
+#[tokio::test]
+#[serial]
+async fn savepoint_rollback() {
+	// Create a new datastore
+	let node_id = Uuid::parse_str("9d2e1c8b-5a0f-4c3e-8d7b-2f6a4e1b0c9d").unwrap();
+	let clock = Arc::new(SizedClock::Fake(FakeClock::new(Timestamp::default())));
+	let (ds, _) = new_ds(node_id, clock).await;
+	// Insert an initial key
+	let mut tx = ds.transaction(Write, Optimistic).await.unwrap();
+	tx.set("test", "initial").await.unwrap();
+	tx.commit().await.unwrap();
+	// Create a writeable transaction
+	let mut tx = ds.transaction(Write, Optimistic).await.unwrap();
+	tx.set("before", "ok").await.unwrap();
+	tx.savepoint("one", 0).await.unwrap();
+	tx.set("test", "changed").await.unwrap();
+	tx.set("after", "ok").await.unwrap();
+	tx.del("before").await.unwrap();
+	// Roll back the changes made since the savepoint
+	tx.rollback_to("one").await.unwrap();
+	let val = tx.get("test").await.unwrap();
+	assert!(matches!(val.as_deref(), Some(b"initial")));
+	let val = tx.get("before").await.unwrap();
+	assert!(matches!(val.as_deref(), Some(b"ok")));
+	let val = tx.get("after").await.unwrap();
+	assert!(val.is_none());
+	// The savepoint can be rolled back to again
+	tx.set("test", "again").await.unwrap();
+	tx.rollback_to("one").await.unwrap();
+	let val = tx.get("test").await.unwrap();
+	assert!(matches!(val.as_deref(), Some(b"initial")));
+	// Unknown savepoints can not be rolled back to
+	let res = tx.rollback_to("two").await;
+	assert!(matches!(res, Err(Error::SavepointNotFound { .. })));
+	tx.commit().await.unwrap();
+	// Check that only the changes before the savepoint were committed
+	let mut tx = ds.transaction(Read, Optimistic).await.unwrap();
+	let val = tx.get("test").await.unwrap();
+	assert!(matches!(val.as_deref(), Some(b"initial")));
+	let val = tx.get("before").await.unwrap();
+	assert!(matches!(val.as_deref(), Some(b"ok")));
+	let val = tx.get("after").await.unwrap();
+	assert!(val.is_none());
+	tx.cancel().await.unwrap();
+}
+
+#[tokio::test]
+#[serial]
+async fn savepoint_nested() {
+	// Create a new datastore
+	let node_id = Uuid::parse_str("4b7f3a2e-1c6d-4e8f-9a0b-5d3c2e1f7a6b").unwrap();
+	let clock = Arc::new(SizedClock::Fake(FakeClock::new(Timestamp::default())));
+	let (ds, _) = new_ds(node_id, clock).await;
+	// Create a writeable transaction
+	let mut tx = ds.transaction(Write, Optimistic).await.unwrap();
+	tx.set("test", "zero").await.unwrap();
+	tx.savepoint("outer", 0).await.unwrap();
+	tx.set("test", "one").await.unwrap();
+	tx.savepoint("inner", 0).await.unwrap();
+	tx.set("test", "two").await.unwrap();
+	tx.set("nested", "ok").await.unwrap();
+	// Rolling back the inner savepoint keeps the outer changes
+	tx.rollback_to("inner").await.unwrap();
+	let val = tx.get("test").await.unwrap();
+	assert!(matches!(val.as_deref(), Some(b"one")));
+	let val = tx.get("nested").await.unwrap();
+	assert!(val.is_none());
+	// Releasing the inner savepoint keeps its changes
+	tx.set("nested", "ok").await.unwrap();
+	tx.release("inner").await.unwrap();
+	let val = tx.get("nested").await.unwrap();
+	assert!(matches!(val.as_deref(), Some(b"ok")));
+	let res = tx.rollback_to("inner").await;
+	assert!(matches!(res, Err(Error::SavepointNotFound { .. })));
+	// Rolling back the outer savepoint undoes the released changes
+	tx.rollback_to("outer").await.unwrap();
+	let val = tx.get("test").await.unwrap();
+	assert!(matches!(val.as_deref(), Some(b"zero")));
+	let val = tx.get("nested").await.unwrap();
+	assert!(val.is_none());
+	// Rolling back the outer savepoint removes any later savepoints
+	tx.savepoint("inner", 0).await.unwrap();
+	tx.set("test", "three").await.unwrap();
+	tx.rollback_to("outer").await.unwrap();
+	let res = tx.release("inner").await;
+	assert!(matches!(res, Err(Error::SavepointNotFound { .. })));
+	tx.cancel().await.unwrap();
+}
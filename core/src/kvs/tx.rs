@@ -1,11 +1,15 @@
 use std::borrow::Cow;
+use std::collections::BTreeSet;
 use std::fmt;
 use std::fmt::Debug;
 use std::ops::Range;
+#[cfg(any(test, feature = "test-hooks"))]
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
 
 use channel::{Receiver, Sender};
 use futures::lock::Mutex;
+use serde_json::json;
 use uuid::Uuid;
 
 use sql::permission::Permissions;
@@ -35,7 +39,9 @@ use crate::key::key_req::KeyRequirements;
 use crate::kvs::cache::Cache;
 use crate::kvs::cache::Entry;
 use crate::kvs::clock::SizedClock;
+use crate::kvs::csv;
 use crate::kvs::lq_structs::{LqValue, TrackedResult};
+use crate::kvs::savepoint::Savepoints;
 use crate::kvs::Check;
 use crate::options::EngineOptions;
 use crate::sql;
@@ -92,12 +98,16 @@ where
 #[non_exhaustive]
 pub struct Transaction {
 	pub(super) inner: Inner,
+	pub(super) write: bool,
 	pub(super) cache: Cache,
+	pub(super) savepoints: Savepoints,
 	pub(super) cf: cf::Writer,
 	pub(super) vso: Arc<Mutex<Oracle>>,
 	pub(super) clock: Arc<SizedClock>,
 	pub(super) prepared_async_events: (Arc<Sender<TrackedResult>>, Arc<Receiver<TrackedResult>>),
 	pub(super) engine_options: EngineOptions,
+	#[cfg(any(test, feature = "test-hooks"))]
+	pub(super) conflicts: Arc<AtomicUsize>,
 }
 
 #[allow(clippy::large_enum_variant)]
@@ -242,6 +252,15 @@ impl Transaction {
 		}
 	}
 
+	/// Check if transaction is writeable.
+	///
+	/// If the transaction was opened as read-only, then any calls
+	/// to functions which modify data in this transaction will result
+	/// in a [`Error::TxReadonly`] error.
+	pub fn writeable(&self) -> bool {
+		self.write
+	}
+
 	/// Cancel a transaction.
 	///
 	/// This reverses all changes made within the transaction.
@@ -295,6 +314,16 @@ impl Transaction {
 	pub async fn commit(&mut self) -> Result<(), Error> {
 		#[cfg(debug_assertions)]
 		trace!("Commit");
+		// Simulate a conflicting transaction if requested
+		#[cfg(any(test, feature = "test-hooks"))]
+		if self
+			.conflicts
+			.fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| n.checked_sub(1))
+			.is_ok()
+		{
+			self.cancel().await?;
+			return Err(Error::TxConflict);
+		}
 		match self {
 			#[cfg(feature = "kv-mem")]
 			Transaction {
@@ -336,6 +365,92 @@ impl Transaction {
 		}
 	}
 
+	/// Create a named savepoint within this transaction.
+	///
+	/// Any changes made after this point can be reversed
+	/// with [`Transaction::rollback_to`], without cancelling
+	/// the whole transaction. The number of notifications which
+	/// have been buffered by the caller is returned again when
+	/// rolling back, so that later notifications can be discarded.
+	pub async fn savepoint(&mut self, name: &str, notifications: usize) -> Result<(), Error> {
+		#[cfg(debug_assertions)]
+		trace!("Savepoint {name}");
+		self.savepoints.push(name, self.cf.mark(), notifications);
+		Ok(())
+	}
+
+	/// Roll back to a named savepoint.
+	///
+	/// This reverses all changes made since the savepoint was
+	/// created, including any buffered change feed mutations,
+	/// and removes any savepoints created after it. Returns the
+	/// number of notifications which were buffered when the
+	/// savepoint was created.
+	pub async fn rollback_to(&mut self, name: &str) -> Result<usize, Error> {
+		#[cfg(debug_assertions)]
+		trace!("Rollback to {name}");
+		let rollback = match self.savepoints.rollback(name) {
+			Some(v) => v,
+			None => {
+				return Err(Error::SavepointNotFound {
+					value: name.to_owned(),
+				})
+			}
+		};
+		// Restore the values without recording them again
+		let savepoints = std::mem::take(&mut self.savepoints);
+		let mut res = Ok(());
+		for (key, val) in rollback.undo {
+			res = match val {
+				Some(val) => self.set(key, val).await,
+				None => self.del(key).await,
+			};
+			if res.is_err() {
+				break;
+			}
+		}
+		self.savepoints = savepoints;
+		// Discard the change feed mutations made since the savepoint
+		self.cf.truncate(&rollback.changes);
+		// Cached definitions may have been reverted
+		self.clear_cache();
+		res.map(|_| rollback.notifications)
+	}
+
+	/// Release a named savepoint.
+	///
+	/// This keeps all changes made since the savepoint was
+	/// created, and removes any savepoints created after it.
+	pub async fn release(&mut self, name: &str) -> Result<(), Error> {
+		#[cfg(debug_assertions)]
+		trace!("Release {name}");
+		match self.savepoints.release(name) {
+			true => Ok(()),
+			false => Err(Error::SavepointNotFound {
+				value: name.to_owned(),
+			}),
+		}
+	}
+
+	/// Ensure that the transaction can be written to, before
+	/// any request is made to the underlying datastore.
+	fn check_write(&self) -> Result<(), Error> {
+		match self.write {
+			true => Ok(()),
+			false => Err(Error::TxReadonly),
+		}
+	}
+
+	/// Record the current value of a key before it is changed,
+	/// so that it can be restored when rolling back to a savepoint.
+	async fn record(&mut self, key: &Key) -> Result<(), Error> {
+		if self.savepoints.needs(key) {
+			let val = self.get(key.clone()).await?;
+			self.savepoints.record(key.clone(), val);
+		}
+		Ok(())
+	}
+
 	/// From the existing transaction, consume all the remaining live query registration events and return them synchronously
 	/// This function does not check that a transaction was committed, but the intention is to consume from this
 	/// only once the transaction is committed
@@ -370,6 +485,8 @@ impl Transaction {
 		let key = key.into();
 		#[cfg(debug_assertions)]
 		trace!("Del {}", sprint_key(&key));
+		self.check_write()?;
+		self.record(&key).await?;
 		match self {
 			#[cfg(feature = "kv-mem")]
 			Transaction {
@@ -520,6 +637,8 @@ impl Transaction {
 		let key = key.into();
 		#[cfg(debug_assertions)]
 		trace!("Set {} => {:?}", sprint_key(&key), val);
+		self.check_write()?;
+		self.record(&key).await?;
 		match self {
 			#[cfg(feature = "kv-mem")]
 			Transaction {
@@ -662,6 +781,7 @@ impl Transaction {
 			sprint_key(&ts_key),
 			sprint_key(&suffix)
 		);
+		self.check_write()?;
 		match self {
 			#[cfg(feature = "kv-mem")]
 			Transaction {
@@ -728,6 +848,9 @@ impl Transaction {
 		K: Into<Key> + Debug,
 		V: Into<Val> + Debug,
 	{
+		let key = key.into();
+		self.check_write()?;
+		self.record(&key).await?;
 		match self {
 			#[cfg(feature = "kv-mem")]
 			Transaction {
@@ -912,6 +1035,8 @@ impl Transaction {
 		let key = key.into();
 		#[cfg(debug_assertions)]
 		trace!("Putc {} if {:?} => {:?}", sprint_key(&key), chk, val);
+		self.check_write()?;
+		self.record(&key).await?;
 		match self {
 			#[cfg(feature = "kv-mem")]
 			Transaction {
@@ -963,6 +1088,8 @@ impl Transaction {
 		let key = key.into();
 		#[cfg(debug_assertions)]
 		trace!("Delc {} if {:?}", sprint_key(&key), chk);
+		self.check_write()?;
+		self.record(&key).await?;
 		match self {
 			#[cfg(feature = "kv-mem")]
 			Transaction {
@@ -1042,6 +1169,36 @@ impl Transaction {
 		}
 		Ok(out)
 	}
+	/// Count the keys in a specific range of the datastore, stopping once the limit is reached.
+	///
+	/// This function fetches key-value pairs from the underlying datastore in batches of 1000.
+	pub async fn count<K>(&mut self, rng: Range<K>, limit: u32) -> Result<usize, Error>
+	where
+		K: Into<Key> + Debug,
+	{
+		let beg: Key = rng.start.into();
+		let end: Key = rng.end.into();
+		#[cfg(debug_assertions)]
+		trace!("Count {}..{} (limit: {limit})", sprint_key(&beg), sprint_key(&end));
+		let mut count = 0;
+		let mut next_page = Some(ScanPage {
+			range: beg..end,
+			limit: Limit::Limited(limit),
+		});
+		// Start processing
+		while let Some(page) = next_page {
+			// Never count past the limit
+			let batch = (limit as usize - count).min(1000) as u32;
+			if batch == 0 {
+				break;
+			}
+			// Get records batch
+			let res = self.scan_paged(page, batch).await?;
+			next_page = res.next_page;
+			count += res.values.len();
+		}
+		Ok(count)
+	}
 	/// Delete a range of keys from the datastore.
 	///
 	/// This function fetches key-value pairs from the underlying datastore in batches of 1000.
@@ -1055,17 +1212,21 @@ impl Transaction {
 		};
 		#[cfg(debug_assertions)]
 		trace!("Delr {}..{} (limit: {limit})", sprint_key(&rng.start), sprint_key(&rng.end));
+		self.check_write()?;
 		match self {
+			// Savepoints need each deleted key to be recorded
 			#[cfg(feature = "kv-tikv")]
 			Transaction {
 				inner: Inner::TiKV(v),
+				savepoints,
 				..
-			} => v.delr(rng, limit).await,
+			} if savepoints.is_empty() => v.delr(rng, limit).await,
 			#[cfg(feature = "kv-fdb")]
 			Transaction {
 				inner: Inner::FoundationDB(v),
+				savepoints,
 				..
-			} => v.delr(rng).await,
+			} if savepoints.is_empty() => v.delr(rng).await,
 			#[allow(unreachable_patterns)]
 			_ => self._delr(rng, limit).await,
 		}
@@ -2704,6 +2865,257 @@ impl Transaction {
 		Ok(())
 	}
 
+	/// Writes the definitions and records of the specified tables as
+	/// newline-delimited JSON, with one compact JSON object on each line.
+	/// Every table is exported when no tables are specified.
+	pub async fn export_ndjson(
+		&mut self,
+		ns: &str,
+		db: &str,
+		tables: &[&str],
+		chn: Sender<Vec<u8>>,
+	) -> Result<(), Error> {
+		// Fetch the specified tables
+		let tbs = self.all_tb(ns, db).await?;
+		let tbs: Vec<_> = tbs
+			.iter()
+			.filter(|tb| tables.is_empty() || tables.contains(&tb.name.as_str()))
+			.collect();
+		// Output the definitions of each table
+		for tb in tbs.iter() {
+			let name = tb.name.as_str();
+			// Output TABLE
+			chn.send(bytes!(json!({ "type": "table", "table": name, "sql": tb.to_string() })))
+				.await?;
+			// Output FIELDS
+			for fd in self.all_tb_fields(ns, db, name).await?.iter() {
+				let sql = fd.to_string();
+				chn.send(bytes!(json!({ "type": "field", "table": name, "sql": sql }))).await?;
+			}
+			// Output INDEXES
+			for ix in self.all_tb_indexes(ns, db, name).await?.iter() {
+				let sql = ix.to_string();
+				chn.send(bytes!(json!({ "type": "index", "table": name, "sql": sql }))).await?;
+			}
+			// Output EVENTS
+			for ev in self.all_tb_events(ns, db, name).await?.iter() {
+				let sql = ev.to_string();
+				chn.send(bytes!(json!({ "type": "event", "table": name, "sql": sql }))).await?;
+			}
+		}
+		// Output the records of each table
+		for tb in tbs.iter() {
+			let name = tb.name.as_str();
+			// Fetch records
+			let beg = crate::key::thing::prefix(ns, db, name);
+			let end = crate::key::thing::suffix(ns, db, name);
+			let mut nxt: Option<ScanPage<Vec<u8>>> = Some(ScanPage::from(beg..end));
+			while let Some(page) = nxt {
+				let res = self.scan_paged(page, 1000).await?;
+				nxt = res.next_page;
+				// Output each record as soon as it is fetched
+				for (_, v) in res.values.into_iter() {
+					let v: Value = (&v).into();
+					let data = v.into_json();
+					chn.send(bytes!(json!({ "type": "record", "table": name, "data": data })))
+						.await?;
+				}
+			}
+		}
+		// Everything exported
+		Ok(())
+	}
+
+	/// Writes up to `limit` records of a table, starting with the record at
+	/// position `start`, as newline-delimited JSON in the same format as
+	/// [`Self::export_ndjson`]. When the position and key of the next record
+	/// from a previous batch are given, and they are not beyond `start`, the
+	/// scan resumes from there instead of skipping over the whole table again.
+	/// Returns the number of records which were written, along with the
+	/// position and key of the next record, if there is one.
+	#[allow(clippy::too_many_arguments)]
+	pub async fn export_ndjson_batch(
+		&mut self,
+		ns: &str,
+		db: &str,
+		tb: &str,
+		start: usize,
+		limit: usize,
+		resume: Option<(usize, Vec<u8>)>,
+		chn: Sender<Vec<u8>>,
+	) -> Result<(usize, Option<(usize, Vec<u8>)>), Error> {
+		// Resume from the previous batch if possible
+		let (mut pos, beg) = match resume {
+			Some((pos, key)) if pos <= start => (pos, key),
+			_ => (0, crate::key::thing::prefix(ns, db, tb)),
+		};
+		let end = crate::key::thing::suffix(ns, db, tb);
+		let mut nxt: Option<ScanPage<Vec<u8>>> = Some(ScanPage::from(beg..end));
+		let mut count = 0;
+		while let Some(page) = nxt {
+			let res = self.scan_paged(page, 1000).await?;
+			nxt = res.next_page;
+			for (k, v) in res.values.into_iter() {
+				// Stop at the first record after the batch
+				if count == limit {
+					return Ok((count, Some((pos, k))));
+				}
+				// Skip the records before the batch
+				if pos >= start {
+					let v: Value = (&v).into();
+					let data = v.into_json();
+					chn.send(bytes!(json!({ "type": "record", "table": tb, "data": data })))
+						.await?;
+					count += 1;
+				}
+				pos += 1;
+			}
+		}
+		// Every record was scanned
+		Ok((count, None))
+	}
+
+	/// Writes the records which have changed since the specified time as
+	/// newline-delimited JSON, in the same format as [`Self::export_ndjson`].
+	/// Changes are read from the change feeds of the database, and deleted
+	/// records are written as `delete` lines. Tables without a change feed
+	/// can not be exported, so a `skipped` line is written for each of them.
+	/// The export fails if changes since the specified time may already have
+	/// been removed from the change feeds. Changes are found at the
+	/// granularity of the timestamps recorded for the change feeds, so
+	/// changes which were made shortly before the specified time may also be
+	/// exported.
+	pub async fn export_incremental(
+		&mut self,
+		ns: &str,
+		db: &str,
+		since: &sql::Datetime,
+		chn: Sender<Vec<u8>>,
+	) -> Result<(), Error> {
+		let cf = match self.get_db(ns, db).await {
+			Ok(v) => v.changefeed,
+			Err(Error::DbNotFound {
+				..
+			}) => return Ok(()),
+			Err(e) => return Err(e),
+		};
+		let tbs = self.all_tb(ns, db).await?;
+		// Find how long the change feeds retain changes for
+		let expiry = tbs
+			.iter()
+			.filter_map(|tb| tb.changefeed)
+			.chain(cf)
+			.map(|cf| cf.expiry.as_secs())
+			.max()
+			.unwrap_or_default();
+		// Check that no changes since the specified time have been removed
+		let ts = u64::try_from(since.0.timestamp()).unwrap_or_default();
+		let beg = crate::key::database::ts::new(ns, db, ts).encode()?;
+		let end = crate::key::database::ts::suffix(ns, db);
+		if let Some((k, _)) = self.getr(beg..end, u32::MAX).await?.last() {
+			let latest = crate::key::database::ts::Ts::decode(k)?.ts;
+			if ts < latest.saturating_sub(expiry) {
+				return Err(Error::ExportChangesExpired {
+					since: since.to_raw(),
+				});
+			}
+		}
+		// Output a line for each table without a change feed
+		for tb in tbs.iter().filter(|tb| cf.is_none() && tb.changefeed.is_none()) {
+			let name = tb.name.as_str();
+			chn.send(bytes!(json!({ "type": "skipped", "table": name }))).await?;
+		}
+		// Find the first change at the specified time
+		let beg = match self.get_versionstamp_from_timestamp(ts, ns, db, true).await? {
+			Some(vs) => crate::key::change::prefix_ts(ns, db, vs),
+			None => crate::key::change::prefix(ns, db),
+		};
+		let end = crate::key::change::suffix(ns, db);
+		// Output each change in the order it was made
+		let mut nxt: Option<ScanPage<Vec<u8>>> = Some(ScanPage::from(beg..end));
+		while let Some(page) = nxt {
+			let res = self.scan_paged(page, 1000).await?;
+			nxt = res.next_page;
+			for (_, v) in res.values.into_iter() {
+				let cf::TableMutations(name, muts) = v.into();
+				for m in muts {
+					match m {
+						cf::TableMutation::Set(_, v) | cf::TableMutation::SetWithDiff(_, v, _) => {
+							let data = v.into_json();
+							let line = json!({ "type": "record", "table": name, "data": data });
+							chn.send(bytes!(line)).await?;
+						}
+						cf::TableMutation::Del(id) | cf::TableMutation::DelWithOriginal(id, _) => {
+							let id = id.to_string();
+							let line = json!({ "type": "delete", "table": name, "id": id });
+							chn.send(bytes!(line)).await?;
+						}
+						// Table definitions are not records
+						cf::TableMutation::Def(_) => {}
+					}
+				}
+			}
+		}
+		// Everything exported
+		Ok(())
+	}
+
+	/// Writes the records of a table as CSV, with a header row followed by
+	/// one row per record. The fields of nested objects are written to their
+	/// own columns, named using dot notation. Every field of the records is
+	/// exported when no fields are specified.
+	pub async fn export_csv(
+		&mut self,
+		ns: &str,
+		db: &str,
+		tb: &str,
+		fields: &[&str],
+		chn: Sender<Vec<u8>>,
+	) -> Result<(), Error> {
+		let beg = crate::key::thing::prefix(ns, db, tb);
+		let end = crate::key::thing::suffix(ns, db, tb);
+		// Find the columns of every record
+		let mut columns: Vec<BTreeSet<String>> = vec![BTreeSet::new(); fields.len().max(1)];
+		let mut nxt: Option<ScanPage<Vec<u8>>> = Some(ScanPage::from(beg.clone()..end.clone()));
+		while let Some(page) = nxt {
+			let res = self.scan_paged(page, 1000).await?;
+			nxt = res.next_page;
+			for (_, v) in res.values.into_iter() {
+				let v: Value = (&v).into();
+				for col in csv::flatten(&v).into_keys() {
+					match fields.iter().position(|f| csv::is_column_of(&col, f)) {
+						Some(i) => columns[i].insert(col),
+						None if fields.is_empty() => columns[0].insert(col),
+						None => false,
+					};
+				}
+			}
+		}
+		// Fields which are missing from every record still have a column
+		for (i, f) in fields.iter().enumerate() {
+			if columns[i].is_empty() {
+				columns[i].insert(f.to_string());
+			}
+		}
+		let columns: Vec<String> = columns.into_iter().flatten().collect();
+		// Output the header row
+		chn.send(csv::row(columns.iter().map(|c| csv::escape(c.to_owned(), false)))).await?;
+		// Output each record as a row
+		let mut nxt: Option<ScanPage<Vec<u8>>> = Some(ScanPage::from(beg..end));
+		while let Some(page) = nxt {
+			let res = self.scan_paged(page, 1000).await?;
+			nxt = res.next_page;
+			for (_, v) in res.values.into_iter() {
+				let v: Value = (&v).into();
+				let cells = csv::flatten(&v);
+				let row = columns.iter().map(|c| csv::cell(cells.get(c).copied()));
+				chn.send(csv::row(row)).await?;
+			}
+		}
+		// Everything exported
+		Ok(())
+	}
+
 	// change will record the change in the changefeed if enabled.
 	// To actually persist the record changes into the underlying kvs,
 	// you must call the `complete_changes` function and then commit the transaction.
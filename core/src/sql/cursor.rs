@@ -0,0 +1,142 @@
+use crate::ctx::Context;
+use crate::dbs::{Options, Transaction};
+use crate::doc::CursorDoc;
+use crate::err::Error;
+use crate::sql::thing::Thing;
+use crate::sql::value::Value;
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use reblessive::tree::Stk;
+use revision::revisioned;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
+pub struct Cursor(pub Value);
+
+/// The position which a cursor continues from
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum Position {
+	/// Continue from the record after this record id
+	Record(Thing),
+	/// Continue from the record ordered after these
+	/// ORDER BY values, and then after this record id
+	Keys(Vec<Value>, Thing),
+}
+
+impl Cursor {
+	/// Encode a record id as an opaque cursor token, which is safe to use in URLs
+	pub fn encode(rid: &Thing) -> String {
+		URL_SAFE_NO_PAD.encode(rid.to_string())
+	}
+	/// Decode a cursor token into the record id which it points to
+	pub fn decode(token: &str) -> Result<Thing, Error> {
+		URL_SAFE_NO_PAD
+			.decode(token)
+			.ok()
+			.and_then(|v| String::from_utf8(v).ok())
+			.and_then(|v| crate::syn::thing(&v).ok())
+			.ok_or_else(|| Error::InvalidCursor {
+				value: format!("'{token}'"),
+			})
+	}
+	/// Encode the ORDER BY values of a record, followed by its record id, as an
+	/// opaque cursor token, which continues from the record in the same order
+	pub fn encode_keys(keys: &[Value], rid: &Thing) -> String {
+		let mut v = keys.to_vec();
+		v.push(Value::Thing(rid.clone()));
+		URL_SAFE_NO_PAD.encode(Value::from(v).to_string())
+	}
+	/// Decode a cursor token into the position which it continues from
+	fn decode_position(token: &str) -> Result<Position, Error> {
+		let invalid = || Error::InvalidCursor {
+			value: format!("'{token}'"),
+		};
+		let v = URL_SAFE_NO_PAD
+			.decode(token)
+			.ok()
+			.and_then(|v| String::from_utf8(v).ok())
+			.ok_or_else(invalid)?;
+		// A token of ordered values is encoded as an array
+		match v.starts_with('[') {
+			true => match crate::syn::value(&v) {
+				Ok(v) => Self::position(v).map_err(|_| invalid()),
+				Err(_) => Err(invalid()),
+			},
+			false => crate::syn::thing(&v).map(Position::Record).map_err(|_| invalid()),
+		}
+	}
+	/// Convert a computed cursor value into the position which it continues from
+	fn position(v: Value) -> Result<Position, Error> {
+		match v {
+			// A record id can be used directly
+			Value::Thing(v) => Ok(Position::Record(v)),
+			// Otherwise the cursor token must be decoded
+			Value::Strand(v) => Self::decode_position(v.as_str()),
+			// The ORDER BY values must be followed by a record id
+			Value::Array(v) if v.len() > 1 => match v.split_last() {
+				Some((Value::Thing(rid), keys)) => Ok(Position::Keys(keys.to_vec(), rid.clone())),
+				_ => Err(Error::InvalidCursor {
+					value: v.to_string(),
+				}),
+			},
+			// An invalid value was specified
+			v => Err(Error::InvalidCursor {
+				value: v.to_string(),
+			}),
+		}
+	}
+
+	pub(crate) async fn process(
+		&self,
+		stk: &mut Stk,
+		ctx: &Context<'_>,
+		opt: &Options,
+		txn: &Transaction,
+		doc: Option<&CursorDoc<'_>>,
+	) -> Result<Position, Error> {
+		Self::position(self.0.compute(stk, ctx, opt, txn, doc).await?)
+	}
+}
+
+impl fmt::Display for Cursor {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "AFTER {}", self.0)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::sql::Id;
+
+	#[test]
+	fn cursor_token() {
+		for rid in [
+			Thing::from(("person", "tobie")),
+			Thing::from(("person", Id::from(100i64))),
+			Thing::from(("person", Id::from(vec!["London", "2024"]))),
+		] {
+			let token = Cursor::encode(&rid);
+			assert!(token.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_'));
+			assert_eq!(Cursor::decode(&token).unwrap(), rid);
+		}
+		assert!(matches!(Cursor::decode("not a cursor"), Err(Error::InvalidCursor { .. })));
+	}
+
+	#[test]
+	fn cursor_keys_token() {
+		let rid = Thing::from(("post", Id::from(7i64)));
+		let keys = vec![Value::from("2024-01-01"), Value::from(3i64)];
+		let token = Cursor::encode_keys(&keys, &rid);
+		assert!(token.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_'));
+		assert_eq!(Cursor::decode_position(&token).unwrap(), Position::Keys(keys, rid.clone()));
+		let token = Cursor::encode(&rid);
+		assert_eq!(Cursor::decode_position(&token).unwrap(), Position::Record(rid));
+		// The last ordered value must be a record id
+		let token = URL_SAFE_NO_PAD.encode("[1, 2]");
+		assert!(matches!(Cursor::decode_position(&token), Err(Error::InvalidCursor { .. })));
+	}
+}
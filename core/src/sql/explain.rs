@@ -2,18 +2,44 @@ use revision::revisioned;
 use serde::{Deserialize, Serialize};
 use std::fmt;
 
-#[revisioned(revision = 1)]
+#[revisioned(revision = 2)]
 #[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
-pub struct Explain(pub bool);
+pub struct Explain {
+	/// Whether the records are fetched, to explain the number of records
+	pub full: bool,
+	/// Whether the records are iterated, to report the runtime statistics
+	#[revision(start = 2)]
+	pub analyze: bool,
+}
+
+impl Explain {
+	/// An `EXPLAIN FULL` clause which also explains the fetched records
+	pub fn full() -> Self {
+		Self {
+			full: true,
+			analyze: false,
+		}
+	}
+	/// An `EXPLAIN ANALYZE` clause which also reports the runtime statistics
+	pub fn analyze() -> Self {
+		Self {
+			full: false,
+			analyze: true,
+		}
+	}
+}
 
 impl fmt::Display for Explain {
 	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
 		f.write_str("EXPLAIN")?;
-		if self.0 {
+		if self.full {
 			f.write_str(" FULL")?;
 		}
+		if self.analyze {
+			f.write_str(" ANALYZE")?;
+		}
 		Ok(())
 	}
 }
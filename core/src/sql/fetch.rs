@@ -40,11 +40,19 @@ impl InfoStructure for Fetchs {
 	}
 }
 
-#[revisioned(revision = 1)]
+#[revisioned(revision = 2)]
 #[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
-pub struct Fetch(pub Idiom);
+pub struct Fetch(pub Idiom, #[revision(start = 2)] pub Option<u64>);
+
+impl Fetch {
+	/// The number of record links which are expanded along
+	/// this path, defaulting to a single level of records
+	pub fn depth(&self) -> u64 {
+		self.1.unwrap_or(1)
+	}
+}
 
 impl Deref for Fetch {
 	type Target = Idiom;
@@ -55,6 +63,10 @@ impl Deref for Fetch {
 
 impl Display for Fetch {
 	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
-		Display::fmt(&self.0, f)
+		Display::fmt(&self.0, f)?;
+		if let Some(depth) = self.1 {
+			write!(f, " DEPTH {depth}")?;
+		}
+		Ok(())
 	}
 }
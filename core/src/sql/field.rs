@@ -3,7 +3,7 @@ use crate::dbs::{Options, Transaction};
 use crate::doc::CursorDoc;
 use crate::err::Error;
 use crate::sql::statements::info::InfoStructure;
-use crate::sql::{fmt::Fmt, Idiom, Part, Value};
+use crate::sql::{fmt::Fmt, Expression, Idiom, Part, Value};
 use crate::syn;
 use reblessive::tree::Stk;
 use revision::revisioned;
@@ -102,12 +102,28 @@ impl Fields {
 	) -> Result<Value, Error> {
 		// Ensure futures are run
 		let opt = &opt.new_with_futures(true);
+		// Check which aliases are referenced by other fields
+		let refs = self.alias_references(group);
+		// Fields are read from the document before any alias of the same name
+		let source = doc.doc.as_ref();
+		for (field, r) in self.0.iter().zip(&refs) {
+			if let (Some(alias), Some(j)) = (field.alias(), r.earlier) {
+				if source.pick(alias).is_none() {
+					return Err(Error::InvalidFieldReference {
+						field: self.0[j].to_string(),
+						alias: alias.to_owned(),
+					});
+				}
+			}
+		}
 		// Process the desired output
 		let mut out = match self.is_all() {
 			true => doc.doc.compute(stk, ctx, opt, txn, Some(doc)).await?,
 			false => Value::base(),
 		};
-		for v in self.other() {
+		// Store the document along with any referenced aliases
+		let mut scope = refs.iter().any(|r| r.later).then(|| source.clone());
+		for (i, v) in self.0.iter().enumerate() {
 			match v {
 				Field::All => (),
 				Field::Single {
@@ -118,6 +134,11 @@ impl Fields {
 						.as_ref()
 						.map(Cow::Borrowed)
 						.unwrap_or_else(|| Cow::Owned(expr.to_idiom()));
+					// Earlier aliases are visible to this expression
+					let scoped = scope
+						.as_ref()
+						.map(|v| CursorDoc::new(doc.ir, doc.rid, doc.doc_id, Cow::Borrowed(v)));
+					let doc = scoped.as_ref().unwrap_or(doc);
 					match expr {
 						// This expression is a grouped aggregate function
 						Value::Function(f) if group && f.is_aggregate() => {
@@ -238,11 +259,104 @@ impl Fields {
 							}
 						}
 					}
+					// Make this alias visible to later expressions
+					if let (Some(alias), Some(scope)) = (alias, scope.as_mut()) {
+						if refs[i].later && source.pick(alias).is_none() {
+							scope.put(alias, out.pick(alias));
+						}
+					}
 				}
 			}
 		}
 		Ok(out)
 	}
+
+	/// Check how each field alias is referenced by the expressions of other
+	/// fields. An alias is only visible to these expressions when the
+	/// document has no field of the same name, so whether a reference is
+	/// valid is checked for each document. When grouping, the aliases of
+	/// aggregate functions are only computed once the group is complete, so
+	/// these are never visible to other expressions.
+	fn alias_references(&self, group: bool) -> Vec<AliasReferences> {
+		let mut refs = vec![AliasReferences::default(); self.0.len()];
+		for (i, field) in self.0.iter().enumerate() {
+			let Field::Single {
+				expr,
+				alias: Some(alias),
+			} = field
+			else {
+				continue;
+			};
+			if group && matches!(expr, Value::Function(f) if f.is_aggregate()) {
+				continue;
+			}
+			for (j, other) in self.0.iter().enumerate() {
+				let Field::Single {
+					expr,
+					..
+				} = other
+				else {
+					continue;
+				};
+				if i == j || !references(expr, alias) {
+					continue;
+				}
+				if j > i {
+					refs[i].later = true;
+				} else if refs[i].earlier.is_none()
+					&& !self.0[..j].iter().any(|f| f.alias() == Some(alias))
+				{
+					refs[i].earlier = Some(j);
+				}
+			}
+		}
+		refs
+	}
+}
+
+/// How the alias of a field is referenced by the other fields
+#[derive(Clone, Copy, Default)]
+struct AliasReferences {
+	/// The alias is referenced by a later field
+	later: bool,
+	/// The first field which references the alias before it is defined
+	earlier: Option<usize>,
+}
+
+/// Check if an expression reads the value of the specified alias
+fn references(expr: &Value, alias: &Idiom) -> bool {
+	match expr {
+		Value::Idiom(v) => {
+			// Compare the leading field parts of the idiom with the alias
+			let overlaps = matches!(v.first(), Some(Part::Field(_)))
+				&& v.iter()
+					.take_while(|p| matches!(p, Part::Field(_)))
+					.zip(alias.iter())
+					.all(|(a, b)| a == b);
+			overlaps
+				|| v.iter().any(|p| match p {
+					Part::Start(v) | Part::Value(v) => references(v, alias),
+					Part::Method(_, args) => args.iter().any(|v| references(v, alias)),
+					_ => false,
+				})
+		}
+		Value::Expression(v) => match v.as_ref() {
+			Expression::Unary {
+				v,
+				..
+			} => references(v, alias),
+			Expression::Binary {
+				l,
+				r,
+				..
+			} => references(l, alias) || references(r, alias),
+		},
+		Value::Function(v) => v.args().iter().any(|v| references(v, alias)),
+		Value::Cast(v) => references(&v.1, alias),
+		Value::Array(v) => v.iter().any(|v| references(v, alias)),
+		Value::Object(v) => v.values().any(|v| references(v, alias)),
+		_ => false,
+	}
 }
 
 #[revisioned(revision = 1)]
@@ -261,6 +375,19 @@ pub enum Field {
 	},
 }
 
+impl Field {
+	/// Get the alias of this field, if one is specified
+	pub(crate) fn alias(&self) -> Option<&Idiom> {
+		match self {
+			Self::All => None,
+			Self::Single {
+				alias,
+				..
+			} => alias.as_ref(),
+		}
+	}
+}
+
 impl Display for Field {
 	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
 		match self {
@@ -36,10 +36,15 @@ pub(crate) enum OptimisedAggregate {
 	None,
 	Count,
 	CountFunction,
+	CountDistinct,
 	MathMax,
 	MathMin,
 	MathSum,
 	MathMean,
+	MathStddev,
+	MathStddevPop,
+	MathVariance,
+	MathVariancePop,
 	TimeMax,
 	TimeMin,
 }
@@ -133,6 +138,7 @@ impl Function {
 			Self::Normal(f, _) if f == "array::group" => true,
 			Self::Normal(f, _) if f == "array::last" => true,
 			Self::Normal(f, _) if f == "count" => true,
+			Self::Normal(f, _) if f == "count::distinct" => true,
 			Self::Normal(f, _) if f == "math::bottom" => true,
 			Self::Normal(f, _) if f == "math::interquartile" => true,
 			Self::Normal(f, _) if f == "math::max" => true,
@@ -146,10 +152,14 @@ impl Function {
 			Self::Normal(f, _) if f == "math::sample" => true,
 			Self::Normal(f, _) if f == "math::spread" => true,
 			Self::Normal(f, _) if f == "math::stddev" => true,
+			Self::Normal(f, _) if f == "math::stddev::pop" => true,
+			Self::Normal(f, _) if f == "math::stddev::samp" => true,
 			Self::Normal(f, _) if f == "math::sum" => true,
 			Self::Normal(f, _) if f == "math::top" => true,
 			Self::Normal(f, _) if f == "math::trimean" => true,
 			Self::Normal(f, _) if f == "math::variance" => true,
+			Self::Normal(f, _) if f == "math::variance::pop" => true,
+			Self::Normal(f, _) if f == "math::variance::samp" => true,
 			Self::Normal(f, _) if f == "time::max" => true,
 			Self::Normal(f, _) if f == "time::min" => true,
 			_ => false,
@@ -164,10 +174,17 @@ impl Function {
 					OptimisedAggregate::CountFunction
 				}
 			}
+			Self::Normal(f, _) if f == "count::distinct" => OptimisedAggregate::CountDistinct,
 			Self::Normal(f, _) if f == "math::max" => OptimisedAggregate::MathMax,
 			Self::Normal(f, _) if f == "math::mean" => OptimisedAggregate::MathMean,
 			Self::Normal(f, _) if f == "math::min" => OptimisedAggregate::MathMin,
 			Self::Normal(f, _) if f == "math::sum" => OptimisedAggregate::MathSum,
+			Self::Normal(f, _) if f == "math::stddev" => OptimisedAggregate::MathStddev,
+			Self::Normal(f, _) if f == "math::stddev::pop" => OptimisedAggregate::MathStddevPop,
+			Self::Normal(f, _) if f == "math::stddev::samp" => OptimisedAggregate::MathStddev,
+			Self::Normal(f, _) if f == "math::variance" => OptimisedAggregate::MathVariance,
+			Self::Normal(f, _) if f == "math::variance::pop" => OptimisedAggregate::MathVariancePop,
+			Self::Normal(f, _) if f == "math::variance::samp" => OptimisedAggregate::MathVariance,
 			Self::Normal(f, _) if f == "time::max" => OptimisedAggregate::TimeMax,
 			Self::Normal(f, _) if f == "time::min" => OptimisedAggregate::TimeMin,
 			_ => OptimisedAggregate::None,
@@ -310,6 +327,9 @@ impl Function {
 impl fmt::Display for Function {
 	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
 		match self {
+			Self::Normal(s, e) if s == "count::distinct" => {
+				write!(f, "count(DISTINCT {})", Fmt::comma_separated(e))
+			}
 			Self::Normal(s, e) => write!(f, "{s}({})", Fmt::comma_separated(e)),
 			Self::Custom(s, e) => write!(f, "fn::{s}({})", Fmt::comma_separated(e)),
 			Self::Script(s, e) => write!(f, "function({}) {{{s}}}", Fmt::comma_separated(e)),
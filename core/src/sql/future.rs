@@ -1,5 +1,5 @@
 use crate::ctx::Context;
-use crate::dbs::{Options, Transaction};
+use crate::dbs::{Futures, Options, Transaction};
 use crate::doc::CursorDoc;
 use crate::err::Error;
 use crate::sql::block::Block;
@@ -36,8 +36,8 @@ impl Future {
 	) -> Result<Value, Error> {
 		// Process the future if enabled
 		match opt.futures {
-			true => stk.run(|stk| self.0.compute(stk, ctx, opt, txn, doc)).await?.ok(),
-			false => Ok(self.clone().into()),
+			Futures::Enabled => stk.run(|stk| self.0.compute(stk, ctx, opt, txn, doc)).await?.ok(),
+			_ => Ok(self.clone().into()),
 		}
 	}
 }
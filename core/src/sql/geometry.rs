@@ -323,7 +323,7 @@ impl Geometry {
 			Self::Line(v) => match other {
 				Self::Point(w) => v.contains(w),
 				Self::Line(w) => v.contains(w),
-				Self::MultiLine(w) => w.iter().all(|x| w.contains(x)),
+				Self::MultiLine(w) => w.iter().all(|x| v.contains(x)),
 				Self::Collection(w) => w.iter().all(|x| self.contains(x)),
 				_ => false,
 			},
@@ -331,20 +331,21 @@ impl Geometry {
 				Self::Point(w) => v.contains(w),
 				Self::Line(w) => v.contains(w),
 				Self::Polygon(w) => v.contains(w),
-				Self::MultiPolygon(w) => w.iter().all(|x| w.contains(x)),
+				Self::MultiPoint(w) => w.iter().all(|x| v.contains(x)),
+				Self::MultiLine(w) => w.iter().all(|x| v.contains(x)),
+				Self::MultiPolygon(w) => w.iter().all(|x| v.contains(x)),
 				Self::Collection(w) => w.iter().all(|x| self.contains(x)),
-				_ => false,
 			},
 			Self::MultiPoint(v) => match other {
 				Self::Point(w) => v.contains(w),
-				Self::MultiPoint(w) => w.iter().all(|x| w.contains(x)),
+				Self::MultiPoint(w) => w.iter().all(|x| v.contains(x)),
 				Self::Collection(w) => w.iter().all(|x| self.contains(x)),
 				_ => false,
 			},
 			Self::MultiLine(v) => match other {
 				Self::Point(w) => v.contains(w),
 				Self::Line(w) => v.contains(w),
-				Self::MultiLine(w) => w.iter().all(|x| w.contains(x)),
+				Self::MultiLine(w) => w.iter().all(|x| v.contains(x)),
 				Self::Collection(w) => w.iter().all(|x| self.contains(x)),
 				_ => false,
 			},
@@ -0,0 +1,78 @@
+use crate::sql::expression::Expression;
+use crate::sql::field::{Field, Fields};
+use crate::sql::statements::info::InfoStructure;
+use crate::sql::value::Value;
+use revision::revisioned;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::ops::Deref;
+
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
+pub struct Having(pub Value);
+
+impl Deref for Having {
+	type Target = Value;
+	fn deref(&self) -> &Self::Target {
+		&self.0
+	}
+}
+
+impl Having {
+	/// Rewrite the condition so that any expression which is also
+	/// projected in the SELECT fields points to its output field.
+	pub(crate) fn resolve(&self, fields: &Fields) -> Value {
+		Self::rewrite(&self.0, fields)
+	}
+
+	fn rewrite(val: &Value, fields: &Fields) -> Value {
+		// Check if this expression is one of the projected fields
+		for field in fields.other() {
+			if let Field::Single {
+				expr,
+				alias,
+			} = field
+			{
+				if expr == val {
+					return Value::Idiom(alias.clone().unwrap_or_else(|| expr.to_idiom()));
+				}
+			}
+		}
+		// Otherwise rewrite any nested expressions
+		match val {
+			Value::Expression(e) => match e.as_ref() {
+				Expression::Unary {
+					o,
+					v,
+				} => Value::from(Expression::Unary {
+					o: o.to_owned(),
+					v: Self::rewrite(v, fields),
+				}),
+				Expression::Binary {
+					l,
+					o,
+					r,
+				} => Value::from(Expression::Binary {
+					l: Self::rewrite(l, fields),
+					o: o.to_owned(),
+					r: Self::rewrite(r, fields),
+				}),
+			},
+			v => v.to_owned(),
+		}
+	}
+}
+
+impl fmt::Display for Having {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "HAVING {}", self.0)
+	}
+}
+
+impl InfoStructure for Having {
+	fn structure(self) -> Value {
+		self.0.structure()
+	}
+}
@@ -9,13 +9,32 @@ use revision::revisioned;
 use serde::{Deserialize, Serialize};
 use std::fmt;
 
-#[revisioned(revision = 1)]
+#[revisioned(revision = 2)]
 #[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
-pub struct Limit(pub Value);
+pub struct Limit(pub Value, #[revision(start = 2)] pub LimitKind);
+
+/// Whether a limit is a number of rows, or a percentage of the result set
+#[revisioned(revision = 1)]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
+pub enum LimitKind {
+	/// The maximum number of rows
+	#[default]
+	Rows,
+	/// A percentage of the result set
+	Percent,
+}
 
 impl Limit {
+	/// Check if this limit is a percentage of the result set
+	pub fn is_percentage(&self) -> bool {
+		self.1 == LimitKind::Percent
+	}
+	/// Process this limit, returning either the maximum
+	/// number of rows, or a percentage between 0 and 100
 	pub(crate) async fn process(
 		&self,
 		stk: &mut Stk,
@@ -26,7 +45,23 @@ impl Limit {
 	) -> Result<usize, Error> {
 		match self.0.compute(stk, ctx, opt, txn, doc).await {
 			// This is a valid limiting number
-			Ok(Value::Number(Number::Int(v))) if v >= 0 => Ok(v as usize),
+			Ok(Value::Number(v))
+				if v.is_integer()
+					&& v.is_zero_or_positive()
+					&& (!self.is_percentage() || v <= Number::Int(100)) =>
+			{
+				match usize::try_from(v.clone()) {
+					Ok(v) => Ok(v),
+					// The number is too large for this platform
+					Err(_) => Err(Error::LimitOverflow {
+						value: v.to_string(),
+					}),
+				}
+			}
+			// An invalid percentage was specified
+			Ok(v) if self.is_percentage() => Err(Error::InvalidLimitPercentage {
+				value: v.as_string(),
+			}),
 			// An invalid value was specified
 			Ok(v) => Err(Error::InvalidLimit {
 				value: v.as_string(),
@@ -35,10 +70,24 @@ impl Limit {
 			Err(e) => Err(e),
 		}
 	}
+	/// Get the number of rows which a percentage
+	/// limit selects from a result set of a given
+	/// length, returning at least one row when the
+	/// percentage and the result set are non-empty
+	pub(crate) fn rows(percentage: usize, len: usize) -> usize {
+		match percentage {
+			0 => 0,
+			v => (len * v / 100).max(1).min(len),
+		}
+	}
 }
 
 impl fmt::Display for Limit {
 	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-		write!(f, "LIMIT {}", self.0)
+		write!(f, "LIMIT {}", self.0)?;
+		if self.is_percentage() {
+			f.write_str("%")?
+		}
+		Ok(())
 	}
 }
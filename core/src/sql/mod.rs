@@ -12,6 +12,7 @@ pub(crate) mod change_feed_include;
 pub(crate) mod changefeed;
 pub(crate) mod cond;
 pub(crate) mod constant;
+pub(crate) mod cursor;
 pub(crate) mod data;
 pub(crate) mod datetime;
 pub(crate) mod dir;
@@ -29,12 +30,14 @@ pub(crate) mod future;
 pub(crate) mod geometry;
 pub(crate) mod graph;
 pub(crate) mod group;
+pub(crate) mod having;
 pub(crate) mod id;
 pub(crate) mod ident;
 pub(crate) mod idiom;
 pub(crate) mod kind;
 pub(crate) mod language;
 pub(crate) mod limit;
+pub(crate) mod lock;
 pub(crate) mod mock;
 pub(crate) mod model;
 pub(crate) mod number;
@@ -50,6 +53,7 @@ pub(crate) mod permission;
 pub(crate) mod query;
 pub(crate) mod range;
 pub(crate) mod regex;
+pub(crate) mod sample;
 pub(crate) mod scoring;
 pub(crate) mod script;
 pub(crate) mod split;
@@ -83,6 +87,7 @@ pub use self::cast::Cast;
 pub use self::changefeed::ChangeFeed;
 pub use self::cond::Cond;
 pub use self::constant::Constant;
+pub use self::cursor::Cursor;
 pub use self::data::Data;
 pub use self::datetime::Datetime;
 pub use self::dir::Dir;
@@ -100,6 +105,7 @@ pub use self::geometry::Geometry;
 pub use self::graph::Graph;
 pub use self::group::Group;
 pub use self::group::Groups;
+pub use self::having::Having;
 pub use self::id::Id;
 pub use self::ident::Ident;
 pub use self::idiom::Idiom;
@@ -107,12 +113,15 @@ pub use self::idiom::Idioms;
 pub use self::index::Index;
 pub use self::kind::Kind;
 pub use self::limit::Limit;
+pub use self::limit::LimitKind;
+pub use self::lock::LockMode;
 pub use self::mock::Mock;
 pub use self::model::Model;
 pub use self::number::Number;
 pub use self::object::Object;
 pub use self::operation::Operation;
 pub use self::operator::Operator;
+pub use self::order::NullsOrder;
 pub use self::order::Order;
 pub use self::order::Orders;
 pub use self::output::Output;
@@ -123,6 +132,7 @@ pub use self::permission::Permissions;
 pub use self::query::Query;
 pub use self::range::Range;
 pub use self::regex::Regex;
+pub use self::sample::Sample;
 pub use self::scoring::Scoring;
 pub use self::script::Script;
 pub use self::split::Split;
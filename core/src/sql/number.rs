@@ -134,6 +134,7 @@ macro_rules! try_into_prim {
 try_into_prim!(
 	i8 => to_i8, i16 => to_i16, i32 => to_i32, i64 => to_i64, i128 => to_i128,
 	u8 => to_u8, u16 => to_u16, u32 => to_u32, u64 => to_u64, u128 => to_u128,
+	usize => to_usize,
 	f32 => to_f32, f64 => to_f64
 );
 
@@ -1,11 +1,17 @@
 use crate::sql::fmt::Fmt;
 use crate::sql::idiom::Idiom;
-use crate::sql::Value;
+use crate::sql::paths::ID;
+use crate::sql::{Strand, Value};
+use feruca::{Collator, Locale, Tailoring};
+use language_tags::LanguageTag;
 use revision::revisioned;
 use serde::{Deserialize, Serialize};
 use std::cmp::Ordering;
+use std::collections::hash_map::DefaultHasher;
 use std::fmt;
+use std::hash::{Hash, Hasher};
 use std::ops::Deref;
+use std::sync::{Arc, Mutex};
 
 #[revisioned(revision = 1)]
 #[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
@@ -14,29 +20,31 @@ use std::ops::Deref;
 pub struct Orders(pub Vec<Order>);
 
 impl Orders {
-	pub(crate) fn compare(&self, a: &Value, b: &Value) -> Ordering {
-		for order in &self.0 {
-			// Reverse the ordering if DESC
-			let o = match order.random {
-				true => {
-					let a = rand::random::<f64>();
-					let b = rand::random::<f64>();
-					a.partial_cmp(&b)
-				}
-				false => match order.direction {
-					true => a.compare(b, order, order.collate, order.numeric),
-					false => b.compare(a, order, order.collate, order.numeric),
-				},
-			};
-			//
-			match o {
-				Some(Ordering::Greater) => return Ordering::Greater,
-				Some(Ordering::Equal) => continue,
-				Some(Ordering::Less) => return Ordering::Less,
-				None => continue,
-			}
+	/// Returns a comparator for sorting values. Any random ordering gives each
+	/// value a key from its own seed, or otherwise from a new random seed, so
+	/// that the order stays consistent throughout one sort, but differs each
+	/// time the comparator is created.
+	pub(crate) fn comparator(&self) -> Comparator {
+		Comparator {
+			orders: self.clone(),
+			seed: rand::random::<u64>(),
+			collators: self
+				.0
+				.iter()
+				.map(|o| o.collator().map(|c| Arc::new(Mutex::new(c))))
+				.collect(),
 		}
-		Ordering::Equal
+	}
+
+	/// Check if any of the values are ordered randomly, without a seed
+	pub(crate) fn has_unseeded_random(&self) -> bool {
+		self.0.iter().any(|order| order.random && order.seed.is_none())
+	}
+
+	/// Check if the values are only ordered randomly, without a seed. Any
+	/// limited result can then be chosen from a random sample of the values.
+	pub(crate) fn is_unseeded_random(&self) -> bool {
+		matches!(self.0.as_slice(), [order] if order.random && order.seed.is_none())
 	}
 }
 
@@ -61,7 +69,58 @@ impl fmt::Display for Orders {
 	}
 }
 
-#[revisioned(revision = 1)]
+/// Compares values in the order of an ORDER BY clause. The collator for
+/// each locale is created once, and is then used throughout one sort.
+#[derive(Clone)]
+pub(crate) struct Comparator {
+	orders: Orders,
+	seed: u64,
+	collators: Vec<Option<Arc<Mutex<Collator>>>>,
+}
+
+impl Comparator {
+	pub(crate) fn compare(&self, a: &Value, b: &Value) -> Ordering {
+		for (order, collator) in self.orders.iter().zip(&self.collators) {
+			// Reverse the ordering if DESC
+			let o = match order.random {
+				true => {
+					let seed = order.seed.unwrap_or(self.seed);
+					Some(random_key(a, seed).cmp(&random_key(b, seed)))
+				}
+				false => match order.compare_nulls(a, b) {
+					Some(o) => Some(o),
+					None => match collator.as_ref().and_then(|c| order.compare_locale(a, b, c)) {
+						Some(o) if order.direction => Some(o),
+						Some(o) => Some(o.reverse()),
+						None => match order.direction {
+							true => a.compare(b, order, order.collate, order.numeric),
+							false => b.compare(a, order, order.collate, order.numeric),
+						},
+					},
+				},
+			};
+			//
+			match o {
+				Some(Ordering::Greater) => return Ordering::Greater,
+				Some(Ordering::Equal) => continue,
+				Some(Ordering::Less) => return Ordering::Less,
+				None => continue,
+			}
+		}
+		Ordering::Equal
+	}
+
+	/// Check if a value is ordered after the position of a cursor. Any values
+	/// which are ordered equally are ordered by their record ids instead.
+	pub(crate) fn is_after(&self, val: &Value, cursor: &Value) -> bool {
+		match self.compare(val, cursor) {
+			Ordering::Equal => val.pick(ID.as_ref()) > cursor.pick(ID.as_ref()),
+			o => o == Ordering::Greater,
+		}
+	}
+}
+
+#[revisioned(revision = 4)]
 #[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
@@ -72,6 +131,84 @@ pub struct Order {
 	pub numeric: bool,
 	/// true if the direction is ascending
 	pub direction: bool,
+	#[revision(start = 2)]
+	pub nulls: NullsOrder,
+	/// The BCP-47 language tag of the locale to collate strings with
+	#[revision(start = 3)]
+	pub locale: Option<Strand>,
+	/// The seed of a random ordering, which makes the order repeatable
+	#[revision(start = 4)]
+	pub seed: Option<u64>,
+}
+
+impl Order {
+	/// Compares the position of any NONE or NULL values, regardless of the direction
+	fn compare_nulls(&self, a: &Value, b: &Value) -> Option<Ordering> {
+		let first = match self.nulls {
+			NullsOrder::Default => return None,
+			NullsOrder::First => Ordering::Less,
+			NullsOrder::Last => Ordering::Greater,
+		};
+		match (a.pick(self).is_none_or_null(), b.pick(self).is_none_or_null()) {
+			(true, true) => Some(Ordering::Equal),
+			(true, false) => Some(first),
+			(false, true) => Some(first.reverse()),
+			(false, false) => None,
+		}
+	}
+
+	/// Creates a collator for the locale of this ordering, if one is specified
+	fn collator(&self) -> Option<Collator> {
+		let tailoring = tailoring(self.locale.as_ref()?)?;
+		Some(Collator::new(tailoring, true, true))
+	}
+
+	/// Compares two strings using the Unicode collation algorithm
+	fn compare_locale(&self, a: &Value, b: &Value, collator: &Mutex<Collator>) -> Option<Ordering> {
+		match (a.pick(self), b.pick(self)) {
+			(Value::Strand(a), Value::Strand(b)) => {
+				let mut collator = collator.lock().unwrap_or_else(|e| e.into_inner());
+				Some(collator.collate(a.as_str(), b.as_str()))
+			}
+			_ => None,
+		}
+	}
+}
+
+/// Hashes a value together with a seed, giving it a random but repeatable sort key
+fn random_key(v: &Value, seed: u64) -> u64 {
+	let mut hasher = DefaultHasher::new();
+	seed.hash(&mut hasher);
+	v.hash(&mut hasher);
+	hasher.finish()
+}
+
+/// The languages which are collated with the CLDR root collation order, as
+/// they have no tailoring of their own. In German, for example, the root
+/// order already sorts `ä` next to `a`.
+const ROOT_LANGUAGES: &[&str] = &["de", "en", "fr", "id", "it", "ms", "nl", "pt"];
+
+/// Selects the CLDR tailoring for a locale, if strings can be collated in it.
+/// Collation uses the feruca crate, a pure Rust implementation of the Unicode
+/// collation algorithm, because the ICU collation crates need collation data
+/// to be built into, or linked with, every build. feruca provides the root
+/// order and the Arabic tailoring. Any other tailored locale, such as `sv-SE`
+/// which sorts `ä` after `z`, is rejected rather than sorted incorrectly.
+pub(crate) fn tailoring(locale: &str) -> Option<Tailoring> {
+	let tag = LanguageTag::parse(locale).ok()?;
+	// Extensions, such as `-u-co-phonebk`, select other collations
+	if tag.extension().is_some() || tag.private_use().is_some() {
+		return None;
+	}
+	let language = tag.primary_language().to_ascii_lowercase();
+	let locale = match language.as_str() {
+		"ar" => Locale::ArabicScript,
+		// Canadian French compares accents from the end of the string
+		"fr" if tag.region().is_some_and(|r| r.eq_ignore_ascii_case("ca")) => return None,
+		v if ROOT_LANGUAGES.contains(&v) => Locale::Root,
+		_ => return None,
+	};
+	Some(Tailoring::Cldr(locale))
 }
 
 impl Deref for Order {
@@ -85,11 +222,17 @@ impl fmt::Display for Order {
 	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
 		write!(f, "{}", self.order)?;
 		if self.random {
-			write!(f, "RAND()")?;
+			match self.seed {
+				Some(seed) => write!(f, "RAND({seed})")?,
+				None => write!(f, "RAND()")?,
+			}
 		}
 		if self.collate {
 			write!(f, " COLLATE")?;
 		}
+		if let Some(ref v) = self.locale {
+			write!(f, " {v}")?;
+		}
 		if self.numeric {
 			write!(f, " NUMERIC")?;
 		}
@@ -97,6 +240,24 @@ impl fmt::Display for Order {
 			false => write!(f, " DESC")?,
 			true => (),
 		};
+		match self.nulls {
+			NullsOrder::First => write!(f, " NULLS FIRST")?,
+			NullsOrder::Last => write!(f, " NULLS LAST")?,
+			NullsOrder::Default => (),
+		};
 		Ok(())
 	}
 }
+
+/// Where any NONE or NULL values are placed in an ordering
+#[revisioned(revision = 1)]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
+pub enum NullsOrder {
+	/// Nulls are ordered before any other value when ascending, and after them when descending
+	#[default]
+	Default,
+	First,
+	Last,
+}
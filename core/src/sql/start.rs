@@ -2,7 +2,6 @@ use crate::ctx::Context;
 use crate::dbs::{Options, Transaction};
 use crate::doc::CursorDoc;
 use crate::err::Error;
-use crate::sql::number::Number;
 use crate::sql::value::Value;
 use reblessive::tree::Stk;
 use revision::revisioned;
@@ -26,7 +25,15 @@ impl Start {
 	) -> Result<usize, Error> {
 		match self.0.compute(stk, ctx, opt, txn, doc).await {
 			// This is a valid starting number
-			Ok(Value::Number(Number::Int(v))) if v >= 0 => Ok(v as usize),
+			Ok(Value::Number(v)) if v.is_integer() && v.is_zero_or_positive() => {
+				match usize::try_from(v.clone()) {
+					Ok(v) => Ok(v),
+					// The number is too large for this platform
+					Err(_) => Err(Error::StartOverflow {
+						value: v.to_string(),
+					}),
+				}
+			}
 			// An invalid value was specified
 			Ok(v) => Err(Error::InvalidStart {
 				value: v.as_string(),
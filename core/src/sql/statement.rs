@@ -9,8 +9,10 @@ use crate::sql::{
 		AnalyzeStatement, BeginStatement, BreakStatement, CancelStatement, CommitStatement,
 		ContinueStatement, CreateStatement, DefineStatement, DeleteStatement, ForeachStatement,
 		IfelseStatement, InfoStatement, InsertStatement, KillStatement, LiveStatement,
-		OptionStatement, OutputStatement, RelateStatement, RemoveStatement, SelectStatement,
-		SetStatement, ShowStatement, SleepStatement, ThrowStatement, UpdateStatement, UseStatement,
+		OptionStatement, OutputStatement, RelateStatement, ReleaseStatement, RemoveStatement,
+		RollbackStatement, SavepointStatement, SelectStatement, SetStatement, ShowStatement,
+		SleepStatement, ThrowStatement, UnionStatement, UpdateStatement, UpsertStatement,
+		UseStatement, WithStatement,
 	},
 	value::Value,
 };
@@ -54,7 +56,7 @@ impl Display for Statements {
 	}
 }
 
-#[revisioned(revision = 2)]
+#[revisioned(revision = 6)]
 #[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Store, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
@@ -88,6 +90,18 @@ pub enum Statement {
 	Use(UseStatement),
 	#[revision(start = 2)]
 	Rebuild(RebuildStatement),
+	#[revision(start = 3)]
+	Union(UnionStatement),
+	#[revision(start = 4)]
+	With(WithStatement),
+	#[revision(start = 5)]
+	Upsert(UpsertStatement),
+	#[revision(start = 6)]
+	Savepoint(SavepointStatement),
+	#[revision(start = 6)]
+	Rollback(RollbackStatement),
+	#[revision(start = 6)]
+	Release(ReleaseStatement),
 }
 
 impl Statement {
@@ -129,8 +143,11 @@ impl Statement {
 			Self::Show(_) => false,
 			Self::Sleep(_) => false,
 			Self::Throw(_) => false,
+			Self::Union(v) => v.writeable(),
 			Self::Update(v) => v.writeable(),
+			Self::Upsert(v) => v.writeable(),
 			Self::Use(_) => false,
+			Self::With(v) => v.writeable(),
 			_ => unreachable!(),
 		}
 	}
@@ -165,7 +182,10 @@ impl Statement {
 			Self::Show(v) => v.compute(ctx, opt, txn, doc).await,
 			Self::Sleep(v) => v.compute(ctx, opt, txn, doc).await,
 			Self::Throw(v) => v.compute(stk, ctx, opt, txn, doc).await,
+			Self::Union(v) => v.compute(stk, ctx, opt, txn, doc).await,
 			Self::Update(v) => v.compute(stk, ctx, opt, txn, doc).await,
+			Self::Upsert(v) => v.compute(stk, ctx, opt, txn, doc).await,
+			Self::With(v) => v.compute(stk, ctx, opt, txn, doc).await,
 			Self::Value(v) => {
 				// Ensure futures are processed
 				let opt = &opt.new_with_futures(true);
@@ -200,14 +220,20 @@ impl Display for Statement {
 			Self::Output(v) => write!(Pretty::from(f), "{v}"),
 			Self::Rebuild(v) => write!(Pretty::from(f), "{v}"),
 			Self::Relate(v) => write!(Pretty::from(f), "{v}"),
+			Self::Release(v) => write!(Pretty::from(f), "{v}"),
 			Self::Remove(v) => write!(Pretty::from(f), "{v}"),
+			Self::Rollback(v) => write!(Pretty::from(f), "{v}"),
+			Self::Savepoint(v) => write!(Pretty::from(f), "{v}"),
 			Self::Select(v) => write!(Pretty::from(f), "{v}"),
 			Self::Set(v) => write!(Pretty::from(f), "{v}"),
 			Self::Show(v) => write!(Pretty::from(f), "{v}"),
 			Self::Sleep(v) => write!(Pretty::from(f), "{v}"),
 			Self::Throw(v) => write!(Pretty::from(f), "{v}"),
+			Self::Union(v) => write!(Pretty::from(f), "{v}"),
 			Self::Update(v) => write!(Pretty::from(f), "{v}"),
+			Self::Upsert(v) => write!(Pretty::from(f), "{v}"),
 			Self::Use(v) => write!(Pretty::from(f), "{v}"),
+			Self::With(v) => write!(Pretty::from(f), "{v}"),
 		}
 	}
 }
@@ -2,6 +2,7 @@ use crate::ctx::Context;
 use crate::dbs::{Iterator, Options, Statement, Transaction};
 use crate::doc::CursorDoc;
 use crate::err::Error;
+use crate::idx::planner::QueryPlanner;
 use crate::sql::{Cond, Output, Timeout, Value, Values};
 use derive::Store;
 use reblessive::tree::Stk;
@@ -45,20 +46,36 @@ impl DeleteStatement {
 		let stm = Statement::from(self);
 		// Ensure futures are stored
 		let opt = &opt.new_with_futures(false).with_projections(false);
+		// Get a query planner
+		let with = None;
+		let mut planner = QueryPlanner::new(opt, &with, &self.cond);
 		// Loop over the delete targets
 		for w in self.what.0.iter() {
 			let v = w.compute(stk, ctx, opt, txn, doc).await?;
-			i.prepare(stk, ctx, opt, txn, &stm, v).await.map_err(|e| match e {
-				Error::InvalidStatementTarget {
-					value: v,
-				} => Error::DeleteStatement {
-					value: v,
-				},
-				e => e,
-			})?;
+			match v {
+				// Tables filtered by a WHERE clause are
+				// iterated using any matching indexes
+				Value::Table(t) if self.cond.is_some() => {
+					planner.add_iterables(stk, ctx, txn, t, &mut i).await?;
+				}
+				v => i.prepare(stk, ctx, opt, txn, &stm, v).await.map_err(|e| match e {
+					Error::InvalidStatementTarget {
+						value: v,
+					} => Error::DeleteStatement {
+						value: v,
+					},
+					e => e,
+				})?,
+			}
+		}
+		// Create a new context
+		let mut ctx = Context::new(ctx);
+		// Add query executors if any
+		if planner.has_executors() {
+			ctx.set_query_planner(&planner);
 		}
 		// Output the results
-		match i.output(stk, ctx, opt, txn, &stm).await? {
+		match i.output(stk, &ctx, opt, txn, &stm).await? {
 			// This is a single record result
 			Value::Array(mut a) if self.only => match a.len() {
 				// There was exactly one result
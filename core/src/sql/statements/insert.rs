@@ -1,10 +1,12 @@
+use crate::cnf::INSERT_SELECT_BATCH_SIZE;
 use crate::ctx::Context;
 use crate::dbs::{Iterable, Iterator, Options, Statement, Transaction};
 use crate::doc::CursorDoc;
 use crate::err::Error;
-use crate::sql::{Data, Output, Timeout, Value};
+use crate::sql::statements::SelectStatement;
+use crate::sql::{Data, Output, Subquery, Table, Timeout, Value};
 use derive::Store;
-use reblessive::tree::Stk;
+use reblessive::{tree::Stk, TreeStack};
 use revision::revisioned;
 use serde::{Deserialize, Serialize};
 use std::fmt;
@@ -64,6 +66,10 @@ impl InsertStatement {
 				}
 				// Check if this is a modern statement
 				Data::SingleExpression(v) => {
+					// Insert the rows of a SELECT as they are selected
+					if let Some(s) = Self::streamable(&into, v) {
+						return self.compute_select(stk, ctx, opt, txn, doc, &into, s).await;
+					}
 					let v = v.compute(stk, ctx, opt, txn, doc).await?;
 					match v {
 						Value::Array(v) => {
@@ -100,6 +106,93 @@ impl InsertStatement {
 		// Output the results
 		i.output(stk, ctx, opt, txn, &stm).await
 	}
+
+	/// Check if the value is a SELECT whose rows can be inserted as
+	/// they are selected. The SELECT must not scan the table which is
+	/// being inserted into, as it could then select inserted records.
+	fn streamable<'b>(into: &Table, v: &'b Value) -> Option<&'b SelectStatement> {
+		match v {
+			Value::Subquery(v) => match v.as_ref() {
+				Subquery::Select(s) if !s.only => s
+					.what
+					.iter()
+					.all(|v| match v {
+						Value::Table(t) => t.0 != into.0,
+						Value::Range(r) => r.tb != into.0,
+						Value::Thing(_) | Value::Mock(_) => true,
+						_ => false,
+					})
+					.then_some(s),
+				_ => None,
+			},
+			_ => None,
+		}
+	}
+
+	/// Insert the rows of a SELECT in batches as they are selected,
+	/// rather than waiting for the full result set to be computed.
+	#[allow(clippy::too_many_arguments)]
+	async fn compute_select(
+		&self,
+		stk: &mut Stk,
+		ctx: &Context<'_>,
+		opt: &Options,
+		txn: &Transaction,
+		doc: Option<&CursorDoc<'_>>,
+		into: &Table,
+		sel: &SelectStatement,
+	) -> Result<Value, Error> {
+		// Add the parent document, as for any subquery
+		let mut sub = Context::new(ctx);
+		if let Some(doc) = doc {
+			sub.add_value("parent", doc.doc.as_ref());
+		}
+		// Create a channel for the selected rows
+		let (chn, rcv) = channel::bounded(INSERT_SELECT_BATCH_SIZE);
+		// Select the rows on their own stack, so that
+		// they can be selected while they are inserted
+		let select = async {
+			let mut stack = TreeStack::new();
+			stack.enter(|stk| sel.stream(stk, &sub, opt, txn, doc, chn)).finish().await
+		};
+		// Insert each batch of rows as it is received
+		let insert = async {
+			let res = async {
+				// Assign the statement
+				let stm = Statement::from(self);
+				// Collect the output of each batch
+				let mut out = Vec::new();
+				while let Ok(v) = rcv.recv().await {
+					// Create a new iterator
+					let mut i = Iterator::new();
+					let mut next = Some(v);
+					while let Some(v) = next {
+						// Specify the new table record id
+						let id = v.rid().generate(into, true)?;
+						// Pass the mergeable to the iterator
+						i.ingest(Iterable::Mergeable(id, v));
+						// Take any other rows which are waiting
+						next = rcv.try_recv().ok();
+					}
+					// Output the results of the batch
+					if let Value::Array(v) = i.output(stk, ctx, opt, txn, &stm).await? {
+						out.extend(v);
+					}
+				}
+				Ok(Value::from(out))
+			}
+			.await;
+			// Stop the SELECT if the rows could not be inserted
+			rcv.close();
+			res
+		};
+		// Wait for both to finish
+		match futures::join!(select, insert) {
+			(_, Err(e)) => Err(e),
+			(Err(e), _) => Err(e),
+			(Ok(_), Ok(v)) => Ok(v),
+		}
+	}
 }
 
 impl fmt::Display for InsertStatement {
@@ -10,7 +10,9 @@ use crate::dbs::{Options, Transaction};
 use crate::doc::CursorDoc;
 use crate::err::Error;
 use crate::fflags::FFLAGS;
+use crate::iam::{Action, Auth, ResourceKind, Role};
 use crate::kvs::lq_structs::{KillEntry, TrackedResult};
+use crate::sql::Base;
 use crate::sql::Uuid;
 use crate::sql::Value;
 
@@ -19,8 +21,8 @@ use crate::sql::Value;
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
 pub struct KillStatement {
-	// Uuid of Live Query
-	// or Param resolving to Uuid of Live Query
+	// Uuid of Live Query or running statement
+	// or Param resolving to either Uuid
 	pub id: Value,
 }
 
@@ -34,11 +36,7 @@ impl KillStatement {
 		txn: &Transaction,
 		_doc: Option<&CursorDoc<'_>>,
 	) -> Result<Value, Error> {
-		// Is realtime enabled?
-		opt.realtime()?;
-		// Valid options?
-		opt.valid_for_db()?;
-		// Resolve live query id
+		// Resolve the query id
 		let live_query_id = match &self.id {
 			Value::Uuid(id) => *id,
 			Value::Param(param) => match param.compute(stk, ctx, opt, txn, None).await? {
@@ -74,6 +72,22 @@ impl KillStatement {
 				});
 			}
 		};
+		// Kill the statement if it is running within the session
+		if let Some(queries) = ctx.get_running_queries() {
+			match queries.cancel(&live_query_id.0, |q| Self::can_kill(opt, Some(&q.auth))) {
+				Some(true) => return Ok(Value::None),
+				Some(false) => {
+					return Err(Error::KillStatement {
+						value: "KILL statement uuid belongs to another user".to_string(),
+					})
+				}
+				None => (),
+			}
+		}
+		// Is realtime enabled?
+		opt.realtime()?;
+		// Valid options?
+		opt.valid_for_db()?;
 		// Claim transaction
 		let mut run = txn.lock().await;
 		if FFLAGS.change_feed_live_queries.enabled() {
@@ -89,6 +103,14 @@ impl KillStatement {
 			match run.get(key).await? {
 				Some(val) => match std::str::from_utf8(&val) {
 					Ok(tb) => {
+						// Fetch the table live query
+						let lv = run.get_tb_live(opt.ns(), opt.db(), tb, &live_query_id).await?;
+						// Only the user which started the live query, or an owner, can kill it
+						if !Self::can_kill(opt, lv.auth.as_ref()) {
+							return Err(Error::KillStatement {
+								value: "KILL statement uuid belongs to another session".to_string(),
+							});
+						}
 						// Delete the node live query
 						let key = crate::key::node::lq::new(
 							opt.id()?,
@@ -118,6 +140,22 @@ impl KillStatement {
 		// Return the query id
 		Ok(Value::None)
 	}
+
+	/// Check if the current user started a query, or is an owner
+	/// of the root, namespace, or database which it runs within
+	fn can_kill(opt: &Options, auth: Option<&Auth>) -> bool {
+		// Any query can be killed when authentication is disabled
+		if !opt.auth_enabled {
+			return true;
+		}
+		// The user which started the query can kill it
+		if auth.is_some_and(|v| v == opt.auth.as_ref()) {
+			return true;
+		}
+		// Owners can kill any query within their level
+		opt.auth.has_role(&Role::Owner)
+			&& opt.is_allowed(Action::Edit, ResourceKind::Any, &Base::Db).is_ok()
+	}
 }
 
 impl fmt::Display for KillStatement {
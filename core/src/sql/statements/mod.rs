@@ -17,14 +17,20 @@ pub(crate) mod option;
 pub(crate) mod output;
 pub(crate) mod rebuild;
 pub(crate) mod relate;
+pub(crate) mod release;
 pub(crate) mod remove;
+pub(crate) mod rollback;
+pub(crate) mod savepoint;
 pub(crate) mod select;
 pub(crate) mod set;
 pub(crate) mod show;
 pub(crate) mod sleep;
 pub(crate) mod throw;
+pub(crate) mod union;
 pub(crate) mod update;
+pub(crate) mod upsert;
 pub(crate) mod r#use;
+pub(crate) mod with;
 
 pub use self::analyze::AnalyzeStatement;
 pub use self::begin::BeginStatement;
@@ -44,12 +50,18 @@ pub use self::r#break::BreakStatement;
 pub use self::r#continue::ContinueStatement;
 pub use self::r#use::UseStatement;
 pub use self::relate::RelateStatement;
+pub use self::release::ReleaseStatement;
+pub use self::rollback::RollbackStatement;
+pub use self::savepoint::SavepointStatement;
 pub use self::select::SelectStatement;
 pub use self::set::SetStatement;
 pub use self::show::ShowStatement;
 pub use self::sleep::SleepStatement;
 pub use self::throw::ThrowStatement;
+pub use self::union::UnionStatement;
 pub use self::update::UpdateStatement;
+pub use self::upsert::UpsertStatement;
+pub use self::with::{Cte, WithStatement};
 
 pub use self::define::{
 	DefineAnalyzerStatement, DefineDatabaseStatement, DefineEventStatement, DefineFieldStatement,
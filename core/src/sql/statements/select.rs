@@ -3,43 +3,65 @@ use crate::dbs::{Iterable, Iterator, Options, Statement, Transaction};
 use crate::doc::CursorDoc;
 use crate::err::Error;
 use crate::idx::planner::QueryPlanner;
+use crate::sql::cursor::Position;
+use crate::sql::paths::ID;
 use crate::sql::{
-	Cond, Explain, Fetchs, Field, Fields, Groups, Idioms, Limit, Orders, Splits, Start, Timeout,
-	Value, Values, Version, With,
+	Cond, Cursor, Explain, Expression, Fetchs, Field, Fields, Groups, Having, Ident, Idioms, Limit,
+	LockMode, NullsOrder, Operator, Orders, Range, Sample, Splits, Start, Timeout, Value, Values,
+	Version, With,
 };
+use channel::Sender;
 use derive::Store;
 use reblessive::tree::Stk;
 use revision::revisioned;
 use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
 use std::fmt;
+use std::ops::Bound;
 
-#[revisioned(revision = 2)]
+#[revisioned(revision = 8)]
 #[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Store, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
 pub struct SelectStatement {
+	#[revision(start = 4)]
+	pub distinct: bool,
 	pub expr: Fields,
 	pub omit: Option<Idioms>,
 	#[revision(start = 2)]
 	pub only: bool,
 	pub what: Values,
+	#[revision(start = 6)]
+	pub alias: Option<Ident>,
 	pub with: Option<With>,
 	pub cond: Option<Cond>,
 	pub split: Option<Splits>,
 	pub group: Option<Groups>,
+	#[revision(start = 3)]
+	pub having: Option<Having>,
 	pub order: Option<Orders>,
+	#[revision(start = 7)]
+	pub sample: Option<Sample>,
 	pub limit: Option<Limit>,
 	pub start: Option<Start>,
+	#[revision(start = 8)]
+	pub after: Option<Cursor>,
 	pub fetch: Option<Fetchs>,
 	pub version: Option<Version>,
 	pub timeout: Option<Timeout>,
 	pub parallel: bool,
+	#[revision(start = 5)]
+	pub lock: Option<LockMode>,
 	pub explain: Option<Explain>,
 }
 
 impl SelectStatement {
 	/// Check if we require a writeable transaction
 	pub(crate) fn writeable(&self) -> bool {
+		// Locking records requires a writeable transaction
+		if self.lock.is_some() {
+			return true;
+		}
 		if self.expr.iter().any(|v| match v {
 			Field::All => false,
 			Field::Single {
@@ -63,6 +85,34 @@ impl SelectStatement {
 		opt: &Options,
 		txn: &Transaction,
 		doc: Option<&CursorDoc<'_>>,
+	) -> Result<Value, Error> {
+		self.process(stk, ctx, opt, txn, doc, None).await
+	}
+
+	/// Process this type, sending each selected record to the
+	/// channel as it is iterated, rather than returning them.
+	/// The ONLY clause is not applied to the streamed records.
+	pub(crate) async fn stream(
+		&self,
+		stk: &mut Stk,
+		ctx: &Context<'_>,
+		opt: &Options,
+		txn: &Transaction,
+		doc: Option<&CursorDoc<'_>>,
+		chn: Sender<Value>,
+	) -> Result<(), Error> {
+		self.process(stk, ctx, opt, txn, doc, Some(chn)).await?;
+		Ok(())
+	}
+
+	async fn process(
+		&self,
+		stk: &mut Stk,
+		ctx: &Context<'_>,
+		opt: &Options,
+		txn: &Transaction,
+		doc: Option<&CursorDoc<'_>>,
+		chn: Option<Sender<Value>>,
 	) -> Result<Value, Error> {
 		// Valid options?
 		opt.valid_for_db()?;
@@ -70,27 +120,87 @@ impl SelectStatement {
 		let mut i = Iterator::new();
 		// Ensure futures are stored
 		let opt = &opt.new_with_futures(false).with_projections(true);
-		// Get a query planner
-		let mut planner = QueryPlanner::new(opt, &self.with, &self.cond);
 		// Used for ONLY: is the limit 1?
 		let limit_is_one_or_zero = match &self.limit {
-			Some(l) => l.process(stk, ctx, opt, txn, doc).await? <= 1,
+			Some(l) if !l.is_percentage() => l.process(stk, ctx, opt, txn, doc).await? <= 1,
 			_ => false,
 		};
 		// Fail for multiple targets without a limit
 		if self.only && !limit_is_one_or_zero && self.what.0.len() > 1 {
 			return Err(Error::SingleOnlyOutput);
 		}
+		// Get the position of any cursor
+		let after = match &self.after {
+			Some(v) => Some(v.process(stk, ctx, opt, txn, doc).await?),
+			None => None,
+		};
+		// The condition which the query planner uses
+		let mut cond = Cow::Borrowed(&self.cond);
+		let after = match after {
+			// Records after a record id are scanned in record id order
+			Some(Position::Record(rid)) => {
+				if let Some(orders) = &self.order {
+					if !matches!(orders.0.as_slice(), [o] if o.order.is_id() && o.direction) {
+						return Err(Error::InvalidCursorOrder);
+					}
+				}
+				Some(rid)
+			}
+			// Records are skipped until they are ordered after the ordered values
+			Some(Position::Keys(keys, rid)) => {
+				let orders = self.cursor_orders(keys.len())?;
+				if let Some(c) = self.cursor_bound(orders, &keys[0]) {
+					cond = Cow::Owned(Some(c));
+				}
+				let mut row = Value::base();
+				for (order, key) in orders.iter().zip(keys) {
+					row.put(order, key);
+				}
+				row.put(ID.as_ref(), Value::Thing(rid));
+				i.start_after(row, orders.comparator());
+				None
+			}
+			None => None,
+		};
+		// Get a query planner
+		let mut planner = QueryPlanner::new(opt, &self.with, &cond);
+		// The result of any aliased subquery
+		let mut aliased = None;
 		// Loop over the select targets
 		for w in self.what.0.iter() {
 			let v = w.compute(stk, ctx, opt, txn, doc).await?;
+			if self.alias.is_some() {
+				aliased = Some(v.clone());
+			}
 			match v {
 				Value::Table(t) => {
 					if self.only && !limit_is_one_or_zero {
 						return Err(Error::SingleOnlyOutput);
 					}
-
-					planner.add_iterables(stk, ctx, txn, t, &mut i).await?;
+					// Scan the table from the record after the cursor
+					if let Some(rid) = &after {
+						if rid.tb != t.0 {
+							return Err(Error::InvalidCursor {
+								value: rid.to_string(),
+							});
+						}
+						i.ingest(Iterable::Range(Range {
+							tb: t.0,
+							beg: Bound::Excluded(rid.id.clone()),
+							end: Bound::Unbounded,
+						}));
+						continue;
+					}
+					// A common table expression shadows a table with the same name
+					match ctx.cte(&t) {
+						Some(Value::Array(v)) => {
+							for v in v.iter() {
+								i.ingest(Iterable::Value(v.clone()));
+							}
+						}
+						Some(v) => i.ingest(Iterable::Value(v.clone())),
+						None => planner.add_iterables(stk, ctx, txn, t, &mut i).await?,
+					}
 				}
 				Value::Thing(v) => i.ingest(Iterable::Thing(v)),
 				Value::Range(v) => {
@@ -142,12 +252,22 @@ impl SelectStatement {
 		}
 		// Create a new context
 		let mut ctx = Context::new(ctx);
+		// Bind an aliased subquery to its name, so
+		// that it can be selected from by subqueries
+		if let (Some(alias), Some(v)) = (&self.alias, aliased) {
+			ctx.add_cte(alias.to_raw(), v);
+		}
 		// Assign the statement
 		let stm = Statement::from(self);
 		// Add query executors if any
 		if planner.has_executors() {
 			ctx.set_query_planner(&planner);
 		}
+		// Send the results on as they are iterated
+		if let Some(chn) = chn {
+			i.stream(stk, &ctx, opt, txn, &stm, chn).await?;
+			return Ok(Value::None);
+		}
 		// Output the results
 		match i.output(stk, &ctx, opt, txn, &stm).await? {
 			// This is a single record result
@@ -163,11 +283,83 @@ impl SelectStatement {
 			v => Ok(v),
 		}
 	}
+
+	/// Check that the ordered values of an AFTER cursor match the ORDER BY clause
+	fn cursor_orders(&self, len: usize) -> Result<&Orders, Error> {
+		let invalid = |message: &str| Error::InvalidCursorKeys {
+			message: message.to_owned(),
+		};
+		let orders = match &self.order {
+			Some(orders) if orders.len() == len => orders,
+			_ => {
+				return Err(invalid(
+					"it must contain a value for each field in the ORDER BY clause, followed by a record id",
+				))
+			}
+		};
+		if orders.has_unseeded_random() {
+			return Err(invalid("the records are ordered randomly"));
+		}
+		if self.group.is_some() || self.split.is_some() {
+			return Err(invalid("the records are grouped or split"));
+		}
+		// Records with equal values are ordered by their record ids
+		let id = self.expr.iter().any(|v| match v {
+			Field::All => true,
+			Field::Single {
+				expr: Value::Idiom(i),
+				alias: None,
+			} => i.is_id(),
+			_ => false,
+		});
+		if !id || self.expr.single().is_some() {
+			return Err(invalid("the record id is not selected"));
+		}
+		Ok(orders)
+	}
+
+	/// Bounds the records by the first ordered value of an AFTER cursor, so that
+	/// any index on the ordered field can be used to seek to the cursor position
+	fn cursor_bound(&self, orders: &Orders, key: &Value) -> Option<Cond> {
+		// The ordered field must be the same field as in the stored records
+		if !matches!(self.expr.0.as_slice(), [Field::All]) || self.omit.is_some() {
+			return None;
+		}
+		let order = orders.first()?;
+		if order.random
+			|| order.collate
+			|| order.numeric
+			|| order.locale.is_some()
+			|| !matches!(order.nulls, NullsOrder::Default)
+		{
+			return None;
+		}
+		let bound = Value::Expression(Box::new(Expression::Binary {
+			l: Value::Idiom(order.order.clone()),
+			o: match order.direction {
+				true => Operator::MoreThanOrEqual,
+				false => Operator::LessThanOrEqual,
+			},
+			r: key.clone(),
+		}));
+		Some(Cond(match &self.cond {
+			Some(c) => Value::Expression(Box::new(Expression::Binary {
+				l: c.0.clone(),
+				o: Operator::And,
+				r: bound,
+			})),
+			None => bound,
+		}))
+	}
 }
 
 impl fmt::Display for SelectStatement {
 	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-		write!(f, "SELECT {}", self.expr)?;
+		f.write_str("SELECT")?;
+		if self.distinct {
+			f.write_str(" DISTINCT")?
+		}
+		write!(f, " {}", self.expr)?;
 		if let Some(ref v) = self.omit {
 			write!(f, " OMIT {v}")?
 		}
@@ -176,6 +368,9 @@ impl fmt::Display for SelectStatement {
 			f.write_str(" ONLY")?
 		}
 		write!(f, " {}", self.what)?;
+		if let Some(ref v) = self.alias {
+			write!(f, " AS {v}")?
+		}
 		if let Some(ref v) = self.with {
 			write!(f, " {v}")?
 		}
@@ -188,15 +383,24 @@ impl fmt::Display for SelectStatement {
 		if let Some(ref v) = self.group {
 			write!(f, " {v}")?
 		}
+		if let Some(ref v) = self.having {
+			write!(f, " {v}")?
+		}
 		if let Some(ref v) = self.order {
 			write!(f, " {v}")?
 		}
+		if let Some(ref v) = self.sample {
+			write!(f, " {v}")?
+		}
 		if let Some(ref v) = self.limit {
 			write!(f, " {v}")?
 		}
 		if let Some(ref v) = self.start {
 			write!(f, " {v}")?
 		}
+		if let Some(ref v) = self.after {
+			write!(f, " {v}")?
+		}
 		if let Some(ref v) = self.fetch {
 			write!(f, " {v}")?
 		}
@@ -209,6 +413,9 @@ impl fmt::Display for SelectStatement {
 		if self.parallel {
 			f.write_str(" PARALLEL")?
 		}
+		if let Some(ref v) = self.lock {
+			write!(f, " {v}")?
+		}
 		if let Some(ref v) = self.explain {
 			write!(f, " {v}")?
 		}
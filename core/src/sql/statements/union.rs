@@ -0,0 +1,103 @@
+use crate::ctx::Context;
+use crate::dbs::{Options, Transaction};
+use crate::doc::CursorDoc;
+use crate::err::Error;
+use crate::sql::statements::SelectStatement;
+use crate::sql::{Limit, Orders, Start, Value};
+use derive::Store;
+use reblessive::tree::Stk;
+use revision::revisioned;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fmt;
+
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Store, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
+pub struct UnionStatement {
+	pub exprs: Vec<SelectStatement>,
+	pub distinct: bool,
+	pub order: Option<Orders>,
+	pub limit: Option<Limit>,
+	pub start: Option<Start>,
+}
+
+impl UnionStatement {
+	/// Check if we require a writeable transaction
+	pub(crate) fn writeable(&self) -> bool {
+		self.exprs.iter().any(|v| v.writeable())
+	}
+
+	/// Process this type returning a computed simple Value
+	pub(crate) async fn compute(
+		&self,
+		stk: &mut Stk,
+		ctx: &Context<'_>,
+		opt: &Options,
+		txn: &Transaction,
+		doc: Option<&CursorDoc<'_>>,
+	) -> Result<Value, Error> {
+		// Valid options?
+		opt.valid_for_db()?;
+		// Compute each of the select statements
+		let mut values = Vec::new();
+		for v in self.exprs.iter() {
+			match v.compute(stk, ctx, opt, txn, doc).await? {
+				Value::Array(v) => values.extend(v),
+				Value::None => {}
+				v => values.push(v),
+			}
+		}
+		// Remove any duplicate values
+		if self.distinct {
+			let mut seen = HashSet::with_capacity(values.len());
+			values.retain(|v| seen.insert(v.to_owned()));
+		}
+		// Sort the combined values
+		if let Some(orders) = &self.order {
+			let cmp = orders.comparator();
+			values.sort_by(|a, b| cmp.compare(a, b));
+		}
+		// Apply the start and limit clauses
+		let start = match &self.start {
+			Some(v) => v.process(stk, ctx, opt, txn, doc).await?,
+			None => 0,
+		};
+		let limit = match &self.limit {
+			Some(v) if v.is_percentage() => {
+				let p = v.process(stk, ctx, opt, txn, doc).await?;
+				Limit::rows(p, values.len().saturating_sub(start))
+			}
+			Some(v) => v.process(stk, ctx, opt, txn, doc).await?,
+			None => usize::MAX,
+		};
+		Ok(values.into_iter().skip(start).take(limit).collect::<Vec<_>>().into())
+	}
+}
+
+impl fmt::Display for UnionStatement {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let operator = if self.distinct {
+			" UNION DISTINCT "
+		} else {
+			" UNION ALL "
+		};
+		for (i, v) in self.exprs.iter().enumerate() {
+			if i > 0 {
+				f.write_str(operator)?;
+			}
+			write!(f, "{v}")?;
+		}
+		if let Some(ref v) = self.order {
+			write!(f, " {v}")?
+		}
+		if let Some(ref v) = self.limit {
+			write!(f, " {v}")?
+		}
+		if let Some(ref v) = self.start {
+			write!(f, " {v}")?
+		}
+		Ok(())
+	}
+}
@@ -0,0 +1,107 @@
+use crate::ctx::Context;
+use crate::dbs::{Iterator, Options, Statement, Transaction};
+use crate::doc::CursorDoc;
+use crate::err::Error;
+use crate::sql::{Data, Output, Timeout, Value, Values};
+use derive::Store;
+use reblessive::tree::Stk;
+use revision::revisioned;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Store, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
+pub struct UpsertStatement {
+	pub only: bool,
+	pub what: Values,
+	pub data: Option<Data>,
+	/// The data which is applied instead, if the record already exists
+	pub update: Option<Data>,
+	pub output: Option<Output>,
+	pub timeout: Option<Timeout>,
+	pub parallel: bool,
+}
+
+impl UpsertStatement {
+	/// Check if we require a writeable transaction
+	pub(crate) fn writeable(&self) -> bool {
+		true
+	}
+	/// Process this type returning a computed simple Value
+	pub(crate) async fn compute(
+		&self,
+		stk: &mut Stk,
+		ctx: &Context<'_>,
+		opt: &Options,
+		txn: &Transaction,
+		doc: Option<&CursorDoc<'_>>,
+	) -> Result<Value, Error> {
+		// Valid options?
+		opt.valid_for_db()?;
+		// Create a new iterator
+		let mut i = Iterator::new();
+		// Assign the statement
+		let stm = Statement::from(self);
+		// Ensure futures are stored
+		let opt = &opt.new_with_futures(false).with_projections(false);
+		// Loop over the upsert targets
+		for w in self.what.0.iter() {
+			let v = w.compute(stk, ctx, opt, txn, doc).await?;
+			// Only specific records can be upserted, as
+			// a missing record is created with its own id
+			if let Value::Table(_) | Value::Range(_) | Value::Edges(_) = v {
+				return Err(Error::UpsertStatement {
+					value: v.to_string(),
+				});
+			}
+			i.prepare(stk, ctx, opt, txn, &stm, v).await.map_err(|e| match e {
+				Error::InvalidStatementTarget {
+					value: v,
+				} => Error::UpsertStatement {
+					value: v,
+				},
+				e => e,
+			})?;
+		}
+		// Output the results
+		match i.output(stk, ctx, opt, txn, &stm).await? {
+			// This is a single record result
+			Value::Array(mut a) if self.only => match a.len() {
+				// There was exactly one result
+				1 => Ok(a.remove(0)),
+				// There were no results
+				_ => Err(Error::SingleOnlyOutput),
+			},
+			// This is standard query result
+			v => Ok(v),
+		}
+	}
+}
+
+impl fmt::Display for UpsertStatement {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "UPSERT")?;
+		if self.only {
+			f.write_str(" ONLY")?
+		}
+		write!(f, " INTO {}", self.what)?;
+		if let Some(ref v) = self.data {
+			write!(f, " {v}")?
+		}
+		if let Some(ref v) = self.update {
+			write!(f, " {v}")?
+		}
+		if let Some(ref v) = self.output {
+			write!(f, " {v}")?
+		}
+		if let Some(ref v) = self.timeout {
+			write!(f, " {v}")?
+		}
+		if self.parallel {
+			f.write_str(" PARALLEL")?
+		}
+		Ok(())
+	}
+}
@@ -0,0 +1,83 @@
+use crate::ctx::Context;
+use crate::dbs::{Options, Transaction};
+use crate::doc::CursorDoc;
+use crate::err::Error;
+use crate::sql::fmt::Fmt;
+use crate::sql::statements::SelectStatement;
+use crate::sql::{Ident, Value};
+use derive::Store;
+use reblessive::tree::Stk;
+use revision::revisioned;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Store, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
+pub struct WithStatement {
+	pub recursive: bool,
+	pub ctes: Vec<Cte>,
+	pub stmt: SelectStatement,
+}
+
+impl WithStatement {
+	/// Check if we require a writeable transaction
+	pub(crate) fn writeable(&self) -> bool {
+		self.ctes.iter().any(|v| v.expr.writeable()) || self.stmt.writeable()
+	}
+
+	/// Process this type returning a computed simple Value
+	pub(crate) async fn compute(
+		&self,
+		stk: &mut Stk,
+		ctx: &Context<'_>,
+		opt: &Options,
+		txn: &Transaction,
+		doc: Option<&CursorDoc<'_>>,
+	) -> Result<Value, Error> {
+		// Valid options?
+		opt.valid_for_db()?;
+		// Recursive expressions are not yet supported
+		if self.recursive {
+			return Err(Error::Unimplemented(
+				"Recursive common table expressions are not yet supported".to_string(),
+			));
+		}
+		// Create a new context
+		let mut ctx = Context::new(ctx);
+		// Compute each expression once, in order, so that
+		// later expressions can reference earlier ones
+		for v in self.ctes.iter() {
+			let res = v.expr.compute(stk, &ctx, opt, txn, doc).await?;
+			ctx.add_cte(v.name.to_raw(), res);
+		}
+		// Process the main query
+		self.stmt.compute(stk, &ctx, opt, txn, doc).await
+	}
+}
+
+impl fmt::Display for WithStatement {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.write_str("WITH")?;
+		if self.recursive {
+			f.write_str(" RECURSIVE")?
+		}
+		write!(f, " {} {}", Fmt::comma_separated(&self.ctes), self.stmt)
+	}
+}
+
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
+pub struct Cte {
+	pub name: Ident,
+	pub expr: SelectStatement,
+}
+
+impl fmt::Display for Cte {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "{} AS ({})", self.name, self.expr)
+	}
+}
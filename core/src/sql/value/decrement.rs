@@ -20,7 +20,7 @@ impl Value {
 		match self.get(stk, ctx, opt, txn, None, path).await? {
 			Value::Number(v) => match val {
 				Value::Number(x) => self.set(stk, ctx, opt, txn, path, Value::from(v - x)).await,
-				_ => Ok(()),
+				x => Err(Error::TrySub(v.to_string(), x.to_string())),
 			},
 			Value::Array(v) => match val {
 				Value::Array(x) => self.set(stk, ctx, opt, txn, path, Value::from(v - x)).await,
@@ -32,7 +32,7 @@ impl Value {
 				}
 				_ => Ok(()),
 			},
-			_ => Ok(()),
+			v => Err(Error::TrySub(v.to_string(), val.to_string())),
 		}
 	}
 }
@@ -119,4 +119,27 @@ mod tests {
 			.unwrap();
 		assert_eq!(res, val);
 	}
+
+	#[tokio::test]
+	async fn decrement_mismatch() {
+		let (ctx, opt, txn) = mock().await;
+		let idi = Idiom::parse("test");
+		let mut val = Value::parse("{ test: 100, name: 'Tobie' }");
+		let res = val.clone();
+		let mut stack = reblessive::TreeStack::new();
+		let err = stack
+			.enter(|stk| val.decrement(stk, &ctx, &opt, &txn, &idi, Value::from("text")))
+			.finish()
+			.await
+			.unwrap_err();
+		assert!(matches!(err, Error::TrySub(..)));
+		let idi = Idiom::parse("name");
+		let err = stack
+			.enter(|stk| val.decrement(stk, &ctx, &opt, &txn, &idi, Value::from(10)))
+			.finish()
+			.await
+			.unwrap_err();
+		assert!(matches!(err, Error::TrySub(..)));
+		assert_eq!(res, val);
+	}
 }
@@ -31,28 +31,33 @@ impl Value {
 				}
 			}
 			(Value::Array(a), Value::Array(b)) if a != b => {
-				let mut n = 0;
-				while n < min(a.len(), b.len()) {
-					let path = path.clone().push(n.into());
-					ops.append(&mut a[n].diff(&b[n], path));
-					n += 1;
+				// Skip the unchanged elements at the start and end
+				let pre = a.iter().zip(b.iter()).take_while(|(a, b)| a == b).count();
+				let suf = a[pre..]
+					.iter()
+					.rev()
+					.zip(b[pre..].iter().rev())
+					.take_while(|(a, b)| a == b)
+					.count();
+				let (a, b) = (&a[pre..a.len() - suf], &b[pre..b.len() - suf]);
+				// Diff the changed elements in both arrays
+				let n = min(a.len(), b.len());
+				for i in 0..n {
+					let path = path.clone().push((pre + i).into());
+					ops.append(&mut a[i].diff(&b[i], path));
 				}
-				while n < b.len() {
-					if n >= a.len() {
-						ops.push(Operation::Add {
-							path: path.clone().push(n.into()),
-							value: b[n].clone(),
-						})
-					}
-					n += 1;
+				// Insert any added elements
+				for (i, v) in b.iter().enumerate().skip(n) {
+					ops.push(Operation::Add {
+						path: path.clone().push((pre + i).into()),
+						value: v.clone(),
+					})
 				}
-				while n < a.len() {
-					if n >= b.len() {
-						ops.push(Operation::Remove {
-							path: path.clone().push(n.into()),
-						})
-					}
-					n += 1;
+				// Remove any old elements, starting at the end
+				for i in (n..a.len()).rev() {
+					ops.push(Operation::Remove {
+						path: path.clone().push((pre + i).into()),
+					})
 				}
 			}
 			(Value::Strand(a), Value::Strand(b)) if a != b => ops.push(Operation::Change {
@@ -112,6 +117,36 @@ mod tests {
 		assert_eq!(res.to_operations().unwrap(), old.diff(&now, Idiom::default()));
 	}
 
+	#[test]
+	fn diff_insert_array() {
+		let old = Value::parse("{ test: [1,2,3] }");
+		let now = Value::parse("{ test: [0,1,2,3] }");
+		let res = Value::parse("[{ op: 'add', path: '/test/0', value: 0 }]");
+		assert_eq!(res.to_operations().unwrap(), old.diff(&now, Idiom::default()));
+	}
+
+	#[test]
+	fn diff_remove_array() {
+		let old = Value::parse("{ test: [1,2,3,4,5] }");
+		let now = Value::parse("{ test: [1,5] }");
+		let res = Value::parse(
+			"[
+				{ op: 'remove', path: '/test/3' },
+				{ op: 'remove', path: '/test/2' },
+				{ op: 'remove', path: '/test/1' },
+			]",
+		);
+		assert_eq!(res.to_operations().unwrap(), old.diff(&now, Idiom::default()));
+	}
+
+	#[test]
+	fn diff_replace_array() {
+		let old = Value::parse("{ test: [1,2,{ id: 3, tags: ['a'] },4] }");
+		let now = Value::parse("{ test: [1,2,{ id: 3, tags: ['a','b'] },4] }");
+		let res = Value::parse("[{ op: 'add', path: '/test/2/tags/1', value: 'b' }]");
+		assert_eq!(res.to_operations().unwrap(), old.diff(&now, Idiom::default()));
+	}
+
 	#[test]
 	fn diff_replace_embedded() {
 		let old = Value::parse("{ test: { other: 'test' } }");
@@ -17,7 +17,12 @@ impl Value {
 						Some(v) => v._each(path.next(), prev.push(p.clone())),
 						None => vec![],
 					},
-					Part::All => self._each(path.next(), prev.push(p.clone())),
+					Part::All => v
+						.iter()
+						.flat_map(|(f, v)| {
+							v._each(path.next(), prev.clone().push(Part::from(f.to_owned())))
+						})
+						.collect::<Vec<_>>(),
 					_ => vec![],
 				},
 				// Current path part is an array
@@ -138,4 +143,13 @@ mod tests {
 		assert_eq!(val.pick(&res[2]), Value::from("design"));
 		assert_eq!(val.pick(&res[3]), Value::from("operations"));
 	}
+
+	#[test]
+	fn each_object_field_all() {
+		let idi = Idiom::parse("test.*");
+		let val = Value::parse("{ test: { other: null, something: 123 } }");
+		let res = vec![Idiom::parse("test.other"), Idiom::parse("test.something")];
+		assert_eq!(res, val.each(&idi));
+		assert_eq!(val.pick(&res[1]), Value::from(123));
+	}
 }
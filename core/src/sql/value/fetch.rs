@@ -2,45 +2,109 @@ use crate::ctx::Context;
 use crate::dbs::{Options, Transaction};
 use crate::err::Error;
 use crate::sql::edges::Edges;
+use crate::sql::fetch::Fetch;
 use crate::sql::field::{Field, Fields};
 use crate::sql::part::Next;
 use crate::sql::part::Part;
 use crate::sql::statements::select::SelectStatement;
+use crate::sql::thing::Thing;
 use crate::sql::value::{Value, Values};
 use futures::future::try_join_all;
 use reblessive::tree::Stk;
 
+/// The progress of a FETCH clause through nested records
+#[derive(Clone, Copy)]
+struct Walk<'a> {
+	/// The path which is fetched within each record
+	path: &'a [Part],
+	/// The number of record links which can still be expanded
+	depth: u64,
+	/// The records which have been fetched on the way to this value
+	visited: &'a [Thing],
+}
+
 impl Value {
-	/// Was marked recursive
+	/// Fetch the record links at the path of a FETCH clause
 	pub(crate) async fn fetch(
+		&mut self,
+		stk: &mut Stk,
+		ctx: &Context<'_>,
+		opt: &Options,
+		txn: &Transaction,
+		fetch: &Fetch,
+	) -> Result<(), Error> {
+		// The record itself is never fetched within itself
+		let visited: Vec<Thing> = match self {
+			Value::Object(v) => v.rid().into_iter().collect(),
+			_ => vec![],
+		};
+		let walk = Walk {
+			path: fetch,
+			depth: fetch.depth(),
+			visited: &visited,
+		};
+		self.fetch_path(stk, ctx, opt, txn, fetch, walk).await
+	}
+
+	/// Was marked recursive
+	async fn fetch_path(
 		&mut self,
 		stk: &mut Stk,
 		ctx: &Context<'_>,
 		opt: &Options,
 		txn: &Transaction,
 		path: &[Part],
+		walk: Walk<'_>,
 	) -> Result<(), Error> {
 		match path.first() {
 			// Get the current path part
 			Some(p) => match self {
 				// Current path part is an object
 				Value::Object(v) => match p {
-					Part::Graph(_) => match v.rid() {
+					Part::Graph(g) => match v.get_mut(g.to_raw().as_str()) {
+						// The traversal was selected, so fetch within it
 						Some(v) => {
-							let mut v = Value::Thing(v);
-							stk.run(|stk| v.fetch(stk, ctx, opt, txn, path.next())).await
+							stk.run(|stk| v.fetch_path(stk, ctx, opt, txn, path.next(), walk)).await
 						}
-						None => Ok(()),
+						// Otherwise walk the edges from this record
+						None => match v.rid() {
+							Some(rid) => {
+								// Split the graph traversal from the remaining path
+								let len =
+									path.iter().take_while(|p| matches!(p, Part::Graph(_))).count();
+								let (graph, rest) = path.split_at(len);
+								// Records without matching edges fetch an empty array
+								let rid = Value::Thing(rid);
+								let mut res = match stk
+									.run(|stk| rid.get(stk, ctx, opt, txn, None, graph))
+									.await?
+								{
+									Value::Array(v) => Value::Array(v),
+									_ => Value::Array(Default::default()),
+								};
+								stk.run(|stk| res.fetch_path(stk, ctx, opt, txn, rest, walk))
+									.await?;
+								// Attach the fetched records inline at the traversal
+								stk.run(|stk| self.set(stk, ctx, opt, txn, graph, res)).await
+							}
+							None => Ok(()),
+						},
 					},
 					Part::Field(f) => match v.get_mut(f as &str) {
-						Some(v) => stk.run(|stk| v.fetch(stk, ctx, opt, txn, path.next())).await,
+						Some(v) => {
+							stk.run(|stk| v.fetch_path(stk, ctx, opt, txn, path.next(), walk)).await
+						}
 						None => Ok(()),
 					},
 					Part::Index(i) => match v.get_mut(&i.to_string()) {
-						Some(v) => stk.run(|stk| v.fetch(stk, ctx, opt, txn, path.next())).await,
+						Some(v) => {
+							stk.run(|stk| v.fetch_path(stk, ctx, opt, txn, path.next(), walk)).await
+						}
 						None => Ok(()),
 					},
-					Part::All => stk.run(|stk| self.fetch(stk, ctx, opt, txn, path.next())).await,
+					Part::All => {
+						stk.run(|stk| self.fetch_path(stk, ctx, opt, txn, path.next(), walk)).await
+					}
 					_ => Ok(()),
 				},
 				// Current path part is an array
@@ -48,24 +112,30 @@ impl Value {
 					Part::All => {
 						let path = path.next();
 						stk.scope(|scope| {
-							let futs = v
-								.iter_mut()
-								.map(|v| scope.run(|stk| v.fetch(stk, ctx, opt, txn, path)));
+							let futs = v.iter_mut().map(|v| {
+								scope.run(|stk| v.fetch_path(stk, ctx, opt, txn, path, walk))
+							});
 							try_join_all(futs)
 						})
 						.await?;
 						Ok(())
 					}
 					Part::First => match v.first_mut() {
-						Some(v) => stk.run(|stk| v.fetch(stk, ctx, opt, txn, path.next())).await,
+						Some(v) => {
+							stk.run(|stk| v.fetch_path(stk, ctx, opt, txn, path.next(), walk)).await
+						}
 						None => Ok(()),
 					},
 					Part::Last => match v.last_mut() {
-						Some(v) => stk.run(|stk| v.fetch(stk, ctx, opt, txn, path.next())).await,
+						Some(v) => {
+							stk.run(|stk| v.fetch_path(stk, ctx, opt, txn, path.next(), walk)).await
+						}
 						None => Ok(()),
 					},
 					Part::Index(i) => match v.get_mut(i.to_usize()) {
-						Some(v) => stk.run(|stk| v.fetch(stk, ctx, opt, txn, path.next())).await,
+						Some(v) => {
+							stk.run(|stk| v.fetch_path(stk, ctx, opt, txn, path.next(), walk)).await
+						}
 						None => Ok(()),
 					},
 					Part::Where(w) => {
@@ -73,16 +143,16 @@ impl Value {
 						for v in v.iter_mut() {
 							let cur = v.into();
 							if w.compute(stk, ctx, opt, txn, Some(&cur)).await?.is_truthy() {
-								stk.run(|stk| v.fetch(stk, ctx, opt, txn, path)).await?;
+								stk.run(|stk| v.fetch_path(stk, ctx, opt, txn, path, walk)).await?;
 							}
 						}
 						Ok(())
 					}
 					_ => {
 						stk.scope(|scope| {
-							let futs = v
-								.iter_mut()
-								.map(|v| scope.run(|stk| v.fetch(stk, ctx, opt, txn, path)));
+							let futs = v.iter_mut().map(|v| {
+								scope.run(|stk| v.fetch_path(stk, ctx, opt, txn, path, walk))
+							});
 							try_join_all(futs)
 						})
 						.await?;
@@ -118,15 +188,7 @@ impl Value {
 							Ok(())
 						}
 						// This is a remote field expression
-						_ => {
-							let stm = SelectStatement {
-								expr: Fields(vec![Field::All], false),
-								what: Values(vec![Value::from(val)]),
-								..SelectStatement::default()
-							};
-							*self = stm.compute(stk, ctx, opt, txn, None).await?.first();
-							Ok(())
-						}
+						_ => self.fetch_record(stk, ctx, opt, txn, val, walk).await,
 					}
 				}
 				// Ignore everything else
@@ -139,7 +201,7 @@ impl Value {
 					stk.scope(|scope| {
 						let futs = v
 							.iter_mut()
-							.map(|v| scope.run(|stk| v.fetch(stk, ctx, opt, txn, path)));
+							.map(|v| scope.run(|stk| v.fetch_path(stk, ctx, opt, txn, path, walk)));
 						try_join_all(futs)
 					})
 					.await?;
@@ -150,17 +212,47 @@ impl Value {
 					// Clone the thing
 					let val = v.clone();
 					// Fetch the remote embedded record
-					let stm = SelectStatement {
-						expr: Fields(vec![Field::All], false),
-						what: Values(vec![Value::from(val)]),
-						..SelectStatement::default()
-					};
-					*self = stm.compute(stk, ctx, opt, txn, None).await?.first();
-					Ok(())
+					self.fetch_record(stk, ctx, opt, txn, val, walk).await
 				}
 				// Ignore everything else
 				_ => Ok(()),
 			},
 		}
 	}
+
+	/// Fetch a remote record, and then the same path within that
+	/// record, until the depth of the FETCH clause has been reached
+	async fn fetch_record(
+		&mut self,
+		stk: &mut Stk,
+		ctx: &Context<'_>,
+		opt: &Options,
+		txn: &Transaction,
+		val: Thing,
+		walk: Walk<'_>,
+	) -> Result<(), Error> {
+		// Stop at the depth limit, and leave cyclic references as links
+		if walk.depth == 0 || walk.visited.contains(&val) {
+			return Ok(());
+		}
+		// Fetch the remote embedded record
+		let stm = SelectStatement {
+			expr: Fields(vec![Field::All], false),
+			what: Values(vec![Value::from(val.clone())]),
+			..SelectStatement::default()
+		};
+		*self = stm.compute(stk, ctx, opt, txn, None).await?.first();
+		// Fetch the same path within the fetched record
+		if walk.depth > 1 {
+			let mut visited = walk.visited.to_vec();
+			visited.push(val);
+			let walk = Walk {
+				depth: walk.depth - 1,
+				visited: &visited,
+				..walk
+			};
+			stk.run(|stk| self.fetch_path(stk, ctx, opt, txn, walk.path, walk)).await?;
+		}
+		Ok(())
+	}
 }
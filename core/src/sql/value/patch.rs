@@ -1,5 +1,6 @@
 use crate::err::Error;
 use crate::sql::operation::Operation;
+use crate::sql::part::Part;
 use crate::sql::value::Value;
 
 impl Value {
@@ -13,10 +14,26 @@ impl Value {
 				Operation::Add {
 					path,
 					value,
-				} => match tmp_val.pick(&path) {
-					Value::Array(_) => tmp_val.inc(&path, value),
-					_ => tmp_val.put(&path, value),
-				},
+				} => {
+					// Adding at an array index inserts the value at that index
+					if let Some((Part::Index(i), parent)) = path.split_last() {
+						if let Value::Array(mut v) = tmp_val.pick(parent) {
+							// The index can not be past the end of the array
+							if i.to_usize() > v.len() {
+								return Err(Error::InvalidPatch {
+									message: format!("The array index `{path}` is out of bounds"),
+								});
+							}
+							v.insert(i.to_usize(), value);
+							tmp_val.put(parent, v.into());
+							continue;
+						}
+					}
+					match tmp_val.pick(&path) {
+						Value::Array(_) => tmp_val.inc(&path, value),
+						_ => tmp_val.put(&path, value),
+					}
+				}
 				Operation::Remove {
 					path,
 				} => tmp_val.cut(&path),
@@ -51,6 +68,12 @@ impl Value {
 					from,
 				} => {
 					let found_val = tmp_val.pick(&from);
+					// The value being copied must exist
+					if found_val.is_none() {
+						return Err(Error::InvalidPatch {
+							message: format!("The `from` path `{from}` does not exist"),
+						});
+					}
 					tmp_val.put(&path, found_val);
 				}
 				Operation::Move {
@@ -58,8 +81,24 @@ impl Value {
 					from,
 				} => {
 					let found_val = tmp_val.pick(&from);
-					tmp_val.put(&path, found_val);
+					// The value being moved must exist
+					if found_val.is_none() {
+						return Err(Error::InvalidPatch {
+							message: format!("The `from` path `{from}` does not exist"),
+						});
+					}
+					// Moving a value to where it already is leaves it unchanged
+					if path == from {
+						continue;
+					}
+					// A value can not be moved into one of its own children
+					if path.starts_with(&from) {
+						return Err(Error::InvalidPatch {
+							message: format!("The path `{from}` can not be moved into `{path}`"),
+						});
+					}
 					tmp_val.cut(&from);
+					tmp_val.put(&path, found_val);
 				}
 				Operation::Test {
 					path,
@@ -153,6 +192,21 @@ mod tests {
 		assert_eq!(res, val);
 	}
 
+	#[tokio::test]
+	async fn patch_add_array_index() {
+		let mut val = Value::parse("{ test: [1, 3], other: [[1]] }");
+		let ops = Value::parse(
+			"[
+				{ op: 'add', path: '/test/1', value: 2 },
+				{ op: 'add', path: '/test/3', value: 4 },
+				{ op: 'add', path: '/other/0', value: [0] },
+			]",
+		);
+		let res = Value::parse("{ test: [1, 2, 3, 4], other: [[0], [1]] }");
+		val.patch(ops).unwrap();
+		assert_eq!(res, val);
+	}
+
 	#[tokio::test]
 	async fn patch_add_embedded() {
 		let mut val = Value::parse("{ test: { other: null, something: 123 } }");
@@ -218,6 +272,57 @@ mod tests {
 		assert_eq!(res, val);
 	}
 
+	#[tokio::test]
+	async fn patch_copy_nested() {
+		let mut val = Value::parse("{ test: { other: 'test' } }");
+		let ops = Value::parse("[{ op: 'copy', path: '/test/copy', from: '/test' }]");
+		let res = Value::parse("{ test: { copy: { other: 'test' }, other: 'test' } }");
+		val.patch(ops).unwrap();
+		assert_eq!(res, val);
+	}
+
+	#[tokio::test]
+	async fn patch_move_same_path() {
+		let mut val = Value::parse("{ test: { other: 'test' } }");
+		let ops = Value::parse("[{ op: 'move', path: '/test', from: '/test' }]");
+		let res = Value::parse("{ test: { other: 'test' } }");
+		val.patch(ops).unwrap();
+		assert_eq!(res, val);
+	}
+
+	#[tokio::test]
+	async fn patch_copy_invalid() {
+		let mut val = Value::parse("{ test: { other: 'test' }, temp: true }");
+		let should = val.clone();
+		let ops = Value::parse(
+			"[{ op: 'remove', path: '/temp' }, { op: 'copy', path: '/temp', from: '/missing' }]",
+		);
+		assert!(val.patch(ops).is_err());
+		assert_eq!(val, should);
+	}
+
+	#[tokio::test]
+	async fn patch_add_array_index_invalid() {
+		let mut val = Value::parse("{ test: [1, 2] }");
+		let should = val.clone();
+		let ops = Value::parse("[{ op: 'add', path: '/test/3', value: 4 }]");
+		assert!(val.patch(ops).is_err());
+		assert_eq!(val, should);
+	}
+
+	#[tokio::test]
+	async fn patch_move_invalid() {
+		let mut val = Value::parse("{ test: { other: 'test' }, temp: true }");
+		let should = val.clone();
+		let ops = Value::parse("[{ op: 'move', path: '/temp', from: '/missing' }]");
+		assert!(val.patch(ops).is_err());
+		assert_eq!(val, should);
+		// A value can not be moved into one of its own children
+		let ops = Value::parse("[{ op: 'move', path: '/test/other/inner', from: '/test' }]");
+		assert!(val.patch(ops).is_err());
+		assert_eq!(val, should);
+	}
+
 	#[tokio::test]
 	async fn patch_change_invalid() {
 		// See https://github.com/surrealdb/surrealdb/issues/2001
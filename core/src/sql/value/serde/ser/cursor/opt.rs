@@ -0,0 +1,56 @@
+use crate::err::Error;
+use crate::sql::value::serde::ser;
+use crate::sql::Cursor;
+use serde::ser::Impossible;
+use serde::ser::Serialize;
+
+#[non_exhaustive]
+pub struct Serializer;
+
+impl ser::Serializer for Serializer {
+	type Ok = Option<Cursor>;
+	type Error = Error;
+
+	type SerializeSeq = Impossible<Option<Cursor>, Error>;
+	type SerializeTuple = Impossible<Option<Cursor>, Error>;
+	type SerializeTupleStruct = Impossible<Option<Cursor>, Error>;
+	type SerializeTupleVariant = Impossible<Option<Cursor>, Error>;
+	type SerializeMap = Impossible<Option<Cursor>, Error>;
+	type SerializeStruct = Impossible<Option<Cursor>, Error>;
+	type SerializeStructVariant = Impossible<Option<Cursor>, Error>;
+
+	const EXPECTED: &'static str = "an `Option<Cursor>`";
+
+	#[inline]
+	fn serialize_none(self) -> Result<Self::Ok, Self::Error> {
+		Ok(None)
+	}
+
+	#[inline]
+	fn serialize_some<T>(self, value: &T) -> Result<Self::Ok, Self::Error>
+	where
+		T: ?Sized + Serialize,
+	{
+		Ok(Some(Cursor(value.serialize(ser::value::Serializer.wrap())?)))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use ser::Serializer as _;
+
+	#[test]
+	fn none() {
+		let option: Option<Cursor> = None;
+		let serialized = option.serialize(Serializer.wrap()).unwrap();
+		assert_eq!(option, serialized);
+	}
+
+	#[test]
+	fn some() {
+		let option = Some(Cursor::default());
+		let serialized = option.serialize(Serializer.wrap()).unwrap();
+		assert_eq!(option, serialized);
+	}
+}
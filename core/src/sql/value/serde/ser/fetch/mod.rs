@@ -1 +1,92 @@
 pub(super) mod vec;
+
+use crate::err::Error;
+use crate::sql::value::serde::ser;
+use crate::sql::Fetch;
+use crate::sql::Idiom;
+use ser::Serializer as _;
+use serde::ser::Error as _;
+use serde::ser::Impossible;
+use serde::ser::Serialize;
+
+pub(super) struct Serializer;
+
+impl ser::Serializer for Serializer {
+	type Ok = Fetch;
+	type Error = Error;
+
+	type SerializeSeq = Impossible<Fetch, Error>;
+	type SerializeTuple = Impossible<Fetch, Error>;
+	type SerializeTupleStruct = SerializeFetch;
+	type SerializeTupleVariant = Impossible<Fetch, Error>;
+	type SerializeMap = Impossible<Fetch, Error>;
+	type SerializeStruct = Impossible<Fetch, Error>;
+	type SerializeStructVariant = Impossible<Fetch, Error>;
+
+	const EXPECTED: &'static str = "a struct `Fetch`";
+
+	fn serialize_tuple_struct(
+		self,
+		_name: &'static str,
+		_len: usize,
+	) -> Result<Self::SerializeTupleStruct, Error> {
+		Ok(SerializeFetch::default())
+	}
+}
+
+#[derive(Default)]
+pub(super) struct SerializeFetch {
+	index: usize,
+	idiom: Option<Idiom>,
+	depth: Option<u64>,
+}
+
+impl serde::ser::SerializeTupleStruct for SerializeFetch {
+	type Ok = Fetch;
+	type Error = Error;
+
+	fn serialize_field<T>(&mut self, value: &T) -> Result<(), Self::Error>
+	where
+		T: Serialize + ?Sized,
+	{
+		match self.index {
+			0 => {
+				self.idiom = Some(Idiom(value.serialize(ser::part::vec::Serializer.wrap())?));
+			}
+			1 => {
+				self.depth = value.serialize(ser::primitive::u64::opt::Serializer.wrap())?;
+			}
+			index => {
+				return Err(Error::custom(format!("unexpected `Fetch` index `{index}`")));
+			}
+		}
+		self.index += 1;
+		Ok(())
+	}
+
+	fn end(self) -> Result<Self::Ok, Self::Error> {
+		match self.idiom {
+			Some(idiom) => Ok(Fetch(idiom, self.depth)),
+			_ => Err(Error::custom("`Fetch` missing required value(s)")),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn default() {
+		let fetch = Fetch::default();
+		let serialized = fetch.serialize(Serializer.wrap()).unwrap();
+		assert_eq!(fetch, serialized);
+	}
+
+	#[test]
+	fn depth() {
+		let fetch = Fetch(Default::default(), Some(2));
+		let serialized = fetch.serialize(Serializer.wrap()).unwrap();
+		assert_eq!(fetch, serialized);
+	}
+}
@@ -0,0 +1,56 @@
+use crate::err::Error;
+use crate::sql::value::serde::ser;
+use crate::sql::Having;
+use serde::ser::Impossible;
+use serde::ser::Serialize;
+
+#[non_exhaustive]
+pub struct Serializer;
+
+impl ser::Serializer for Serializer {
+	type Ok = Option<Having>;
+	type Error = Error;
+
+	type SerializeSeq = Impossible<Option<Having>, Error>;
+	type SerializeTuple = Impossible<Option<Having>, Error>;
+	type SerializeTupleStruct = Impossible<Option<Having>, Error>;
+	type SerializeTupleVariant = Impossible<Option<Having>, Error>;
+	type SerializeMap = Impossible<Option<Having>, Error>;
+	type SerializeStruct = Impossible<Option<Having>, Error>;
+	type SerializeStructVariant = Impossible<Option<Having>, Error>;
+
+	const EXPECTED: &'static str = "an `Option<Having>`";
+
+	#[inline]
+	fn serialize_none(self) -> Result<Self::Ok, Self::Error> {
+		Ok(None)
+	}
+
+	#[inline]
+	fn serialize_some<T>(self, value: &T) -> Result<Self::Ok, Self::Error>
+	where
+		T: ?Sized + Serialize,
+	{
+		Ok(Some(Having(value.serialize(ser::value::Serializer.wrap())?)))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use ser::Serializer as _;
+
+	#[test]
+	fn none() {
+		let option: Option<Having> = None;
+		let serialized = option.serialize(Serializer.wrap()).unwrap();
+		assert_eq!(option, serialized);
+	}
+
+	#[test]
+	fn some() {
+		let option = Some(Having::default());
+		let serialized = option.serialize(Serializer.wrap()).unwrap();
+		assert_eq!(option, serialized);
+	}
+}
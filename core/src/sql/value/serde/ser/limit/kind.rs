@@ -0,0 +1,57 @@
+use crate::err::Error;
+use crate::sql::value::serde::ser;
+use crate::sql::LimitKind;
+use serde::ser::Error as _;
+use serde::ser::Impossible;
+
+pub(super) struct Serializer;
+
+impl ser::Serializer for Serializer {
+	type Ok = LimitKind;
+	type Error = Error;
+
+	type SerializeSeq = Impossible<LimitKind, Error>;
+	type SerializeTuple = Impossible<LimitKind, Error>;
+	type SerializeTupleStruct = Impossible<LimitKind, Error>;
+	type SerializeTupleVariant = Impossible<LimitKind, Error>;
+	type SerializeMap = Impossible<LimitKind, Error>;
+	type SerializeStruct = Impossible<LimitKind, Error>;
+	type SerializeStructVariant = Impossible<LimitKind, Error>;
+
+	const EXPECTED: &'static str = "an enum `LimitKind`";
+
+	#[inline]
+	fn serialize_unit_variant(
+		self,
+		name: &'static str,
+		_variant_index: u32,
+		variant: &'static str,
+	) -> Result<Self::Ok, Error> {
+		match variant {
+			"Rows" => Ok(LimitKind::Rows),
+			"Percent" => Ok(LimitKind::Percent),
+			variant => Err(Error::custom(format!("unexpected unit variant `{name}::{variant}`"))),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use ser::Serializer as _;
+	use serde::Serialize;
+
+	#[test]
+	fn rows() {
+		let kind = LimitKind::Rows;
+		let serialized = kind.serialize(Serializer.wrap()).unwrap();
+		assert_eq!(kind, serialized);
+	}
+
+	#[test]
+	fn percent() {
+		let kind = LimitKind::Percent;
+		let serialized = kind.serialize(Serializer.wrap()).unwrap();
+		assert_eq!(kind, serialized);
+	}
+}
@@ -1 +1,94 @@
+mod kind;
 pub(super) mod opt;
+
+use crate::err::Error;
+use crate::sql::value::serde::ser;
+use crate::sql::Limit;
+use crate::sql::LimitKind;
+use crate::sql::Value;
+use ser::Serializer as _;
+use serde::ser::Error as _;
+use serde::ser::Impossible;
+use serde::ser::Serialize;
+
+pub(super) struct Serializer;
+
+impl ser::Serializer for Serializer {
+	type Ok = Limit;
+	type Error = Error;
+
+	type SerializeSeq = Impossible<Limit, Error>;
+	type SerializeTuple = Impossible<Limit, Error>;
+	type SerializeTupleStruct = SerializeLimit;
+	type SerializeTupleVariant = Impossible<Limit, Error>;
+	type SerializeMap = Impossible<Limit, Error>;
+	type SerializeStruct = Impossible<Limit, Error>;
+	type SerializeStructVariant = Impossible<Limit, Error>;
+
+	const EXPECTED: &'static str = "a struct `Limit`";
+
+	fn serialize_tuple_struct(
+		self,
+		_name: &'static str,
+		_len: usize,
+	) -> Result<Self::SerializeTupleStruct, Error> {
+		Ok(SerializeLimit::default())
+	}
+}
+
+#[derive(Default)]
+pub(super) struct SerializeLimit {
+	index: usize,
+	value: Option<Value>,
+	kind: Option<LimitKind>,
+}
+
+impl serde::ser::SerializeTupleStruct for SerializeLimit {
+	type Ok = Limit;
+	type Error = Error;
+
+	fn serialize_field<T>(&mut self, value: &T) -> Result<(), Self::Error>
+	where
+		T: Serialize + ?Sized,
+	{
+		match self.index {
+			0 => {
+				self.value = Some(value.serialize(ser::value::Serializer.wrap())?);
+			}
+			1 => {
+				self.kind = Some(value.serialize(kind::Serializer.wrap())?);
+			}
+			index => {
+				return Err(Error::custom(format!("unexpected `Limit` index `{index}`")));
+			}
+		}
+		self.index += 1;
+		Ok(())
+	}
+
+	fn end(self) -> Result<Self::Ok, Self::Error> {
+		match (self.value, self.kind) {
+			(Some(value), Some(kind)) => Ok(Limit(value, kind)),
+			_ => Err(Error::custom("`Limit` missing required value(s)")),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn default() {
+		let limit = Limit::default();
+		let serialized = limit.serialize(Serializer.wrap()).unwrap();
+		assert_eq!(limit, serialized);
+	}
+
+	#[test]
+	fn percentage() {
+		let limit = Limit(Value::from(10), LimitKind::Percent);
+		let serialized = limit.serialize(Serializer.wrap()).unwrap();
+		assert_eq!(limit, serialized);
+	}
+}
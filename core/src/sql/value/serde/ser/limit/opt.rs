@@ -31,7 +31,7 @@ impl ser::Serializer for Serializer {
 	where
 		T: ?Sized + Serialize,
 	{
-		Ok(Some(Limit(value.serialize(ser::value::Serializer.wrap())?)))
+		Ok(Some(value.serialize(super::Serializer.wrap())?))
 	}
 }
 
@@ -0,0 +1,59 @@
+pub(super) mod opt;
+
+use crate::err::Error;
+use crate::sql::value::serde::ser;
+use crate::sql::LockMode;
+use serde::ser::Error as _;
+use serde::ser::Impossible;
+
+pub(super) struct Serializer;
+
+impl ser::Serializer for Serializer {
+	type Ok = LockMode;
+	type Error = Error;
+
+	type SerializeSeq = Impossible<LockMode, Error>;
+	type SerializeTuple = Impossible<LockMode, Error>;
+	type SerializeTupleStruct = Impossible<LockMode, Error>;
+	type SerializeTupleVariant = Impossible<LockMode, Error>;
+	type SerializeMap = Impossible<LockMode, Error>;
+	type SerializeStruct = Impossible<LockMode, Error>;
+	type SerializeStructVariant = Impossible<LockMode, Error>;
+
+	const EXPECTED: &'static str = "an enum `LockMode`";
+
+	#[inline]
+	fn serialize_unit_variant(
+		self,
+		name: &'static str,
+		_variant_index: u32,
+		variant: &'static str,
+	) -> Result<Self::Ok, Error> {
+		match variant {
+			"Update" => Ok(LockMode::Update),
+			"Share" => Ok(LockMode::Share),
+			variant => Err(Error::custom(format!("unexpected unit variant `{name}::{variant}`"))),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use ser::Serializer as _;
+	use serde::Serialize;
+
+	#[test]
+	fn update() {
+		let lock = LockMode::Update;
+		let serialized = lock.serialize(Serializer.wrap()).unwrap();
+		assert_eq!(lock, serialized);
+	}
+
+	#[test]
+	fn share() {
+		let lock = LockMode::Share;
+		let serialized = lock.serialize(Serializer.wrap()).unwrap();
+		assert_eq!(lock, serialized);
+	}
+}
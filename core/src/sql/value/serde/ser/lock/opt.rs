@@ -0,0 +1,56 @@
+use crate::err::Error;
+use crate::sql::value::serde::ser;
+use crate::sql::LockMode;
+use serde::ser::Impossible;
+use serde::ser::Serialize;
+
+#[non_exhaustive]
+pub struct Serializer;
+
+impl ser::Serializer for Serializer {
+	type Ok = Option<LockMode>;
+	type Error = Error;
+
+	type SerializeSeq = Impossible<Option<LockMode>, Error>;
+	type SerializeTuple = Impossible<Option<LockMode>, Error>;
+	type SerializeTupleStruct = Impossible<Option<LockMode>, Error>;
+	type SerializeTupleVariant = Impossible<Option<LockMode>, Error>;
+	type SerializeMap = Impossible<Option<LockMode>, Error>;
+	type SerializeStruct = Impossible<Option<LockMode>, Error>;
+	type SerializeStructVariant = Impossible<Option<LockMode>, Error>;
+
+	const EXPECTED: &'static str = "an `Option<LockMode>`";
+
+	#[inline]
+	fn serialize_none(self) -> Result<Self::Ok, Self::Error> {
+		Ok(None)
+	}
+
+	#[inline]
+	fn serialize_some<T>(self, value: &T) -> Result<Self::Ok, Self::Error>
+	where
+		T: ?Sized + Serialize,
+	{
+		Ok(Some(value.serialize(super::Serializer.wrap())?))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use ser::Serializer as _;
+
+	#[test]
+	fn none() {
+		let option: Option<LockMode> = None;
+		let serialized = option.serialize(Serializer.wrap()).unwrap();
+		assert_eq!(option, serialized);
+	}
+
+	#[test]
+	fn some() {
+		let option = Some(LockMode::Update);
+		let serialized = option.serialize(Serializer.wrap()).unwrap();
+		assert_eq!(option, serialized);
+	}
+}
@@ -5,6 +5,7 @@ mod cast;
 mod changefeed;
 mod cond;
 mod constant;
+mod cursor;
 mod data;
 mod datetime;
 mod decimal;
@@ -23,6 +24,7 @@ mod function;
 mod geometry;
 mod graph;
 mod group;
+mod having;
 mod id;
 mod ident;
 mod idiom;
@@ -30,6 +32,7 @@ mod index;
 mod kind;
 mod language;
 mod limit;
+mod lock;
 mod mock;
 mod number;
 mod operator;
@@ -41,6 +44,7 @@ mod permissions;
 mod primitive;
 mod range;
 mod relation;
+mod sample;
 mod scoring;
 mod split;
 mod start;
@@ -1,9 +1,12 @@
+mod nulls;
 pub(super) mod vec;
 
 use crate::err::Error;
 use crate::sql::value::serde::ser;
 use crate::sql::Idiom;
+use crate::sql::NullsOrder;
 use crate::sql::Order;
+use crate::sql::Strand;
 use ser::Serializer as _;
 use serde::ser::Error as _;
 use serde::ser::Impossible;
@@ -42,6 +45,9 @@ pub(super) struct SerializeOrder {
 	collate: Option<bool>,
 	numeric: Option<bool>,
 	direction: Option<bool>,
+	nulls: NullsOrder,
+	locale: Option<Strand>,
+	seed: Option<u64>,
 }
 
 impl serde::ser::SerializeStruct for SerializeOrder {
@@ -68,6 +74,15 @@ impl serde::ser::SerializeStruct for SerializeOrder {
 			"direction" => {
 				self.direction = Some(value.serialize(ser::primitive::bool::Serializer.wrap())?);
 			}
+			"nulls" => {
+				self.nulls = value.serialize(nulls::Serializer.wrap())?;
+			}
+			"locale" => {
+				self.locale = value.serialize(ser::strand::opt::Serializer.wrap())?;
+			}
+			"seed" => {
+				self.seed = value.serialize(ser::primitive::u64::opt::Serializer.wrap())?;
+			}
 			key => {
 				return Err(Error::custom(format!("unexpected field `Order::{key}`")));
 			}
@@ -84,6 +99,9 @@ impl serde::ser::SerializeStruct for SerializeOrder {
 					collate,
 					numeric,
 					direction,
+					nulls: self.nulls,
+					locale: self.locale,
+					seed: self.seed,
 				})
 			}
 			_ => Err(Error::custom("`Order` missing required field(s)")),
@@ -102,4 +120,36 @@ mod tests {
 		let serialized = order.serialize(Serializer.wrap()).unwrap();
 		assert_eq!(order, serialized);
 	}
+
+	#[test]
+	fn nulls() {
+		let order = Order {
+			nulls: NullsOrder::Last,
+			..Default::default()
+		};
+		let serialized = order.serialize(Serializer.wrap()).unwrap();
+		assert_eq!(order, serialized);
+	}
+
+	#[test]
+	fn locale() {
+		let order = Order {
+			collate: true,
+			locale: Some("de-DE".into()),
+			..Default::default()
+		};
+		let serialized = order.serialize(Serializer.wrap()).unwrap();
+		assert_eq!(order, serialized);
+	}
+
+	#[test]
+	fn seed() {
+		let order = Order {
+			random: true,
+			seed: Some(42),
+			..Default::default()
+		};
+		let serialized = order.serialize(Serializer.wrap()).unwrap();
+		assert_eq!(order, serialized);
+	}
 }
@@ -0,0 +1,65 @@
+use crate::err::Error;
+use crate::sql::value::serde::ser;
+use crate::sql::NullsOrder;
+use serde::ser::Error as _;
+use serde::ser::Impossible;
+
+pub(super) struct Serializer;
+
+impl ser::Serializer for Serializer {
+	type Ok = NullsOrder;
+	type Error = Error;
+
+	type SerializeSeq = Impossible<NullsOrder, Error>;
+	type SerializeTuple = Impossible<NullsOrder, Error>;
+	type SerializeTupleStruct = Impossible<NullsOrder, Error>;
+	type SerializeTupleVariant = Impossible<NullsOrder, Error>;
+	type SerializeMap = Impossible<NullsOrder, Error>;
+	type SerializeStruct = Impossible<NullsOrder, Error>;
+	type SerializeStructVariant = Impossible<NullsOrder, Error>;
+
+	const EXPECTED: &'static str = "an enum `NullsOrder`";
+
+	#[inline]
+	fn serialize_unit_variant(
+		self,
+		name: &'static str,
+		_variant_index: u32,
+		variant: &'static str,
+	) -> Result<Self::Ok, Error> {
+		match variant {
+			"Default" => Ok(NullsOrder::Default),
+			"First" => Ok(NullsOrder::First),
+			"Last" => Ok(NullsOrder::Last),
+			variant => Err(Error::custom(format!("unexpected unit variant `{name}::{variant}`"))),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use ser::Serializer as _;
+	use serde::Serialize;
+
+	#[test]
+	fn default() {
+		let nulls = NullsOrder::Default;
+		let serialized = nulls.serialize(Serializer.wrap()).unwrap();
+		assert_eq!(nulls, serialized);
+	}
+
+	#[test]
+	fn first() {
+		let nulls = NullsOrder::First;
+		let serialized = nulls.serialize(Serializer.wrap()).unwrap();
+		assert_eq!(nulls, serialized);
+	}
+
+	#[test]
+	fn last() {
+		let nulls = NullsOrder::Last;
+		let serialized = nulls.serialize(Serializer.wrap()).unwrap();
+		assert_eq!(nulls, serialized);
+	}
+}
@@ -35,6 +35,7 @@ impl ser::Serializer for Serializer {
 			"Diff" => Ok(Output::Diff),
 			"After" => Ok(Output::After),
 			"Before" => Ok(Output::Before),
+			"Patch" => Ok(Output::Patch),
 			variant => Err(Error::custom(format!("unexpected unit variant `{name}::{variant}`"))),
 		}
 	}
@@ -100,6 +101,13 @@ mod tests {
 		assert_eq!(output, serialized);
 	}
 
+	#[test]
+	fn patch() {
+		let output = Output::Patch;
+		let serialized = output.serialize(Serializer.wrap()).unwrap();
+		assert_eq!(output, serialized);
+	}
+
 	#[test]
 	fn fields() {
 		let output = Output::Fields(Default::default());
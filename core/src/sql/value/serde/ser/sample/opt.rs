@@ -0,0 +1,75 @@
+use crate::err::Error;
+use crate::sql::value::serde::ser;
+use crate::sql::Sample;
+use serde::ser::Impossible;
+use serde::ser::Serialize;
+
+#[non_exhaustive]
+pub struct Serializer;
+
+impl ser::Serializer for Serializer {
+	type Ok = Option<Sample>;
+	type Error = Error;
+
+	type SerializeSeq = Impossible<Option<Sample>, Error>;
+	type SerializeTuple = Impossible<Option<Sample>, Error>;
+	type SerializeTupleStruct = Impossible<Option<Sample>, Error>;
+	type SerializeTupleVariant = Impossible<Option<Sample>, Error>;
+	type SerializeMap = Impossible<Option<Sample>, Error>;
+	type SerializeStruct = Impossible<Option<Sample>, Error>;
+	type SerializeStructVariant = Impossible<Option<Sample>, Error>;
+
+	const EXPECTED: &'static str = "an `Option<Sample>`";
+
+	#[inline]
+	fn serialize_none(self) -> Result<Self::Ok, Self::Error> {
+		Ok(None)
+	}
+
+	#[inline]
+	fn serialize_some<T>(self, value: &T) -> Result<Self::Ok, Self::Error>
+	where
+		T: ?Sized + Serialize,
+	{
+		value.serialize(self.wrap())
+	}
+
+	#[inline]
+	fn serialize_newtype_struct<T>(
+		self,
+		_name: &'static str,
+		value: &T,
+	) -> Result<Self::Ok, Self::Error>
+	where
+		T: ?Sized + Serialize,
+	{
+		Ok(Some(Sample(value.serialize(ser::primitive::u64::Serializer.wrap())?)))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use ser::Serializer as _;
+
+	#[test]
+	fn none() {
+		let option: Option<Sample> = None;
+		let serialized = option.serialize(Serializer.wrap()).unwrap();
+		assert_eq!(option, serialized);
+	}
+
+	#[test]
+	fn some_default() {
+		let option = Some(Sample::default());
+		let serialized = option.serialize(Serializer.wrap()).unwrap();
+		assert_eq!(option, serialized);
+	}
+
+	#[test]
+	fn some_records() {
+		let option = Some(Sample(10));
+		let serialized = option.serialize(Serializer.wrap()).unwrap();
+		assert_eq!(option, serialized);
+	}
+}
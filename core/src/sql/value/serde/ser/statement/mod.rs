@@ -16,14 +16,20 @@ pub mod option;
 pub mod output;
 pub mod rebuild;
 pub mod relate;
+pub mod release;
 pub mod remove;
+pub mod rollback;
+pub mod savepoint;
 pub mod select;
 pub mod set;
 pub mod show;
 pub mod sleep;
 pub mod throw;
+pub mod union;
 pub mod update;
+pub mod upsert;
 pub mod vec;
+pub mod with;
 pub mod yuse;
 
 use crate::err::Error;
@@ -79,14 +85,20 @@ impl ser::Serializer for Serializer {
 			"Output" => Ok(Statement::Output(value.serialize(output::Serializer.wrap())?)),
 			"Rebuild" => Ok(Statement::Rebuild(value.serialize(rebuild::Serializer.wrap())?)),
 			"Relate" => Ok(Statement::Relate(value.serialize(relate::Serializer.wrap())?)),
+			"Release" => Ok(Statement::Release(value.serialize(release::Serializer.wrap())?)),
 			"Remove" => Ok(Statement::Remove(value.serialize(remove::Serializer.wrap())?)),
+			"Rollback" => Ok(Statement::Rollback(value.serialize(rollback::Serializer.wrap())?)),
+			"Savepoint" => Ok(Statement::Savepoint(value.serialize(savepoint::Serializer.wrap())?)),
 			"Select" => Ok(Statement::Select(value.serialize(select::Serializer.wrap())?)),
 			"Set" => Ok(Statement::Set(value.serialize(set::Serializer.wrap())?)),
 			"Show" => Ok(Statement::Show(value.serialize(show::Serializer.wrap())?)),
 			"Sleep" => Ok(Statement::Sleep(value.serialize(sleep::Serializer.wrap())?)),
 			"Throw" => Ok(Statement::Throw(value.serialize(throw::Serializer.wrap())?)),
+			"Union" => Ok(Statement::Union(value.serialize(union::Serializer.wrap())?)),
 			"Update" => Ok(Statement::Update(value.serialize(update::Serializer.wrap())?)),
+			"Upsert" => Ok(Statement::Upsert(value.serialize(upsert::Serializer.wrap())?)),
 			"Use" => Ok(Statement::Use(value.serialize(yuse::Serializer.wrap())?)),
+			"With" => Ok(Statement::With(value.serialize(with::Serializer.wrap())?)),
 			variant => {
 				Err(Error::custom(format!("unexpected newtype variant `{name}::{variant}`")))
 			}
@@ -210,6 +222,13 @@ mod tests {
 		assert_eq!(statement, serialized);
 	}
 
+	#[test]
+	fn release() {
+		let statement = Statement::Release(Default::default());
+		let serialized = statement.serialize(Serializer.wrap()).unwrap();
+		assert_eq!(statement, serialized);
+	}
+
 	#[test]
 	fn remove() {
 		let statement = Statement::Remove(RemoveStatement::Table(Default::default()));
@@ -217,6 +236,20 @@ mod tests {
 		assert_eq!(statement, serialized);
 	}
 
+	#[test]
+	fn rollback() {
+		let statement = Statement::Rollback(Default::default());
+		let serialized = statement.serialize(Serializer.wrap()).unwrap();
+		assert_eq!(statement, serialized);
+	}
+
+	#[test]
+	fn savepoint() {
+		let statement = Statement::Savepoint(Default::default());
+		let serialized = statement.serialize(Serializer.wrap()).unwrap();
+		assert_eq!(statement, serialized);
+	}
+
 	#[test]
 	fn select() {
 		let statement = Statement::Select(Default::default());
@@ -245,6 +278,13 @@ mod tests {
 		assert_eq!(statement, serialized);
 	}
 
+	#[test]
+	fn union() {
+		let statement = Statement::Union(Default::default());
+		let serialized = statement.serialize(Serializer.wrap()).unwrap();
+		assert_eq!(statement, serialized);
+	}
+
 	#[test]
 	fn update() {
 		let statement = Statement::Update(Default::default());
@@ -258,4 +298,18 @@ mod tests {
 		let serialized = statement.serialize(Serializer.wrap()).unwrap();
 		assert_eq!(statement, serialized);
 	}
+
+	#[test]
+	fn with() {
+		let statement = Statement::With(Default::default());
+		let serialized = statement.serialize(Serializer.wrap()).unwrap();
+		assert_eq!(statement, serialized);
+	}
+
+	#[test]
+	fn upsert() {
+		let statement = Statement::Upsert(Default::default());
+		let serialized = statement.serialize(Serializer.wrap()).unwrap();
+		assert_eq!(statement, serialized);
+	}
 }
@@ -0,0 +1,79 @@
+use crate::err::Error;
+use crate::sql::statements::ReleaseStatement;
+use crate::sql::value::serde::ser;
+use crate::sql::Ident;
+use ser::Serializer as _;
+use serde::ser::Error as _;
+use serde::ser::Impossible;
+use serde::ser::Serialize;
+
+#[non_exhaustive]
+pub struct Serializer;
+
+impl ser::Serializer for Serializer {
+	type Ok = ReleaseStatement;
+	type Error = Error;
+
+	type SerializeSeq = Impossible<ReleaseStatement, Error>;
+	type SerializeTuple = Impossible<ReleaseStatement, Error>;
+	type SerializeTupleStruct = Impossible<ReleaseStatement, Error>;
+	type SerializeTupleVariant = Impossible<ReleaseStatement, Error>;
+	type SerializeMap = Impossible<ReleaseStatement, Error>;
+	type SerializeStruct = SerializeReleaseStatement;
+	type SerializeStructVariant = Impossible<ReleaseStatement, Error>;
+
+	const EXPECTED: &'static str = "a struct `ReleaseStatement`";
+
+	#[inline]
+	fn serialize_struct(
+		self,
+		_name: &'static str,
+		_len: usize,
+	) -> Result<Self::SerializeStruct, Error> {
+		Ok(SerializeReleaseStatement::default())
+	}
+}
+
+#[derive(Default)]
+#[non_exhaustive]
+pub struct SerializeReleaseStatement {
+	name: Ident,
+}
+
+impl serde::ser::SerializeStruct for SerializeReleaseStatement {
+	type Ok = ReleaseStatement;
+	type Error = Error;
+
+	fn serialize_field<T>(&mut self, key: &'static str, value: &T) -> Result<(), Error>
+	where
+		T: ?Sized + Serialize,
+	{
+		match key {
+			"name" => {
+				self.name = Ident(value.serialize(ser::string::Serializer.wrap())?);
+			}
+			key => {
+				return Err(Error::custom(format!("unexpected field `ReleaseStatement::{key}`")));
+			}
+		}
+		Ok(())
+	}
+
+	fn end(self) -> Result<Self::Ok, Error> {
+		Ok(ReleaseStatement {
+			name: self.name,
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn default() {
+		let stmt = ReleaseStatement::default();
+		let value: ReleaseStatement = stmt.serialize(Serializer.wrap()).unwrap();
+		assert_eq!(value, stmt);
+	}
+}
@@ -0,0 +1,79 @@
+use crate::err::Error;
+use crate::sql::statements::RollbackStatement;
+use crate::sql::value::serde::ser;
+use crate::sql::Ident;
+use ser::Serializer as _;
+use serde::ser::Error as _;
+use serde::ser::Impossible;
+use serde::ser::Serialize;
+
+#[non_exhaustive]
+pub struct Serializer;
+
+impl ser::Serializer for Serializer {
+	type Ok = RollbackStatement;
+	type Error = Error;
+
+	type SerializeSeq = Impossible<RollbackStatement, Error>;
+	type SerializeTuple = Impossible<RollbackStatement, Error>;
+	type SerializeTupleStruct = Impossible<RollbackStatement, Error>;
+	type SerializeTupleVariant = Impossible<RollbackStatement, Error>;
+	type SerializeMap = Impossible<RollbackStatement, Error>;
+	type SerializeStruct = SerializeRollbackStatement;
+	type SerializeStructVariant = Impossible<RollbackStatement, Error>;
+
+	const EXPECTED: &'static str = "a struct `RollbackStatement`";
+
+	#[inline]
+	fn serialize_struct(
+		self,
+		_name: &'static str,
+		_len: usize,
+	) -> Result<Self::SerializeStruct, Error> {
+		Ok(SerializeRollbackStatement::default())
+	}
+}
+
+#[derive(Default)]
+#[non_exhaustive]
+pub struct SerializeRollbackStatement {
+	name: Ident,
+}
+
+impl serde::ser::SerializeStruct for SerializeRollbackStatement {
+	type Ok = RollbackStatement;
+	type Error = Error;
+
+	fn serialize_field<T>(&mut self, key: &'static str, value: &T) -> Result<(), Error>
+	where
+		T: ?Sized + Serialize,
+	{
+		match key {
+			"name" => {
+				self.name = Ident(value.serialize(ser::string::Serializer.wrap())?);
+			}
+			key => {
+				return Err(Error::custom(format!("unexpected field `RollbackStatement::{key}`")));
+			}
+		}
+		Ok(())
+	}
+
+	fn end(self) -> Result<Self::Ok, Error> {
+		Ok(RollbackStatement {
+			name: self.name,
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn default() {
+		let stmt = RollbackStatement::default();
+		let value: RollbackStatement = stmt.serialize(Serializer.wrap()).unwrap();
+		assert_eq!(value, stmt);
+	}
+}
@@ -4,12 +4,17 @@ use crate::sql::statements::SelectStatement;
 use crate::sql::value::serde::ser;
 use crate::sql::with::With;
 use crate::sql::Cond;
+use crate::sql::Cursor;
 use crate::sql::Fetchs;
 use crate::sql::Fields;
 use crate::sql::Groups;
+use crate::sql::Having;
+use crate::sql::Ident;
 use crate::sql::Idioms;
 use crate::sql::Limit;
+use crate::sql::LockMode;
 use crate::sql::Orders;
+use crate::sql::Sample;
 use crate::sql::Splits;
 use crate::sql::Start;
 use crate::sql::Timeout;
@@ -50,21 +55,27 @@ impl ser::Serializer for Serializer {
 #[derive(Default)]
 #[non_exhaustive]
 pub struct SerializeSelectStatement {
+	distinct: Option<bool>,
 	expr: Option<Fields>,
 	omit: Option<Idioms>,
 	only: Option<bool>,
 	what: Option<Values>,
+	alias: Option<Ident>,
 	with: Option<With>,
 	cond: Option<Cond>,
 	split: Option<Splits>,
 	group: Option<Groups>,
+	having: Option<Having>,
 	order: Option<Orders>,
+	sample: Option<Sample>,
 	limit: Option<Limit>,
 	start: Option<Start>,
+	after: Option<Cursor>,
 	fetch: Option<Fetchs>,
 	version: Option<Version>,
 	timeout: Option<Timeout>,
 	parallel: Option<bool>,
+	lock: Option<LockMode>,
 	explain: Option<Explain>,
 }
 
@@ -77,6 +88,9 @@ impl serde::ser::SerializeStruct for SerializeSelectStatement {
 		T: ?Sized + Serialize,
 	{
 		match key {
+			"distinct" => {
+				self.distinct = Some(value.serialize(ser::primitive::bool::Serializer.wrap())?);
+			}
 			"expr" => {
 				self.expr = Some(value.serialize(ser::fields::Serializer.wrap())?);
 			}
@@ -89,6 +103,9 @@ impl serde::ser::SerializeStruct for SerializeSelectStatement {
 			"what" => {
 				self.what = Some(Values(value.serialize(ser::value::vec::Serializer.wrap())?));
 			}
+			"alias" => {
+				self.alias = value.serialize(ser::string::opt::Serializer.wrap())?.map(Ident);
+			}
 			"with" => {
 				self.with = value.serialize(ser::with::opt::Serializer.wrap())?;
 			}
@@ -101,15 +118,24 @@ impl serde::ser::SerializeStruct for SerializeSelectStatement {
 			"group" => {
 				self.group = value.serialize(ser::group::vec::opt::Serializer.wrap())?.map(Groups);
 			}
+			"having" => {
+				self.having = value.serialize(ser::having::opt::Serializer.wrap())?;
+			}
 			"order" => {
 				self.order = value.serialize(ser::order::vec::opt::Serializer.wrap())?.map(Orders);
 			}
+			"sample" => {
+				self.sample = value.serialize(ser::sample::opt::Serializer.wrap())?;
+			}
 			"limit" => {
 				self.limit = value.serialize(ser::limit::opt::Serializer.wrap())?;
 			}
 			"start" => {
 				self.start = value.serialize(ser::start::opt::Serializer.wrap())?;
 			}
+			"after" => {
+				self.after = value.serialize(ser::cursor::opt::Serializer.wrap())?;
+			}
 			"fetch" => {
 				self.fetch = value.serialize(ser::fetch::vec::opt::Serializer.wrap())?.map(Fetchs);
 			}
@@ -122,6 +148,9 @@ impl serde::ser::SerializeStruct for SerializeSelectStatement {
 			"parallel" => {
 				self.parallel = Some(value.serialize(ser::primitive::bool::Serializer.wrap())?);
 			}
+			"lock" => {
+				self.lock = value.serialize(ser::lock::opt::Serializer.wrap())?;
+			}
 			"explain" => {
 				self.explain = value.serialize(ser::explain::opt::Serializer.wrap())?;
 			}
@@ -135,19 +164,25 @@ impl serde::ser::SerializeStruct for SerializeSelectStatement {
 	fn end(self) -> Result<Self::Ok, Error> {
 		match (self.expr, self.what, self.parallel) {
 			(Some(expr), Some(what), Some(parallel)) => Ok(SelectStatement {
+				distinct: self.distinct.is_some_and(|v| v),
 				expr,
 				omit: self.omit,
 				only: self.only.is_some_and(|v| v),
 				what,
+				alias: self.alias,
 				with: self.with,
 				parallel,
+				lock: self.lock,
 				explain: self.explain,
 				cond: self.cond,
 				split: self.split,
 				group: self.group,
+				having: self.having,
 				order: self.order,
+				sample: self.sample,
 				limit: self.limit,
 				start: self.start,
+				after: self.after,
 				fetch: self.fetch,
 				version: self.version,
 				timeout: self.timeout,
@@ -168,6 +203,16 @@ mod tests {
 		assert_eq!(value, stmt);
 	}
 
+	#[test]
+	fn with_distinct() {
+		let stmt = SelectStatement {
+			distinct: true,
+			..Default::default()
+		};
+		let value: SelectStatement = stmt.serialize(Serializer.wrap()).unwrap();
+		assert_eq!(value, stmt);
+	}
+
 	#[test]
 	fn with_cond() {
 		let stmt = SelectStatement {
@@ -198,6 +243,16 @@ mod tests {
 		assert_eq!(value, stmt);
 	}
 
+	#[test]
+	fn with_having() {
+		let stmt = SelectStatement {
+			having: Some(Default::default()),
+			..Default::default()
+		};
+		let value: SelectStatement = stmt.serialize(Serializer.wrap()).unwrap();
+		assert_eq!(value, stmt);
+	}
+
 	#[test]
 	fn with_order() {
 		let stmt = SelectStatement {
@@ -208,6 +263,16 @@ mod tests {
 		assert_eq!(value, stmt);
 	}
 
+	#[test]
+	fn with_sample() {
+		let stmt = SelectStatement {
+			sample: Some(Sample(10)),
+			..Default::default()
+		};
+		let value: SelectStatement = stmt.serialize(Serializer.wrap()).unwrap();
+		assert_eq!(value, stmt);
+	}
+
 	#[test]
 	fn with_limit() {
 		let stmt = SelectStatement {
@@ -228,6 +293,16 @@ mod tests {
 		assert_eq!(value, stmt);
 	}
 
+	#[test]
+	fn with_after() {
+		let stmt = SelectStatement {
+			after: Some(Default::default()),
+			..Default::default()
+		};
+		let value: SelectStatement = stmt.serialize(Serializer.wrap()).unwrap();
+		assert_eq!(value, stmt);
+	}
+
 	#[test]
 	fn with_fetch() {
 		let stmt = SelectStatement {
@@ -258,6 +333,26 @@ mod tests {
 		assert_eq!(value, stmt);
 	}
 
+	#[test]
+	fn with_alias() {
+		let stmt = SelectStatement {
+			alias: Some(Ident("adults".to_owned())),
+			..Default::default()
+		};
+		let value: SelectStatement = stmt.serialize(Serializer.wrap()).unwrap();
+		assert_eq!(value, stmt);
+	}
+
+	#[test]
+	fn with_lock() {
+		let stmt = SelectStatement {
+			lock: Some(LockMode::Share),
+			..Default::default()
+		};
+		let value: SelectStatement = stmt.serialize(Serializer.wrap()).unwrap();
+		assert_eq!(value, stmt);
+	}
+
 	#[test]
 	fn with_explain() {
 		let stmt = SelectStatement {
@@ -271,7 +366,17 @@ mod tests {
 	#[test]
 	fn with_explain_full() {
 		let stmt = SelectStatement {
-			explain: Some(Explain(true)),
+			explain: Some(Explain::full()),
+			..Default::default()
+		};
+		let value: SelectStatement = stmt.serialize(Serializer.wrap()).unwrap();
+		assert_eq!(value, stmt);
+	}
+
+	#[test]
+	fn with_explain_analyze() {
+		let stmt = SelectStatement {
+			explain: Some(Explain::analyze()),
 			..Default::default()
 		};
 		let value: SelectStatement = stmt.serialize(Serializer.wrap()).unwrap();
@@ -0,0 +1,196 @@
+use crate::err::Error;
+use crate::sql::statements::{SelectStatement, UnionStatement};
+use crate::sql::value::serde::ser;
+use crate::sql::Limit;
+use crate::sql::Orders;
+use crate::sql::Start;
+use ser::Serializer as _;
+use serde::ser::Error as _;
+use serde::ser::Impossible;
+use serde::ser::Serialize;
+
+#[non_exhaustive]
+pub struct Serializer;
+
+impl ser::Serializer for Serializer {
+	type Ok = UnionStatement;
+	type Error = Error;
+
+	type SerializeSeq = Impossible<UnionStatement, Error>;
+	type SerializeTuple = Impossible<UnionStatement, Error>;
+	type SerializeTupleStruct = Impossible<UnionStatement, Error>;
+	type SerializeTupleVariant = Impossible<UnionStatement, Error>;
+	type SerializeMap = Impossible<UnionStatement, Error>;
+	type SerializeStruct = SerializeUnionStatement;
+	type SerializeStructVariant = Impossible<UnionStatement, Error>;
+
+	const EXPECTED: &'static str = "a struct `UnionStatement`";
+
+	#[inline]
+	fn serialize_struct(
+		self,
+		_name: &'static str,
+		_len: usize,
+	) -> Result<Self::SerializeStruct, Error> {
+		Ok(SerializeUnionStatement::default())
+	}
+}
+
+#[derive(Default)]
+#[non_exhaustive]
+pub struct SerializeUnionStatement {
+	exprs: Vec<SelectStatement>,
+	distinct: Option<bool>,
+	order: Option<Orders>,
+	limit: Option<Limit>,
+	start: Option<Start>,
+}
+
+impl serde::ser::SerializeStruct for SerializeUnionStatement {
+	type Ok = UnionStatement;
+	type Error = Error;
+
+	fn serialize_field<T>(&mut self, key: &'static str, value: &T) -> Result<(), Error>
+	where
+		T: ?Sized + Serialize,
+	{
+		match key {
+			"exprs" => {
+				self.exprs = value.serialize(SelectStatementVecSerializer.wrap())?;
+			}
+			"distinct" => {
+				self.distinct = Some(value.serialize(ser::primitive::bool::Serializer.wrap())?);
+			}
+			"order" => {
+				self.order = value.serialize(ser::order::vec::opt::Serializer.wrap())?.map(Orders);
+			}
+			"limit" => {
+				self.limit = value.serialize(ser::limit::opt::Serializer.wrap())?;
+			}
+			"start" => {
+				self.start = value.serialize(ser::start::opt::Serializer.wrap())?;
+			}
+			key => {
+				return Err(Error::custom(format!("unexpected field `UnionStatement::{key}`")));
+			}
+		}
+		Ok(())
+	}
+
+	fn end(self) -> Result<Self::Ok, Error> {
+		match self.distinct {
+			Some(distinct) => Ok(UnionStatement {
+				exprs: self.exprs,
+				distinct,
+				order: self.order,
+				limit: self.limit,
+				start: self.start,
+			}),
+			_ => Err(Error::custom("`UnionStatement` missing required field(s)")),
+		}
+	}
+}
+
+#[non_exhaustive]
+pub struct SelectStatementVecSerializer;
+
+impl ser::Serializer for SelectStatementVecSerializer {
+	type Ok = Vec<SelectStatement>;
+	type Error = Error;
+
+	type SerializeSeq = SerializeSelectStatementVec;
+	type SerializeTuple = Impossible<Vec<SelectStatement>, Error>;
+	type SerializeTupleStruct = Impossible<Vec<SelectStatement>, Error>;
+	type SerializeTupleVariant = Impossible<Vec<SelectStatement>, Error>;
+	type SerializeMap = Impossible<Vec<SelectStatement>, Error>;
+	type SerializeStruct = Impossible<Vec<SelectStatement>, Error>;
+	type SerializeStructVariant = Impossible<Vec<SelectStatement>, Error>;
+
+	const EXPECTED: &'static str = "a `Vec<SelectStatement>`";
+
+	fn serialize_seq(self, len: Option<usize>) -> Result<Self::SerializeSeq, Error> {
+		Ok(SerializeSelectStatementVec(Vec::with_capacity(len.unwrap_or_default())))
+	}
+}
+
+#[non_exhaustive]
+pub struct SerializeSelectStatementVec(Vec<SelectStatement>);
+
+impl serde::ser::SerializeSeq for SerializeSelectStatementVec {
+	type Ok = Vec<SelectStatement>;
+	type Error = Error;
+
+	fn serialize_element<T>(&mut self, value: &T) -> Result<(), Self::Error>
+	where
+		T: Serialize + ?Sized,
+	{
+		self.0.push(value.serialize(ser::statement::select::Serializer.wrap())?);
+		Ok(())
+	}
+
+	fn end(self) -> Result<Self::Ok, Self::Error> {
+		Ok(self.0)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn default() {
+		let stmt = UnionStatement::default();
+		let value: UnionStatement = stmt.serialize(Serializer.wrap()).unwrap();
+		assert_eq!(value, stmt);
+	}
+
+	#[test]
+	fn with_exprs() {
+		let stmt = UnionStatement {
+			exprs: vec![Default::default(), Default::default()],
+			..Default::default()
+		};
+		let value: UnionStatement = stmt.serialize(Serializer.wrap()).unwrap();
+		assert_eq!(value, stmt);
+	}
+
+	#[test]
+	fn with_distinct() {
+		let stmt = UnionStatement {
+			distinct: true,
+			..Default::default()
+		};
+		let value: UnionStatement = stmt.serialize(Serializer.wrap()).unwrap();
+		assert_eq!(value, stmt);
+	}
+
+	#[test]
+	fn with_order() {
+		let stmt = UnionStatement {
+			order: Some(Default::default()),
+			..Default::default()
+		};
+		let value: UnionStatement = stmt.serialize(Serializer.wrap()).unwrap();
+		assert_eq!(value, stmt);
+	}
+
+	#[test]
+	fn with_limit() {
+		let stmt = UnionStatement {
+			limit: Some(Default::default()),
+			..Default::default()
+		};
+		let value: UnionStatement = stmt.serialize(Serializer.wrap()).unwrap();
+		assert_eq!(value, stmt);
+	}
+
+	#[test]
+	fn with_start() {
+		let stmt = UnionStatement {
+			start: Some(Default::default()),
+			..Default::default()
+		};
+		let value: UnionStatement = stmt.serialize(Serializer.wrap()).unwrap();
+		assert_eq!(value, stmt);
+	}
+}
@@ -0,0 +1,222 @@
+use crate::err::Error;
+use crate::sql::statements::{Cte, SelectStatement, WithStatement};
+use crate::sql::value::serde::ser;
+use crate::sql::Ident;
+use ser::Serializer as _;
+use serde::ser::Error as _;
+use serde::ser::Impossible;
+use serde::ser::Serialize;
+
+#[non_exhaustive]
+pub struct Serializer;
+
+impl ser::Serializer for Serializer {
+	type Ok = WithStatement;
+	type Error = Error;
+
+	type SerializeSeq = Impossible<WithStatement, Error>;
+	type SerializeTuple = Impossible<WithStatement, Error>;
+	type SerializeTupleStruct = Impossible<WithStatement, Error>;
+	type SerializeTupleVariant = Impossible<WithStatement, Error>;
+	type SerializeMap = Impossible<WithStatement, Error>;
+	type SerializeStruct = SerializeWithStatement;
+	type SerializeStructVariant = Impossible<WithStatement, Error>;
+
+	const EXPECTED: &'static str = "a struct `WithStatement`";
+
+	#[inline]
+	fn serialize_struct(
+		self,
+		_name: &'static str,
+		_len: usize,
+	) -> Result<Self::SerializeStruct, Error> {
+		Ok(SerializeWithStatement::default())
+	}
+}
+
+#[derive(Default)]
+#[non_exhaustive]
+pub struct SerializeWithStatement {
+	recursive: Option<bool>,
+	ctes: Vec<Cte>,
+	stmt: Option<SelectStatement>,
+}
+
+impl serde::ser::SerializeStruct for SerializeWithStatement {
+	type Ok = WithStatement;
+	type Error = Error;
+
+	fn serialize_field<T>(&mut self, key: &'static str, value: &T) -> Result<(), Error>
+	where
+		T: ?Sized + Serialize,
+	{
+		match key {
+			"recursive" => {
+				self.recursive = Some(value.serialize(ser::primitive::bool::Serializer.wrap())?);
+			}
+			"ctes" => {
+				self.ctes = value.serialize(CteVecSerializer.wrap())?;
+			}
+			"stmt" => {
+				self.stmt = Some(value.serialize(ser::statement::select::Serializer.wrap())?);
+			}
+			key => {
+				return Err(Error::custom(format!("unexpected field `WithStatement::{key}`")));
+			}
+		}
+		Ok(())
+	}
+
+	fn end(self) -> Result<Self::Ok, Error> {
+		match (self.recursive, self.stmt) {
+			(Some(recursive), Some(stmt)) => Ok(WithStatement {
+				recursive,
+				ctes: self.ctes,
+				stmt,
+			}),
+			_ => Err(Error::custom("`WithStatement` missing required field(s)")),
+		}
+	}
+}
+
+#[non_exhaustive]
+pub struct CteVecSerializer;
+
+impl ser::Serializer for CteVecSerializer {
+	type Ok = Vec<Cte>;
+	type Error = Error;
+
+	type SerializeSeq = SerializeCteVec;
+	type SerializeTuple = Impossible<Vec<Cte>, Error>;
+	type SerializeTupleStruct = Impossible<Vec<Cte>, Error>;
+	type SerializeTupleVariant = Impossible<Vec<Cte>, Error>;
+	type SerializeMap = Impossible<Vec<Cte>, Error>;
+	type SerializeStruct = Impossible<Vec<Cte>, Error>;
+	type SerializeStructVariant = Impossible<Vec<Cte>, Error>;
+
+	const EXPECTED: &'static str = "a `Vec<Cte>`";
+
+	fn serialize_seq(self, len: Option<usize>) -> Result<Self::SerializeSeq, Error> {
+		Ok(SerializeCteVec(Vec::with_capacity(len.unwrap_or_default())))
+	}
+}
+
+#[non_exhaustive]
+pub struct SerializeCteVec(Vec<Cte>);
+
+impl serde::ser::SerializeSeq for SerializeCteVec {
+	type Ok = Vec<Cte>;
+	type Error = Error;
+
+	fn serialize_element<T>(&mut self, value: &T) -> Result<(), Self::Error>
+	where
+		T: Serialize + ?Sized,
+	{
+		self.0.push(value.serialize(CteSerializer.wrap())?);
+		Ok(())
+	}
+
+	fn end(self) -> Result<Self::Ok, Self::Error> {
+		Ok(self.0)
+	}
+}
+
+struct CteSerializer;
+
+impl ser::Serializer for CteSerializer {
+	type Ok = Cte;
+	type Error = Error;
+
+	type SerializeSeq = Impossible<Cte, Error>;
+	type SerializeTuple = Impossible<Cte, Error>;
+	type SerializeTupleStruct = Impossible<Cte, Error>;
+	type SerializeTupleVariant = Impossible<Cte, Error>;
+	type SerializeMap = Impossible<Cte, Error>;
+	type SerializeStruct = SerializeCte;
+	type SerializeStructVariant = Impossible<Cte, Error>;
+
+	const EXPECTED: &'static str = "a struct `Cte`";
+
+	#[inline]
+	fn serialize_struct(
+		self,
+		_name: &'static str,
+		_len: usize,
+	) -> Result<Self::SerializeStruct, Error> {
+		Ok(SerializeCte::default())
+	}
+}
+
+#[derive(Default)]
+struct SerializeCte {
+	name: Ident,
+	expr: Option<SelectStatement>,
+}
+
+impl serde::ser::SerializeStruct for SerializeCte {
+	type Ok = Cte;
+	type Error = Error;
+
+	fn serialize_field<T>(&mut self, key: &'static str, value: &T) -> Result<(), Error>
+	where
+		T: ?Sized + Serialize,
+	{
+		match key {
+			"name" => {
+				self.name = Ident(value.serialize(ser::string::Serializer.wrap())?);
+			}
+			"expr" => {
+				self.expr = Some(value.serialize(ser::statement::select::Serializer.wrap())?);
+			}
+			key => {
+				return Err(Error::custom(format!("unexpected field `Cte::{key}`")));
+			}
+		}
+		Ok(())
+	}
+
+	fn end(self) -> Result<Self::Ok, Error> {
+		match self.expr {
+			Some(expr) => Ok(Cte {
+				name: self.name,
+				expr,
+			}),
+			_ => Err(Error::custom("`Cte` missing required field(s)")),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn default() {
+		let stmt = WithStatement::default();
+		let value: WithStatement = stmt.serialize(Serializer.wrap()).unwrap();
+		assert_eq!(value, stmt);
+	}
+
+	#[test]
+	fn with_recursive() {
+		let stmt = WithStatement {
+			recursive: true,
+			..Default::default()
+		};
+		let value: WithStatement = stmt.serialize(Serializer.wrap()).unwrap();
+		assert_eq!(value, stmt);
+	}
+
+	#[test]
+	fn with_ctes() {
+		let stmt = WithStatement {
+			ctes: vec![Cte {
+				name: Ident("recent".to_owned()),
+				expr: Default::default(),
+			}],
+			..Default::default()
+		};
+		let value: WithStatement = stmt.serialize(Serializer.wrap()).unwrap();
+		assert_eq!(value, stmt);
+	}
+}
@@ -4,6 +4,7 @@ use crate::ctx::Context;
 use crate::dbs::{Options, Transaction};
 use crate::doc::CursorDoc;
 use crate::err::Error;
+use crate::fnc::util::string;
 use crate::fnc::util::string::fuzzy::Fuzzy;
 use crate::sql::statements::info::InfoStructure;
 use crate::sql::{
@@ -26,6 +27,7 @@ use serde_json::Value as Json;
 use std::cmp::Ordering;
 use std::collections::BTreeMap;
 use std::collections::HashMap;
+use std::collections::HashSet;
 use std::fmt::{self, Display, Formatter, Write};
 use std::ops::Deref;
 
@@ -2474,6 +2476,27 @@ impl Value {
 		}
 	}
 
+	/// Check if all the terms of another Value are present in this Value, when
+	/// no full-text index can be used. Strings are split into normalised terms.
+	pub fn matches_terms(&self, other: &Value) -> bool {
+		// Collect the terms of every string in this Value
+		fn collect(v: &Value, terms: &mut HashSet<String>) {
+			match v {
+				Value::Strand(v) => terms.extend(string::terms::terms(v.as_str())),
+				Value::Array(v) => v.iter().for_each(|v| collect(v, terms)),
+				_ => {}
+			}
+		}
+		let Value::Strand(query) = other else {
+			return false;
+		};
+		let mut terms = HashSet::new();
+		collect(self, &mut terms);
+		// If there are no query terms, it can't be a match
+		let mut query = string::terms::terms(query.as_str()).peekable();
+		query.peek().is_some() && query.all(|t| terms.contains(&t))
+	}
+
 	/// Check if this Value contains another Value
 	pub fn contains(&self, other: &Value) -> bool {
 		match self {
@@ -21,7 +21,12 @@ impl Value {
 						Some(v) => v._walk(path.next(), prev.push(p.clone())),
 						None => Value::None._walk(path.next(), prev.push(p.clone())),
 					},
-					Part::All => self._walk(path.next(), prev.push(p.clone())),
+					Part::All => v
+						.iter()
+						.flat_map(|(f, v)| {
+							v._walk(path.next(), prev.clone().push(Part::from(f.to_owned())))
+						})
+						.collect::<Vec<_>>(),
 					_ => vec![],
 				},
 				// Current path part is an array
@@ -166,4 +171,15 @@ mod tests {
 		];
 		assert_eq!(res, val.walk(&idi));
 	}
+
+	#[test]
+	fn walk_object_field_all() {
+		let idi = Idiom::parse("test.*");
+		let val = Value::parse("{ test: { other: null, something: 123 } }");
+		let res = vec![
+			(Idiom::parse("test.other"), Value::Null),
+			(Idiom::parse("test.something"), Value::from(123)),
+		];
+		assert_eq!(res, val.walk(&idi));
+	}
 }
@@ -161,6 +161,7 @@ impl<'a> Lexer<'a> {
 			b';' => t!(";"),
 			b',' => t!(","),
 			b'@' => t!("@"),
+			b'%' => t!("%"),
 			byte::CR | byte::FF | byte::LF | byte::SP | byte::VT | byte::TAB => {
 				self.eat_whitespace();
 				return self.next_token_inner();
@@ -130,9 +130,13 @@ pub(crate) static PATHS: phf::Map<UniCase<&'static str>, PathKind> = phf_map! {
 		UniCase::ascii("object::values") => PathKind::Function,
 		UniCase::ascii("object::matches") => PathKind::Function,
 		//
+		UniCase::ascii("bytes::from_base64") => PathKind::Function,
 		UniCase::ascii("bytes::len") => PathKind::Function,
+		UniCase::ascii("bytes::slice") => PathKind::Function,
+		UniCase::ascii("bytes::to_base64") => PathKind::Function,
 		//
 		UniCase::ascii("count") => PathKind::Function,
+		UniCase::ascii("count::distinct") => PathKind::Function,
 		//
 		UniCase::ascii("crypto::md5") => PathKind::Function,
 		UniCase::ascii("crypto::sha1") => PathKind::Function,
@@ -166,6 +170,8 @@ pub(crate) static PATHS: phf::Map<UniCase<&'static str>, PathKind> = phf_map! {
 		UniCase::ascii("geo::distance") => PathKind::Function,
 		UniCase::ascii("geo::hash::decode") => PathKind::Function,
 		UniCase::ascii("geo::hash::encode") => PathKind::Function,
+		UniCase::ascii("geo::intersects") => PathKind::Function,
+		UniCase::ascii("geo::within") => PathKind::Function,
 		//
 		UniCase::ascii("math::abs") => PathKind::Function,
 		UniCase::ascii("math::bottom") => PathKind::Function,
@@ -187,10 +193,14 @@ pub(crate) static PATHS: phf::Map<UniCase<&'static str>, PathKind> = phf_map! {
 		UniCase::ascii("math::spread") => PathKind::Function,
 		UniCase::ascii("math::sqrt") => PathKind::Function,
 		UniCase::ascii("math::stddev") => PathKind::Function,
+		UniCase::ascii("math::stddev::pop") => PathKind::Function,
+		UniCase::ascii("math::stddev::samp") => PathKind::Function,
 		UniCase::ascii("math::sum") => PathKind::Function,
 		UniCase::ascii("math::top") => PathKind::Function,
 		UniCase::ascii("math::trimean") => PathKind::Function,
 		UniCase::ascii("math::variance") => PathKind::Function,
+		UniCase::ascii("math::variance::pop") => PathKind::Function,
+		UniCase::ascii("math::variance::samp") => PathKind::Function,
 		//
 		UniCase::ascii("meta::id") => PathKind::Function,
 		UniCase::ascii("meta::table") => PathKind::Function,
@@ -239,6 +249,7 @@ pub(crate) static PATHS: phf::Map<UniCase<&'static str>, PathKind> = phf_map! {
 		UniCase::ascii("string::repeat") => PathKind::Function,
 		UniCase::ascii("string::replace") => PathKind::Function,
 		UniCase::ascii("string::reverse") => PathKind::Function,
+		UniCase::ascii("string::similarity") => PathKind::Function,
 		UniCase::ascii("string::slice") => PathKind::Function,
 		UniCase::ascii("string::slug") => PathKind::Function,
 		UniCase::ascii("string::split") => PathKind::Function,
@@ -474,6 +485,16 @@ impl Parser<'_> {
 		name: String,
 	) -> ParseResult<Function> {
 		let start = expected!(self, t!("(")).span;
+		// Counting only distinct values is written as `count(DISTINCT value)`
+		if name == "count"
+			&& self.peek_kind() == t!("DISTINCT")
+			&& self.peek_token_at(1).kind != t!(")")
+		{
+			self.pop_peek();
+			let arg = stk.run(|ctx| self.parse_value_field(ctx)).await?;
+			self.expect_closing_delimiter(t!(")"), start)?;
+			return Ok(Function::Normal("count::distinct".to_owned(), vec![arg]));
+		}
 		let mut args = Vec::new();
 		loop {
 			if self.eat(t!(")")) {
@@ -13,7 +13,7 @@ impl Parser<'_> {
 		&mut self,
 		ctx: &mut Stk,
 	) -> ParseResult<InsertStatement> {
-		let ignore = self.eat(t!("IGNORE"));
+		let mut ignore = self.eat(t!("IGNORE"));
 		expected!(self, t!("INTO"));
 		let next = self.next();
 		// TODO: Explain that more complicated expressions are not allowed here.
@@ -29,7 +29,8 @@ impl Parser<'_> {
 		};
 
 		let data = match self.peek_kind() {
-			t!("(") => {
+			// A parenthesized SELECT is a subquery rather than a list of fields
+			t!("(") if self.peek_token_at(1).kind != t!("SELECT") => {
 				let start = self.pop_peek().span;
 				let fields = self.parse_idiom_list(ctx).await?;
 				self.expect_closing_delimiter(t!(")"), start)?;
@@ -67,7 +68,16 @@ impl Parser<'_> {
 		};
 
 		let update = if self.eat(t!("ON")) {
-			Some(self.parse_insert_update(ctx).await?)
+			expected!(self, t!("DUPLICATE"));
+			expected!(self, t!("KEY"));
+			// ON DUPLICATE KEY IGNORE is the same as INSERT IGNORE
+			if self.eat(t!("IGNORE")) {
+				ignore = true;
+				None
+			} else {
+				expected!(self, t!("UPDATE"));
+				Some(self.parse_insert_update_assignments(ctx).await?)
+			}
 		} else {
 			None
 		};
@@ -85,10 +95,14 @@ impl Parser<'_> {
 		})
 	}
 
-	async fn parse_insert_update(&mut self, ctx: &mut Stk) -> ParseResult<Data> {
+	pub(super) async fn parse_insert_update(&mut self, ctx: &mut Stk) -> ParseResult<Data> {
 		expected!(self, t!("DUPLICATE"));
 		expected!(self, t!("KEY"));
 		expected!(self, t!("UPDATE"));
+		self.parse_insert_update_assignments(ctx).await
+	}
+
+	async fn parse_insert_update_assignments(&mut self, ctx: &mut Stk) -> ParseResult<Data> {
 		let l = self.parse_plain_idiom(ctx).await?;
 		let o = self.parse_assigner()?;
 		let r = ctx.run(|ctx| self.parse_value(ctx)).await?;
@@ -16,7 +16,7 @@ use crate::{
 		statements::{
 			analyze::AnalyzeStatement, BeginStatement, BreakStatement, CancelStatement,
 			CommitStatement, ContinueStatement, ForeachStatement, InfoStatement, OutputStatement,
-			UseStatement,
+			ReleaseStatement, RollbackStatement, SavepointStatement, UseStatement,
 		},
 		Expression, Operator, Statement, Statements, Value,
 	},
@@ -34,7 +34,10 @@ mod parts;
 mod relate;
 mod remove;
 mod select;
+mod union;
 mod update;
+mod upsert;
+mod with;
 
 impl Parser<'_> {
 	pub async fn parse_stmt_list(&mut self, ctx: &mut Stk) -> ParseResult<Statements> {
@@ -81,15 +84,19 @@ impl Parser<'_> {
 				| t!("CANCEL") | t!("COMMIT")
 				| t!("CONTINUE") | t!("CREATE")
 				| t!("DEFINE") | t!("DELETE")
+				| t!("EXPLAIN")
 				| t!("FOR") | t!("IF")
 				| t!("INFO") | t!("INSERT")
 				| t!("KILL") | t!("LIVE")
 				| t!("OPTION") | t!("REBUILD")
 				| t!("RETURN") | t!("RELATE")
-				| t!("REMOVE") | t!("SELECT")
-				| t!("LET") | t!("SHOW")
-				| t!("SLEEP") | t!("THROW")
-				| t!("UPDATE") | t!("USE")
+				| t!("RELEASE") | t!("REMOVE")
+				| t!("ROLLBACK") | t!("SAVEPOINT")
+				| t!("SELECT") | t!("LET")
+				| t!("SHOW") | t!("SLEEP")
+				| t!("THROW") | t!("UPDATE")
+				| t!("UPSERT") | t!("USE")
+				| t!("WITH")
 		)
 	}
 
@@ -178,13 +185,33 @@ impl Parser<'_> {
 				self.pop_peek();
 				ctx.run(|ctx| self.parse_relate_stmt(ctx)).await.map(Statement::Relate)
 			}
+			t!("RELEASE") => {
+				self.pop_peek();
+				self.parse_release().map(Statement::Release)
+			}
 			t!("REMOVE") => {
 				self.pop_peek();
 				self.parse_remove_stmt().map(Statement::Remove)
 			}
+			t!("ROLLBACK") => {
+				self.pop_peek();
+				self.parse_rollback().map(Statement::Rollback)
+			}
+			t!("SAVEPOINT") => {
+				self.pop_peek();
+				self.parse_savepoint().map(Statement::Savepoint)
+			}
 			t!("SELECT") => {
 				self.pop_peek();
-				ctx.run(|ctx| self.parse_select_stmt(ctx)).await.map(Statement::Select)
+				ctx.run(|ctx| self.parse_select_or_union_stmt(ctx)).await
+			}
+			t!("EXPLAIN") => {
+				self.pop_peek();
+				ctx.run(|ctx| self.parse_explain_select_stmt(ctx)).await.map(Statement::Select)
+			}
+			t!("WITH") => {
+				self.pop_peek();
+				ctx.run(|ctx| self.parse_with_stmt(ctx)).await.map(Statement::With)
 			}
 			t!("LET") => {
 				self.pop_peek();
@@ -206,6 +233,10 @@ impl Parser<'_> {
 				self.pop_peek();
 				ctx.run(|ctx| self.parse_update_stmt(ctx)).await.map(Statement::Update)
 			}
+			t!("UPSERT") => {
+				self.pop_peek();
+				ctx.run(|ctx| self.parse_upsert_stmt(ctx)).await.map(Statement::Upsert)
+			}
 			t!("USE") => {
 				self.pop_peek();
 				self.parse_use_stmt().map(Statement::Use)
@@ -279,6 +310,10 @@ impl Parser<'_> {
 				self.pop_peek();
 				self.parse_select_stmt(ctx).await.map(Entry::Select)
 			}
+			t!("EXPLAIN") => {
+				self.pop_peek();
+				self.parse_explain_select_stmt(ctx).await.map(Entry::Select)
+			}
 			t!("LET") => {
 				self.pop_peek();
 				self.parse_let_stmt(ctx).await.map(Entry::Set)
@@ -384,6 +419,42 @@ impl Parser<'_> {
 		Ok(CommitStatement)
 	}
 
+	/// Parsers a savepoint statement.
+	///
+	/// # Parser State
+	/// Expects `SAVEPOINT` to already be consumed.
+	fn parse_savepoint(&mut self) -> ParseResult<SavepointStatement> {
+		let name = self.next_token_value()?;
+		Ok(SavepointStatement {
+			name,
+		})
+	}
+
+	/// Parsers a rollback statement.
+	///
+	/// # Parser State
+	/// Expects `ROLLBACK` to already be consumed.
+	fn parse_rollback(&mut self) -> ParseResult<RollbackStatement> {
+		expected!(self, t!("TO"));
+		self.eat(t!("SAVEPOINT"));
+		let name = self.next_token_value()?;
+		Ok(RollbackStatement {
+			name,
+		})
+	}
+
+	/// Parsers a release statement.
+	///
+	/// # Parser State
+	/// Expects `RELEASE` to already be consumed.
+	fn parse_release(&mut self) -> ParseResult<ReleaseStatement> {
+		self.eat(t!("SAVEPOINT"));
+		let name = self.next_token_value()?;
+		Ok(ReleaseStatement {
+			name,
+		})
+	}
+
 	/// Parsers a USE statement.
 	///
 	/// # Parser State
@@ -6,7 +6,7 @@ use crate::{
 	sql::{
 		change_feed_include::ChangeFeedInclude, changefeed::ChangeFeed, index::Distance,
 		index::VectorType, Base, Cond, Data, Duration, Fetch, Fetchs, Field, Fields, Group, Groups,
-		Ident, Idiom, Output, Permission, Permissions, Tables, Timeout, Value, View,
+		Having, Ident, Idiom, Output, Permission, Permissions, Tables, Timeout, Value, View,
 	},
 	syn::{
 		parser::{
@@ -89,6 +89,10 @@ impl Parser<'_> {
 				self.pop_peek();
 				Output::Before
 			}
+			t!("PATCH") => {
+				self.pop_peek();
+				Output::Patch
+			}
 			_ => Output::Fields(self.parse_fields(ctx).await?),
 		};
 		Ok(Some(res))
@@ -107,7 +111,19 @@ impl Parser<'_> {
 		if !self.eat(t!("FETCH")) {
 			return Ok(None);
 		}
-		let v = self.parse_idiom_list(ctx).await?.into_iter().map(Fetch).collect();
+		let mut v = Vec::new();
+		loop {
+			let idiom = self.parse_plain_idiom(ctx).await?;
+			let depth = if self.eat(t!("DEPTH")) {
+				Some(self.next_token_value()?)
+			} else {
+				None
+			};
+			v.push(Fetch(idiom, depth));
+			if !self.eat(t!(",")) {
+				break;
+			}
+		}
 		Ok(Some(Fetchs(v)))
 	}
 
@@ -119,6 +135,14 @@ impl Parser<'_> {
 		Ok(Some(Cond(v)))
 	}
 
+	pub async fn try_parse_having(&mut self, ctx: &mut Stk) -> ParseResult<Option<Having>> {
+		if !self.eat(t!("HAVING")) {
+			return Ok(None);
+		}
+		let v = ctx.run(|ctx| self.parse_value_field(ctx)).await?;
+		Ok(Some(Having(v)))
+	}
+
 	pub fn check_idiom<'a>(
 		kind: MissingKind,
 		fields: &'a Fields,
@@ -128,12 +152,13 @@ impl Parser<'_> {
 	) -> ParseResult<&'a Field> {
 		let mut found = None;
 		for field in fields.iter() {
+			// Any `*` field can not produce a computed value
 			let Field::Single {
 				expr,
 				alias,
 			} = field
 			else {
-				unreachable!()
+				continue;
 			};
 
 			if let Some(alias) = alias {
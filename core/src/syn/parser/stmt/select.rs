@@ -2,16 +2,17 @@ use reblessive::Stk;
 
 use crate::{
 	sql::{
-		statements::SelectStatement, Explain, Field, Fields, Ident, Idioms, Limit, Order, Orders,
-		Split, Splits, Start, Values, Version, With,
+		order::tailoring, statements::SelectStatement, Cursor, Explain, Field, Fields, Ident,
+		Idioms, Limit, LimitKind, LockMode, NullsOrder, Order, Orders, Sample, Split, Splits,
+		Start, Strand, Value, Values, Version, With,
 	},
 	syn::{
 		parser::{
 			error::MissingKind,
 			mac::{expected, unexpected},
-			ParseResult, Parser,
+			ParseError, ParseErrorKind, ParseResult, Parser,
 		},
-		token::{t, Span},
+		token::{t, Span, TokenKind},
 	},
 };
 
@@ -20,6 +21,16 @@ impl Parser<'_> {
 		&mut self,
 		stk: &mut Stk,
 	) -> ParseResult<SelectStatement> {
+		// A field which is named `distinct` is still a valid projection
+		let distinct = self.peek_kind() == t!("DISTINCT")
+			&& !matches!(
+				self.peek_token_at(1).kind,
+				t!("FROM") | t!(",") | t!(".") | t!("[") | t!("AS")
+			);
+		if distinct {
+			self.pop_peek();
+		}
+
 		let before = self.peek().span;
 		let expr = self.parse_fields(stk).await?;
 		let fields_span = before.covers(self.last_span());
@@ -39,13 +50,16 @@ impl Parser<'_> {
 			what.push(stk.run(|ctx| self.parse_value(ctx)).await?);
 		}
 		let what = Values(what);
+		let alias = self.try_parse_alias(&what)?;
 
 		let with = self.try_parse_with()?;
 		let cond = self.try_parse_condition(stk).await?;
 		let split = self.try_parse_split(&expr, fields_span)?;
 		let group = self.try_parse_group(&expr, fields_span)?;
-		let order = self.try_parse_orders(&expr, fields_span)?;
-		let (limit, start) = if let t!("START") = self.peek_kind() {
+		let having = self.try_parse_having(stk).await?;
+		let order = self.try_parse_orders(stk, &expr, fields_span).await?;
+		let sample = self.try_parse_sample(&order)?;
+		let (limit, (start, after)) = if let t!("START") | t!("OFFSET") = self.peek_kind() {
 			let start = self.try_parse_start(stk).await?;
 			let limit = self.try_parse_limit(stk).await?;
 			(limit, start)
@@ -54,32 +68,81 @@ impl Parser<'_> {
 			let start = self.try_parse_start(stk).await?;
 			(limit, start)
 		};
+		let after = match after {
+			Some(v) => Some(v),
+			None => self.try_parse_after(stk).await?,
+		};
 		let fetch = self.try_parse_fetch(stk).await?;
 		let version = self.try_parse_version()?;
 		let timeout = self.try_parse_timeout()?;
 		let parallel = self.eat(t!("PARALLEL"));
-		let explain = self.eat(t!("EXPLAIN")).then(|| Explain(self.eat(t!("FULL"))));
+		let lock = self.try_parse_lock()?;
+		let explain = self.eat(t!("EXPLAIN")).then(|| self.parse_explain());
 
 		Ok(SelectStatement {
+			distinct,
 			expr,
 			omit,
 			only,
 			what,
+			alias,
 			with,
 			cond,
 			split,
 			group,
+			having,
 			order,
+			sample,
 			limit,
 			start,
+			after,
 			fetch,
 			version,
 			timeout,
 			parallel,
+			lock,
 			explain,
 		})
 	}
 
+	/// Parses the `EXPLAIN [ANALYZE | FULL] SELECT ...` form of a select statement, which
+	/// behaves the same as a trailing `EXPLAIN [ANALYZE | FULL]` clause.
+	///
+	/// Expects the `EXPLAIN` keyword to already be consumed.
+	pub(crate) async fn parse_explain_select_stmt(
+		&mut self,
+		stk: &mut Stk,
+	) -> ParseResult<SelectStatement> {
+		let explain = self.parse_explain();
+		expected!(self, t!("SELECT"));
+		let mut stmt = self.parse_select_stmt(stk).await?;
+		if stmt.explain.is_some() {
+			return Err(ParseError::new(
+				ParseErrorKind::UnexpectedExplain {
+					found: t!("EXPLAIN"),
+					expected: "the query to end",
+					explain: "EXPLAIN was already specified before the SELECT statement",
+				},
+				self.last_span(),
+			));
+		}
+		stmt.explain = Some(explain);
+		Ok(stmt)
+	}
+
+	/// Parses the optional `ANALYZE` or `FULL` keyword of an `EXPLAIN` clause.
+	///
+	/// Expects the `EXPLAIN` keyword to already be consumed.
+	fn parse_explain(&mut self) -> Explain {
+		if self.eat(t!("ANALYZE")) {
+			Explain::analyze()
+		} else if self.eat(t!("FULL")) {
+			Explain::full()
+		} else {
+			Explain::default()
+		}
+	}
+
 	fn try_parse_with(&mut self) -> ParseResult<Option<With>> {
 		if !self.eat(t!("WITH")) {
 			return Ok(None);
@@ -135,8 +198,9 @@ impl Parser<'_> {
 		Ok(Some(Splits(res)))
 	}
 
-	fn try_parse_orders(
+	async fn try_parse_orders(
 		&mut self,
+		stk: &mut Stk,
 		fields: &Fields,
 		fields_span: Span,
 	) -> ParseResult<Option<Orders>> {
@@ -149,6 +213,10 @@ impl Parser<'_> {
 		if let t!("RAND") = self.peek_kind() {
 			self.pop_peek();
 			let start = expected!(self, t!("(")).span;
+			let seed = match self.peek_kind() {
+				t!(")") => None,
+				_ => Some(self.next_token_value()?),
+			};
 			self.expect_closing_delimiter(t!(")"), start)?;
 			return Ok(Some(Orders(vec![Order {
 				order: Default::default(),
@@ -156,24 +224,27 @@ impl Parser<'_> {
 				collate: false,
 				numeric: false,
 				direction: true,
+				nulls: NullsOrder::Default,
+				locale: None,
+				seed,
 			}])));
 		};
 
 		let has_all = fields.contains(&Field::All);
 
 		let before = self.recent_span();
-		let order = self.parse_order()?;
+		let (order, computed) = self.parse_order(stk).await?;
 		let order_span = before.covers(self.last_span());
-		if !has_all {
+		if !has_all || computed {
 			Self::check_idiom(MissingKind::Order, fields, fields_span, &order, order_span)?;
 		}
 
 		let mut orders = vec![order];
 		while self.eat(t!(",")) {
 			let before = self.recent_span();
-			let order = self.parse_order()?;
+			let (order, computed) = self.parse_order(stk).await?;
 			let order_span = before.covers(self.last_span());
-			if !has_all {
+			if !has_all || computed {
 				Self::check_idiom(MissingKind::Order, fields, fields_span, &order, order_span)?;
 			}
 			orders.push(order)
@@ -182,9 +253,33 @@ impl Parser<'_> {
 		Ok(Some(Orders(orders)))
 	}
 
-	fn parse_order(&mut self) -> ParseResult<Order> {
-		let start = self.parse_basic_idiom()?;
+	/// Parses a single ordering, returning whether it orders by a function
+	/// call, which must then be one of the selected fields.
+	async fn parse_order(&mut self, stk: &mut Stk) -> ParseResult<(Order, bool)> {
+		// A function call orders by the field which the call produces
+		let computed = self.peek_token_at(1).kind == t!("::");
+		let start = if computed {
+			stk.run(|ctx| self.parse_value(ctx)).await?.to_idiom()
+		} else {
+			self.parse_basic_idiom()?
+		};
 		let collate = self.eat(t!("COLLATE"));
+		let locale = if collate && self.peek_kind() == TokenKind::Strand {
+			let locale: Strand = self.next_token_value()?;
+			if tailoring(&locale).is_none() {
+				return Err(ParseError::new(
+					ParseErrorKind::UnexpectedExplain {
+						found: TokenKind::Strand,
+						expected: "a supported locale",
+						explain: "The collation locale must be a BCP-47 language tag, such as 'de-DE', for Arabic, Dutch, English, French, German, Indonesian, Italian, Malay, or Portuguese",
+					},
+					self.last_span(),
+				));
+			}
+			Some(locale)
+		} else {
+			None
+		};
 		let numeric = self.eat(t!("NUMERIC"));
 		let direction = match self.peek_kind() {
 			t!("ASCENDING") => {
@@ -197,13 +292,26 @@ impl Parser<'_> {
 			}
 			_ => true,
 		};
-		Ok(Order {
+		let nulls = if self.eat(t!("NULLS")) {
+			match self.next().kind {
+				t!("FIRST") => NullsOrder::First,
+				t!("LAST") => NullsOrder::Last,
+				x => unexpected!(self, x, "`FIRST` or `LAST`"),
+			}
+		} else {
+			NullsOrder::Default
+		};
+		let order = Order {
 			order: start,
 			random: false,
 			collate,
 			numeric,
 			direction,
-		})
+			nulls,
+			locale,
+			seed: None,
+		};
+		Ok((order, computed))
 	}
 
 	async fn try_parse_limit(&mut self, ctx: &mut Stk) -> ParseResult<Option<Limit>> {
@@ -212,16 +320,102 @@ impl Parser<'_> {
 		}
 		self.eat(t!("BY"));
 		let value = ctx.run(|ctx| self.parse_value(ctx)).await?;
-		Ok(Some(Limit(value)))
+		let kind = match self.eat(t!("%")) {
+			true => LimitKind::Percent,
+			false => LimitKind::Rows,
+		};
+		Ok(Some(Limit(value, kind)))
 	}
 
-	async fn try_parse_start(&mut self, ctx: &mut Stk) -> ParseResult<Option<Start>> {
-		if !self.eat(t!("START")) {
+	/// Parses a `START [AT] n` clause, or a `START AFTER cursor` clause which
+	/// is the same as an `AFTER cursor` clause.
+	async fn try_parse_start(
+		&mut self,
+		ctx: &mut Stk,
+	) -> ParseResult<(Option<Start>, Option<Cursor>)> {
+		// OFFSET is accepted as an alias of START
+		if self.eat(t!("START")) {
+			if self.eat(t!("AFTER")) {
+				let value = ctx.run(|ctx| self.parse_value(ctx)).await?;
+				return Ok((None, Some(Cursor(value))));
+			}
+			self.eat(t!("AT"));
+		} else if !self.eat(t!("OFFSET")) {
+			return Ok((None, None));
+		}
+		let value = ctx.run(|ctx| self.parse_value(ctx)).await?;
+		Ok((Some(Start(value)), None))
+	}
+
+	/// Parses an `AFTER cursor` clause, which continues from the record after the cursor.
+	async fn try_parse_after(&mut self, ctx: &mut Stk) -> ParseResult<Option<Cursor>> {
+		if !self.eat(t!("AFTER")) {
 			return Ok(None);
 		}
-		self.eat(t!("AT"));
 		let value = ctx.run(|ctx| self.parse_value(ctx)).await?;
-		Ok(Some(Start(value)))
+		Ok(Some(Cursor(value)))
+	}
+
+	/// Parses a `SAMPLE n RECORDS` clause, which can not be combined with an `ORDER BY` clause.
+	fn try_parse_sample(&mut self, order: &Option<Orders>) -> ParseResult<Option<Sample>> {
+		if !self.eat(t!("SAMPLE")) {
+			return Ok(None);
+		}
+		let span = self.last_span();
+		if order.is_some() {
+			return Err(ParseError::new(
+				ParseErrorKind::UnexpectedExplain {
+					found: t!("SAMPLE"),
+					expected: "the ORDER BY clause to end",
+					explain: "A randomly sampled result can not be ordered, remove either the ORDER BY or the SAMPLE clause",
+				},
+				span,
+			));
+		}
+		let sample = Sample(self.next_token_value()?);
+		expected!(self, t!("RECORDS"));
+		if self.peek_kind() == t!("ORDER") {
+			return Err(ParseError::new(
+				ParseErrorKind::UnexpectedExplain {
+					found: t!("ORDER"),
+					expected: "the SAMPLE clause to be last",
+					explain: "A randomly sampled result can not be ordered, remove either the ORDER BY or the SAMPLE clause",
+				},
+				self.peek().span,
+			));
+		}
+		Ok(Some(sample))
+	}
+
+	/// Parses the `AS alias` of a subquery which is selected from.
+	fn try_parse_alias(&mut self, what: &Values) -> ParseResult<Option<Ident>> {
+		if !self.eat(t!("AS")) {
+			return Ok(None);
+		}
+		let span = self.last_span();
+		if !matches!(what.0.as_slice(), [Value::Subquery(_)]) {
+			return Err(ParseError::new(
+				ParseErrorKind::UnexpectedExplain {
+					found: t!("AS"),
+					expected: "a single subquery to select from",
+					explain: "Only a single subquery in the FROM clause can be given an alias",
+				},
+				span,
+			));
+		}
+		Ok(Some(self.next_token_value()?))
+	}
+
+	fn try_parse_lock(&mut self) -> ParseResult<Option<LockMode>> {
+		if !self.eat(t!("FOR")) {
+			return Ok(None);
+		}
+		let lock = match self.next().kind {
+			t!("UPDATE") => LockMode::Update,
+			t!("SHARE") => LockMode::Share,
+			x => unexpected!(self, x, "`UPDATE` or `SHARE`"),
+		};
+		Ok(Some(lock))
 	}
 
 	fn try_parse_version(&mut self) -> ParseResult<Option<Version>> {
@@ -0,0 +1,76 @@
+use reblessive::Stk;
+
+use crate::{
+	sql::{statements::UnionStatement, Statement},
+	syn::{
+		parser::{mac::expected, ParseError, ParseErrorKind, ParseResult, Parser},
+		token::t,
+	},
+};
+
+impl Parser<'_> {
+	/// Parses a select statement, along with any `UNION [ALL | DISTINCT] SELECT ...` which
+	/// follows it.
+	///
+	/// Expects the first `SELECT` keyword to already be consumed. The `ORDER BY`, `LIMIT`, and
+	/// `START` clauses of the last select statement apply to the combined result of the union.
+	pub(crate) async fn parse_select_or_union_stmt(
+		&mut self,
+		stk: &mut Stk,
+	) -> ParseResult<Statement> {
+		let first = self.parse_select_stmt(stk).await?;
+		if self.peek_kind() != t!("UNION") {
+			return Ok(Statement::Select(first));
+		}
+
+		let mut exprs = vec![first];
+		let mut distinct = None;
+		while self.eat(t!("UNION")) {
+			let span = self.last_span();
+			// A plain UNION removes duplicates, as in UNION DISTINCT
+			let is_distinct = !self.eat(t!("ALL"));
+			if is_distinct {
+				self.eat(t!("DISTINCT"));
+			}
+			if distinct.is_some_and(|x| x != is_distinct) {
+				return Err(ParseError::new(
+					ParseErrorKind::UnexpectedExplain {
+						found: t!("UNION"),
+						expected: "a consistent UNION operator",
+						explain: "UNION ALL and UNION DISTINCT can not be combined in one query",
+					},
+					span,
+				));
+			}
+			distinct = Some(is_distinct);
+			// Only the last select statement may be ordered or limited
+			let last = exprs.last().expect("a union has at least one select statement");
+			if last.order.is_some() || last.limit.is_some() || last.start.is_some() {
+				return Err(ParseError::new(
+					ParseErrorKind::UnexpectedExplain {
+						found: t!("UNION"),
+						expected: "the query to end",
+						explain:
+							"ORDER BY, LIMIT, and START can only follow the last SELECT of a UNION",
+					},
+					span,
+				));
+			}
+			expected!(self, t!("SELECT"));
+			exprs.push(self.parse_select_stmt(stk).await?);
+		}
+
+		let last = exprs.last_mut().expect("a union has at least one select statement");
+		let order = last.order.take();
+		let limit = last.limit.take();
+		let start = last.start.take();
+
+		Ok(Statement::Union(UnionStatement {
+			exprs,
+			distinct: distinct.unwrap_or_default(),
+			order,
+			limit,
+			start,
+		}))
+	}
+}
@@ -0,0 +1,39 @@
+use reblessive::Stk;
+
+use crate::{
+	sql::{statements::UpsertStatement, Values},
+	syn::{
+		parser::{ParseResult, Parser},
+		token::t,
+	},
+};
+
+impl Parser<'_> {
+	/// Parses an `UPSERT [ONLY] [INTO] what [data] [ON DUPLICATE KEY UPDATE ...]` statement.
+	///
+	/// Expects the `UPSERT` keyword to already be consumed.
+	pub async fn parse_upsert_stmt(&mut self, stk: &mut Stk) -> ParseResult<UpsertStatement> {
+		let only = self.eat(t!("ONLY"));
+		self.eat(t!("INTO"));
+		let what = Values(self.parse_what_list(stk).await?);
+		let data = self.try_parse_data(stk).await?;
+		let update = if self.eat(t!("ON")) {
+			Some(self.parse_insert_update(stk).await?)
+		} else {
+			None
+		};
+		let output = self.try_parse_output(stk).await?;
+		let timeout = self.try_parse_timeout()?;
+		let parallel = self.eat(t!("PARALLEL"));
+
+		Ok(UpsertStatement {
+			only,
+			what,
+			data,
+			update,
+			output,
+			timeout,
+			parallel,
+		})
+	}
+}
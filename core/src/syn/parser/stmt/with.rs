@@ -0,0 +1,45 @@
+use reblessive::Stk;
+
+use crate::{
+	sql::statements::{Cte, WithStatement},
+	syn::{
+		parser::{mac::expected, ParseResult, Parser},
+		token::t,
+	},
+};
+
+impl Parser<'_> {
+	/// Parses a `WITH [RECURSIVE] name AS (SELECT ...), ... SELECT ...` statement.
+	///
+	/// Expects the `WITH` keyword to already be consumed.
+	pub(crate) async fn parse_with_stmt(&mut self, stk: &mut Stk) -> ParseResult<WithStatement> {
+		let recursive = self.eat(t!("RECURSIVE"));
+
+		let mut ctes = Vec::new();
+		loop {
+			let name = self.next_token_value()?;
+			expected!(self, t!("AS"));
+			let open = expected!(self, t!("(")).span;
+			expected!(self, t!("SELECT"));
+			let expr = stk.run(|stk| self.parse_select_stmt(stk)).await?;
+			self.expect_closing_delimiter(t!(")"), open)?;
+			ctes.push(Cte {
+				name,
+				expr,
+			});
+
+			if !self.eat(t!(",")) {
+				break;
+			}
+		}
+
+		expected!(self, t!("SELECT"));
+		let stmt = self.parse_select_stmt(stk).await?;
+
+		Ok(WithStatement {
+			recursive,
+			ctes,
+			stmt,
+		})
+	}
+}
@@ -13,19 +13,21 @@ use crate::{
 			DefineIndexStatement, DefineNamespaceStatement, DefineParamStatement, DefineStatement,
 			DefineTableStatement, DefineTokenStatement, DeleteStatement, ForeachStatement,
 			IfelseStatement, InfoStatement, InsertStatement, KillStatement, OptionStatement,
-			OutputStatement, RelateStatement, RemoveAnalyzerStatement, RemoveDatabaseStatement,
-			RemoveEventStatement, RemoveFieldStatement, RemoveFunctionStatement,
-			RemoveIndexStatement, RemoveNamespaceStatement, RemoveParamStatement,
-			RemoveScopeStatement, RemoveStatement, RemoveTableStatement, RemoveTokenStatement,
-			RemoveUserStatement, SelectStatement, SetStatement, ThrowStatement, UpdateStatement,
+			OutputStatement, RelateStatement, ReleaseStatement, RemoveAnalyzerStatement,
+			RemoveDatabaseStatement, RemoveEventStatement, RemoveFieldStatement,
+			RemoveFunctionStatement, RemoveIndexStatement, RemoveNamespaceStatement,
+			RemoveParamStatement, RemoveScopeStatement, RemoveStatement, RemoveTableStatement,
+			RemoveTokenStatement, RemoveUserStatement, RollbackStatement, SavepointStatement,
+			SelectStatement, SetStatement, ThrowStatement, UpdateStatement, UpsertStatement,
 			UseStatement,
 		},
 		tokenizer::Tokenizer,
-		Algorithm, Array, Base, Block, Cond, Data, Datetime, Dir, Duration, Edges, Explain,
-		Expression, Fetch, Fetchs, Field, Fields, Future, Graph, Group, Groups, Id, Ident, Idiom,
-		Idioms, Index, Kind, Limit, Number, Object, Operator, Order, Orders, Output, Param, Part,
-		Permission, Permissions, Scoring, Split, Splits, Start, Statement, Strand, Subquery, Table,
-		TableType, Tables, Thing, Timeout, Uuid, Value, Values, Version, With,
+		Algorithm, Array, Base, Block, Cond, Cursor, Data, Datetime, Dir, Duration, Edges, Explain,
+		Expression, Fetch, Fetchs, Field, Fields, Future, Graph, Group, Groups, Having, Id, Ident,
+		Idiom, Idioms, Index, Kind, Limit, LimitKind, LockMode, NullsOrder, Number, Object,
+		Operator, Order, Orders, Output, Param, Part, Permission, Permissions, Sample, Scoring,
+		Split, Splits, Start, Statement, Strand, Subquery, Table, TableType, Tables, Thing,
+		Timeout, Uuid, Value, Values, Version, With,
 	},
 	syn::parser::mac::test_parse,
 };
@@ -70,6 +72,32 @@ pub fn parse_commit() {
 	assert_eq!(res, Statement::Commit(CommitStatement));
 }
 
+#[test]
+pub fn parse_savepoint() {
+	let res = test_parse!(parse_stmt, r#"SAVEPOINT before_update"#).unwrap();
+	assert_eq!(
+		res,
+		Statement::Savepoint(SavepointStatement {
+			name: Ident("before_update".to_owned()),
+		})
+	);
+	let res = test_parse!(parse_stmt, r#"ROLLBACK TO before_update"#).unwrap();
+	let expect = Statement::Rollback(RollbackStatement {
+		name: Ident("before_update".to_owned()),
+	});
+	assert_eq!(res, expect);
+	let res = test_parse!(parse_stmt, r#"ROLLBACK TO SAVEPOINT before_update"#).unwrap();
+	assert_eq!(res, expect);
+	let res = test_parse!(parse_stmt, r#"RELEASE SAVEPOINT before_update"#).unwrap();
+	let expect = Statement::Release(ReleaseStatement {
+		name: Ident("before_update".to_owned()),
+	});
+	assert_eq!(res, expect);
+	let res = test_parse!(parse_stmt, r#"RELEASE before_update"#).unwrap();
+	assert_eq!(res, expect);
+	test_parse!(parse_stmt, r#"ROLLBACK before_update"#).unwrap_err();
+}
+
 #[test]
 pub fn parse_continue() {
 	let res = test_parse!(parse_stmt, r#"CONTINUE"#).unwrap();
@@ -709,6 +737,7 @@ SELECT bar as foo,[1,2],bar OMIT bar FROM ONLY a,1
     WHERE true
     SPLIT ON foo,bar
     GROUP foo,bar
+    HAVING true
     ORDER BY foo COLLATE NUMERIC ASC
     START AT { a: true }
     LIMIT BY a:b
@@ -734,6 +763,7 @@ SELECT bar as foo,[1,2],bar OMIT bar FROM ONLY a,1
 	assert_eq!(
 		res,
 		Statement::Select(SelectStatement {
+			distinct: false,
 			expr: Fields(
 				vec![
 					Field::Single {
@@ -757,6 +787,7 @@ SELECT bar as foo,[1,2],bar OMIT bar FROM ONLY a,1
 			omit: Some(Idioms(vec![Idiom(vec![Part::Field(Ident("bar".to_owned()))])])),
 			only: true,
 			what: Values(vec![Value::Table(Table("a".to_owned())), Value::Number(Number::Int(1))]),
+			alias: None,
 			with: Some(With::Index(vec!["index".to_owned(), "index_2".to_owned()])),
 			cond: Some(Cond(Value::Bool(true))),
 			split: Some(Splits(vec![
@@ -767,29 +798,517 @@ SELECT bar as foo,[1,2],bar OMIT bar FROM ONLY a,1
 				Group(Idiom(vec![Part::Field(Ident("foo".to_owned()))])),
 				Group(Idiom(vec![Part::Field(Ident("bar".to_owned()))])),
 			])),
+			having: Some(Having(Value::Bool(true))),
 			order: Some(Orders(vec![Order {
 				order: Idiom(vec![Part::Field(Ident("foo".to_owned()))]),
 				random: false,
 				collate: true,
 				numeric: true,
 				direction: true,
+				nulls: NullsOrder::Default,
+				locale: None,
+				seed: None,
 			}])),
-			limit: Some(Limit(Value::Thing(Thing {
-				tb: "a".to_owned(),
-				id: Id::String("b".to_owned()),
-			}))),
+			sample: None,
+			limit: Some(Limit(
+				Value::Thing(Thing {
+					tb: "a".to_owned(),
+					id: Id::String("b".to_owned()),
+				}),
+				LimitKind::Rows,
+			)),
 			start: Some(Start(Value::Object(Object(
 				[("a".to_owned(), Value::Bool(true))].into_iter().collect()
 			)))),
-			fetch: Some(Fetchs(vec![Fetch(Idiom(vec![Part::Field(Ident("foo".to_owned()))]))])),
+			after: None,
+			fetch: Some(Fetchs(vec![Fetch(
+				Idiom(vec![Part::Field(Ident("foo".to_owned()))]),
+				None
+			)])),
 			version: Some(Version(Datetime(expected_datetime))),
 			timeout: None,
 			parallel: false,
-			explain: Some(Explain(true)),
+			lock: None,
+			explain: Some(Explain::full()),
 		}),
 	);
 }
 
+#[test]
+fn parse_select_having() {
+	let res = test_parse!(
+		parse_stmt,
+		r#"SELECT count(), city FROM person GROUP BY city HAVING count() > 10 ORDER BY city"#
+	)
+	.unwrap();
+	assert_eq!(
+		res.to_string(),
+		"SELECT count(), city FROM person GROUP BY city HAVING count() > 10 ORDER BY city"
+	);
+}
+
+#[test]
+fn parse_select_distinct() {
+	let res = test_parse!(parse_stmt, r#"SELECT DISTINCT name FROM person"#).unwrap();
+	assert_eq!(res.to_string(), "SELECT DISTINCT name FROM person");
+
+	let res = test_parse!(parse_stmt, r#"SELECT distinct, name FROM person"#).unwrap();
+	assert_eq!(res.to_string(), "SELECT distinct, name FROM person");
+}
+
+#[test]
+fn parse_select_omit() {
+	let res =
+		test_parse!(parse_stmt, r#"SELECT * OMIT password, internal.notes FROM user"#).unwrap();
+	assert_eq!(res.to_string(), "SELECT * OMIT password, internal.notes FROM user");
+
+	let res =
+		test_parse!(parse_stmt, r#"SELECT *, age OMIT tags[0] FROM user WHERE age > 18"#).unwrap();
+	assert_eq!(res.to_string(), "SELECT *, age OMIT tags[0] FROM user WHERE age > 18");
+}
+
+#[test]
+fn parse_select_offset() {
+	let res = test_parse!(parse_stmt, r#"SELECT * FROM t OFFSET 10"#).unwrap();
+	let Statement::Select(stmt) = res else {
+		panic!("expected a select statement");
+	};
+	assert_eq!(stmt.start, Some(Start(Value::Number(Number::Int(10)))));
+	assert_eq!(stmt.to_string(), "SELECT * FROM t START 10");
+
+	let res = test_parse!(parse_stmt, r#"SELECT * FROM t LIMIT 5 OFFSET 10"#).unwrap();
+	assert_eq!(res.to_string(), "SELECT * FROM t LIMIT 5 START 10");
+
+	let res = test_parse!(parse_stmt, r#"SELECT * FROM t OFFSET 10 LIMIT 5"#).unwrap();
+	assert_eq!(res.to_string(), "SELECT * FROM t LIMIT 5 START 10");
+}
+
+#[test]
+fn parse_select_after() {
+	let res =
+		test_parse!(parse_stmt, r#"SELECT * FROM t ORDER BY id LIMIT 10 AFTER $cursor"#).unwrap();
+	let Statement::Select(stmt) = res else {
+		panic!("expected a select statement");
+	};
+	assert_eq!(stmt.after, Some(Cursor(Value::Param(Param(Ident("cursor".to_owned()))))));
+	assert_eq!(stmt.to_string(), "SELECT * FROM t ORDER BY id LIMIT 10 AFTER $cursor");
+
+	let res = test_parse!(parse_stmt, r#"SELECT * FROM t START 5 AFTER t:10"#).unwrap();
+	assert_eq!(res.to_string(), "SELECT * FROM t START 5 AFTER t:10");
+}
+
+#[test]
+fn parse_select_start_after() {
+	let res = test_parse!(
+		parse_stmt,
+		r#"SELECT * FROM post ORDER BY created LIMIT 20 START AFTER $cursor"#
+	)
+	.unwrap();
+	let Statement::Select(stmt) = res else {
+		panic!("expected a select statement");
+	};
+	assert_eq!(stmt.start, None);
+	assert_eq!(stmt.after, Some(Cursor(Value::Param(Param(Ident("cursor".to_owned()))))));
+	assert_eq!(stmt.to_string(), "SELECT * FROM post ORDER BY created LIMIT 20 AFTER $cursor");
+
+	let res = test_parse!(
+		parse_stmt,
+		r#"SELECT * FROM post ORDER BY created START AFTER [1, post:1] LIMIT 20"#
+	)
+	.unwrap();
+	assert_eq!(res.to_string(), "SELECT * FROM post ORDER BY created LIMIT 20 AFTER [1, post:1]");
+}
+
+#[test]
+fn parse_select_limit_percentage() {
+	let res = test_parse!(parse_stmt, r#"SELECT * FROM t LIMIT 10%"#).unwrap();
+	let Statement::Select(stmt) = res else {
+		panic!("expected a select statement");
+	};
+	assert_eq!(stmt.limit, Some(Limit(Value::Number(Number::Int(10)), LimitKind::Percent)));
+	assert_eq!(stmt.to_string(), "SELECT * FROM t LIMIT 10%");
+
+	let res = test_parse!(parse_stmt, r#"SELECT * FROM t LIMIT $p% START 5"#).unwrap();
+	assert_eq!(res.to_string(), "SELECT * FROM t LIMIT $p% START 5");
+}
+
+#[test]
+fn parse_select_subquery_alias() {
+	let res = test_parse!(
+		parse_stmt,
+		r#"SELECT * FROM (SELECT id FROM user WHERE active = true) AS active WHERE id != NONE"#
+	)
+	.unwrap();
+	let Statement::Select(stmt) = res else {
+		panic!("expected a select statement");
+	};
+	assert_eq!(stmt.alias, Some(Ident("active".to_owned())));
+	assert_eq!(
+		stmt.to_string(),
+		"SELECT * FROM (SELECT id FROM user WHERE active = true) AS active WHERE id != NONE"
+	);
+
+	test_parse!(parse_stmt, r#"SELECT * FROM user AS u"#).unwrap_err();
+	test_parse!(parse_stmt, r#"SELECT * FROM (SELECT * FROM a), (SELECT * FROM b) AS c"#)
+		.unwrap_err();
+}
+
+#[test]
+fn parse_select_sample() {
+	let res =
+		test_parse!(parse_stmt, r#"SELECT * FROM person WHERE age > 18 SAMPLE 10 RECORDS LIMIT 5"#)
+			.unwrap();
+	let Statement::Select(stmt) = res else {
+		panic!("expected a select statement");
+	};
+	assert_eq!(stmt.sample, Some(Sample(10)));
+	assert_eq!(stmt.to_string(), "SELECT * FROM person WHERE age > 18 SAMPLE 10 RECORDS LIMIT 5");
+
+	test_parse!(parse_stmt, r#"SELECT * FROM person SAMPLE 10"#).unwrap_err();
+	test_parse!(parse_stmt, r#"SELECT * FROM person ORDER BY age SAMPLE 10 RECORDS"#).unwrap_err();
+	test_parse!(parse_stmt, r#"SELECT * FROM person SAMPLE 10 RECORDS ORDER BY age"#).unwrap_err();
+}
+
+#[test]
+fn parse_select_order_nulls() {
+	let res = test_parse!(
+		parse_stmt,
+		r#"SELECT * FROM t ORDER BY a ASC NULLS LAST, b DESC NULLS FIRST, c"#
+	)
+	.unwrap();
+	let Statement::Select(stmt) = res else {
+		panic!("expected a select statement");
+	};
+	let nulls = stmt.order.unwrap().iter().map(|o| o.nulls).collect::<Vec<_>>();
+	assert_eq!(nulls, vec![NullsOrder::Last, NullsOrder::First, NullsOrder::Default]);
+
+	let res = test_parse!(parse_stmt, r#"SELECT * FROM t ORDER BY a NULLS LAST"#).unwrap();
+	assert_eq!(res.to_string(), "SELECT * FROM t ORDER BY a NULLS LAST");
+
+	test_parse!(parse_stmt, r#"SELECT * FROM t ORDER BY a NULLS"#).unwrap_err();
+}
+
+#[test]
+fn parse_select_order_function() {
+	let res = test_parse!(
+		parse_stmt,
+		r#"SELECT *, string::similarity(name, 'aple') FROM t ORDER BY string::similarity(name, 'aple') DESC"#
+	)
+	.unwrap();
+	let Statement::Select(stmt) = &res else {
+		panic!("expected a select statement");
+	};
+	let orders = stmt.order.as_ref().unwrap();
+	assert_eq!(orders[0].order, Idiom::from("string::similarity".to_owned()));
+	assert!(!orders[0].direction);
+	// The formatted statement orders by the selected field
+	assert_eq!(
+		res.to_string(),
+		"SELECT *, string::similarity(name, 'aple') FROM t ORDER BY `string::similarity` DESC"
+	);
+	let res = test_parse!(
+		parse_stmt,
+		r#"SELECT string::similarity(name, 'aple') FROM t ORDER BY `string::similarity` DESC"#
+	)
+	.unwrap();
+	assert_eq!(
+		res.to_string(),
+		"SELECT string::similarity(name, 'aple') FROM t ORDER BY `string::similarity` DESC"
+	);
+
+	test_parse!(parse_stmt, r#"SELECT * FROM t ORDER BY string::len(name)"#).unwrap_err();
+	test_parse!(parse_stmt, r#"SELECT name FROM t ORDER BY string::len(name)"#).unwrap_err();
+}
+
+#[test]
+fn parse_select_alias_reference() {
+	let res =
+		test_parse!(parse_stmt, "SELECT amount * 1.2f AS gross, gross - amount AS tax FROM sale")
+			.unwrap();
+	let Statement::Select(stmt) = &res else {
+		panic!("expected a select statement");
+	};
+	assert_eq!(
+		stmt.expr.0[1],
+		Field::Single {
+			expr: Value::Expression(Box::new(Expression::Binary {
+				l: Value::Idiom(Idiom(vec![Part::Field(Ident("gross".to_owned()))])),
+				o: Operator::Sub,
+				r: Value::Idiom(Idiom(vec![Part::Field(Ident("amount".to_owned()))])),
+			})),
+			alias: Some(Idiom(vec![Part::Field(Ident("tax".to_owned()))])),
+		}
+	);
+	assert_eq!(res.to_string(), "SELECT amount * 1.2f AS gross, gross - amount AS tax FROM sale");
+}
+
+#[test]
+fn parse_select_order_collate_locale() {
+	let res =
+		test_parse!(parse_stmt, r#"SELECT * FROM t ORDER BY a COLLATE "de-DE" DESC, b COLLATE"#)
+			.unwrap();
+	let Statement::Select(stmt) = &res else {
+		panic!("expected a select statement");
+	};
+	let orders = stmt.order.as_ref().unwrap();
+	assert!(orders[0].collate);
+	assert_eq!(orders[0].locale, Some(Strand::from("de-DE")));
+	assert!(!orders[0].direction);
+	assert!(orders[1].collate);
+	assert_eq!(orders[1].locale, None);
+	// The locale is kept when formatted
+	assert_eq!(res.to_string(), "SELECT * FROM t ORDER BY a COLLATE 'de-DE' DESC, b COLLATE");
+
+	test_parse!(parse_stmt, r#"SELECT * FROM t ORDER BY a COLLATE "not a locale""#).unwrap_err();
+	// Locales with a tailored collation are not supported
+	test_parse!(parse_stmt, r#"SELECT * FROM t ORDER BY a COLLATE "sv-SE""#).unwrap_err();
+	test_parse!(parse_stmt, r#"SELECT * FROM t ORDER BY a COLLATE "de-u-co-phonebk""#).unwrap_err();
+}
+
+#[test]
+fn parse_select_order_random_seed() {
+	let res = test_parse!(parse_stmt, r#"SELECT * FROM t ORDER BY RAND(42) LIMIT 5"#).unwrap();
+	let Statement::Select(stmt) = &res else {
+		panic!("expected a select statement");
+	};
+	let orders = stmt.order.as_ref().unwrap();
+	assert!(orders[0].random);
+	assert_eq!(orders[0].seed, Some(42));
+	// The seed is kept when formatted
+	assert_eq!(res.to_string(), "SELECT * FROM t ORDER BY RAND(42) LIMIT 5");
+
+	let res = test_parse!(parse_stmt, r#"SELECT * FROM t ORDER BY RAND()"#).unwrap();
+	assert_eq!(res.to_string(), "SELECT * FROM t ORDER BY RAND()");
+
+	test_parse!(parse_stmt, r#"SELECT * FROM t ORDER BY RAND('a')"#).unwrap_err();
+}
+
+#[test]
+fn parse_select_fetch_depth() {
+	let res =
+		test_parse!(parse_stmt, r#"SELECT * FROM person FETCH friends DEPTH 2, author"#).unwrap();
+	let Statement::Select(stmt) = &res else {
+		panic!("expected a select statement");
+	};
+	assert_eq!(
+		stmt.fetch,
+		Some(Fetchs(vec![
+			Fetch(Idiom(vec![Part::Field(Ident("friends".to_owned()))]), Some(2)),
+			Fetch(Idiom(vec![Part::Field(Ident("author".to_owned()))]), None),
+		]))
+	);
+	// The depth is kept when formatted
+	assert_eq!(res.to_string(), "SELECT * FROM person FETCH friends DEPTH 2, author");
+
+	test_parse!(parse_stmt, r#"SELECT * FROM person FETCH friends DEPTH"#).unwrap_err();
+}
+
+#[test]
+fn parse_select_fetch_graph() {
+	let res = test_parse!(
+		parse_stmt,
+		r#"SELECT * FROM person FETCH ->purchased->product DEPTH 2, <-knows"#
+	)
+	.unwrap();
+	let Statement::Select(stmt) = &res else {
+		panic!("expected a select statement");
+	};
+	assert_eq!(
+		stmt.fetch,
+		Some(Fetchs(vec![
+			Fetch(
+				Idiom(vec![
+					Part::Graph(Graph {
+						dir: Dir::Out,
+						what: Tables(vec![Table("purchased".to_string())]),
+						expr: Fields::all(),
+						..Default::default()
+					}),
+					Part::Graph(Graph {
+						dir: Dir::Out,
+						what: Tables(vec![Table("product".to_string())]),
+						expr: Fields::all(),
+						..Default::default()
+					}),
+				]),
+				Some(2)
+			),
+			Fetch(
+				Idiom(vec![Part::Graph(Graph {
+					dir: Dir::In,
+					what: Tables(vec![Table("knows".to_string())]),
+					expr: Fields::all(),
+					..Default::default()
+				})]),
+				None
+			),
+		]))
+	);
+	assert_eq!(res.to_string(), "SELECT * FROM person FETCH ->purchased->product DEPTH 2, <-knows");
+}
+
+#[test]
+fn parse_select_group_by_percentile() {
+	let res = test_parse!(
+		parse_stmt,
+		r#"SELECT endpoint, math::percentile(latency, 95) AS p95 FROM requests GROUP BY endpoint"#
+	)
+	.unwrap();
+	let Statement::Select(stmt) = &res else {
+		panic!("expected a select statement");
+	};
+	let Field::Single {
+		expr: Value::Function(f),
+		..
+	} = &stmt.expr.0[1]
+	else {
+		panic!("expected a function field");
+	};
+	assert!(f.is_aggregate());
+	// The function is kept when formatted
+	assert_eq!(
+		res.to_string(),
+		"SELECT endpoint, math::percentile(latency, 95) AS p95 FROM requests GROUP BY endpoint"
+	);
+}
+
+#[test]
+fn parse_select_count_distinct() {
+	let res = test_parse!(
+		parse_stmt,
+		r#"SELECT country, count(DISTINCT email), count(distinct) FROM user GROUP BY country"#
+	)
+	.unwrap();
+	let Statement::Select(stmt) = &res else {
+		panic!("expected a select statement");
+	};
+	let Field::Single {
+		expr: Value::Function(f),
+		..
+	} = &stmt.expr.0[1]
+	else {
+		panic!("expected a function field");
+	};
+	assert_eq!(f.name(), Some("count::distinct"));
+	// A field which is named `distinct` is still counted as normal
+	let Field::Single {
+		expr: Value::Function(f),
+		..
+	} = &stmt.expr.0[2]
+	else {
+		panic!("expected a function field");
+	};
+	assert_eq!(f.name(), Some("count"));
+	assert_eq!(
+		res.to_string(),
+		"SELECT country, count(DISTINCT email), count(distinct) FROM user GROUP BY country"
+	);
+}
+
+#[test]
+fn parse_select_lock() {
+	let res = test_parse!(parse_stmt, r#"SELECT * FROM person:1 FOR UPDATE"#).unwrap();
+	let Statement::Select(stmt) = res else {
+		panic!("expected a select statement");
+	};
+	assert_eq!(stmt.lock, Some(LockMode::Update));
+	assert_eq!(stmt.to_string(), "SELECT * FROM person:1 FOR UPDATE");
+
+	let res =
+		test_parse!(parse_stmt, r#"SELECT * FROM person TIMEOUT 1s FOR SHARE EXPLAIN"#).unwrap();
+	assert_eq!(res.to_string(), "SELECT * FROM person TIMEOUT 1s FOR SHARE EXPLAIN");
+
+	test_parse!(parse_stmt, r#"SELECT * FROM person FOR DELETE"#).unwrap_err();
+}
+
+#[test]
+fn parse_select_explain_prefix() {
+	let res = test_parse!(parse_stmt, r#"EXPLAIN SELECT * FROM person"#).unwrap();
+	let Statement::Select(stmt) = res else {
+		panic!("expected a select statement");
+	};
+	assert_eq!(stmt.explain, Some(Explain::default()));
+	assert_eq!(stmt.to_string(), "SELECT * FROM person EXPLAIN");
+
+	let res = test_parse!(parse_stmt, r#"EXPLAIN ANALYZE SELECT * FROM person"#).unwrap();
+	let Statement::Select(stmt) = res else {
+		panic!("expected a select statement");
+	};
+	assert_eq!(stmt.explain, Some(Explain::analyze()));
+	assert_eq!(stmt.to_string(), "SELECT * FROM person EXPLAIN ANALYZE");
+
+	let res = test_parse!(parse_stmt, r#"SELECT * FROM person EXPLAIN ANALYZE"#).unwrap();
+	assert_eq!(res.to_string(), "SELECT * FROM person EXPLAIN ANALYZE");
+
+	let res = test_parse!(parse_stmt, r#"EXPLAIN FULL SELECT * FROM person"#).unwrap();
+	assert_eq!(res.to_string(), "SELECT * FROM person EXPLAIN FULL");
+
+	test_parse!(parse_stmt, r#"EXPLAIN SELECT * FROM person EXPLAIN"#).unwrap_err();
+	test_parse!(parse_stmt, r#"EXPLAIN UPDATE person"#).unwrap_err();
+}
+
+#[test]
+fn parse_select_union() {
+	let res = test_parse!(
+		parse_stmt,
+		r#"SELECT name FROM person UNION ALL SELECT name FROM animal ORDER BY name LIMIT 5"#
+	)
+	.unwrap();
+	let Statement::Union(stmt) = res else {
+		panic!("expected a union statement");
+	};
+	assert_eq!(stmt.exprs.len(), 2);
+	assert!(!stmt.distinct);
+	assert!(stmt.exprs.iter().all(|v| v.order.is_none() && v.limit.is_none()));
+	assert_eq!(
+		stmt.to_string(),
+		"SELECT name FROM person UNION ALL SELECT name FROM animal ORDER BY name LIMIT 5"
+	);
+
+	let res = test_parse!(
+		parse_stmt,
+		r#"SELECT * FROM a UNION SELECT * FROM b UNION DISTINCT SELECT * FROM c START 1"#
+	)
+	.unwrap();
+	assert_eq!(
+		res.to_string(),
+		"SELECT * FROM a UNION DISTINCT SELECT * FROM b UNION DISTINCT SELECT * FROM c START 1"
+	);
+
+	test_parse!(parse_stmt, r#"SELECT * FROM a UNION ALL SELECT * FROM b UNION SELECT * FROM c"#)
+		.unwrap_err();
+	test_parse!(parse_stmt, r#"SELECT * FROM a LIMIT 1 UNION SELECT * FROM b"#).unwrap_err();
+	test_parse!(parse_stmt, r#"SELECT * FROM a UNION UPDATE b"#).unwrap_err();
+}
+
+#[test]
+fn parse_with() {
+	let res = test_parse!(
+		parse_stmt,
+		r#"WITH adults AS (SELECT * FROM person WHERE age >= 18), names AS (SELECT name FROM adults) SELECT * FROM names"#
+	)
+	.unwrap();
+	let Statement::With(stmt) = res else {
+		panic!("expected a with statement");
+	};
+	assert!(!stmt.recursive);
+	assert_eq!(stmt.ctes.len(), 2);
+	assert_eq!(stmt.ctes[0].name, Ident("adults".to_owned()));
+	assert_eq!(stmt.ctes[1].name, Ident("names".to_owned()));
+	assert_eq!(
+		stmt.to_string(),
+		"WITH adults AS (SELECT * FROM person WHERE age >= 18), names AS (SELECT name FROM adults) SELECT * FROM names"
+	);
+
+	let res = test_parse!(parse_stmt, r#"WITH RECURSIVE a AS (SELECT * FROM b) SELECT * FROM a"#)
+		.unwrap();
+	assert_eq!(res.to_string(), "WITH RECURSIVE a AS (SELECT * FROM b) SELECT * FROM a");
+
+	test_parse!(parse_stmt, r#"WITH a AS (SELECT * FROM b)"#).unwrap_err();
+	test_parse!(parse_stmt, r#"WITH a AS (SELECT * FROM b SELECT * FROM a"#).unwrap_err();
+	test_parse!(parse_stmt, r#"WITH a AS (UPDATE b) SELECT * FROM a"#).unwrap_err();
+}
+
 #[test]
 fn parse_let() {
 	let res = test_parse!(parse_stmt, r#"LET $param = 1"#).unwrap();
@@ -968,6 +1487,27 @@ fn parse_insert() {
 	)
 }
 
+#[test]
+fn parse_insert_select() {
+	let res = test_parse!(
+		parse_stmt,
+		r#"INSERT INTO archive SELECT * FROM event WHERE ts < d"2023-01-01" ON DUPLICATE KEY IGNORE"#
+	)
+	.unwrap();
+	let Statement::Insert(stmt) = &res else {
+		panic!("expected an insert statement");
+	};
+	assert!(stmt.ignore);
+	assert_eq!(stmt.update, None);
+	assert!(matches!(stmt.data, Data::SingleExpression(Value::Subquery(_))));
+	// The statement can be parsed again once formatted
+	let again = crate::syn::parse(&res.to_string()).unwrap();
+	assert_eq!(again.to_string(), format!("{res};"));
+
+	test_parse!(parse_stmt, r#"INSERT INTO archive SELECT * FROM event ON DUPLICATE KEY"#)
+		.unwrap_err();
+}
+
 #[test]
 fn parse_kill() {
 	let res = test_parse!(parse_stmt, r#"KILL $param"#).unwrap();
@@ -1017,11 +1557,14 @@ fn parse_live() {
 	assert_eq!(
 		stmt.fetch,
 		Some(Fetchs(vec![
-			Fetch(Idiom(vec![
-				Part::Field(Ident("a".to_owned())),
-				Part::Where(Value::Idiom(Idiom(vec![Part::Field(Ident("foo".to_owned()))]))),
-			])),
-			Fetch(Idiom(vec![Part::Field(Ident("b".to_owned()))])),
+			Fetch(
+				Idiom(vec![
+					Part::Field(Ident("a".to_owned())),
+					Part::Where(Value::Idiom(Idiom(vec![Part::Field(Ident("foo".to_owned()))]))),
+				]),
+				None
+			),
+			Fetch(Idiom(vec![Part::Field(Ident("b".to_owned()))]), None),
 		])),
 	)
 }
@@ -1045,9 +1588,10 @@ fn parse_return() {
 		res,
 		Statement::Output(OutputStatement {
 			what: Value::Idiom(Idiom(vec![Part::Field(Ident("RETRUN".to_owned()))])),
-			fetch: Some(Fetchs(vec![Fetch(Idiom(vec![Part::Field(
-				Ident("RETURN".to_owned()).to_owned()
-			)]))])),
+			fetch: Some(Fetchs(vec![Fetch(
+				Idiom(vec![Part::Field(Ident("RETURN".to_owned()).to_owned())]),
+				None
+			)])),
 		}),
 	)
 }
@@ -1220,6 +1764,44 @@ fn parse_remove() {
 	);
 }
 
+#[test]
+fn parse_update_output() {
+	let res = test_parse!(parse_stmt, r#"UPDATE person SET age = 30 RETURN BEFORE"#).unwrap();
+	let Statement::Update(stmt) = &res else {
+		panic!("expected an update statement");
+	};
+	assert_eq!(stmt.output, Some(Output::Before));
+	assert_eq!(res.to_string(), "UPDATE person SET age = 30 RETURN BEFORE");
+
+	let res = test_parse!(parse_stmt, r#"UPDATE person SET age = 30 RETURN AFTER"#).unwrap();
+	let Statement::Update(stmt) = &res else {
+		panic!("expected an update statement");
+	};
+	assert_eq!(stmt.output, Some(Output::After));
+	assert_eq!(res.to_string(), "UPDATE person SET age = 30 RETURN AFTER");
+
+	let res = test_parse!(parse_stmt, r#"UPDATE person SET age = 30 RETURN DIFF"#).unwrap();
+	let Statement::Update(stmt) = &res else {
+		panic!("expected an update statement");
+	};
+	assert_eq!(stmt.output, Some(Output::Diff));
+	assert_eq!(res.to_string(), "UPDATE person SET age = 30 RETURN DIFF");
+
+	let res = test_parse!(parse_stmt, r#"UPDATE person SET age = 30 RETURN PATCH"#).unwrap();
+	let Statement::Update(stmt) = &res else {
+		panic!("expected an update statement");
+	};
+	assert_eq!(stmt.output, Some(Output::Patch));
+	assert_eq!(res.to_string(), "UPDATE person SET age = 30 RETURN PATCH");
+
+	let res = test_parse!(parse_stmt, r#"UPDATE person SET age = 30 RETURN NONE"#).unwrap();
+	let Statement::Update(stmt) = &res else {
+		panic!("expected an update statement");
+	};
+	assert_eq!(stmt.output, Some(Output::None));
+	assert_eq!(res.to_string(), "UPDATE person SET age = 30 RETURN NONE");
+}
+
 #[test]
 fn parse_update() {
 	let res = test_parse!(
@@ -1265,3 +1847,43 @@ fn parse_update() {
 		})
 	);
 }
+
+#[test]
+fn parse_upsert() {
+	let res = test_parse!(
+		parse_stmt,
+		r#"UPSERT ONLY INTO person:tobie SET visits = 1 ON DUPLICATE KEY UPDATE visits += 1 RETURN DIFF TIMEOUT 1s PARALLEL"#
+	)
+	.unwrap();
+	assert_eq!(
+		res,
+		Statement::Upsert(UpsertStatement {
+			only: true,
+			what: Values(vec![Value::Thing(Thing {
+				tb: "person".to_owned(),
+				id: Id::String("tobie".to_owned()),
+			})]),
+			data: Some(Data::SetExpression(vec![(
+				Idiom(vec![Part::Field(Ident("visits".to_owned()))]),
+				Operator::Equal,
+				Value::Number(Number::Int(1)),
+			)])),
+			update: Some(Data::UpdateExpression(vec![(
+				Idiom(vec![Part::Field(Ident("visits".to_owned()))]),
+				Operator::Inc,
+				Value::Number(Number::Int(1)),
+			)])),
+			output: Some(Output::Diff),
+			timeout: Some(Timeout(Duration(std::time::Duration::from_secs(1)))),
+			parallel: true,
+		})
+	);
+	// The statement is kept when formatted
+	assert_eq!(
+		res.to_string(),
+		"UPSERT ONLY INTO person:tobie SET visits = 1 ON DUPLICATE KEY UPDATE visits += 1 RETURN DIFF TIMEOUT 1s PARALLEL"
+	);
+
+	let res = test_parse!(parse_stmt, r#"UPSERT person:tobie CONTENT { name: 'Tobie' }"#).unwrap();
+	assert_eq!(res.to_string(), "UPSERT INTO person:tobie CONTENT { name: 'Tobie' }");
+}
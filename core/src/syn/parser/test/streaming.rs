@@ -18,10 +18,11 @@ use crate::{
 		},
 		tokenizer::Tokenizer,
 		Algorithm, Array, Base, Block, Cond, Data, Datetime, Dir, Duration, Edges, Explain,
-		Expression, Fetch, Fetchs, Field, Fields, Future, Graph, Group, Groups, Id, Ident, Idiom,
-		Idioms, Index, Kind, Limit, Number, Object, Operator, Order, Orders, Output, Param, Part,
-		Permission, Permissions, Scoring, Split, Splits, Start, Statement, Strand, Subquery, Table,
-		TableType, Tables, Thing, Timeout, Uuid, Value, Values, Version, With,
+		Expression, Fetch, Fetchs, Field, Fields, Future, Graph, Group, Groups, Having, Id, Ident,
+		Idiom, Idioms, Index, Kind, Limit, LimitKind, NullsOrder, Number, Object, Operator, Order,
+		Orders, Output, Param, Part, Permission, Permissions, Scoring, Split, Splits, Start,
+		Statement, Strand, Subquery, Table, TableType, Tables, Thing, Timeout, Uuid, Value, Values,
+		Version, With,
 	},
 	syn::parser::{Parser, PartialResult},
 };
@@ -80,6 +81,7 @@ static SOURCE: &str = r#"
 		WHERE true
 		SPLIT ON foo,bar
 		GROUP foo,bar
+		HAVING true
 		ORDER BY foo COLLATE NUMERIC ASC
 		START AT { a: true }
 		LIMIT BY a:b
@@ -438,6 +440,7 @@ fn statements() -> Vec<Statement> {
 		Statement::Info(InfoStatement::Sc(Ident("scope".to_owned()), false)),
 		Statement::Info(InfoStatement::User(Ident("user".to_owned()), Some(Base::Ns), false)),
 		Statement::Select(SelectStatement {
+			distinct: false,
 			expr: Fields(
 				vec![
 					Field::Single {
@@ -461,6 +464,7 @@ fn statements() -> Vec<Statement> {
 			omit: Some(Idioms(vec![Idiom(vec![Part::Field(Ident("bar".to_owned()))])])),
 			only: true,
 			what: Values(vec![Value::Table(Table("a".to_owned())), Value::Number(Number::Int(1))]),
+			alias: None,
 			with: Some(With::Index(vec!["index".to_owned(), "index_2".to_owned()])),
 			cond: Some(Cond(Value::Bool(true))),
 			split: Some(Splits(vec![
@@ -471,25 +475,38 @@ fn statements() -> Vec<Statement> {
 				Group(Idiom(vec![Part::Field(Ident("foo".to_owned()))])),
 				Group(Idiom(vec![Part::Field(Ident("bar".to_owned()))])),
 			])),
+			having: Some(Having(Value::Bool(true))),
 			order: Some(Orders(vec![Order {
 				order: Idiom(vec![Part::Field(Ident("foo".to_owned()))]),
 				random: false,
 				collate: true,
 				numeric: true,
 				direction: true,
+				nulls: NullsOrder::Default,
+				locale: None,
+				seed: None,
 			}])),
-			limit: Some(Limit(Value::Thing(Thing {
-				tb: "a".to_owned(),
-				id: Id::String("b".to_owned()),
-			}))),
+			sample: None,
+			limit: Some(Limit(
+				Value::Thing(Thing {
+					tb: "a".to_owned(),
+					id: Id::String("b".to_owned()),
+				}),
+				LimitKind::Rows,
+			)),
 			start: Some(Start(Value::Object(Object(
 				[("a".to_owned(), Value::Bool(true))].into_iter().collect(),
 			)))),
-			fetch: Some(Fetchs(vec![Fetch(Idiom(vec![Part::Field(Ident("foo".to_owned()))]))])),
+			after: None,
+			fetch: Some(Fetchs(vec![Fetch(
+				Idiom(vec![Part::Field(Ident("foo".to_owned()))]),
+				None,
+			)])),
 			version: Some(Version(Datetime(expected_datetime))),
 			timeout: None,
 			parallel: false,
-			explain: Some(Explain(true)),
+			lock: None,
+			explain: Some(Explain::full()),
 		}),
 		Statement::Set(SetStatement {
 			name: "param".to_owned(),
@@ -571,9 +588,10 @@ fn statements() -> Vec<Statement> {
 		}),
 		Statement::Output(OutputStatement {
 			what: Value::Idiom(Idiom(vec![Part::Field(Ident("RETRUN".to_owned()))])),
-			fetch: Some(Fetchs(vec![Fetch(Idiom(vec![Part::Field(
-				Ident("RETURN".to_owned()).to_owned(),
-			)]))])),
+			fetch: Some(Fetchs(vec![Fetch(
+				Idiom(vec![Part::Field(Ident("RETURN".to_owned()).to_owned())]),
+				None,
+			)])),
 		}),
 		Statement::Relate(RelateStatement {
 			only: true,
@@ -54,10 +54,12 @@ keyword! {
 	Default => "DEFAULT",
 	Define => "DEFINE",
 	Delete => "DELETE",
+	Depth => "DEPTH",
 	Descending => "DESCENDING",
 	Diff => "DIFF",
 	Dimension => "DIMENSION",
 	Distance => "DISTANCE",
+	Distinct => "DISTINCT",
 	DocIdsCache => "DOC_IDS_CACHE",
 	DocIdsOrder => "DOC_IDS_ORDER",
 	DocLengthsCache => "DOC_LENGTHS_CACHE",
@@ -77,12 +79,14 @@ keyword! {
 	Field => "FIELD",
 	Fields => "FIELDS",
 	Filters => "FILTERS",
+	First => "FIRST",
 	Flexible => "FLEXIBLE",
 	For => "FOR",
 	From => "FROM",
 	Full => "FULL",
 	Function => "FUNCTION",
 	Group => "GROUP",
+	Having => "HAVING",
 	Highlights => "HIGHLIGHTS",
 	Hnsw => "HNSW",
 	Ignore => "IGNORE",
@@ -96,6 +100,7 @@ keyword! {
 	Key => "KEY",
 	KeepPrunedConnections => "KEEP_PRUNED_CONNECTIONS",
 	Kill => "KILL",
+	Last => "LAST",
 	Let => "LET",
 	Limit => "LIMIT",
 	Live => "LIVE",
@@ -113,7 +118,9 @@ keyword! {
 	NoIndex => "NOINDEX",
 	None => "NONE",
 	Null => "NULL",
+	Nulls => "NULLS",
 	Numeric => "NUMERIC",
+	Offset => "OFFSET",
 	Omit => "OMIT",
 	On => "ON",
 	Only => "ONLY",
@@ -130,13 +137,19 @@ keyword! {
 	Punct => "PUNCT",
 	Readonly => "READONLY",
 	Rebuild => "REBUILD",
+	Records => "RECORDS",
+	Recursive => "RECURSIVE",
 	Relate => "RELATE",
 	Relation => "RELATION",
+	Release => "RELEASE",
 	Remove => "REMOVE",
 	Replace => "REPLACE",
 	Return => "RETURN",
 	Roles => "ROLES",
+	Rollback => "ROLLBACK",
 	Root => "ROOT",
+	Sample => "SAMPLE",
+	Savepoint => "SAVEPOINT",
 	Schemafull => "SCHEMAFULL",
 	Schemaless => "SCHEMALESS",
 	Scope => "SCOPE",
@@ -144,6 +157,7 @@ keyword! {
 	Select => "SELECT",
 	Session => "SESSION",
 	Set => "SET",
+	Share => "SHARE",
 	Show => "SHOW",
 	Signin => "SIGNIN",
 	Signup => "SIGNUP",
@@ -165,9 +179,11 @@ keyword! {
 	Transaction => "TRANSACTION",
 	True => "true",
 	Type => "TYPE",
+	Union => "UNION",
 	Unique => "UNIQUE",
 	Unset => "UNSET",
 	Update => "UPDATE",
+	Upsert => "UPSERT",
 	Uppercase => "UPPERCASE",
 	Use => "USE",
 	User => "USER",
@@ -128,6 +128,9 @@ macro_rules! t {
 	("@") => {
 		$crate::syn::token::TokenKind::At
 	};
+	("%") => {
+		$crate::syn::token::TokenKind::Percent
+	};
 	("||") => {
 		$crate::syn::token::TokenKind::Operator($crate::syn::token::Operator::Or)
 	};
@@ -333,6 +333,8 @@ pub enum TokenKind {
 	Vert,
 	/// `@`
 	At,
+	/// `%`
+	Percent,
 	/// A token which could not be properly lexed.
 	Invalid,
 	/// A token which indicates the end of the file.
@@ -430,6 +432,7 @@ impl TokenKind {
 			TokenKind::Comma => ",",
 			TokenKind::Vert => "|",
 			TokenKind::At => "@",
+			TokenKind::Percent => "%",
 			TokenKind::Invalid => "Invalid",
 			TokenKind::Eof => "Eof",
 			TokenKind::ChangeFeedInclude(_) => "change feed include",
@@ -56,6 +56,7 @@ use crate::iam::Action;
 #[cfg(feature = "ml")]
 #[cfg(not(target_arch = "wasm32"))]
 use crate::iam::ResourceKind;
+use crate::kvs::Compression;
 use crate::kvs::Datastore;
 #[cfg(feature = "ml")]
 #[cfg(not(target_arch = "wasm32"))]
@@ -466,7 +467,7 @@ async fn export(
 			}
 		}
 		_ => {
-			if let Err(error) = kvs.export(sess, chn).await?.await {
+			if let Err(error) = kvs.export(sess, Compression::None, chn).await?.await {
 				if let crate::error::Db::Channel(message) = error {
 					// This is not really an error. Just logging it for improved visibility.
 					trace!("{message}");
@@ -207,6 +207,71 @@ async fn create_with_custom_function() -> Result<(), Error> {
 	Ok(())
 }
 
+#[tokio::test]
+async fn create_with_return_output() -> Result<(), Error> {
+	let sql = "
+		CREATE person:one SET name = 'Tobie', age = 30 RETURN NONE;
+		CREATE person:two SET name = 'Jaime', age = 25 RETURN BEFORE;
+		CREATE person:three SET name = 'Lizzie', age = 20 RETURN AFTER;
+		CREATE person:four SET name = 'Mary', tags = ['a'] RETURN DIFF;
+		CREATE person:five SET name = 'John', age = 40 RETURN name, age + 1 AS next;
+		UPDATE person:four MERGE { name: 'Marie', tags: NONE, age: 35 } RETURN DIFF;
+		SELECT * FROM person:one;
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 7);
+	// Nothing is returned, though the record is still created
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[]");
+	assert_eq!(tmp, val);
+	// A created record had no previous value
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[NONE]");
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[{ age: 20, id: person:three, name: 'Lizzie' }]");
+	assert_eq!(tmp, val);
+	// The diff of a created record replaces the whole document
+	let tmp = res.remove(0).result?;
+	let val = Value::parse(
+		"[
+			[
+				{
+					op: 'replace',
+					path: '/',
+					value: { id: person:four, name: 'Mary', tags: ['a'] }
+				}
+			]
+		]",
+	);
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[{ name: 'John', next: 41 }]");
+	assert_eq!(tmp, val);
+	// The diff of a merged record is a JSON Patch
+	let tmp = res.remove(0).result?;
+	let val = Value::parse(
+		"[
+			[
+				{ op: 'remove', path: '/tags' },
+				{ op: 'add', path: '/age', value: 35 },
+				{ op: 'change', path: '/name', value: '@@ -1,4 +1,5 @@\n Mar\n-y\n+ie\n' }
+			]
+		]",
+	);
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[{ age: 30, id: person:one, name: 'Tobie' }]");
+	assert_eq!(tmp, val);
+	//
+	Ok(())
+}
+
 #[tokio::test]
 async fn create_or_insert_with_permissions() -> Result<(), Error> {
 	let sql = "
@@ -0,0 +1,76 @@
+mod parse;
+use parse::Parse;
+mod helpers;
+use helpers::new_ds;
+use helpers::skip_ok;
+use surrealdb::dbs::Session;
+use surrealdb::err::Error;
+use surrealdb::sql::Value;
+
+#[tokio::test]
+async fn select_with_cte() -> Result<(), Error> {
+	let sql = "
+		CREATE person:1 SET name = 'Tobie', age = 30;
+		CREATE person:2 SET name = 'Jaime', age = 12;
+		CREATE person:3 SET name = 'Lizzie', age = 24;
+		WITH adults AS (SELECT name, age FROM person WHERE age >= 18) SELECT name FROM adults ORDER BY name;
+		WITH adults AS (SELECT name, age FROM person WHERE age >= 18), names AS (SELECT VALUE name FROM adults) SELECT * FROM names;
+		WITH person AS (SELECT name FROM person WHERE age < 18) SELECT * FROM person;
+		WITH empty AS (SELECT * FROM person WHERE age > 100) SELECT * FROM empty;
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 7);
+	//
+	skip_ok(res, 3)?;
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse(
+		"[
+			{
+				name: 'Lizzie'
+			},
+			{
+				name: 'Tobie'
+			}
+		]",
+	);
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("['Tobie', 'Lizzie']");
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse(
+		"[
+			{
+				name: 'Jaime'
+			}
+		]",
+	);
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[]");
+	assert_eq!(tmp, val);
+	//
+	Ok(())
+}
+
+#[tokio::test]
+async fn select_with_cte_recursive() -> Result<(), Error> {
+	let sql = "
+		WITH RECURSIVE a AS (SELECT * FROM person) SELECT * FROM a;
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 1);
+	//
+	let tmp = res.remove(0).result;
+	assert!(matches!(tmp, Err(Error::Unimplemented(_))));
+	//
+	Ok(())
+}
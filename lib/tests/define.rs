@@ -231,6 +231,39 @@ async fn define_statement_table_schemaful() -> Result<(), Error> {
 	Ok(())
 }
 
+#[tokio::test]
+async fn define_statement_table_schemafull_undefined_fields() -> Result<(), Error> {
+	let sql = "
+		DEFINE TABLE strict SCHEMAFULL;
+		DEFINE FIELD name ON strict TYPE string;
+		DEFINE TABLE loose SCHEMALESS;
+		DEFINE FIELD name ON loose TYPE string;
+		CREATE strict:one SET name = 'Tobie', age = 30;
+		CREATE loose:one SET name = 'Tobie', age = 30;
+		UPDATE strict:one SET age = 31, tags = ['a'];
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 7);
+	//
+	skip_ok(res, 4)?;
+	// Fields which are not defined are removed from schemafull tables
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[{ id: strict:one, name: 'Tobie' }]");
+	assert_eq!(tmp, val);
+	// But are kept on schemaless tables
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[{ age: 30, id: loose:one, name: 'Tobie' }]");
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[{ id: strict:one, name: 'Tobie' }]");
+	assert_eq!(tmp, val);
+	//
+	Ok(())
+}
+
 #[tokio::test]
 async fn define_statement_table_foreigntable() -> Result<(), Error> {
 	let sql = "
@@ -434,6 +467,71 @@ async fn define_statement_event_when_event() -> Result<(), Error> {
 	Ok(())
 }
 
+#[tokio::test]
+async fn define_statement_event_chained_recursive_and_failing() -> Result<(), Error> {
+	let sql = "
+		DEFINE EVENT chain ON order WHEN $event = 'CREATE' THEN (
+			CREATE invoice SET order = $after.id
+		);
+		DEFINE EVENT chain ON invoice WHEN $event = 'CREATE' THEN (
+			CREATE email SET invoice = $after.id, order = $after.order
+		);
+		DEFINE EVENT bump ON counter WHEN $after.n < 5 THEN (
+			UPDATE $after.id SET n += 1
+		);
+		DEFINE EVENT loop ON spin WHEN true THEN (
+			UPDATE $after.id SET n += 1
+		);
+		DEFINE EVENT fail ON account WHEN $event = 'CREATE' THEN {
+			CREATE log:account SET account = $after.id;
+			THROW 'account rejected';
+		};
+		CREATE order:one;
+		SELECT VALUE order FROM email;
+		CREATE counter:one SET n = 0;
+		SELECT VALUE n FROM counter:one;
+		CREATE spin:one SET n = 0;
+		SELECT * FROM spin;
+		CREATE account:one;
+		SELECT * FROM account, log;
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 13);
+	//
+	skip_ok(res, 6)?;
+	// An event can trigger an event on another table
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[order:one]");
+	assert_eq!(tmp, val);
+	//
+	skip_ok(res, 1)?;
+	// An event can update the record which triggered it until its condition fails
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[5]");
+	assert_eq!(tmp, val);
+	// An event which always triggers itself is stopped by the computation depth
+	let tmp = res.remove(0).result;
+	assert!(matches!(tmp, Err(Error::ComputationDepthExceeded)), "{tmp:?}");
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[]");
+	assert_eq!(tmp, val);
+	// An event which fails part of the way through rolls back the whole statement
+	let tmp = res.remove(0).result;
+	assert!(matches!(
+		tmp.err(),
+		Some(e) if e.to_string() == "An error occurred: account rejected"
+	));
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[]");
+	assert_eq!(tmp, val);
+	//
+	Ok(())
+}
+
 #[tokio::test]
 async fn define_statement_event_check_doc_always_populated() -> Result<(), Error> {
 	let sql = "
@@ -952,6 +1050,56 @@ async fn define_statement_index_single_unique() -> Result<(), Error> {
 	Ok(())
 }
 
+#[tokio::test]
+async fn define_statement_index_unique_empty_and_concurrent() -> Result<(), Error> {
+	let sql = "
+		DEFINE INDEX email ON user FIELDS email UNIQUE;
+		CREATE user:1;
+		CREATE user:2;
+		CREATE user:3 SET email = NULL;
+		CREATE user:4 SET email = NULL;
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 5);
+	//
+	skip_ok(res, 1)?;
+	// Empty values do not conflict with each other
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[{ id: user:1 }]");
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[{ id: user:2 }]");
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[{ email: NULL, id: user:3 }]");
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[{ email: NULL, id: user:4 }]");
+	assert_eq!(tmp, val);
+	// Only one of two concurrent writes of the same value succeeds
+	let (one, two) = tokio::join!(
+		dbs.execute("CREATE user:5 SET email = 'test@surrealdb.com'", &ses, None),
+		dbs.execute("CREATE user:6 SET email = 'test@surrealdb.com'", &ses, None),
+	);
+	let one = one?.remove(0).result;
+	let two = two?.remove(0).result;
+	assert!(one.is_ok() != two.is_ok(), "{one:?} {two:?}");
+	//
+	let sql = "SELECT VALUE id FROM user WHERE email = 'test@surrealdb.com'";
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 1);
+	//
+	let tmp = res.remove(0).result?;
+	assert!(tmp == Value::parse("[user:5]") || tmp == Value::parse("[user:6]"), "{tmp}");
+	//
+	Ok(())
+}
+
 #[tokio::test]
 async fn define_statement_index_multiple_unique() -> Result<(), Error> {
 	let sql = "
@@ -2953,3 +3101,55 @@ async fn define_table_type_any() -> Result<(), Error> {
 	//
 	Ok(())
 }
+
+#[tokio::test]
+async fn define_table_permissions_with_roles() -> Result<(), Error> {
+	let sql = "
+		DEFINE TABLE post PERMISSIONS
+			FOR select, create, update, delete WHERE $session.rl CONTAINS 'viewer';
+		DEFINE TABLE note PERMISSIONS
+			FOR select, create, update, delete WHERE $session.rl CONTAINS 'owner';
+		CREATE post:1, note:1;
+	";
+	let tests = vec![
+		(Role::Owner, "post", true),
+		(Role::Owner, "note", true),
+		(Role::Editor, "post", true),
+		(Role::Editor, "note", true),
+		(Role::Viewer, "post", true),
+		(Role::Viewer, "note", false),
+	];
+	for (role, tb, allowed) in tests {
+		let dbs = new_ds().await?.with_auth_enabled(true);
+		let ses = Session::owner().with_ns("test").with_db("test");
+		let res = &mut dbs.execute(sql, &ses, None).await?;
+		skip_ok(res, 3)?;
+		// The roles of the session are available to the permissions clauses
+		let ses = Session::for_level(("test", "test").into(), role.clone())
+			.with_ns("test")
+			.with_db("test");
+		let res = &mut dbs.execute("RETURN $session.rl", &ses, None).await?;
+		let val = Value::from(vec![role.to_string().to_lowercase()]);
+		assert_eq!(res.remove(0).result?, val);
+		// Every role can view the records on its database
+		let sql = format!("SELECT * FROM {tb}");
+		let res = &mut dbs.execute(&sql, &ses, None).await?;
+		let tmp = res.remove(0).result?;
+		assert_ne!(tmp, Value::parse("[]"), "{role} should be able to select from {tb}");
+		// Editing is allowed by the role, or by the permissions clause
+		for sql in [
+			format!("CREATE {tb}:2"),
+			format!("UPDATE {tb}:1 SET edited = true"),
+			format!("DELETE {tb}:1 RETURN BEFORE"),
+		] {
+			let res = &mut dbs.execute(&sql, &ses, None).await?;
+			let tmp = res.remove(0).result?;
+			if allowed {
+				assert_ne!(tmp, Value::parse("[]"), "{role} should be able to run {sql}");
+			} else {
+				assert_eq!(tmp, Value::parse("[]"), "{role} should not be able to run {sql}");
+			}
+		}
+	}
+	Ok(())
+}
@@ -3,6 +3,7 @@ use parse::Parse;
 
 mod helpers;
 use helpers::new_ds;
+use helpers::skip_ok;
 use surrealdb::dbs::{Action, Notification, Session};
 use surrealdb::err::Error;
 use surrealdb::iam::Role;
@@ -42,6 +43,132 @@ async fn delete() -> Result<(), Error> {
 	Ok(())
 }
 
+#[tokio::test]
+async fn delete_return() -> Result<(), Error> {
+	let sql = "
+		CREATE person:1 SET name = 'Tobie';
+		CREATE person:2 SET name = 'Jaime';
+		CREATE person:3 SET name = 'Lizzie';
+		CREATE person:4 SET name = 'Tobie';
+		CREATE person:5 SET name = 'Jaime';
+		DELETE person:1 RETURN BEFORE;
+		DELETE person:2 RETURN AFTER;
+		DELETE person:3 RETURN DIFF;
+		DELETE person:4 RETURN NONE;
+		DELETE person:5 RETURN name, $before.name AS deleted;
+		DELETE person RETURN BEFORE;
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 11);
+	//
+	skip_ok(res, 5)?;
+	// The deleted values are returned as an array
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[{ id: person:1, name: 'Tobie' }]");
+	assert_eq!(tmp, val);
+	// There is no record after it has been deleted
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[NONE]");
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[[{ op: 'replace', path: '/', value: NONE }]]");
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[]");
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[{ deleted: 'Jaime', name: NONE }]");
+	assert_eq!(tmp, val);
+	// Nothing was left to be deleted
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[]");
+	assert_eq!(tmp, val);
+	//
+	Ok(())
+}
+
+#[tokio::test]
+async fn delete_where_return_before() -> Result<(), Error> {
+	let sql = "
+		DEFINE INDEX age ON person FIELDS age;
+		CREATE person:1 SET name = 'Tobie', age = -5;
+		CREATE person:2 SET name = 'Jaime', age = 30;
+		CREATE person:3 SET name = 'Lizzie', age = -1;
+		CREATE person:4 SET name = 'Lizzie', age = 0;
+		SELECT * FROM person WHERE age < 0;
+		DELETE person WHERE age < 0 RETURN BEFORE;
+		DELETE person WHERE name = 'Lizzie';
+		SELECT * FROM person;
+		SELECT * FROM person WHERE age >= 0;
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 10);
+	//
+	skip_ok(res, 5)?;
+	// The deleted records are returned as they were before being deleted
+	let before = res.remove(0).result?;
+	let tmp = res.remove(0).result?;
+	let val = Value::parse(
+		"[
+			{ id: person:1, name: 'Tobie', age: -5 },
+			{ id: person:3, name: 'Lizzie', age: -1 }
+		]",
+	);
+	assert_eq!(tmp, val);
+	assert_eq!(tmp, before);
+	// Nothing is returned by default
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[]");
+	assert_eq!(tmp, val);
+	// Only the records which did not match remain
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[{ id: person:2, name: 'Jaime', age: 30 }]");
+	assert_eq!(tmp, val);
+	// The index entries of the deleted records were removed
+	let tmp = res.remove(0).result?;
+	assert_eq!(tmp, val);
+	//
+	Ok(())
+}
+
+#[tokio::test]
+async fn delete_where_within_transaction() -> Result<(), Error> {
+	let sql = "
+		CREATE person:1 SET age = -5;
+		CREATE person:2 SET age = -1;
+		CREATE person:3 SET age = 30;
+		BEGIN;
+		DELETE person WHERE age < 0;
+		THROW 'cancelled';
+		COMMIT;
+		SELECT VALUE id FROM person;
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 6);
+	//
+	skip_ok(res, 3)?;
+	// Nothing is deleted when the transaction fails
+	let tmp = res.remove(0).result;
+	assert!(tmp.is_err());
+	let tmp = res.remove(0).result;
+	assert!(tmp.is_err());
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[person:1, person:2, person:3]");
+	assert_eq!(tmp, val);
+	//
+	Ok(())
+}
+
 //
 // Permissions
 //
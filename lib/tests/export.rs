@@ -0,0 +1,378 @@
+mod parse;
+use parse::Parse;
+mod helpers;
+use helpers::new_ds;
+use serde_json::{json, Value as Json};
+use surrealdb::dbs::Session;
+use surrealdb::err::Error;
+use surrealdb::kvs::{Compression, Datastore, ImportMode, ImportOptions};
+use surrealdb::sql::{Datetime, Value};
+
+async fn export_ndjson(tables: Vec<String>) -> Result<Vec<Json>, Error> {
+	let sql = "
+		DEFINE TABLE person SCHEMAFULL;
+		DEFINE FIELD name ON person TYPE string;
+		DEFINE INDEX name ON person FIELDS name;
+		DEFINE EVENT created ON person WHEN $event = 'CREATE' THEN {};
+		DEFINE TABLE animal;
+		CREATE person:tobie SET name = 'Tobie';
+		CREATE person:jaime SET name = 'Jaime';
+		CREATE animal:dog SET legs = 4;
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	for res in dbs.execute(sql, &ses, None).await? {
+		res.result?;
+	}
+	// Export the database
+	let (snd, rcv) = channel::unbounded();
+	dbs.export_ndjson(&ses, tables, Compression::None, snd).await?.await?;
+	// Parse every exported line
+	let mut out = Vec::new();
+	while let Ok(line) = rcv.try_recv() {
+		assert_eq!(line.last(), Some(&b'\n'));
+		assert_eq!(line.iter().filter(|c| **c == b'\n').count(), 1);
+		out.push(serde_json::from_slice(&line).unwrap());
+	}
+	Ok(out)
+}
+
+#[tokio::test]
+async fn export_ndjson_all_tables() -> Result<(), Error> {
+	let out = export_ndjson(vec![]).await?;
+	let kinds: Vec<_> = out.iter().map(|v| (v["type"].as_str(), v["table"].as_str())).collect();
+	assert_eq!(
+		kinds,
+		vec![
+			(Some("table"), Some("animal")),
+			(Some("table"), Some("person")),
+			(Some("field"), Some("person")),
+			(Some("index"), Some("person")),
+			(Some("event"), Some("person")),
+			(Some("record"), Some("animal")),
+			(Some("record"), Some("person")),
+			(Some("record"), Some("person")),
+		]
+	);
+	assert!(out[1]["sql"].as_str().unwrap().starts_with("DEFINE TABLE person TYPE ANY SCHEMAFULL"));
+	assert_eq!(out[3]["sql"], json!("DEFINE INDEX name ON person FIELDS name"));
+	assert_eq!(out[5]["data"], json!({ "id": "animal:dog", "legs": 4 }));
+	assert_eq!(out[6]["data"], json!({ "id": "person:jaime", "name": "Jaime" }));
+	assert_eq!(out[7]["data"], json!({ "id": "person:tobie", "name": "Tobie" }));
+	Ok(())
+}
+
+#[tokio::test]
+async fn export_ndjson_specified_tables() -> Result<(), Error> {
+	let out = export_ndjson(vec!["animal".to_string()]).await?;
+	assert_eq!(out.len(), 2);
+	assert_eq!(out[0]["type"], json!("table"));
+	assert_eq!(out[0]["table"], json!("animal"));
+	assert_eq!(out[1]["type"], json!("record"));
+	assert_eq!(out[1]["data"], json!({ "id": "animal:dog", "legs": 4 }));
+	Ok(())
+}
+
+async fn export_csv(sql: &str, table: &str, fields: &[&str]) -> Result<String, Error> {
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	for res in dbs.execute(sql, &ses, None).await? {
+		res.result?;
+	}
+	// Export the table
+	let (snd, rcv) = channel::unbounded();
+	let fields = fields.iter().map(|f| f.to_string()).collect();
+	dbs.export_csv(&ses, table.to_string(), fields, Compression::None, snd).await?.await?;
+	// Collect every exported row
+	let mut out = Vec::new();
+	while let Ok(row) = rcv.try_recv() {
+		out.extend(row);
+	}
+	Ok(String::from_utf8(out).unwrap())
+}
+
+#[tokio::test]
+async fn export_csv_all_fields() -> Result<(), Error> {
+	let sql = "
+		CREATE person:1 SET name = 'Tobie', address = { city: 'London', postcode: 'EC1' }, tags = ['a', 'b'];
+		CREATE person:2 SET name = 'Jaime', age = 30, address = { city: 'Zürich' };
+		CREATE person:3 SET name = NULL, nickname = 'Dave \"the rave\", Jr.';
+	";
+	let out = export_csv(sql, "person", &[]).await?;
+	assert_eq!(
+		out,
+		concat!(
+			"address.city,address.postcode,age,id,name,nickname,tags\r\n",
+			"London,EC1,,person:1,Tobie,,\"[\"\"a\"\",\"\"b\"\"]\"\r\n",
+			"Zürich,,30,person:2,Jaime,,\r\n",
+			",,,person:3,,\"Dave \"\"the rave\"\", Jr.\",\r\n",
+		)
+	);
+	Ok(())
+}
+
+#[tokio::test]
+async fn export_csv_specified_fields() -> Result<(), Error> {
+	let sql = "
+		CREATE person:1 SET name = '東京\nTokyo', address = { city: 'London', geo: { lat: 51.5 } };
+		CREATE person:2 SET name = 'Jaime';
+	";
+	let out = export_csv(sql, "person", &["name", "address", "missing"]).await?;
+	assert_eq!(
+		out,
+		concat!(
+			"name,address.city,address.geo.lat,missing\r\n",
+			"\"東京\nTokyo\",London,51.5,\r\n",
+			"Jaime,,,\r\n",
+		)
+	);
+	// A table without records only has a header row
+	let out = export_csv(sql, "animal", &["name"]).await?;
+	assert_eq!(out, "name\r\n");
+	Ok(())
+}
+
+async fn export_compressed_datastore() -> Result<(Datastore, Session), Error> {
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let sql: String = (0..100)
+		.map(|i| format!("CREATE person:{i} SET name = 'Person {i}', tags = ['one', 'two'];"))
+		.collect();
+	for res in dbs.execute(&sql, &ses, None).await? {
+		res.result?;
+	}
+	Ok((dbs, ses))
+}
+
+fn collect(rcv: channel::Receiver<Vec<u8>>) -> Vec<u8> {
+	let mut out = Vec::new();
+	while let Ok(chunk) = rcv.try_recv() {
+		out.extend(chunk);
+	}
+	out
+}
+
+#[tokio::test]
+async fn export_ndjson_compressed() -> Result<(), Error> {
+	let (dbs, ses) = export_compressed_datastore().await?;
+	let (snd, rcv) = channel::unbounded();
+	dbs.export_ndjson(&ses, vec![], Compression::None, snd).await?.await?;
+	let plain = collect(rcv);
+	let res = &mut dbs.execute("SELECT * FROM person", &ses, None).await?;
+	let val = res.remove(0).result?;
+	for compression in [Compression::Gzip, Compression::Zstd] {
+		let (snd, rcv) = channel::unbounded();
+		dbs.export_ndjson(&ses, vec![], compression, snd).await?.await?;
+		let out = collect(rcv);
+		assert_eq!(Compression::detect(&out), compression);
+		assert!(out.len() < plain.len(), "{compression:?}: {} >= {}", out.len(), plain.len());
+		// The compression is detected when importing
+		let tmp = new_ds().await?;
+		let stats = tmp.import_ndjson(&out, &ses, ImportOptions::default()).await?;
+		assert_eq!(stats.written, 100);
+		let res = &mut tmp.execute("SELECT * FROM person", &ses, None).await?;
+		assert_eq!(res.remove(0).result?, val);
+	}
+	Ok(())
+}
+
+#[tokio::test]
+async fn export_sql_compressed() -> Result<(), Error> {
+	let (dbs, ses) = export_compressed_datastore().await?;
+	let (snd, rcv) = channel::unbounded();
+	dbs.export(&ses, Compression::None, snd).await?.await?;
+	let plain = collect(rcv);
+	let res = &mut dbs.execute("SELECT * FROM person", &ses, None).await?;
+	let val = res.remove(0).result?;
+	for compression in [Compression::Gzip, Compression::Zstd] {
+		let (snd, rcv) = channel::unbounded();
+		dbs.export(&ses, compression, snd).await?.await?;
+		let out = collect(rcv);
+		assert_eq!(Compression::detect(&out), compression);
+		assert!(out.len() < plain.len(), "{compression:?}: {} >= {}", out.len(), plain.len());
+		// The compression is detected when importing
+		let tmp = new_ds().await?;
+		for res in tmp.import(&out, &ses).await? {
+			res.result?;
+		}
+		let res = &mut tmp.execute("SELECT * FROM person", &ses, None).await?;
+		assert_eq!(res.remove(0).result?, val);
+	}
+	// Uncompressed exports are still imported as they are
+	let tmp = new_ds().await?;
+	for res in tmp.import(&plain, &ses).await? {
+		res.result?;
+	}
+	let res = &mut tmp.execute("SELECT * FROM person", &ses, None).await?;
+	assert_eq!(res.remove(0).result?, val);
+	Ok(())
+}
+
+#[tokio::test]
+async fn export_incremental_since() -> Result<(), Error> {
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let sql = "
+		DEFINE TABLE person CHANGEFEED 1h;
+		CREATE person:1 SET name = 'Tobie';
+		CREATE person:2 SET name = 'Jaime';
+		CREATE person:3 SET name = 'Lizzie';
+	";
+	for res in dbs.execute(sql, &ses, None).await? {
+		res.result?;
+	}
+	// Take a full export before the incremental changes
+	dbs.tick_at(1690848000).await?;
+	let (snd, rcv) = channel::unbounded();
+	dbs.export_ndjson(&ses, vec![], Compression::None, snd).await?.await?;
+	let full = collect(rcv);
+	//
+	let sql = "
+		UPDATE person:1 SET name = 'Tobie Morgan';
+		DELETE person:2;
+		CREATE person:4 SET name = 'Marcus';
+	";
+	for res in dbs.execute(sql, &ses, None).await? {
+		res.result?;
+	}
+	dbs.tick_at(1690848005).await?;
+	// Only the changes since the full export are exported
+	let since: Datetime = "2023-08-01T00:00:00Z".parse().unwrap();
+	let (snd, rcv) = channel::unbounded();
+	dbs.export_incremental(&ses, since, Compression::None, snd).await?.await?;
+	let incremental = collect(rcv);
+	let out: Vec<Json> = serde_json::Deserializer::from_slice(&incremental)
+		.into_iter()
+		.collect::<Result<_, _>>()
+		.unwrap();
+	assert_eq!(
+		out,
+		vec![
+			json!({
+				"type": "record",
+				"table": "person",
+				"data": { "id": "person:1", "name": "Tobie Morgan" }
+			}),
+			json!({ "type": "delete", "table": "person", "id": "person:2" }),
+			json!({
+				"type": "record",
+				"table": "person",
+				"data": { "id": "person:4", "name": "Marcus" }
+			}),
+		]
+	);
+	// Applying the incremental export to the full export mirrors the database
+	let tmp = new_ds().await?;
+	tmp.import_ndjson(&full, &ses, ImportOptions::default()).await?;
+	let opts = ImportOptions::default().with_mode(ImportMode::Replace);
+	let stats = tmp.import_ndjson(&incremental, &ses, opts).await?;
+	assert_eq!(stats.written, 3);
+	let res = &mut dbs.execute("SELECT * FROM person", &ses, None).await?;
+	let val = res.remove(0).result?;
+	let res = &mut tmp.execute("SELECT * FROM person", &ses, None).await?;
+	assert_eq!(res.remove(0).result?, val);
+	// Nothing has changed since the latest changes
+	let since: Datetime = "2023-08-01T00:00:05Z".parse().unwrap();
+	let (snd, rcv) = channel::unbounded();
+	dbs.export_incremental(&ses, since, Compression::None, snd).await?.await?;
+	assert!(collect(rcv).is_empty());
+	Ok(())
+}
+
+#[tokio::test]
+async fn export_incremental_missing_changes() -> Result<(), Error> {
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let sql = "
+		DEFINE TABLE person CHANGEFEED 1h;
+		DEFINE TABLE log;
+	";
+	for res in dbs.execute(sql, &ses, None).await? {
+		res.result?;
+	}
+	dbs.tick_at(1690848000).await?;
+	let sql = "
+		CREATE person:1 SET name = 'Tobie';
+		CREATE log:1 SET message = 'Created';
+	";
+	for res in dbs.execute(sql, &ses, None).await? {
+		res.result?;
+	}
+	dbs.tick_at(1690848005).await?;
+	// Tables without a change feed are marked as skipped
+	let since: Datetime = "2023-08-01T00:00:00Z".parse().unwrap();
+	let (snd, rcv) = channel::unbounded();
+	dbs.export_incremental(&ses, since.clone(), Compression::None, snd).await?.await?;
+	let out: Vec<Json> = serde_json::Deserializer::from_slice(&collect(rcv))
+		.into_iter()
+		.collect::<Result<_, _>>()
+		.unwrap();
+	assert_eq!(
+		out,
+		vec![
+			json!({ "type": "skipped", "table": "log" }),
+			json!({
+				"type": "record",
+				"table": "person",
+				"data": { "id": "person:1", "name": "Tobie" }
+			}),
+		]
+	);
+	// Changes older than the change feed retention can not be exported
+	dbs.tick_at(1690848000 + 7200).await?;
+	let (snd, _rcv) = channel::unbounded();
+	let res = dbs.export_incremental(&ses, since, Compression::None, snd).await?.await;
+	assert!(matches!(res, Err(Error::ExportChangesExpired { .. })), "Unexpected result: {:?}", res);
+	Ok(())
+}
+
+async fn export_paged(
+	dbs: &Datastore,
+	ses: &Session,
+	start: &str,
+	limit: &str,
+) -> Result<Vec<Json>, Error> {
+	let (snd, rcv) = channel::unbounded();
+	let (start, limit) = (Value::parse(start), Value::parse(limit));
+	dbs.export_paged(ses, "person".to_string(), start, limit, Compression::None, snd)
+		.await?
+		.await?;
+	Ok(serde_json::Deserializer::from_slice(&collect(rcv))
+		.into_iter()
+		.collect::<Result<_, _>>()
+		.unwrap())
+}
+
+#[tokio::test]
+async fn export_paged_cursor() -> Result<(), Error> {
+	let (dbs, ses) = export_compressed_datastore().await?;
+	let res = &mut dbs.execute("SELECT VALUE id FROM person", &ses, None).await?;
+	let ids: Vec<Json> = match res.remove(0).result? {
+		Value::Array(v) => v.into_iter().map(|v| json!(v.to_string())).collect(),
+		v => panic!("Unexpected result: {v}"),
+	};
+	// Each batch is computed from the $cursor param
+	let out = export_paged(&dbs, &ses, "$cursor * 30", "30").await?;
+	assert_eq!(out.iter().map(|v| v["data"]["id"].clone()).collect::<Vec<_>>(), ids);
+	assert!(out.iter().all(|v| v["type"] == json!("record")));
+	// The export stops when a batch does not move forward
+	let out = export_paged(&dbs, &ses, "90", "$cursor + 5").await?;
+	assert_eq!(out.iter().map(|v| v["data"]["id"].clone()).collect::<Vec<_>>(), ids[90..95]);
+	// Batches can skip over records
+	let out = export_paged(&dbs, &ses, "$cursor * 50", "math::floor(10.5)").await?;
+	assert_eq!(out.len(), 20);
+	assert_eq!(out[10]["data"]["id"], ids[50]);
+	Ok(())
+}
+
+#[tokio::test]
+async fn export_paged_overflow() -> Result<(), Error> {
+	let (dbs, ses) = export_compressed_datastore().await?;
+	// A computed START which is too large for the platform is an error
+	let res = export_paged(&dbs, &ses, "$cursor * 1.0e30", "10").await;
+	assert!(matches!(res, Err(Error::StartOverflow { .. })), "Unexpected result: {:?}", res);
+	// As is a computed LIMIT which is too large for the platform
+	let res = export_paged(&dbs, &ses, "0", "($cursor + 1) * 1.0e30").await;
+	assert!(matches!(res, Err(Error::LimitOverflow { .. })), "Unexpected result: {:?}", res);
+	Ok(())
+}
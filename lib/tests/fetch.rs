@@ -2,6 +2,7 @@ mod parse;
 use parse::Parse;
 mod helpers;
 use helpers::new_ds;
+use helpers::skip_ok;
 use surrealdb::dbs::Session;
 use surrealdb::err::Error;
 use surrealdb::sql::Value;
@@ -279,3 +280,193 @@ async fn create_relate_select() -> Result<(), Error> {
 	//
 	Ok(())
 }
+
+#[tokio::test]
+async fn select_fetch_depth() -> Result<(), Error> {
+	let sql = "
+		CREATE person:a SET name = 'A', friends = [person:b];
+		CREATE person:b SET name = 'B', friends = [person:c];
+		CREATE person:c SET name = 'C', friends = [person:d];
+		CREATE person:d SET name = 'D', friends = [];
+		SELECT * FROM person:a FETCH friends;
+		SELECT * FROM person:a FETCH friends DEPTH 2;
+		SELECT * FROM person:a FETCH friends DEPTH 0;
+		RETURN person:a.* FETCH friends DEPTH 3;
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 8);
+	//
+	skip_ok(res, 4)?;
+	// Without a depth a single level of records is fetched
+	let tmp = res.remove(0).result?;
+	let val = Value::parse(
+		"[
+			{
+				id: person:a,
+				name: 'A',
+				friends: [{ id: person:b, name: 'B', friends: [person:c] }]
+			}
+		]",
+	);
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse(
+		"[
+			{
+				id: person:a,
+				name: 'A',
+				friends: [
+					{
+						id: person:b,
+						name: 'B',
+						friends: [{ id: person:c, name: 'C', friends: [person:d] }]
+					}
+				]
+			}
+		]",
+	);
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse(
+		"[
+			{
+				id: person:a,
+				name: 'A',
+				friends: [person:b]
+			}
+		]",
+	);
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse(
+		"{
+			id: person:a,
+			name: 'A',
+			friends: [
+				{
+					id: person:b,
+					name: 'B',
+					friends: [
+						{
+							id: person:c,
+							name: 'C',
+							friends: [{ id: person:d, name: 'D', friends: [] }]
+						}
+					]
+				}
+			]
+		}",
+	);
+	assert_eq!(tmp, val);
+	//
+	Ok(())
+}
+
+#[tokio::test]
+async fn select_fetch_depth_cyclic() -> Result<(), Error> {
+	let sql = "
+		CREATE person:a SET name = 'A', friends = [person:b];
+		CREATE person:b SET name = 'B', friends = [person:a, person:c];
+		CREATE person:c SET name = 'C', friends = [person:b];
+		SELECT * FROM person:a FETCH friends DEPTH 10;
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 4);
+	//
+	skip_ok(res, 3)?;
+	// Records which have already been fetched on the way are kept as links
+	let tmp = res.remove(0).result?;
+	let val = Value::parse(
+		"[
+			{
+				id: person:a,
+				name: 'A',
+				friends: [
+					{
+						id: person:b,
+						name: 'B',
+						friends: [
+							person:a,
+							{ id: person:c, name: 'C', friends: [person:b] }
+						]
+					}
+				]
+			}
+		]",
+	);
+	assert_eq!(tmp, val);
+	//
+	Ok(())
+}
+
+#[tokio::test]
+async fn select_fetch_graph_edges() -> Result<(), Error> {
+	let sql = "
+		CREATE person:tobie SET name = 'Tobie';
+		CREATE person:jaime SET name = 'Jaime';
+		CREATE product:phone SET name = 'Phone';
+		RELATE person:tobie->purchased->product:phone SET id = purchased:one;
+		SELECT * FROM person FETCH ->purchased->product;
+		SELECT name, ->purchased->product AS products FROM person:tobie FETCH products;
+		SELECT * FROM product FETCH <-purchased<-person;
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 7);
+	//
+	skip_ok(res, 4)?;
+	// Records without matching edges fetch an empty array
+	let tmp = res.remove(0).result?;
+	let val = Value::parse(
+		"[
+			{
+				id: person:jaime,
+				name: 'Jaime',
+				'->purchased': { '->product': [] }
+			},
+			{
+				id: person:tobie,
+				name: 'Tobie',
+				'->purchased': {
+					'->product': [{ id: product:phone, name: 'Phone' }]
+				}
+			}
+		]",
+	);
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse(
+		"[
+			{
+				name: 'Tobie',
+				products: [{ id: product:phone, name: 'Phone' }]
+			}
+		]",
+	);
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse(
+		"[
+			{
+				id: product:phone,
+				name: 'Phone',
+				'<-purchased': {
+					'<-person': [{ id: person:tobie, name: 'Tobie' }]
+				}
+			}
+		]",
+	);
+	assert_eq!(tmp, val);
+	//
+	Ok(())
+}
@@ -2,6 +2,7 @@ mod parse;
 use parse::Parse;
 mod helpers;
 use helpers::new_ds;
+use helpers::skip_ok;
 use helpers::with_enough_stack;
 use surrealdb::dbs::Session;
 use surrealdb::err::Error;
@@ -95,6 +96,66 @@ async fn field_definition_value_assert_failure() -> Result<(), Error> {
 	Ok(())
 }
 
+#[tokio::test]
+async fn field_definition_assert_multiple_fields() -> Result<(), Error> {
+	let sql = "
+		DEFINE FIELD name ON person ASSERT $value != NONE;
+		DEFINE FIELD age ON person ASSERT $value >= 0;
+		DEFINE FIELD bio ON person ASSERT $value = NONE OR string::len($value) < 10;
+		CREATE person:one SET name = 'Tobie', age = 0, bio = 'Developer';
+		CREATE person:two SET name = 'Jaime', age = 30;
+		CREATE person:three SET age = 30;
+		CREATE person:four SET name = 'Lizzie', age = -1;
+		CREATE person:five SET name = 'Mary', age = 30, bio = 'Far too long a bio';
+		CREATE person:six SET age = -1, bio = 'Far too long a bio';
+		SELECT VALUE id FROM person;
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 10);
+	//
+	skip_ok(res, 3)?;
+	// Values passing every assertion are stored
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[{ age: 0, bio: 'Developer', id: person:one, name: 'Tobie' }]");
+	assert_eq!(tmp, val);
+	// Assertions on absent fields are checked against NONE
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[{ age: 30, id: person:two, name: 'Jaime' }]");
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result;
+	assert!(matches!(
+		tmp.err(),
+		Some(e) if e.to_string() == "Found NONE for field `name`, with record `person:three`, but field must conform to: $value != NONE"
+	));
+	//
+	let tmp = res.remove(0).result;
+	assert!(matches!(
+		tmp.err(),
+		Some(e) if e.to_string() == "Found -1 for field `age`, with record `person:four`, but field must conform to: $value >= 0"
+	));
+	//
+	let tmp = res.remove(0).result;
+	assert!(matches!(
+		tmp.err(),
+		Some(e) if e.to_string() == "Found 'Far too long a bio' for field `bio`, with record `person:five`, but field must conform to: $value = NONE OR string::len($value) < 10"
+	));
+	// The first failing assertion is reported
+	let tmp = res.remove(0).result;
+	assert!(matches!(
+		tmp.err(),
+		Some(e) if e.to_string() == "Found -1 for field `age`, with record `person:six`, but field must conform to: $value >= 0"
+	));
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[person:one, person:two]");
+	assert_eq!(tmp, val);
+	//
+	Ok(())
+}
+
 #[tokio::test]
 async fn field_definition_value_assert_success() -> Result<(), Error> {
 	let sql = "
@@ -137,6 +198,155 @@ async fn field_definition_value_assert_success() -> Result<(), Error> {
 	Ok(())
 }
 
+#[tokio::test]
+async fn field_definition_value_assert_nested_failure() -> Result<(), Error> {
+	let sql = "
+		DEFINE TABLE person SCHEMAFULL;
+		DEFINE FIELD address ON person TYPE object;
+		DEFINE FIELD address.zip ON person TYPE string ASSERT string::len($value) = 5;
+		CREATE person:test SET address.zip = '123';
+		UPDATE person:test SET address.zip = '123';
+		CREATE person:test SET address.zip = '12345';
+		UPDATE person:test SET address.zip = '1234567';
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 7);
+	//
+	let tmp = res.remove(0).result;
+	assert!(tmp.is_ok());
+	//
+	let tmp = res.remove(0).result;
+	assert!(tmp.is_ok());
+	//
+	let tmp = res.remove(0).result;
+	assert!(tmp.is_ok());
+	//
+	let tmp = res.remove(0).result;
+	assert!(
+		matches!(
+			&tmp,
+			Err(e) if e.to_string() == "Found '123' for field `address.zip`, with record `person:test`, but field must conform to: string::len($value) = 5"
+		),
+		"{}",
+		tmp.unwrap_err().to_string()
+	);
+	//
+	let tmp = res.remove(0).result;
+	assert!(
+		matches!(
+			&tmp,
+			Err(e) if e.to_string() == "Found '123' for field `address.zip`, with record `person:test`, but field must conform to: string::len($value) = 5"
+		),
+		"{}",
+		tmp.unwrap_err().to_string()
+	);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse(
+		"[
+			{
+				address: {
+					zip: '12345',
+				},
+				id: person:test,
+			}
+		]",
+	);
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result;
+	assert!(
+		matches!(
+			&tmp,
+			Err(e) if e.to_string() == "Found '1234567' for field `address.zip`, with record `person:test`, but field must conform to: string::len($value) = 5"
+		),
+		"{}",
+		tmp.unwrap_err().to_string()
+	);
+	//
+	Ok(())
+}
+
+#[tokio::test]
+async fn field_definition_schemafull_removes_undefined() -> Result<(), Error> {
+	let sql = "
+		DEFINE TABLE person SCHEMAFULL;
+		DEFINE FIELD name ON person TYPE string;
+		DEFINE FIELD address ON person TYPE object;
+		DEFINE FIELD address.city ON person TYPE string;
+		DEFINE FIELD metadata ON person TYPE object;
+		DEFINE FIELD metadata.* ON person TYPE string;
+		DEFINE TABLE animal SCHEMALESS;
+		CREATE person:test CONTENT {
+			name: 'Tobie',
+			age: 30,
+			address: { city: 'London', zip: 'N1' },
+			metadata: { source: 'import', owner: 'admin' },
+		};
+		UPDATE person:test SET other = true, address.street = 'Main St', metadata.region = 'eu';
+		CREATE animal:test SET name = 'Fluffy', legs = 4;
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 10);
+	//
+	skip_ok(res, 7)?;
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse(
+		"[
+			{
+				address: {
+					city: 'London',
+				},
+				id: person:test,
+				metadata: {
+					owner: 'admin',
+					source: 'import',
+				},
+				name: 'Tobie',
+			}
+		]",
+	);
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse(
+		"[
+			{
+				address: {
+					city: 'London',
+				},
+				id: person:test,
+				metadata: {
+					owner: 'admin',
+					region: 'eu',
+					source: 'import',
+				},
+				name: 'Tobie',
+			}
+		]",
+	);
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse(
+		"[
+			{
+				id: animal:test,
+				legs: 4,
+				name: 'Fluffy',
+			}
+		]",
+	);
+	assert_eq!(tmp, val);
+	//
+	Ok(())
+}
+
 #[tokio::test]
 async fn field_definition_empty_nested_objects() -> Result<(), Error> {
 	let sql = "
@@ -450,6 +660,97 @@ async fn field_selection_variable_fields_projection() -> Result<(), Error> {
 	Ok(())
 }
 
+#[tokio::test]
+async fn field_selection_alias_reference() -> Result<(), Error> {
+	let sql = "
+		CREATE sale:one SET amount = 10;
+		SELECT amount * 1.5 AS gross, gross - amount AS tax, tax * 2 AS double FROM sale;
+		SELECT amount * 1.5 AS totals.gross, totals.gross - amount AS totals.tax FROM sale;
+		SELECT VALUE amount * 1.5 FROM sale;
+		SELECT *, amount * 2 AS amount, amount + 1 AS next FROM sale;
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 5);
+	//
+	let tmp = res.remove(0).result;
+	assert!(tmp.is_ok());
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[{ gross: 15.0, tax: 5.0, double: 10.0 }]");
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[{ totals: { gross: 15.0, tax: 5.0 } }]");
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[15.0]");
+	assert_eq!(tmp, val);
+	//
+	// Fields of the document are read before any alias of the same name
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[{ id: sale:one, amount: 20, next: 11 }]");
+	assert_eq!(tmp, val);
+	//
+	Ok(())
+}
+
+#[tokio::test]
+async fn field_selection_alias_forward_reference() -> Result<(), Error> {
+	let sql = "
+		CREATE sale:one SET amount = 10;
+		SELECT gross - amount AS tax, amount * 1.5 AS gross FROM sale;
+		SELECT b AS a, a AS b FROM sale;
+		SELECT amount + 1 AS amount FROM sale;
+		CREATE person:one SET name = 'tobie', a = 1, b = 2, address = { city: 'London' };
+		SELECT string::uppercase(name) AS name, name AS original FROM person;
+		SELECT b AS a, a AS b FROM person;
+		SELECT address, address.city AS address.city FROM person;
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 8);
+	//
+	let tmp = res.remove(0).result;
+	assert!(tmp.is_ok());
+	//
+	let tmp = res.remove(0).result;
+	assert!(matches!(
+		tmp.err(),
+		Some(e) if e.to_string() == "Found 'gross - amount AS tax' in SELECT clause, but it references the alias `gross` before it is defined"
+	));
+	//
+	let tmp = res.remove(0).result;
+	assert!(matches!(
+		tmp.err(),
+		Some(e) if e.to_string() == "Found 'b AS a' in SELECT clause, but it references the alias `b` before it is defined"
+	));
+	// A field can reference the source field which it replaces
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[{ amount: 11 }]");
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result;
+	assert!(tmp.is_ok());
+	// Fields of the document are read before any alias of the same name
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[{ name: 'TOBIE', original: 'tobie' }]");
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[{ a: 2, b: 1 }]");
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[{ address: { city: 'London' } }]");
+	assert_eq!(tmp, val);
+	//
+	Ok(())
+}
+
 #[tokio::test]
 async fn field_definition_default_value() -> Result<(), Error> {
 	let sql = "
@@ -577,6 +878,210 @@ async fn field_definition_default_value() -> Result<(), Error> {
 	Ok(())
 }
 
+#[tokio::test]
+async fn field_definition_default_expressions() -> Result<(), Error> {
+	let sql = "
+		DEFINE FIELD status ON task DEFAULT 'open';
+		DEFINE FIELD created ON task DEFAULT time::now();
+		DEFINE FIELD settings.theme ON task DEFAULT 'dark';
+		CREATE task:one SET created = d'2020-01-01';
+		CREATE task:two SET status = 'closed', settings.theme = 'light';
+		CREATE task:three SET status = NONE;
+		UPDATE task:one SET status = NONE;
+		SELECT VALUE created > d'2020-01-01' FROM task;
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 8);
+	//
+	skip_ok(res, 3)?;
+	// Static and nested defaults are applied to new records
+	let tmp = res.remove(0).result?;
+	let val = Value::parse(
+		"[
+			{
+				created: d'2020-01-01T00:00:00Z',
+				id: task:one,
+				settings: { theme: 'dark' },
+				status: 'open',
+			}
+		]",
+	);
+	assert_eq!(tmp, val);
+	// Explicit values are kept
+	let tmp = res.remove(0).result?.pick(&["settings".into()]);
+	let val = Value::parse("[{ theme: 'light' }]");
+	assert_eq!(tmp, val);
+	// Setting a field to NONE leaves it empty, so the default is applied
+	let tmp = res.remove(0).result?.pick(&["status".into()]);
+	let val = Value::parse("['open']");
+	assert_eq!(tmp, val);
+	// Defaults are not applied to existing records
+	let tmp = res.remove(0).result?;
+	let val = Value::parse(
+		"[
+			{
+				created: d'2020-01-01T00:00:00Z',
+				id: task:one,
+				settings: { theme: 'dark' },
+			}
+		]",
+	);
+	assert_eq!(tmp, val);
+	// Default expressions are computed when each record is created
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[false, true, true]");
+	assert_eq!(tmp, val);
+	//
+	Ok(())
+}
+
+#[tokio::test]
+async fn field_definition_default_document_context() -> Result<(), Error> {
+	let sql = "
+		DEFINE FIELD name ON person DEFAULT string::concat(first, ' ', last);
+		CREATE person:one SET first = 'Tobie', last = 'Morgan';
+		CREATE person:two SET first = 'Jaime', last = 'Hitchcock', name = 'Jaime';
+		UPDATE person:one SET first = 'Tobias';
+		UPDATE person:two CONTENT { first: 'Jaime', last: 'Hitchcock' };
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 5);
+	//
+	skip_ok(res, 1)?;
+	// Defaults are computed using the fields of the new record
+	let tmp = res.remove(0).result?.pick(&["name".into()]);
+	let val = Value::parse("['Tobie Morgan']");
+	assert_eq!(tmp, val);
+	// Provided values are never overwritten
+	let tmp = res.remove(0).result?.pick(&["name".into()]);
+	let val = Value::parse("['Jaime']");
+	assert_eq!(tmp, val);
+	// Defaults are not recomputed when a record is updated
+	let tmp = res.remove(0).result?.pick(&["name".into()]);
+	let val = Value::parse("['Tobie Morgan']");
+	assert_eq!(tmp, val);
+	// Defaults do not fill fields removed from an existing record
+	let tmp = res.remove(0).result?.pick(&["name".into()]);
+	let val = Value::parse("[NONE]");
+	assert_eq!(tmp, val);
+	//
+	Ok(())
+}
+
+#[tokio::test]
+async fn field_definition_value_normalise() -> Result<(), Error> {
+	let sql = "
+		DEFINE TABLE user SCHEMAFULL;
+		DEFINE FIELD email ON user TYPE string VALUE string::lowercase($value);
+		DEFINE FIELD secret ON user VALUE NONE;
+		CREATE user:test SET email = 'Info@SurrealDB.com', secret = 'hidden';
+		UPDATE user:test SET email = 'HELLO@SurrealDB.com', secret = 'hidden';
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 5);
+	//
+	let tmp = res.remove(0).result;
+	assert!(tmp.is_ok());
+	//
+	let tmp = res.remove(0).result;
+	assert!(tmp.is_ok());
+	//
+	let tmp = res.remove(0).result;
+	assert!(tmp.is_ok());
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse(
+		"[
+			{
+				email: 'info@surrealdb.com',
+				id: user:test,
+			}
+		]",
+	);
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse(
+		"[
+			{
+				email: 'hello@surrealdb.com',
+				id: user:test,
+			}
+		]",
+	);
+	assert_eq!(tmp, val);
+	//
+	Ok(())
+}
+
+#[tokio::test]
+async fn field_definition_value_computed_from_sibling_fields() -> Result<(), Error> {
+	let sql = "
+		DEFINE TABLE person SCHEMAFULL;
+		DEFINE FIELD first_name ON person TYPE string;
+		DEFINE FIELD last_name ON person TYPE string;
+		DEFINE FIELD full_name ON person VALUE string::concat(first_name, ' ', last_name);
+		DEFINE FIELD nickname ON person VALUE IF $value THEN string::uppercase($value) END;
+		CREATE person:test SET first_name = 'Tobie', last_name = 'Morgan', full_name = 'Someone';
+		UPDATE person:test SET last_name = 'Hitchcock', nickname = 'tobes';
+		UPDATE person:test SET nickname = NONE;
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 8);
+	//
+	skip_ok(res, 5)?;
+	// The computed value overwrites the user input
+	let tmp = res.remove(0).result?;
+	let val = Value::parse(
+		"[
+			{
+				first_name: 'Tobie',
+				full_name: 'Tobie Morgan',
+				id: person:test,
+				last_name: 'Morgan',
+			}
+		]",
+	);
+	assert_eq!(tmp, val);
+	// And is kept in sync with the fields it depends on
+	let tmp = res.remove(0).result?;
+	let val = Value::parse(
+		"[
+			{
+				first_name: 'Tobie',
+				full_name: 'Tobie Hitchcock',
+				id: person:test,
+				last_name: 'Hitchcock',
+				nickname: 'TOBES',
+			}
+		]",
+	);
+	assert_eq!(tmp, val);
+	// A computed value of NONE removes the field
+	let tmp = res.remove(0).result?;
+	let val = Value::parse(
+		"[
+			{
+				first_name: 'Tobie',
+				full_name: 'Tobie Hitchcock',
+				id: person:test,
+				last_name: 'Hitchcock',
+			}
+		]",
+	);
+	assert_eq!(tmp, val);
+	//
+	Ok(())
+}
+
 #[tokio::test]
 async fn field_definition_value_reference() -> Result<(), Error> {
 	let sql = "
@@ -865,6 +1370,58 @@ async fn field_definition_edge_permissions() -> Result<(), Error> {
 	Ok(())
 }
 
+#[tokio::test]
+async fn field_definition_write_permissions() -> Result<(), Error> {
+	let sql = "
+		DEFINE TABLE person SCHEMALESS PERMISSIONS FULL;
+		DEFINE FIELD role ON person PERMISSIONS FOR select FULL, FOR create, update NONE;
+		DEFINE FIELD name ON person PERMISSIONS FOR select, create FULL, FOR update WHERE owner = $auth.id;
+		CREATE user:one;
+		CREATE person:one SET name = 'Tobie', role = 'admin', owner = user:one;
+		CREATE person:two SET name = 'Jaime', role = 'admin', owner = user:two;
+	";
+	let dbs = new_ds().await?.with_auth_enabled(true);
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 6);
+	//
+	skip_ok(res, 6)?;
+	//
+	let sql = "
+		CREATE person:three SET name = 'Jamie', role = 'admin', owner = user:one;
+		UPDATE person:one SET name = 'Changed', role = 'user';
+		UPDATE person:two SET name = 'Changed', role = 'user';
+	";
+	let ses = Session::for_scope("test", "test", "test", Thing::from(("user", "one")).into());
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 3);
+	// Fields which can not be created are skipped
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[{ id: person:three, name: 'Jamie', owner: user:one }]");
+	assert_eq!(tmp, val);
+	// Fields which can not be updated keep their previous value
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[{ id: person:one, name: 'Changed', owner: user:one, role: 'admin' }]");
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[{ id: person:two, name: 'Jaime', owner: user:two, role: 'admin' }]");
+	assert_eq!(tmp, val);
+	// The permissions are not checked for system users
+	let sql = "
+		UPDATE person:two SET name = 'Changed', role = 'user';
+	";
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 1);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[{ id: person:two, name: 'Changed', owner: user:two, role: 'user' }]");
+	assert_eq!(tmp, val);
+	//
+	Ok(())
+}
+
 #[tokio::test]
 async fn field_definition_readonly() -> Result<(), Error> {
 	let sql = "
@@ -2,6 +2,7 @@ mod parse;
 use parse::Parse;
 mod helpers;
 use helpers::new_ds;
+use helpers::skip_ok;
 use surrealdb::dbs::Session;
 use surrealdb::err::Error;
 use surrealdb::sql::{self, Number, Value};
@@ -1314,6 +1315,43 @@ async fn function_array_union() -> Result<(), Error> {
 // bytes
 // --------------------------------------------------
 
+#[tokio::test]
+async fn function_bytes_from_base64() -> Result<(), Error> {
+	let sql = r#"
+		RETURN bytes::from_base64("");
+		RETURN bytes::from_base64("aGVsbG8") = <bytes>"hello";
+		RETURN bytes::from_base64("aGVsbG8=") = <bytes>"hello";
+		RETURN bytes::from_base64("not base64!");
+	"#;
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 4);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::Bytes(Vec::new().into());
+	assert_eq!(tmp, val);
+	// Padding is optional
+	let tmp = res.remove(0).result?;
+	let val = Value::from(true);
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::from(true);
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result;
+	assert!(
+		matches!(
+			&tmp,
+			Err(e) if e.to_string() == "Incorrect arguments for function bytes::from_base64(). invalid base64"
+		),
+		"{tmp:?}"
+	);
+	//
+	Ok(())
+}
+
 #[tokio::test]
 async fn function_bytes_len() -> Result<(), Error> {
 	let sql = r#"
@@ -1351,6 +1389,68 @@ async fn function_bytes_len() -> Result<(), Error> {
 	Ok(())
 }
 
+#[tokio::test]
+async fn function_bytes_slice() -> Result<(), Error> {
+	let sql = r#"
+		RETURN bytes::slice(<bytes>"hello world") = <bytes>"hello world";
+		RETURN bytes::slice(<bytes>"hello world", 6) = <bytes>"world";
+		RETURN bytes::slice(<bytes>"hello world", 0, 5) = <bytes>"hello";
+		RETURN bytes::slice(<bytes>"hello world", -5, 2) = <bytes>"wo";
+		RETURN bytes::slice(<bytes>"hello world", 1, -1) = <bytes>"ello worl";
+		RETURN bytes::len(bytes::slice(<bytes>"hello", 10));
+	"#;
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 6);
+	//
+	for _ in 0..5 {
+		let tmp = res.remove(0).result?;
+		let val = Value::from(true);
+		assert_eq!(tmp, val);
+	}
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::from(0);
+	assert_eq!(tmp, val);
+	//
+	Ok(())
+}
+
+#[tokio::test]
+async fn function_bytes_to_base64() -> Result<(), Error> {
+	let sql = r#"
+		DEFINE FIELD data ON file TYPE bytes;
+		CREATE file:one SET data = bytes::from_base64("aGVsbG8gd29ybGQ=");
+		RETURN bytes::to_base64(file:one.data);
+		RETURN bytes::to_base64(bytes::slice(file:one.data, 0, 5));
+		CREATE file:two SET data = "hello";
+	"#;
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 5);
+	//
+	skip_ok(res, 1)?;
+	// Bytes are stored and retrieved as they were written
+	let tmp = res.remove(0).result?.pick(&["data".into()]);
+	let val = Value::from(vec![Value::Bytes(b"hello world".to_vec().into())]);
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("'aGVsbG8gd29ybGQ'");
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("'aGVsbG8'");
+	assert_eq!(tmp, val);
+	// Only bytes can be stored in a bytes field
+	let tmp = res.remove(0).result;
+	assert!(tmp.is_err(), "{tmp:?}");
+	//
+	Ok(())
+}
+
 // --------------------------------------------------
 // count
 // --------------------------------------------------
@@ -1392,6 +1492,38 @@ async fn function_count() -> Result<(), Error> {
 	Ok(())
 }
 
+#[tokio::test]
+async fn function_count_distinct() -> Result<(), Error> {
+	let sql = r#"
+		RETURN count(DISTINCT [1, 2, 2, 3, 1]);
+		RETURN count::distinct(['a', NONE, NULL, 'a']);
+		RETURN count(DISTINCT 'a');
+		RETURN count(DISTINCT NONE);
+	"#;
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 4);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::from(3);
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::from(1);
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::from(1);
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::from(0);
+	assert_eq!(tmp, val);
+	//
+	Ok(())
+}
+
 // --------------------------------------------------
 // crypto
 // --------------------------------------------------
@@ -2092,6 +2224,95 @@ async fn function_parse_geo_hash_decode() -> Result<(), Error> {
 	Ok(())
 }
 
+#[tokio::test]
+async fn function_parse_geo_intersects() -> Result<(), Error> {
+	let sql = r#"
+		LET $area = {
+			type: 'Polygon',
+			coordinates: [[
+				[-0.38314819, 51.37692386], [0.1785278, 51.37692386],
+				[0.1785278, 51.61460570], [-0.38314819, 51.61460570],
+				[-0.38314819, 51.37692386]
+			]]
+		};
+		RETURN geo::intersects($area, (-0.118092, 51.509865));
+		RETURN geo::intersects($area, (-73.971321, 40.776676));
+		RETURN geo::intersects($area, {
+			type: 'LineString',
+			coordinates: [[-0.5, 51.5], [0.0, 51.5]]
+		});
+		RETURN geo::intersects($area, 'London');
+	"#;
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 5);
+	//
+	skip_ok(res, 1)?;
+	//
+	let tmp = res.remove(0).result?;
+	assert_eq!(tmp, Value::Bool(true));
+	//
+	let tmp = res.remove(0).result?;
+	assert_eq!(tmp, Value::Bool(false));
+	//
+	let tmp = res.remove(0).result?;
+	assert_eq!(tmp, Value::Bool(true));
+	//
+	let tmp = res.remove(0).result?;
+	assert_eq!(tmp, Value::None);
+	//
+	Ok(())
+}
+
+#[tokio::test]
+async fn function_parse_geo_within() -> Result<(), Error> {
+	let sql = r#"
+		LET $area = {
+			type: 'Polygon',
+			coordinates: [[
+				[-0.38314819, 51.37692386], [0.1785278, 51.37692386],
+				[0.1785278, 51.61460570], [-0.38314819, 51.61460570],
+				[-0.38314819, 51.37692386]
+			]]
+		};
+		RETURN geo::within((-0.118092, 51.509865), $area);
+		RETURN geo::within((-73.971321, 40.776676), $area);
+		RETURN geo::within({
+			type: 'LineString',
+			coordinates: [[-0.5, 51.5], [0.0, 51.5]]
+		}, $area);
+		RETURN geo::within({
+			type: 'MultiPoint',
+			coordinates: [[-0.1, 51.5], [0.1, 51.5]]
+		}, $area);
+		RETURN geo::within($area, (-0.118092, 51.509865));
+	"#;
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 6);
+	//
+	skip_ok(res, 1)?;
+	//
+	let tmp = res.remove(0).result?;
+	assert_eq!(tmp, Value::Bool(true));
+	//
+	let tmp = res.remove(0).result?;
+	assert_eq!(tmp, Value::Bool(false));
+	// A line which crosses the boundary is not within the area
+	let tmp = res.remove(0).result?;
+	assert_eq!(tmp, Value::Bool(false));
+	//
+	let tmp = res.remove(0).result?;
+	assert_eq!(tmp, Value::Bool(true));
+	//
+	let tmp = res.remove(0).result?;
+	assert_eq!(tmp, Value::Bool(false));
+	//
+	Ok(())
+}
+
 // --------------------------------------------------
 // math
 // --------------------------------------------------
@@ -2316,11 +2537,13 @@ async fn function_math_median() -> Result<(), Error> {
 		RETURN math::median([]);
 		RETURN math::median([101, 213, 202]);
 		RETURN math::median([101.5, 213.5, 202.5]);
+		RETURN math::median([101, NULL, 213, NONE]);
+		RETURN math::median([NULL]);
 	"#;
 	let dbs = new_ds().await?;
 	let ses = Session::owner().with_ns("test").with_db("test");
 	let res = &mut dbs.execute(sql, &ses, None).await?;
-	assert_eq!(res.len(), 3);
+	assert_eq!(res.len(), 5);
 	//
 	let tmp = res.remove(0).result?;
 	let val = Value::None;
@@ -2334,6 +2557,14 @@ async fn function_math_median() -> Result<(), Error> {
 	let val = Value::from(202.5);
 	assert_eq!(tmp, val);
 	//
+	let tmp = res.remove(0).result?;
+	let val = Value::from(157);
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::None;
+	assert_eq!(tmp, val);
+	//
 	Ok(())
 }
 
@@ -2448,11 +2679,16 @@ async fn function_math_percentile() -> Result<(), Error> {
 		RETURN math::percentile([], 99);
 		RETURN math::percentile([101, 213, 202], 99);
 		RETURN math::percentile([101.5, 213.5, 202.5], 99);
+		RETURN math::percentile([101, 213, 202], 0);
+		RETURN math::percentile([101, 213, 202], 100);
+		RETURN math::percentile([101], 95);
+		RETURN math::percentile([NULL, 10, 20, NONE, 30, 40], 95);
+		RETURN math::percentile([10, 'twenty'], 95);
 	"#;
 	let dbs = new_ds().await?;
 	let ses = Session::owner().with_ns("test").with_db("test");
 	let res = &mut dbs.execute(sql, &ses, None).await?;
-	assert_eq!(res.len(), 3);
+	assert_eq!(res.len(), 8);
 	//
 	let tmp = res.remove(0).result?;
 	assert!(tmp.is_nan());
@@ -2465,6 +2701,25 @@ async fn function_math_percentile() -> Result<(), Error> {
 	let val = Value::from(213.28);
 	assert_eq!(tmp, val);
 	//
+	let tmp = res.remove(0).result?;
+	let val = Value::from(101);
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::from(213);
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::from(101);
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::from(38.5);
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result;
+	assert!(tmp.is_err());
+	//
 	Ok(())
 }
 
@@ -2602,14 +2857,84 @@ async fn function_math_stddev() -> Result<(), Error> {
 		RETURN math::stddev([]);
 		RETURN math::stddev([101, 213, 202]);
 		RETURN math::stddev([101.5, 213.5, 202.5]);
+		RETURN math::stddev([101, NULL, 213, NONE, 202]);
 	"#;
 	let dbs = new_ds().await?;
 	let ses = Session::owner().with_ns("test").with_db("test");
 	let res = &mut dbs.execute(sql, &ses, None).await?;
-	assert_eq!(res.len(), 3);
+	assert_eq!(res.len(), 4);
 	//
 	let tmp = res.remove(0).result?;
-	assert!(tmp.is_nan());
+	let val = Value::None;
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::from(61.73329733620261);
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::from(61.73329733620261);
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::from(61.73329733620261);
+	assert_eq!(tmp, val);
+	//
+	Ok(())
+}
+
+#[tokio::test]
+async fn function_math_stddev_pop() -> Result<(), Error> {
+	let sql = r#"
+		RETURN math::stddev::pop([]);
+		RETURN math::stddev::pop([7]);
+		RETURN math::stddev::pop([2, 4, 4, 4, 5, 5, 7, 9]);
+		RETURN math::stddev::pop([2, 4, NULL, 4, 4, 5, 5, 7, NONE, 9]);
+	"#;
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 4);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::None;
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::from(0.0);
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::from(2.0);
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::from(2.0);
+	assert_eq!(tmp, val);
+	//
+	Ok(())
+}
+
+#[tokio::test]
+async fn function_math_stddev_samp() -> Result<(), Error> {
+	let sql = r#"
+		RETURN math::stddev::samp([]);
+		RETURN math::stddev::samp([7]);
+		RETURN math::stddev::samp([101, 213, 202]);
+		RETURN math::stddev::samp([NULL, 101, 213, 202]);
+	"#;
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 4);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::None;
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::from(0.0);
+	assert_eq!(tmp, val);
 	//
 	let tmp = res.remove(0).result?;
 	let val = Value::from(61.73329733620261);
@@ -2713,14 +3038,16 @@ async fn function_math_variance() -> Result<(), Error> {
 		RETURN math::variance([]);
 		RETURN math::variance([101, 213, 202]);
 		RETURN math::variance([101.5, 213.5, 202.5]);
+		RETURN math::variance([101, NULL, 213, NONE, 202]);
 	"#;
 	let dbs = new_ds().await?;
 	let ses = Session::owner().with_ns("test").with_db("test");
 	let res = &mut dbs.execute(sql, &ses, None).await?;
-	assert_eq!(res.len(), 3);
+	assert_eq!(res.len(), 4);
 	//
 	let tmp = res.remove(0).result?;
-	assert!(tmp.is_nan());
+	let val = Value::None;
+	assert_eq!(tmp, val);
 	//
 	let tmp = res.remove(0).result?;
 	let val = Value::from(3811);
@@ -2730,6 +3057,74 @@ async fn function_math_variance() -> Result<(), Error> {
 	let val = Value::from(3811.0);
 	assert_eq!(tmp, val);
 	//
+	let tmp = res.remove(0).result?;
+	let val = Value::from(3811);
+	assert_eq!(tmp, val);
+	//
+	Ok(())
+}
+
+#[tokio::test]
+async fn function_math_variance_pop() -> Result<(), Error> {
+	let sql = r#"
+		RETURN math::variance::pop([]);
+		RETURN math::variance::pop([7]);
+		RETURN math::variance::pop([2, 4, 4, 4, 5, 5, 7, 9]);
+		RETURN math::variance::pop([2, 4, NULL, 4, 4, 5, 5, 7, NONE, 9]);
+	"#;
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 4);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::None;
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::from(0.0);
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::from(4.0);
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::from(4.0);
+	assert_eq!(tmp, val);
+	//
+	Ok(())
+}
+
+#[tokio::test]
+async fn function_math_variance_samp() -> Result<(), Error> {
+	let sql = r#"
+		RETURN math::variance::samp([]);
+		RETURN math::variance::samp([7]);
+		RETURN math::variance::samp([101, 213, 202]);
+		RETURN math::variance::samp([NULL, 101, 213, 202]);
+	"#;
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 4);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::None;
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::from(0.0);
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::from(3811);
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::from(3811);
+	assert_eq!(tmp, val);
+	//
 	Ok(())
 }
 
@@ -4042,6 +4437,66 @@ async fn function_string_reverse() -> Result<(), Error> {
 	Ok(())
 }
 
+#[tokio::test]
+async fn function_string_distance_levenshtein() -> Result<(), Error> {
+	let sql = r#"
+		RETURN string::distance::levenshtein("", "");
+		RETURN string::distance::levenshtein("apple", "apple");
+		RETURN string::distance::levenshtein("apple", "aple");
+		RETURN string::distance::levenshtein("kitten", "sitting");
+		RETURN string::distance::levenshtein("text", "TEXT");
+	"#;
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 5);
+	//
+	let tmp = res.remove(0).result?;
+	assert_eq!(tmp, Value::from(0));
+	//
+	let tmp = res.remove(0).result?;
+	assert_eq!(tmp, Value::from(0));
+	//
+	let tmp = res.remove(0).result?;
+	assert_eq!(tmp, Value::from(1));
+	//
+	let tmp = res.remove(0).result?;
+	assert_eq!(tmp, Value::from(3));
+	//
+	let tmp = res.remove(0).result?;
+	assert_eq!(tmp, Value::from(4));
+	//
+	Ok(())
+}
+
+#[tokio::test]
+async fn function_string_similarity() -> Result<(), Error> {
+	let sql = r#"
+		RETURN string::similarity("", "");
+		RETURN string::similarity("apple", "apple");
+		RETURN string::similarity("apple", "aple");
+		RETURN string::similarity("abc", "xyz");
+	"#;
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 4);
+	//
+	let tmp = res.remove(0).result?;
+	assert_eq!(tmp, Value::from(1.0));
+	//
+	let tmp = res.remove(0).result?;
+	assert_eq!(tmp, Value::from(1.0));
+	//
+	let tmp = res.remove(0).result?;
+	assert_eq!(tmp, Value::from(0.8));
+	//
+	let tmp = res.remove(0).result?;
+	assert_eq!(tmp, Value::from(0.0));
+	//
+	Ok(())
+}
+
 #[tokio::test]
 async fn function_string_similarity_fuzzy() -> Result<(), Error> {
 	let sql = r#"
@@ -4421,11 +4876,22 @@ async fn function_time_group() -> Result<(), Error> {
 	let sql = r#"
 		RETURN time::group(d"1987-06-22T08:30:45Z", 'hour');
 		RETURN time::group(d"1987-06-22T08:30:45Z", 'month');
+		RETURN time::group(d"1987-06-22T08:30:45Z", 15m);
+		RETURN time::group(d"1987-06-22T08:30:45Z", 1h);
+		RETURN time::group(d"1987-06-22T08:30:45Z", 6h);
+		RETURN time::group(d"1987-06-22T08:30:45Z", 1d);
+		RETURN time::group(d"1987-06-24T08:30:45Z", 1w);
+		RETURN time::group(d"1987-06-22T00:00:00Z", 1w);
+		RETURN time::group(d"1960-03-02T08:30:45Z", 1d);
+		RETURN time::group(d"2024-03-31T01:30:00+01:00", 1h);
+		RETURN time::group(d"2024-03-31T03:30:00+02:00", 1h);
+		RETURN time::group(d"1987-06-22T08:30:45Z", 0s);
+		RETURN time::group(d"1987-06-22T08:30:45Z", 'week');
 	"#;
 	let dbs = new_ds().await?;
 	let ses = Session::owner().with_ns("test").with_db("test");
 	let res = &mut dbs.execute(sql, &ses, None).await?;
-	assert_eq!(res.len(), 2);
+	assert_eq!(res.len(), 13);
 	//
 	let tmp = res.remove(0).result?;
 	let val = Value::parse("d'1987-06-22T08:00:00Z'");
@@ -4435,6 +4901,49 @@ async fn function_time_group() -> Result<(), Error> {
 	let val = Value::parse("d'1987-06-01T00:00:00Z'");
 	assert_eq!(tmp, val);
 	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("d'1987-06-22T08:30:00Z'");
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("d'1987-06-22T08:00:00Z'");
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("d'1987-06-22T06:00:00Z'");
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("d'1987-06-22T00:00:00Z'");
+	assert_eq!(tmp, val);
+	// Weekly buckets start on a Monday
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("d'1987-06-22T00:00:00Z'");
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("d'1987-06-22T00:00:00Z'");
+	assert_eq!(tmp, val);
+	// Datetimes before the Unix epoch are floored too
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("d'1960-03-02T00:00:00Z'");
+	assert_eq!(tmp, val);
+	// Buckets are in UTC, so they are not affected by daylight saving time
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("d'2024-03-31T00:00:00Z'");
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("d'2024-03-31T01:00:00Z'");
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("d'1987-06-22T08:30:45Z'");
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result;
+	assert!(matches!(tmp, Err(Error::InvalidArguments { .. })));
+	//
 	Ok(())
 }
 
@@ -6538,6 +7047,69 @@ async fn function_custom_optional_args() -> Result<(), Error> {
 	Ok(())
 }
 
+#[tokio::test]
+async fn function_custom_recursion_and_scope() -> Result<(), Error> {
+	let sql = r#"
+		DEFINE FUNCTION fn::factorial($n: int) {
+			RETURN IF $n <= 1 { 1 } ELSE { $n * fn::factorial($n - 1) };
+		};
+		DEFINE FUNCTION fn::forever($n: int) { fn::forever($n + 1) };
+		DEFINE FUNCTION fn::scaled($n: number) { $n * $scale };
+		DEFINE FUNCTION fn::shadowed($scale: number) { $scale };
+		RETURN fn::factorial(10);
+		RETURN fn::forever(0);
+		LET $scale = 3;
+		RETURN fn::scaled(2);
+		RETURN fn::shadowed(5);
+		RETURN $scale;
+		RETURN fn::factorial('ten');
+		RETURN fn::scaled('two');
+	"#;
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 12);
+	//
+	for _ in 0..4 {
+		res.remove(0).result?;
+	}
+	// Functions can call themselves recursively
+	let tmp = res.remove(0).result?;
+	let val = Value::from(3628800);
+	assert_eq!(tmp, val);
+	// But the depth of the recursion is limited
+	let tmp = res.remove(0).result;
+	assert!(matches!(tmp, Err(Error::ComputationDepthExceeded)), "found {:?}", tmp);
+	//
+	res.remove(0).result?;
+	// The body can use variables from the outer scope
+	let tmp = res.remove(0).result?;
+	let val = Value::from(6);
+	assert_eq!(tmp, val);
+	// While arguments shadow them only within the function
+	let tmp = res.remove(0).result?;
+	let val = Value::from(5);
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::from(3);
+	assert_eq!(tmp, val);
+	// Arguments are checked against their types
+	let tmp = res.remove(0).result;
+	assert!(matches!(
+		tmp.err(),
+		Some(e) if e.to_string() == "Expected a int but found 'ten'"
+	));
+	//
+	let tmp = res.remove(0).result;
+	assert!(matches!(
+		tmp.err(),
+		Some(e) if e.to_string() == "Expected a number but found 'two'"
+	));
+	//
+	Ok(())
+}
+
 #[tokio::test]
 async fn function_outside_database() -> Result<(), Error> {
 	let sql = "RETURN fn::does_not_exist();";
@@ -125,3 +125,39 @@ async fn concurrency() -> Result<(), Error> {
 
 	Ok(())
 }
+
+#[tokio::test]
+async fn future_deferred_in_session() -> Result<(), Error> {
+	let sql = "
+		CREATE person:test SET birthday = <datetime> '2001-06-22', can_drive = <future> { birthday + 18y < time::now() };
+		SELECT * FROM person:test;
+		SELECT can_drive FROM person:test;
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test").with_deferred_futures(true);
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 3);
+	// Futures are returned without being computed
+	let tmp = res.remove(0).result?;
+	let val = Value::parse(
+		"[{ id: person:test, birthday: d'2001-06-22T00:00:00Z', can_drive: <future> { birthday + 18y < time::now() } }]",
+	);
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[{ can_drive: <future> { birthday + 18y < time::now() } }]");
+	assert_eq!(tmp, val);
+	// The stored future is still computed for other sessions
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute("SELECT can_drive FROM person:test", &ses, None).await?;
+	assert_eq!(res.len(), 1);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[{ can_drive: true }]");
+	assert_eq!(tmp, val);
+	//
+	Ok(())
+}
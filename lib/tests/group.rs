@@ -674,3 +674,436 @@ async fn select_array_count_subquery_group_by() -> Result<(), Error> {
 	//
 	Ok(())
 }
+
+#[tokio::test]
+async fn select_group_by_having() -> Result<(), Error> {
+	let sql = "
+		CREATE person:1 SET city = 'London', age = 20;
+		CREATE person:2 SET city = 'London', age = 30;
+		CREATE person:3 SET city = 'London', age = 40;
+		CREATE person:4 SET city = 'Paris', age = 50;
+		CREATE person:5 SET city = 'Tokyo', age = 60;
+		CREATE person:6 SET city = 'Tokyo', age = 70;
+		SELECT count(), city FROM person GROUP BY city HAVING count() > 1;
+		SELECT math::max(age) AS oldest, city FROM person GROUP BY city HAVING oldest >= 50 ORDER BY oldest DESC;
+		SELECT count() AS total, city FROM person GROUP BY city HAVING total > 1 LIMIT 1;
+		SELECT count() AS total, city FROM person GROUP BY city HAVING total > 1 START 1;
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 10);
+	//
+	skip_ok(res, 6)?;
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse(
+		"[
+			{
+				city: 'London',
+				count: 3
+			},
+			{
+				city: 'Tokyo',
+				count: 2
+			}
+		]",
+	);
+	assert_eq!(format!("{tmp:#}"), format!("{val:#}"));
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse(
+		"[
+			{
+				city: 'Tokyo',
+				oldest: 70
+			},
+			{
+				city: 'Paris',
+				oldest: 50
+			}
+		]",
+	);
+	assert_eq!(format!("{tmp:#}"), format!("{val:#}"));
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse(
+		"[
+			{
+				city: 'London',
+				total: 3
+			}
+		]",
+	);
+	assert_eq!(format!("{tmp:#}"), format!("{val:#}"));
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse(
+		"[
+			{
+				city: 'Tokyo',
+				total: 2
+			}
+		]",
+	);
+	assert_eq!(format!("{tmp:#}"), format!("{val:#}"));
+	//
+	Ok(())
+}
+
+#[tokio::test]
+async fn select_group_by_count_distinct() -> Result<(), Error> {
+	let sql = "
+		CREATE user:1 SET country = 'GBR', email = 'a@example.com';
+		CREATE user:2 SET country = 'GBR', email = 'a@example.com';
+		CREATE user:3 SET country = 'GBR', email = 'a@example.com';
+		CREATE user:4 SET country = 'USA', email = 'a@example.com';
+		CREATE user:5 SET country = 'USA', email = 'b@example.com';
+		CREATE user:6 SET country = 'USA', email = 'c@example.com';
+		CREATE user:7 SET country = 'FRA', email = 'a@example.com';
+		CREATE user:8 SET country = 'FRA', email = NULL;
+		CREATE user:9 SET country = 'FRA';
+		CREATE user:10 SET country = 'FRA', email = 'a@example.com';
+		SELECT country, count() AS total, count(DISTINCT email) AS emails FROM user GROUP BY country;
+		SELECT count(DISTINCT email) FROM user GROUP ALL;
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 12);
+	//
+	skip_ok(res, 10)?;
+	// Identical values are counted once and missing values are not counted
+	let tmp = res.remove(0).result?;
+	let val = Value::parse(
+		"[
+			{
+				country: 'FRA',
+				emails: 1,
+				total: 4
+			},
+			{
+				country: 'GBR',
+				emails: 1,
+				total: 3
+			},
+			{
+				country: 'USA',
+				emails: 3,
+				total: 3
+			}
+		]",
+	);
+	assert_eq!(format!("{tmp:#}"), format!("{val:#}"));
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse(
+		"[
+			{
+				'count::distinct': 3
+			}
+		]",
+	);
+	assert_eq!(format!("{tmp:#}"), format!("{val:#}"));
+	//
+	Ok(())
+}
+
+#[tokio::test]
+async fn select_group_by_statistics() -> Result<(), Error> {
+	let sql = "
+		CREATE score:1 SET team = 'a', points = 1;
+		CREATE score:2 SET team = 'a', points = NULL;
+		CREATE score:3 SET team = 'a', points = 3;
+		CREATE score:4 SET team = 'b', points = 5;
+		CREATE score:5 SET team = 'c', points = NULL;
+		CREATE score:6 SET team = 'c';
+		SELECT
+			team,
+			math::median(points) AS median,
+			math::stddev::pop(points) AS stddev_pop,
+			math::stddev::samp(points) AS stddev_samp,
+			math::variance::pop(points) AS variance_pop,
+			math::variance::samp(points) AS variance_samp
+		FROM score GROUP BY team;
+		SELECT math::stddev(points), math::variance(points) FROM score GROUP ALL;
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 8);
+	//
+	skip_ok(res, 6)?;
+	// Missing values are ignored, and groups without any values return NONE
+	let tmp = res.remove(0).result?;
+	let val = Value::parse(
+		"[
+			{
+				median: 2f,
+				stddev_pop: 1f,
+				stddev_samp: 1.4142135623730951f,
+				team: 'a',
+				variance_pop: 1f,
+				variance_samp: 2f
+			},
+			{
+				median: 5f,
+				stddev_pop: 0f,
+				stddev_samp: 0f,
+				team: 'b',
+				variance_pop: 0f,
+				variance_samp: 0f
+			},
+			{
+				median: NONE,
+				stddev_pop: NONE,
+				stddev_samp: NONE,
+				team: 'c',
+				variance_pop: NONE,
+				variance_samp: NONE
+			}
+		]",
+	);
+	assert_eq!(format!("{tmp:#}"), format!("{val:#}"));
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse(
+		"[
+			{
+				'math::stddev': 2f,
+				'math::variance': 4f
+			}
+		]",
+	);
+	assert_eq!(format!("{tmp:#}"), format!("{val:#}"));
+	//
+	Ok(())
+}
+
+#[tokio::test]
+async fn select_group_by_mean_and_variance() -> Result<(), Error> {
+	let sql = "
+		SELECT math::mean(value) AS mean, math::variance(value) AS variance FROM reading GROUP ALL;
+		INSERT INTO reading [
+			{ sensor: 'a', value: 2 },
+			{ sensor: 'a', value: 4 },
+			{ sensor: 'a', value: 4 },
+			{ sensor: 'a', value: 4 },
+			{ sensor: 'a', value: 5 },
+			{ sensor: 'a', value: 5 },
+			{ sensor: 'a', value: 7 },
+			{ sensor: 'a', value: 9 },
+			{ sensor: 'b', value: 1000000004 },
+			{ sensor: 'b', value: 1000000007 },
+			{ sensor: 'b', value: 1000000013 },
+			{ sensor: 'b', value: 1000000016 },
+			{ sensor: 'c', value: NULL },
+		];
+		SELECT
+			sensor,
+			mean,
+			median,
+			math::fixed(stddev_pop, 9) AS stddev_pop,
+			math::fixed(variance_pop, 9) AS variance_pop,
+			math::fixed(variance, 9) AS variance
+		FROM (
+			SELECT
+				sensor,
+				math::mean(value) AS mean,
+				math::median(value) AS median,
+				math::stddev::pop(value) AS stddev_pop,
+				math::variance::pop(value) AS variance_pop,
+				math::variance(value) AS variance
+			FROM reading WHERE sensor != 'c' GROUP BY sensor
+		);
+		SELECT
+			math::mean(value) AS mean,
+			math::median(value) AS median,
+			math::stddev(value) AS stddev,
+			math::variance(value) AS variance
+		FROM reading WHERE sensor = 'c' GROUP ALL;
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 4);
+	// An empty group has no mean or variance
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[{ mean: NONE, variance: NONE }]");
+	assert_eq!(tmp, val);
+	//
+	skip_ok(res, 1)?;
+	// Large values do not lose precision when accumulating the variance
+	let tmp = res.remove(0).result?;
+	let val = Value::parse(
+		"[
+			{
+				mean: 5,
+				median: 4.5f,
+				sensor: 'a',
+				stddev_pop: 2f,
+				variance: 4.571428571f,
+				variance_pop: 4f
+			},
+			{
+				mean: 1000000010,
+				median: 1000000010f,
+				sensor: 'b',
+				stddev_pop: 4.74341649f,
+				variance: 30f,
+				variance_pop: 22.5f
+			}
+		]",
+	);
+	assert_eq!(format!("{tmp:#}"), format!("{val:#}"));
+	// Missing values are ignored, so the group is empty
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[{ mean: NONE, median: NONE, stddev: NONE, variance: NONE }]");
+	assert_eq!(tmp, val);
+	//
+	Ok(())
+}
+
+#[tokio::test]
+async fn select_group_by_percentile() -> Result<(), Error> {
+	let sql = "
+		CREATE request:1 SET endpoint = '/users', latency = 10;
+		CREATE request:2 SET endpoint = '/users', latency = 20;
+		CREATE request:3 SET endpoint = '/users', latency = 30;
+		CREATE request:4 SET endpoint = '/users', latency = 40;
+		CREATE request:5 SET endpoint = '/users', latency = NULL;
+		CREATE request:6 SET endpoint = '/posts', latency = 100;
+		SELECT endpoint, math::percentile(latency, 95) AS p95, math::percentile(latency, 50) AS p50 FROM request GROUP BY endpoint;
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 7);
+	//
+	skip_ok(res, 6)?;
+	// Percentiles are interpolated between the ordered values of each group
+	let tmp = res.remove(0).result?;
+	let val = Value::parse(
+		"[
+			{
+				endpoint: '/posts',
+				p50: 100f,
+				p95: 100f
+			},
+			{
+				endpoint: '/users',
+				p50: 25f,
+				p95: 38.5f
+			}
+		]",
+	);
+	assert_eq!(format!("{tmp:#}"), format!("{val:#}"));
+	//
+	Ok(())
+}
+
+#[tokio::test]
+async fn select_group_all() -> Result<(), Error> {
+	let sql = "
+		SELECT count(), math::sum(amount) AS total FROM sale GROUP ALL;
+		CREATE sale:1 SET amount = 10, region = 'eu';
+		CREATE sale:2 SET amount = 20, region = 'us';
+		CREATE sale:3 SET amount = 30, region = 'eu';
+		SELECT count(), math::sum(amount) AS total FROM sale GROUP ALL;
+		SELECT count(), math::sum(amount) AS total FROM sale WHERE region = 'eu' GROUP ALL;
+		SELECT count(), math::sum(amount) AS total FROM sale WHERE region = 'asia' GROUP ALL;
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 7);
+	// An empty table still produces a single aggregate row
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[{ count: 0, total: 0 }]");
+	assert_eq!(tmp, val);
+	//
+	skip_ok(res, 3)?;
+	// All records are aggregated into a single row
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[{ count: 3, total: 60 }]");
+	assert_eq!(tmp, val);
+	// Records are filtered before they are aggregated
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[{ count: 2, total: 40 }]");
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[{ count: 0, total: 0 }]");
+	assert_eq!(tmp, val);
+	//
+	Ok(())
+}
+
+#[tokio::test]
+async fn select_group_by_multiple_fields() -> Result<(), Error> {
+	let sql = "
+		CREATE order:1 SET city = 'ab', status = 'c', amount = 1;
+		CREATE order:2 SET city = 'a', status = 'bc', amount = 2;
+		CREATE order:3 SET city = 'ab', status = 'c', amount = 4;
+		CREATE order:4 SET city = 'a', status = 'b', amount = 8;
+		CREATE order:5 SET city = 'ab', status = NULL, amount = 16;
+		SELECT city, status, count() AS count, math::sum(amount) AS total FROM order GROUP BY city, status;
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 6);
+	//
+	skip_ok(res, 5)?;
+	// Each distinct combination of values is a separate group
+	let tmp = res.remove(0).result?;
+	let val = Value::parse(
+		"[
+			{ city: 'a', count: 1, status: 'b', total: 8 },
+			{ city: 'a', count: 1, status: 'bc', total: 2 },
+			{ city: 'ab', count: 1, status: NULL, total: 16 },
+			{ city: 'ab', count: 2, status: 'c', total: 5 }
+		]",
+	);
+	assert_eq!(tmp, val);
+	//
+	Ok(())
+}
+
+#[tokio::test]
+async fn select_group_by_time_bucket() -> Result<(), Error> {
+	let sql = "
+		CREATE event:1 SET created = d'2024-01-01T00:05:00Z';
+		CREATE event:2 SET created = d'2024-01-01T00:55:00Z';
+		CREATE event:3 SET created = d'2024-01-01T01:00:00Z';
+		CREATE event:4 SET created = d'2024-01-01T03:30:00Z';
+		CREATE event:5 SET created = d'2024-01-01T03:59:59Z';
+		SELECT count() AS total, time::group(created, 1h) AS hour FROM event GROUP BY hour;
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 6);
+	skip_ok(res, 5)?;
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse(
+		"[
+			{
+				hour: d'2024-01-01T00:00:00Z',
+				total: 2
+			},
+			{
+				hour: d'2024-01-01T01:00:00Z',
+				total: 1
+			},
+			{
+				hour: d'2024-01-01T03:00:00Z',
+				total: 2
+			}
+		]",
+	);
+	assert_eq!(tmp, val);
+	//
+	Ok(())
+}
@@ -0,0 +1,204 @@
+mod parse;
+use parse::Parse;
+mod helpers;
+use helpers::new_ds;
+use surrealdb::dbs::Session;
+use surrealdb::err::Error;
+use surrealdb::kvs::{ImportMode, ImportOptions};
+use surrealdb::sql::Value;
+
+const NDJSON: &str = r#"
+{"type":"table","table":"person","sql":"DEFINE TABLE person SCHEMAFULL"}
+{"type":"field","table":"person","sql":"DEFINE FIELD name ON person TYPE string"}
+{"type":"field","table":"person","sql":"DEFINE FIELD age ON person TYPE option<int>"}
+{"type":"record","table":"person","data":{"id":"person:tobie","name":"Tobie"}}
+{"type":"record","table":"person","data":{"id":"person:jaime","name":"Jaime","age":30}}
+"#;
+
+#[tokio::test]
+async fn import_ndjson_insert() -> Result<(), Error> {
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let stats = dbs.import_ndjson(NDJSON, &ses, ImportOptions::default()).await?;
+	assert_eq!(stats.lines, 5);
+	assert_eq!(stats.written, 2);
+	assert_eq!(stats.skipped, 0);
+	//
+	let res = &mut dbs.execute("SELECT * FROM person", &ses, None).await?;
+	let tmp = res.remove(0).result?;
+	let val = Value::parse(
+		"[
+			{ id: person:jaime, name: 'Jaime', age: 30 },
+			{ id: person:tobie, name: 'Tobie' }
+		]",
+	);
+	assert_eq!(tmp, val);
+	// Inserting existing records fails
+	let res = dbs.import_ndjson(NDJSON, &ses, ImportOptions::default()).await;
+	assert!(
+		matches!(
+			res,
+			Err(Error::ImportFailed {
+				line: 5,
+				..
+			})
+		),
+		"Unexpected result: {:?}",
+		res
+	);
+	Ok(())
+}
+
+#[tokio::test]
+async fn import_ndjson_upsert_and_replace() -> Result<(), Error> {
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	dbs.import_ndjson(NDJSON, &ses, ImportOptions::default()).await?;
+	let ndjson = r#"{"type":"record","table":"person","data":{"id":"person:jaime","name":"James"}}
+		{"type":"record","table":"person","data":{"id":"person:marcus","name":"Marcus"}}"#;
+	// Upserted records are merged into existing records
+	let opts = ImportOptions::default().with_mode(ImportMode::Upsert);
+	let stats = dbs.import_ndjson(ndjson, &ses, opts).await?;
+	assert_eq!(stats.written, 2);
+	let res = &mut dbs.execute("SELECT * FROM person", &ses, None).await?;
+	let tmp = res.remove(0).result?;
+	let val = Value::parse(
+		"[
+			{ id: person:jaime, name: 'James', age: 30 },
+			{ id: person:marcus, name: 'Marcus' },
+			{ id: person:tobie, name: 'Tobie' }
+		]",
+	);
+	assert_eq!(tmp, val);
+	// Replaced records lose any fields which are not imported
+	let opts = ImportOptions::default().with_mode(ImportMode::Replace);
+	let stats = dbs.import_ndjson(ndjson, &ses, opts).await?;
+	assert_eq!(stats.written, 2);
+	let res = &mut dbs.execute("SELECT * FROM person:jaime", &ses, None).await?;
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[{ id: person:jaime, name: 'James' }]");
+	assert_eq!(tmp, val);
+	Ok(())
+}
+
+#[tokio::test]
+async fn import_ndjson_dry_run() -> Result<(), Error> {
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let opts = ImportOptions::default().with_dry_run(true);
+	let stats = dbs.import_ndjson(NDJSON, &ses, opts.clone()).await?;
+	assert_eq!(stats.lines, 5);
+	assert_eq!(stats.written, 2);
+	// Nothing is written in a dry run
+	let res = &mut dbs.execute("INFO FOR DB; SELECT * FROM person", &ses, None).await?;
+	let tmp = res.remove(0).result?;
+	assert_eq!(tmp.pick(&["tables".into()]), Value::parse("{}"));
+	let tmp = res.remove(0).result?;
+	assert_eq!(tmp, Value::parse("[]"));
+	// The schema is still validated in a dry run
+	let ndjson = format!(
+		"{NDJSON}{}",
+		r#"{"type":"record","table":"person","data":{"id":"person:1","name":"One","age":"old"}}"#
+	);
+	let res = dbs.import_ndjson(&ndjson, &ses, opts.clone()).await;
+	assert!(
+		matches!(
+			res,
+			Err(Error::ImportFailed {
+				line: 7,
+				..
+			})
+		),
+		"Unexpected result: {:?}",
+		res
+	);
+	let stats = dbs.import_ndjson(&ndjson, &ses, opts.with_skip_errors(true)).await?;
+	assert_eq!(stats.lines, 6);
+	assert_eq!(stats.written, 2);
+	assert_eq!(stats.skipped, 1);
+	Ok(())
+}
+
+#[tokio::test]
+async fn import_ndjson_malformed_lines() -> Result<(), Error> {
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let ndjson = r#"{"type":"record","table":"person","data":{"id":"person:1"}}
+		{"type":"record","table":"person","data":
+		{"type":"unknown"}
+		{"type":"record","table":"person","data":{"id":"person:2"}}"#;
+	// A malformed line fails the import by default
+	let res = dbs.import_ndjson(ndjson, &ses, ImportOptions::default()).await;
+	assert!(
+		matches!(
+			res,
+			Err(Error::ImportFailed {
+				line: 2,
+				..
+			})
+		),
+		"Unexpected result: {:?}",
+		res
+	);
+	let res = &mut dbs.execute("SELECT VALUE id FROM person", &ses, None).await?;
+	let tmp = res.remove(0).result?;
+	assert_eq!(tmp, Value::parse("[]"));
+	// Malformed lines can be skipped
+	let opts = ImportOptions::default().with_skip_errors(true);
+	let stats = dbs.import_ndjson(ndjson, &ses, opts).await?;
+	assert_eq!(stats.lines, 4);
+	assert_eq!(stats.written, 2);
+	assert_eq!(stats.skipped, 2);
+	let res = &mut dbs.execute("SELECT VALUE id FROM person", &ses, None).await?;
+	let tmp = res.remove(0).result?;
+	assert_eq!(tmp, Value::parse("[person:1, person:2]"));
+	Ok(())
+}
+
+#[tokio::test]
+async fn import_ndjson_skipped_lines_changefeed() -> Result<(), Error> {
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let sql = "
+		DEFINE TABLE person CHANGEFEED 1h;
+		DEFINE EVENT reject ON person WHEN $after.name = 'Bad' THEN { THROW 'rejected' };
+	";
+	for res in dbs.execute(sql, &ses, None).await? {
+		res.result?;
+	}
+	let ndjson = r#"{"type":"record","table":"person","data":{"id":"person:1","name":"Tobie"}}
+		{"type":"record","table":"person","data":{"id":"person:2","name":"Bad"}}
+		{"type":"record","table":"person","data":{"id":"person:3","name":"Jaime"}}"#;
+	// The failed line is only rejected after its change is recorded
+	let opts = ImportOptions::default().with_skip_errors(true);
+	let stats = dbs.import_ndjson(ndjson, &ses, opts).await?;
+	assert_eq!(stats.written, 2);
+	assert_eq!(stats.skipped, 1);
+	// A skipped line leaves no change feed entry
+	let res = &mut dbs.execute("SHOW CHANGES FOR TABLE person SINCE 0", &ses, None).await?;
+	let tmp = res.remove(0).result?.to_string();
+	assert!(tmp.contains("person:1"), "{tmp}");
+	assert!(tmp.contains("person:3"), "{tmp}");
+	assert!(!tmp.contains("person:2"), "{tmp}");
+	Ok(())
+}
+
+#[tokio::test]
+async fn import_ndjson_large_batches() -> Result<(), Error> {
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let ndjson = (0..2500)
+		.map(|i| {
+			format!(r#"{{"type":"record","table":"item","data":{{"id":"item:{i}","n":{i}}}}}"#)
+		})
+		.collect::<Vec<_>>()
+		.join("\n");
+	let opts = ImportOptions::default().with_batch_size(1000);
+	let stats = dbs.import_ndjson(&ndjson, &ses, opts).await?;
+	assert_eq!(stats.lines, 2500);
+	assert_eq!(stats.written, 2500);
+	let res = &mut dbs.execute("SELECT count() FROM item GROUP ALL", &ses, None).await?;
+	let tmp = res.remove(0).result?;
+	assert_eq!(tmp, Value::parse("[{ count: 2500 }]"));
+	Ok(())
+}
@@ -2,6 +2,7 @@ mod parse;
 use parse::Parse;
 mod helpers;
 use helpers::new_ds;
+use helpers::skip_ok;
 use surrealdb::dbs::Session;
 use surrealdb::err::Error;
 use surrealdb::iam::Role;
@@ -193,6 +194,218 @@ async fn insert_statement_duplicate_key_update() -> Result<(), Error> {
 	Ok(())
 }
 
+#[tokio::test]
+async fn insert_statement_duplicate_key_counts() -> Result<(), Error> {
+	let sql = "
+		INSERT INTO person [{ id: 1, count: 0 }, { id: 2, count: 0 }];
+		INSERT INTO person [{ id: 2 }, { id: 3, count: 0 }, { id: 1 }] ON DUPLICATE KEY UPDATE count += 1;
+		SELECT id, count FROM person;
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test").with_collect_metrics(true);
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 3);
+	//
+	let tmp = res.remove(0);
+	let metrics = tmp.metrics.unwrap();
+	assert_eq!(metrics.records_inserted, 2);
+	assert_eq!(metrics.records_updated, 0);
+	// Existing records are updated, and new records are inserted
+	let tmp = res.remove(0);
+	let metrics = tmp.metrics.unwrap();
+	assert_eq!(metrics.records_inserted, 1);
+	assert_eq!(metrics.records_updated, 2);
+	assert_eq!(metrics.records_returned, 3);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse(
+		"[
+			{ id: person:1, count: 1 },
+			{ id: person:2, count: 1 },
+			{ id: person:3, count: 0 }
+		]",
+	);
+	assert_eq!(tmp, val);
+	//
+	Ok(())
+}
+
+#[tokio::test]
+async fn insert_statement_select() -> Result<(), Error> {
+	let sql = "
+		CREATE event:1 SET ts = d'2022-01-01', name = 'old';
+		CREATE event:2 SET ts = d'2024-01-01', name = 'new';
+		INSERT INTO archive SELECT * FROM event WHERE ts < d'2023-01-01';
+		INSERT INTO archive SELECT * FROM event WHERE ts > d'2025-01-01';
+		INSERT INTO archive SELECT * FROM nothing;
+		INSERT INTO archive SELECT name FROM event WHERE name = 'new';
+		SELECT * FROM archive WHERE name = 'new';
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 7);
+	//
+	skip_ok(res, 2)?;
+	// The selected record ids are moved to the table being inserted into
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[{ id: archive:1, name: 'old', ts: d'2022-01-01T00:00:00Z' }]");
+	assert_eq!(tmp, val);
+	// Nothing is inserted when the query selects nothing
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[]");
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[]");
+	assert_eq!(tmp, val);
+	// Rows without an id are given a new record id
+	let tmp = res.remove(0).result?;
+	assert_eq!(tmp.first().pick(&[Part::from("name")]), Value::from("new"));
+	//
+	let tmp = res.remove(0).result?;
+	assert_eq!(tmp.first().pick(&[Part::from("name")]), Value::from("new"));
+	assert!(tmp.first().pick(&[Part::from("id")]).is_thing());
+	//
+	Ok(())
+}
+
+#[tokio::test]
+async fn insert_statement_select_ignore() -> Result<(), Error> {
+	let sql = "
+		CREATE event:1 SET name = 'one';
+		CREATE event:2 SET name = 'two';
+		CREATE archive:1 SET name = 'archived';
+		INSERT IGNORE INTO archive SELECT * FROM event;
+		CREATE event:3 SET name = 'three';
+		INSERT INTO archive SELECT * FROM event ON DUPLICATE KEY IGNORE;
+		INSERT INTO archive SELECT * FROM event ON DUPLICATE KEY UPDATE name = $input.name;
+		SELECT * FROM archive;
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 8);
+	//
+	skip_ok(res, 3)?;
+	// Only the records which did not exist are inserted
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[{ id: archive:2, name: 'two' }]");
+	assert_eq!(tmp, val);
+	//
+	skip_ok(res, 1)?;
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[{ id: archive:3, name: 'three' }]");
+	assert_eq!(tmp, val);
+	// While existing records are updated by the ON DUPLICATE KEY UPDATE clause
+	let tmp = res.remove(0).result?;
+	let val = Value::parse(
+		"[
+			{ id: archive:1, name: 'one' },
+			{ id: archive:2, name: 'two' },
+			{ id: archive:3, name: 'three' }
+		]",
+	);
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	assert_eq!(tmp, val);
+	//
+	Ok(())
+}
+
+#[tokio::test]
+async fn insert_statement_select_large_batch() -> Result<(), Error> {
+	let sql = "
+		CREATE |event:1000| SET value = 1 RETURN NONE;
+		INSERT INTO archive SELECT * FROM event RETURN NONE;
+		SELECT count(), math::sum(value) AS total FROM archive GROUP ALL;
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 3);
+	//
+	skip_ok(res, 1)?;
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[]");
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[{ count: 1000, total: 1000 }]");
+	assert_eq!(tmp, val);
+	//
+	Ok(())
+}
+
+#[tokio::test]
+async fn insert_statement_select_same_table() -> Result<(), Error> {
+	let sql = "
+		CREATE event:1 SET name = 'one';
+		CREATE event:2 SET name = 'two';
+		INSERT INTO event SELECT name FROM event RETURN NONE;
+		SELECT count() FROM event GROUP ALL;
+		INSERT INTO archive SELECT * FROM event:1, event:2 ORDER BY name DESC LIMIT 1;
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 5);
+	//
+	skip_ok(res, 3)?;
+	// The inserted records are not selected again
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[{ count: 4 }]");
+	assert_eq!(tmp, val);
+	// Clauses which need every row are applied before inserting
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[{ id: archive:2, name: 'two' }]");
+	assert_eq!(tmp, val);
+	//
+	Ok(())
+}
+
+#[tokio::test]
+async fn insert_statement_select_schema() -> Result<(), Error> {
+	let sql = "
+		DEFINE TABLE archive SCHEMAFULL;
+		DEFINE FIELD name ON archive TYPE string;
+		DEFINE FIELD archived ON archive TYPE bool DEFAULT true;
+		DEFINE EVENT log ON archive WHEN $event = 'CREATE' THEN (
+			CREATE log SET record = $after.id
+		);
+		CREATE event:1 SET name = 'one', extra = true;
+		CREATE event:2 SET name = 2;
+		INSERT INTO archive SELECT * FROM event:1;
+		INSERT INTO archive SELECT * FROM event:2;
+		SELECT VALUE record FROM log;
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 9);
+	//
+	skip_ok(res, 6)?;
+	// Each row is checked against the field definitions of the table
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[{ archived: true, id: archive:1, name: 'one' }]");
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result;
+	assert!(matches!(
+		tmp.err(),
+		Some(e) if e.to_string() == "Found 2 for field `name`, with record `archive:2`, but expected a string"
+	));
+	// And the table events are run for each inserted row
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[archive:1]");
+	assert_eq!(tmp, val);
+	//
+	Ok(())
+}
+
 //
 // Permissions
 //
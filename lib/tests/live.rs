@@ -1,10 +1,13 @@
 mod helpers;
 mod parse;
+use parse::Parse;
 
 use helpers::new_ds;
-use surrealdb::dbs::Session;
+use surrealdb::dbs::{Action, Notification, Session};
 use surrealdb::err::Error;
 use surrealdb::fflags::FFLAGS;
+use surrealdb::iam::Role;
+use surrealdb::sql::Thing;
 use surrealdb::sql::Value;
 
 #[tokio::test]
@@ -97,3 +100,291 @@ async fn live_query_sends_registered_lq_details() -> Result<(), Error> {
 
 	Ok(())
 }
+
+#[tokio::test]
+async fn live_query_notifies_insert_update_and_delete() -> Result<(), Error> {
+	let dbs = new_ds().await?.with_notifications();
+	let ses = Session::owner().with_ns("test").with_db("test").with_rt(true);
+	let res = &mut dbs.execute("LIVE SELECT * FROM person WHERE age >= 18", &ses, None).await?;
+	assert_eq!(res.len(), 1);
+	let live_id = match res.remove(0).result? {
+		Value::Uuid(id) => id,
+		_ => panic!("expected uuid"),
+	};
+	let notifications = dbs.notifications().expect("expected notifications");
+	// Records which do not match the condition are not notified
+	let sql = "
+		INSERT INTO person { id: person:one, age: 20 };
+		CREATE person:two SET age = 10;
+		UPDATE person:one SET age = 21;
+		DELETE person:one;
+	";
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 4);
+	for r in res.drain(..) {
+		r.result?;
+	}
+	//
+	let notification = notifications.recv().await.unwrap();
+	assert_eq!(
+		notification,
+		Notification::new(live_id, Action::Create, Value::parse("{ id: person:one, age: 20 }"))
+	);
+	//
+	let notification = notifications.recv().await.unwrap();
+	assert_eq!(
+		notification,
+		Notification::new(live_id, Action::Update, Value::parse("{ id: person:one, age: 21 }"))
+	);
+	//
+	let notification = notifications.recv().await.unwrap();
+	assert_eq!(
+		notification,
+		Notification::new(live_id, Action::Delete, Value::parse("{ id: person:one, age: 21 }"))
+	);
+	assert!(notifications.try_recv().is_err());
+	// Once killed the live query is no longer notified
+	let sql = format!("KILL u{live_id}; CREATE person:three SET age = 30;");
+	let res = &mut dbs.execute(&sql, &ses, None).await?;
+	assert_eq!(res.len(), 2);
+	for r in res.drain(..) {
+		r.result?;
+	}
+	assert!(notifications.try_recv().is_err());
+	//
+	Ok(())
+}
+
+#[tokio::test]
+async fn live_query_kill_requires_ownership() -> Result<(), Error> {
+	if FFLAGS.change_feed_live_queries.enabled() {
+		return Ok(());
+	}
+	let dbs = new_ds().await?.with_auth_enabled(true);
+	let owner = Session::for_level(("test", "test").into(), Role::Viewer)
+		.with_ns("test")
+		.with_db("test")
+		.with_rt(true);
+	let other = Session::for_level(("test", "test").into(), Role::Editor)
+		.with_ns("test")
+		.with_db("test")
+		.with_rt(true);
+	let root = Session::owner().with_ns("test").with_db("test").with_rt(true);
+	// Register two live queries from the same session
+	let sql = "LIVE SELECT * FROM person; LIVE SELECT * FROM person;";
+	let res = &mut dbs.execute(sql, &owner, None).await?;
+	assert_eq!(res.len(), 2);
+	let first = res.remove(0).result?;
+	let second = res.remove(0).result?;
+	// Another session can not kill the live query
+	let sql = format!("KILL u{first}");
+	let res = &mut dbs.execute(&sql, &other, None).await?;
+	assert_eq!(res.len(), 1);
+	//
+	let tmp = res.remove(0).result;
+	assert!(matches!(
+		tmp.err(),
+		Some(e) if e.to_string() == "Can not execute KILL statement using id 'KILL statement uuid belongs to another session'"
+	));
+	// The owning session can kill the live query
+	let res = &mut dbs.execute(&sql, &owner, None).await?;
+	assert_eq!(res.len(), 1);
+	//
+	let tmp = res.remove(0).result?;
+	assert_eq!(tmp, Value::None);
+	// A killed live query no longer exists
+	let res = &mut dbs.execute(&sql, &owner, None).await?;
+	assert_eq!(res.len(), 1);
+	//
+	let tmp = res.remove(0).result;
+	assert!(matches!(
+		tmp.err(),
+		Some(e) if e.to_string() == "Can not execute KILL statement using id 'KILL statement uuid did not exist'"
+	));
+	// A root user can kill any live query
+	let sql = format!("KILL u{second}");
+	let res = &mut dbs.execute(&sql, &root, None).await?;
+	assert_eq!(res.len(), 1);
+	//
+	let tmp = res.remove(0).result?;
+	assert_eq!(tmp, Value::None);
+	//
+	Ok(())
+}
+
+#[tokio::test]
+async fn live_query_uses_the_session_which_registered_it() -> Result<(), Error> {
+	if FFLAGS.change_feed_live_queries.enabled() {
+		return Ok(());
+	}
+	let dbs = new_ds().await?.with_auth_enabled(true).with_notifications();
+	let root = Session::owner().with_ns("test").with_db("test");
+	let user = Session::for_scope("test", "test", "account", Thing::from(("user", "one")).into())
+		.with_rt(true);
+	let sql = "
+		CREATE user:one;
+		DEFINE TABLE person PERMISSIONS
+			FOR select WHERE public = true OR owner = $auth.id
+			FOR create, update, delete NONE;
+	";
+	let res = &mut dbs.execute(sql, &root, None).await?;
+	assert_eq!(res.len(), 2);
+	for r in res.drain(..) {
+		r.result?;
+	}
+	// The live query is registered with the id which is returned
+	let res = &mut dbs.execute("LIVE SELECT * FROM person", &user, None).await?;
+	assert_eq!(res.len(), 1);
+	let live_id = match res.remove(0).result? {
+		Value::Uuid(id) => id,
+		_ => panic!("expected uuid"),
+	};
+	let res = &mut dbs.execute("INFO FOR TABLE person", &root, None).await?;
+	assert_eq!(res.len(), 1);
+	let tmp = res.remove(0).result?.pick(&["lives".into()]);
+	let val = Value::parse(&format!("{{ '{}': 'LIVE SELECT * FROM person' }}", live_id.to_raw()));
+	assert_eq!(tmp, val);
+	// Notifications are checked against the permissions of the registering session
+	let sql = "
+		CREATE person:one SET public = false;
+		CREATE person:two SET public = false, owner = user:one;
+		CREATE person:three SET public = true;
+	";
+	let res = &mut dbs.execute(sql, &root, None).await?;
+	assert_eq!(res.len(), 3);
+	for r in res.drain(..) {
+		r.result?;
+	}
+	let notifications = dbs.notifications().expect("expected notifications");
+	//
+	let notification = notifications.recv().await.unwrap();
+	assert_eq!(
+		notification,
+		Notification::new(
+			live_id,
+			Action::Create,
+			Value::parse("{ id: person:two, owner: user:one, public: false }")
+		)
+	);
+	//
+	let notification = notifications.recv().await.unwrap();
+	assert_eq!(
+		notification,
+		Notification::new(
+			live_id,
+			Action::Create,
+			Value::parse("{ id: person:three, public: true }")
+		)
+	);
+	assert!(notifications.try_recv().is_err());
+	//
+	Ok(())
+}
+
+#[tokio::test]
+async fn live_query_kill_removes_registration() -> Result<(), Error> {
+	if FFLAGS.change_feed_live_queries.enabled() {
+		return Ok(());
+	}
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test").with_rt(true);
+	let res = &mut dbs.execute("LIVE SELECT * FROM person", &ses, None).await?;
+	assert_eq!(res.len(), 1);
+	let live_id = res.remove(0).result?;
+	//
+	let sql = format!(
+		"
+		INFO FOR TABLE person;
+		LET $id = u{live_id};
+		KILL $id;
+		INFO FOR TABLE person;
+		KILL $id;
+		LET $bad = 'not-a-uuid';
+		KILL $bad;
+	"
+	);
+	let res = &mut dbs.execute(&sql, &ses, None).await?;
+	assert_eq!(res.len(), 7);
+	// The live query is registered on the table
+	let tmp = res.remove(0).result?.pick(&["lives".into()]);
+	assert!(matches!(tmp, Value::Object(lives) if lives.len() == 1));
+	//
+	let tmp = res.remove(0).result;
+	assert!(tmp.is_ok());
+	//
+	let tmp = res.remove(0).result?;
+	assert_eq!(tmp, Value::None);
+	// And is removed once killed
+	let tmp = res.remove(0).result?.pick(&["lives".into()]);
+	let val = Value::parse("{}");
+	assert_eq!(tmp, val);
+	// Unknown live query ids are an error
+	let tmp = res.remove(0).result;
+	assert!(matches!(
+		tmp.err(),
+		Some(e) if e.to_string() == "Can not execute KILL statement using id 'KILL statement uuid did not exist'"
+	));
+	//
+	let tmp = res.remove(0).result;
+	assert!(tmp.is_ok());
+	//
+	let tmp = res.remove(0).result;
+	assert!(matches!(
+		tmp.err(),
+		Some(e) if e.to_string() == "Can not execute KILL statement using id 'KILL received a parameter that could not be converted to a UUID'"
+	));
+	//
+	Ok(())
+}
+
+#[tokio::test]
+async fn live_query_kill_by_owner() -> Result<(), Error> {
+	if FFLAGS.change_feed_live_queries.enabled() {
+		return Ok(());
+	}
+	let dbs = new_ds().await?.with_auth_enabled(true);
+	let viewer = Session::for_level(("test", "test").into(), Role::Viewer)
+		.with_ns("test")
+		.with_db("test")
+		.with_rt(true);
+	let ns_owner = Session::for_level(("test",).into(), Role::Owner)
+		.with_ns("test")
+		.with_db("test")
+		.with_rt(true);
+	let db_owner = Session::for_level(("test", "test").into(), Role::Owner)
+		.with_ns("test")
+		.with_db("test")
+		.with_rt(true);
+	let other_owner = Session::for_level(("other",).into(), Role::Owner)
+		.with_ns("test")
+		.with_db("test")
+		.with_rt(true);
+	// Register two live queries as a viewer
+	let sql = "LIVE SELECT * FROM person; LIVE SELECT * FROM person;";
+	let res = &mut dbs.execute(sql, &viewer, None).await?;
+	assert_eq!(res.len(), 2);
+	let first = res.remove(0).result?;
+	let second = res.remove(0).result?;
+	// An owner of another namespace can not kill the live query
+	let sql = format!("KILL u{first}");
+	let res = &mut dbs.execute(&sql, &other_owner, None).await?;
+	assert_eq!(res.len(), 1);
+	//
+	let tmp = res.remove(0).result;
+	assert!(tmp.is_err());
+	// An owner of the namespace can kill the live query
+	let res = &mut dbs.execute(&sql, &ns_owner, None).await?;
+	assert_eq!(res.len(), 1);
+	//
+	let tmp = res.remove(0).result?;
+	assert_eq!(tmp, Value::None);
+	// An owner of the database can kill the live query
+	let sql = format!("KILL u{second}");
+	let res = &mut dbs.execute(&sql, &db_owner, None).await?;
+	assert_eq!(res.len(), 1);
+	//
+	let tmp = res.remove(0).result?;
+	assert_eq!(tmp, Value::None);
+	//
+	Ok(())
+}
@@ -653,3 +653,78 @@ async fn select_where_matches_without_complex_query() -> Result<(), Error> {
 	assert_eq!(format!("{:#}", tmp), format!("{:#}", val_docs));
 	Ok(())
 }
+
+#[tokio::test]
+async fn select_where_matches_without_index() -> Result<(), Error> {
+	let sql = r"
+		CREATE article:1 SET content = 'Rust is a systems programming language';
+		CREATE article:2 SET content = 'SurrealDB is a database written in Rust';
+		CREATE article:3 SET content = ['A multi-model', 'DATABASE for the web'];
+		SELECT VALUE id FROM article WHERE content @@ 'rust';
+		SELECT VALUE id FROM article WHERE content @@ 'rust database';
+		SELECT VALUE id FROM article WHERE content @@ 'DATABASE';
+		SELECT VALUE id FROM article WHERE content @@ 'the database';
+		SELECT VALUE id FROM article WHERE content @@ 'a database';
+		SELECT VALUE id FROM article WHERE content @@ '';
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 9);
+	//
+	for _ in 0..3 {
+		let _ = res.remove(0).result?;
+	}
+	// A single term matches case-insensitively
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[article:1, article:2]");
+	assert_eq!(tmp, val);
+	// Every term of the query must be present
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[article:2]");
+	assert_eq!(tmp, val);
+	// The terms of every string in an array are matched
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[article:2, article:3]");
+	assert_eq!(tmp, val);
+	// Common words are not ignored
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[article:3]");
+	assert_eq!(tmp, val);
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[article:2, article:3]");
+	assert_eq!(tmp, val);
+	// A query without any terms matches nothing
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[]");
+	assert_eq!(tmp, val);
+	Ok(())
+}
+
+#[tokio::test]
+async fn select_where_matches_without_index_on_field() -> Result<(), Error> {
+	let sql = r"
+		CREATE blog:1 SET title = 'Hello World!', content = 'Full-text search in Rust';
+		CREATE blog:2 SET title = 'Goodbye World!', content = 'Rust and databases';
+		DEFINE ANALYZER simple TOKENIZERS blank,class FILTERS lowercase;
+		DEFINE INDEX blog_title ON blog FIELDS title SEARCH ANALYZER simple BM25;
+		SELECT VALUE id FROM blog WHERE title @@ 'world' AND content @@ 'rust databases';
+		RETURN 'Hello World' @@ 'world hello';
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 6);
+	//
+	for _ in 0..4 {
+		let _ = res.remove(0).result?;
+	}
+	// The index is used for one field, and the terms are matched for the other
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[blog:2]");
+	assert_eq!(tmp, val);
+	// Values are matched outside of a query
+	let tmp = res.remove(0).result?;
+	assert_eq!(tmp, Value::Bool(true));
+	Ok(())
+}
@@ -71,3 +71,43 @@ async fn option_import_indexes_should_be_populated() -> Result<(), Error> {
 	//
 	Ok(())
 }
+
+#[tokio::test]
+async fn option_import_keeps_undefined_fields_on_schemafull_tables() -> Result<(), Error> {
+	let sql = "
+		DEFINE TABLE person SCHEMAFULL;
+		DEFINE FIELD name ON person TYPE string;
+		CREATE person:one SET name = 'Tobie', age = 30;
+		OPTION IMPORT;
+		CREATE person:two SET name = 'Jaime', age = 25;
+		SELECT * FROM person;
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 5);
+	//
+	for _ in 0..2 {
+		let tmp = res.remove(0).result;
+		assert!(tmp.is_ok());
+	}
+	// Undefined fields are removed from schemafull tables
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[{ id: person:one, name: 'Tobie' }]");
+	assert_eq!(tmp, val);
+	// Unless the data is being imported
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[{ age: 25, id: person:two, name: 'Jaime' }]");
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse(
+		"[
+			{ id: person:one, name: 'Tobie' },
+			{ age: 25, id: person:two, name: 'Jaime' }
+		]",
+	);
+	assert_eq!(tmp, val);
+	//
+	Ok(())
+}
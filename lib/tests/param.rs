@@ -2,6 +2,7 @@ mod parse;
 use parse::Parse;
 mod helpers;
 use helpers::new_ds;
+use std::collections::BTreeMap;
 use surrealdb::dbs::Session;
 use surrealdb::err::Error;
 use surrealdb::sql::Value;
@@ -102,3 +103,69 @@ async fn parameter_outside_database() -> Result<(), Error> {
 
 	Ok(())
 }
+
+#[tokio::test]
+async fn session_variables_persist_across_queries() -> Result<(), Error> {
+	let dbs = new_ds().await?;
+	let mut ses = Session::owner().with_ns("test").with_db("test");
+	ses.set_variable("greeting", Value::from("Hello"));
+	// Session variables can be used in a query
+	let sql = "LET $name = 'Tobie'; RETURN $greeting;";
+	let res = &mut dbs.execute_in_session(sql, &mut ses, None).await?;
+	assert_eq!(res.len(), 2);
+	//
+	let tmp = res.remove(0).result?;
+	assert_eq!(tmp, Value::None);
+	//
+	let tmp = res.remove(0).result?;
+	assert_eq!(tmp, Value::from("Hello"));
+	// LET statements are kept in the session
+	assert_eq!(ses.get_variable("name"), Some(&Value::from("Tobie")));
+	let sql = "RETURN string::concat($greeting, ' ', $name);";
+	let res = &mut dbs.execute_in_session(sql, &mut ses, None).await?;
+	let tmp = res.remove(0).result?;
+	assert_eq!(tmp, Value::from("Hello Tobie"));
+	// Later LET statements replace the session variables
+	let sql = "LET $name = 'Jaime'; RETURN $name;";
+	let res = &mut dbs.execute_in_session(sql, &mut ses, None).await?;
+	let tmp = res.remove(1).result?;
+	assert_eq!(tmp, Value::from("Jaime"));
+	assert_eq!(ses.get_variable("name"), Some(&Value::from("Jaime")));
+	// Query variables take precedence over session variables
+	let vars = BTreeMap::from([("name".to_string(), Value::from("Other"))]);
+	let res = &mut dbs.execute("RETURN $name;", &ses, Some(vars)).await?;
+	let tmp = res.remove(0).result?;
+	assert_eq!(tmp, Value::from("Other"));
+	// Queries which do not run in the session do not change its variables
+	let res = &mut dbs.execute("LET $other = 1; RETURN $name;", &ses, None).await?;
+	let tmp = res.remove(1).result?;
+	assert_eq!(tmp, Value::from("Jaime"));
+	assert_eq!(ses.get_variable("other"), None);
+	// Session variables can not replace protected parameters
+	ses.set_variable("session", Value::from("invalid"));
+	let res = &mut dbs.execute_in_session("RETURN $session.ns;", &mut ses, None).await?;
+	let tmp = res.remove(0).result?;
+	assert_eq!(tmp, Value::from("test"));
+	//
+	Ok(())
+}
+
+#[tokio::test]
+async fn session_variables_within_transactions() -> Result<(), Error> {
+	let dbs = new_ds().await?;
+	let mut ses = Session::owner().with_ns("test").with_db("test");
+	// Variables set within a cancelled transaction are not kept
+	let sql = "BEGIN; LET $cancelled = 1; CANCEL;";
+	dbs.execute_in_session(sql, &mut ses, None).await?;
+	assert_eq!(ses.get_variable("cancelled"), None);
+	// Variables set within a failed transaction are not kept
+	let sql = "BEGIN; LET $failed = 1; THROW 'failure'; COMMIT;";
+	dbs.execute_in_session(sql, &mut ses, None).await?;
+	assert_eq!(ses.get_variable("failed"), None);
+	// Variables set within a committed transaction are kept
+	let sql = "BEGIN; LET $committed = 1; COMMIT;";
+	dbs.execute_in_session(sql, &mut ses, None).await?;
+	assert_eq!(ses.get_variable("committed"), Some(&Value::from(1)));
+	//
+	Ok(())
+}
@@ -144,6 +144,87 @@ async fn select_where_iterate_two_no_index() -> Result<(), Error> {
 	Ok(())
 }
 
+#[tokio::test]
+async fn select_where_with_unknown_index() -> Result<(), Error> {
+	let sql = "
+		DEFINE INDEX uniq_name ON TABLE person COLUMNS name UNIQUE;
+		CREATE person:tobie SET name = 'Tobie';
+		SELECT name FROM person WITH INDEX uniq_name, idx_missing WHERE name = 'Tobie';
+		SELECT name FROM person WITH INDEX idx_missing;
+		SELECT name FROM person WITH INDEX uniq_name WHERE name = 'Tobie';
+	";
+	let dbs = new_ds().await?;
+	let mut res = execute_test(&dbs, sql, 5).await?;
+	skip_ok(&mut res, 2)?;
+	// A hinted index which does not exist is an error
+	for _ in 0..2 {
+		let tmp = res.remove(0).result;
+		assert!(matches!(
+			tmp.err(),
+			Some(e) if e.to_string() == "The index 'idx_missing' does not exist"
+		));
+	}
+	//
+	check_result(&mut res, "[{ name: 'Tobie' }]")?;
+	Ok(())
+}
+
+#[tokio::test]
+async fn select_where_composite_index() -> Result<(), Error> {
+	let sql = "
+		DEFINE INDEX city_status ON TABLE order COLUMNS city, status;
+		CREATE order:1 SET city = 'London', status = 'open';
+		CREATE order:2 SET city = 'London', status = 'closed';
+		CREATE order:3 SET city = 'Paris', status = 'open';
+		INFO FOR TABLE order;
+		SELECT VALUE id FROM order WHERE city = 'London' AND status = 'open';
+		SELECT VALUE id FROM order WHERE city = 'London';
+		SELECT VALUE id FROM order WHERE city = 'London' AND status = 'open' EXPLAIN;
+	";
+	let dbs = new_ds().await?;
+	let res = &mut execute_test(&dbs, sql, 8).await?;
+	skip_ok(res, 4)?;
+	//
+	check_result(
+		res,
+		"{
+			events: {},
+			fields: {},
+			indexes: { city_status: 'DEFINE INDEX city_status ON order FIELDS city, status' },
+			lives: {},
+			tables: {}
+		}",
+	)?;
+	// Lookups over all or some of the indexed fields return the matching records
+	check_result(res, "[order:1]")?;
+	check_result(res, "[order:1, order:2]")?;
+	// Composite indexes are not used by the query planner
+	check_result(
+		res,
+		"[
+			{
+				detail: {
+					table: 'order'
+				},
+				operation: 'Iterate Table'
+			},
+			{
+				detail: {
+					reason: 'NO INDEX FOUND'
+				},
+				operation: 'Fallback'
+			},
+			{
+				detail: {
+					type: 'Memory'
+				},
+				operation: 'Collector'
+			}
+		]",
+	)?;
+	Ok(())
+}
+
 async fn execute_test(
 	dbs: &Datastore,
 	sql: &str,
@@ -2600,3 +2681,167 @@ async fn select_with_non_boolean_expression() -> Result<(), Error> {
 	//
 	Ok(())
 }
+
+#[tokio::test]
+async fn select_with_index_estimate() -> Result<(), Error> {
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let sql = "
+		DEFINE INDEX status ON TABLE person COLUMNS status;
+		CREATE |person:1500| SET status = 'active' RETURN NONE;
+		CREATE |person:500| SET status = 'inactive' RETURN NONE;
+		SELECT * FROM person WHERE status = 'active' EXPLAIN;
+		SELECT * FROM person WHERE status = 'inactive' EXPLAIN;
+		SELECT * FROM person WITH INDEX status WHERE status = 'active' EXPLAIN;
+		SELECT count() FROM person WHERE status = 'active' GROUP ALL;
+	";
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 7);
+	skip_ok(res, 3)?;
+	// The table is scanned when most of its records match
+	let tmp = res.remove(0).result?;
+	let val = Value::parse(
+		r#"[
+				{
+					detail: {
+						table: 'person'
+					},
+					operation: 'Iterate Table'
+				},
+				{
+					detail: {
+						reason: 'ESTIMATED TO MATCH MOST RECORDS'
+					},
+					operation: 'Fallback'
+				},
+				{
+					detail: {
+						type: 'Memory'
+					},
+					operation: 'Collector'
+				}
+			]"#,
+	);
+	assert_eq!(format!("{:#}", tmp), format!("{:#}", val));
+	// The index is used when few of the records match
+	let tmp = res.remove(0).result?;
+	let val = Value::parse(
+		r#"[
+				{
+					detail: {
+						plan: {
+							index: 'status',
+							operator: '=',
+							value: 'inactive'
+						},
+						table: 'person'
+					},
+					operation: 'Iterate Index'
+				},
+				{
+					detail: {
+						type: 'Memory'
+					},
+					operation: 'Collector'
+				}
+			]"#,
+	);
+	assert_eq!(format!("{:#}", tmp), format!("{:#}", val));
+	// An index which is specified is always used
+	let tmp = res.remove(0).result?;
+	let val = Value::parse(
+		r#"[
+				{
+					detail: {
+						plan: {
+							index: 'status',
+							operator: '=',
+							value: 'active'
+						},
+						table: 'person'
+					},
+					operation: 'Iterate Index'
+				},
+				{
+					detail: {
+						type: 'Memory'
+					},
+					operation: 'Collector'
+				}
+			]"#,
+	);
+	assert_eq!(format!("{:#}", tmp), format!("{:#}", val));
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[{ count: 1500 }]");
+	assert_eq!(tmp, val);
+	//
+	Ok(())
+}
+
+#[tokio::test]
+async fn select_index_range_narrowed_by_conjunction() -> Result<(), Error> {
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test").with_collect_metrics(true);
+	let sql = "
+		DEFINE INDEX idx_age ON person FIELDS age;
+		CREATE |person:1..50|;
+		UPDATE person SET age = meta::id(id) RETURN NONE;
+		SELECT VALUE age FROM person WHERE age >= 18 AND age <= 30 AND age > 25 EXPLAIN;
+		SELECT VALUE age FROM person WHERE age >= 18 AND age <= 30 AND age > 25;
+		SELECT VALUE age FROM person WHERE age >= 18 AND age < 30 AND age <= 20 AND age != 19;
+		SELECT VALUE age FROM person WHERE age > 30 AND age < 18;
+	";
+	let mut res = dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 7);
+	skip_ok(&mut res, 3)?;
+	// The tightest bounds of the conjunction are used for the range scan
+	let tmp = res.remove(0).result?;
+	let val = Value::parse(
+		r#"[
+			{
+				detail: {
+					plan: {
+						from: {
+							inclusive: false,
+							value: 25
+						},
+						index: 'idx_age',
+						to: {
+							inclusive: true,
+							value: 30
+						}
+					},
+					table: 'person'
+				},
+				operation: 'Iterate Index'
+			},
+			{
+				detail: {
+					type: 'Memory'
+				},
+				operation: 'Collector'
+			}
+		]"#,
+	);
+	assert_eq!(format!("{:#}", tmp), format!("{:#}", val));
+	// Only the keys within the range are read
+	let tmp = res.remove(0);
+	let metrics = tmp.metrics.unwrap();
+	assert_eq!(tmp.result?, Value::parse("[26, 27, 28, 29, 30]"));
+	assert_eq!(metrics.records_scanned, 5);
+	assert_eq!(metrics.records_filtered, 0);
+	// Residual conditions are filtered within the range
+	let tmp = res.remove(0);
+	let metrics = tmp.metrics.unwrap();
+	assert_eq!(tmp.result?, Value::parse("[18, 20]"));
+	assert_eq!(metrics.records_scanned, 3);
+	assert_eq!(metrics.records_filtered, 1);
+	// Contradictory bounds read no keys
+	let tmp = res.remove(0);
+	let metrics = tmp.metrics.unwrap();
+	assert_eq!(tmp.result?, Value::parse("[]"));
+	assert_eq!(metrics.records_scanned, 0);
+	//
+	Ok(())
+}
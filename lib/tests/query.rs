@@ -1,7 +1,9 @@
 mod parse;
 use parse::Parse;
 mod helpers;
+use chrono::Utc;
 use helpers::new_ds;
+use std::time::Duration;
 use surrealdb::dbs::Session;
 use surrealdb::err::Error;
 use surrealdb::sql::Value;
@@ -161,3 +163,58 @@ async fn query_root_record() -> Result<(), Error> {
 	//
 	Ok(())
 }
+
+#[tokio::test]
+async fn query_response_time() -> Result<(), Error> {
+	let sql = "
+		SLEEP 50ms;
+		RETURN [sleep(50ms), <int> 'failed'];
+		CREATE person:test SET slow = sleep(500ms) TIMEOUT 50ms;
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 3);
+	// Each statement records how long it took
+	let tmp = res.remove(0);
+	assert!(tmp.result.is_ok());
+	assert!(tmp.time >= Duration::from_millis(50));
+	// Including statements which fail
+	let tmp = res.remove(0);
+	assert!(tmp.result.is_err());
+	assert!(tmp.time >= Duration::from_millis(50));
+	// The time is serialized along with the status
+	let val = serde_json::to_value(&tmp).unwrap();
+	assert_eq!(val["status"], "ERR");
+	assert_eq!(val["time"], format!("{:?}", tmp.time));
+	// And statements which time out
+	let tmp = res.remove(0);
+	assert!(matches!(tmp.result, Err(Error::QueryTimedout)));
+	assert!(tmp.time >= Duration::from_millis(50));
+	assert!(tmp.time < Duration::from_millis(500));
+	//
+	Ok(())
+}
+
+#[tokio::test]
+async fn query_with_expired_session() -> Result<(), Error> {
+	let sql = "RETURN true;";
+	let dbs = new_ds().await?;
+	// A session without an expiration is always accepted
+	let mut ses = Session::owner().with_ns("test").with_db("test");
+	assert!(!ses.is_expired());
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.remove(0).result?, Value::Bool(true));
+	// A session which has not yet expired is accepted
+	ses.exp = Some(Utc::now().timestamp() + 60);
+	assert!(!ses.is_expired());
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.remove(0).result?, Value::Bool(true));
+	// A session which has expired is rejected
+	ses.exp = Some(Utc::now().timestamp() - 60);
+	assert!(ses.is_expired());
+	let res = dbs.execute(sql, &ses, None).await;
+	assert!(matches!(res, Err(Error::ExpiredSession)));
+	//
+	Ok(())
+}
@@ -2,10 +2,11 @@ mod parse;
 use parse::Parse;
 mod helpers;
 use helpers::new_ds;
+use helpers::skip_ok;
 use surrealdb::dbs::Session;
 use surrealdb::err::Error;
 use surrealdb::iam::Role;
-use surrealdb::sql::Value;
+use surrealdb::sql::{Cursor, Thing, Value};
 
 #[tokio::test]
 async fn select_field_value() -> Result<(), Error> {
@@ -153,6 +154,52 @@ async fn select_field_and_omit() -> Result<(), Error> {
 	Ok(())
 }
 
+#[tokio::test]
+async fn select_omit_nested_and_missing_fields() -> Result<(), Error> {
+	let sql = "
+		CREATE user:one SET name = 'One', password = 'secret', internal = { notes: 'private', score: 1 };
+		CREATE user:two SET name = 'Two';
+		SELECT * OMIT password, internal.notes FROM user;
+		SELECT * OMIT missing, internal.missing.deep FROM user:two;
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 4);
+	skip_ok(res, 2)?;
+	// Nested paths are removed, and records without the fields are unchanged
+	let tmp = res.remove(0).result?;
+	let val = Value::parse(
+		"[
+			{
+				id: user:one,
+				internal: {
+					score: 1,
+				},
+				name: 'One',
+			},
+			{
+				id: user:two,
+				name: 'Two',
+			}
+		]",
+	);
+	assert_eq!(tmp, val);
+	// Omitting fields which do not exist is a no-op
+	let tmp = res.remove(0).result?;
+	let val = Value::parse(
+		"[
+			{
+				id: user:two,
+				name: 'Two',
+			}
+		]",
+	);
+	assert_eq!(tmp, val);
+	//
+	Ok(())
+}
+
 #[tokio::test]
 async fn select_expression_value() -> Result<(), Error> {
 	let sql = "
@@ -1155,3 +1202,1236 @@ async fn select_issue_3510() -> Result<(), Error> {
 	assert_eq!(format!("{:#}", tmp), format!("{:#}", val));
 	Ok(())
 }
+
+#[tokio::test]
+async fn select_distinct() -> Result<(), Error> {
+	let sql = "
+		CREATE person:1 SET name = 'Tobie', city = 'London';
+		CREATE person:2 SET name = 'Jaime', city = 'London';
+		CREATE person:3 SET name = 'Tobie', city = 'Paris';
+		CREATE person:4 SET name = 'Tobie', city = 'London';
+		SELECT DISTINCT name FROM person;
+		SELECT DISTINCT name, city FROM person ORDER BY city, name;
+		SELECT DISTINCT city FROM person ORDER BY city LIMIT 1 START 1;
+		SELECT DISTINCT count(), city FROM person GROUP BY city;
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 8);
+	//
+	skip_ok(res, 4)?;
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse(
+		"[
+			{
+				name: 'Tobie'
+			},
+			{
+				name: 'Jaime'
+			}
+		]",
+	);
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse(
+		"[
+			{
+				city: 'London',
+				name: 'Jaime'
+			},
+			{
+				city: 'London',
+				name: 'Tobie'
+			},
+			{
+				city: 'Paris',
+				name: 'Tobie'
+			}
+		]",
+	);
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse(
+		"[
+			{
+				city: 'Paris'
+			}
+		]",
+	);
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse(
+		"[
+			{
+				city: 'London',
+				count: 3
+			},
+			{
+				city: 'Paris',
+				count: 1
+			}
+		]",
+	);
+	assert_eq!(tmp, val);
+	//
+	Ok(())
+}
+
+#[tokio::test]
+async fn select_distinct_values() -> Result<(), Error> {
+	let sql = "
+		SELECT DISTINCT VALUE $this FROM [1, 2, 1, 'a', 'a', 1.5, true, true, NONE, NONE];
+		SELECT DISTINCT * FROM [{ a: 1, b: [1, 2] }, { b: [1, 2], a: 1 }, { a: 1, b: [2, 1] }];
+		SELECT DISTINCT VALUE tags FROM [{ tags: [[1, 2], [3]] }, { tags: [[1, 2], [3]] }, { tags: [[1, 2]] }];
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 3);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[1, 2, 'a', 1.5, true, NONE]");
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse(
+		"[
+			{
+				a: 1,
+				b: [1, 2]
+			},
+			{
+				a: 1,
+				b: [2, 1]
+			}
+		]",
+	);
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[[[1, 2], [3]], [[1, 2]]]");
+	assert_eq!(tmp, val);
+	//
+	Ok(())
+}
+
+#[tokio::test]
+async fn select_for_update_blocks_concurrent_writes() -> Result<(), Error> {
+	let dbs = std::sync::Arc::new(new_ds().await?);
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute("CREATE person:1 SET x = 0", &ses, None).await?;
+	assert_eq!(res.len(), 1);
+	res.remove(0).result?;
+	//
+	let run = |sql: &'static str, delay: u64| {
+		let dbs = dbs.clone();
+		let ses = ses.clone();
+		tokio::spawn(async move {
+			tokio::time::sleep(std::time::Duration::from_millis(delay)).await;
+			let res = &mut dbs.execute(sql, &ses, None).await?;
+			assert_eq!(res.len(), 1);
+			res.remove(0).result?;
+			Ok::<_, Error>(std::time::Instant::now())
+		})
+	};
+	// The locked read holds the record until it completes
+	let read = run("SELECT *, sleep(500ms) AS s FROM person:1 FOR UPDATE", 0);
+	let write = run("UPDATE person:1 SET x = 1", 100);
+	let read = read.await.unwrap()?;
+	let write = write.await.unwrap()?;
+	assert!(write > read);
+	// Without the lock the read does not block the write
+	let read = run("SELECT *, sleep(500ms) AS s FROM person:1", 0);
+	let write = run("UPDATE person:1 SET x = 2", 100);
+	let read = read.await.unwrap()?;
+	let write = write.await.unwrap()?;
+	assert!(write < read);
+	//
+	let res = &mut dbs.execute("SELECT VALUE x FROM person:1 FOR SHARE", &ses, None).await?;
+	assert_eq!(res.len(), 1);
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[2]");
+	assert_eq!(tmp, val);
+	//
+	Ok(())
+}
+
+#[tokio::test]
+async fn select_limit_percentage() -> Result<(), Error> {
+	let sql = "
+		CREATE |person:1..10| SET num = meta::id(id);
+		SELECT VALUE num FROM person ORDER BY num LIMIT 20%;
+		SELECT VALUE num FROM person ORDER BY num LIMIT 25%;
+		SELECT VALUE num FROM person ORDER BY num LIMIT 5%;
+		SELECT VALUE num FROM person ORDER BY num LIMIT 0%;
+		SELECT VALUE num FROM person ORDER BY num LIMIT 100%;
+		SELECT VALUE num FROM person ORDER BY num LIMIT 50% START 4;
+		SELECT VALUE num FROM person WHERE num > 10 LIMIT 50%;
+		SELECT VALUE num FROM person LIMIT 101%;
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 9);
+	//
+	let tmp = res.remove(0).result;
+	assert!(tmp.is_ok());
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[1, 2]");
+	assert_eq!(tmp, val);
+	// Fractional rows are rounded down
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[1, 2]");
+	assert_eq!(tmp, val);
+	// A non-zero percentage returns at least one row
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[1]");
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[]");
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[1, 2, 3, 4, 5, 6, 7, 8, 9, 10]");
+	assert_eq!(tmp, val);
+	// The percentage applies to the rows after START
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[5, 6, 7]");
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[]");
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result;
+	assert!(matches!(
+		tmp.err(),
+		Some(e) if e.to_string() == "Found 101 but the LIMIT clause must evaluate to a percentage between 0 and 100"
+	));
+	//
+	Ok(())
+}
+
+#[tokio::test]
+async fn select_sample_records() -> Result<(), Error> {
+	let sql = "
+		CREATE |person:1..100| SET num = meta::id(id);
+		SELECT VALUE num FROM person SAMPLE 10 RECORDS;
+		SELECT VALUE num FROM person SAMPLE 500 RECORDS;
+		SELECT VALUE num FROM person WHERE num <= 20 SAMPLE 5 RECORDS LIMIT 3;
+		SELECT count() AS count, num > 50 AS big FROM person GROUP BY big SAMPLE 1 RECORDS;
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 5);
+	//
+	let tmp = res.remove(0).result;
+	assert!(tmp.is_ok());
+	// A sample of distinct records
+	let Value::Array(mut tmp) = res.remove(0).result? else {
+		panic!("expected an array");
+	};
+	assert_eq!(tmp.len(), 10);
+	tmp.sort();
+	tmp.dedup();
+	assert_eq!(tmp.len(), 10);
+	// A sample larger than the table returns every record
+	let Value::Array(tmp) = res.remove(0).result? else {
+		panic!("expected an array");
+	};
+	assert_eq!(tmp.len(), 100);
+	// Only the matching records are sampled
+	let Value::Array(tmp) = res.remove(0).result? else {
+		panic!("expected an array");
+	};
+	assert_eq!(tmp.len(), 3);
+	assert!(tmp.iter().all(|v| v <= &Value::from(20)));
+	// Groups are sampled after they are computed
+	let Value::Array(tmp) = res.remove(0).result? else {
+		panic!("expected an array");
+	};
+	assert_eq!(tmp.len(), 1);
+	//
+	Ok(())
+}
+
+#[tokio::test]
+async fn select_limit_start_params() -> Result<(), Error> {
+	let sql = "
+		CREATE |person:1..10| SET num = meta::id(id);
+		LET $size = 3;
+		SELECT VALUE num FROM person ORDER BY num LIMIT $size START $size;
+		SELECT VALUE num FROM person ORDER BY num LIMIT $size - 1 START $size * 2 + 1;
+		SELECT VALUE num FROM person LIMIT -1;
+		SELECT VALUE num FROM person LIMIT 1.5;
+		SELECT VALUE num FROM person START true;
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 7);
+	//
+	for _ in 0..2 {
+		let tmp = res.remove(0).result;
+		assert!(tmp.is_ok());
+	}
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[4, 5, 6]");
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[8, 9]");
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result;
+	assert!(matches!(
+		tmp.err(),
+		Some(e) if e.to_string() == "Found -1 but the LIMIT clause must evaluate to a positive integer"
+	));
+	//
+	let tmp = res.remove(0).result;
+	assert!(matches!(
+		tmp.err(),
+		Some(e) if e.to_string() == "Found 1.5f but the LIMIT clause must evaluate to a positive integer"
+	));
+	//
+	let tmp = res.remove(0).result;
+	assert!(matches!(
+		tmp.err(),
+		Some(e) if e.to_string() == "Found true but the START clause must evaluate to a positive integer"
+	));
+	//
+	Ok(())
+}
+
+#[tokio::test]
+async fn select_limit_start_cursor() -> Result<(), Error> {
+	let sql = "
+		CREATE |person:1..10| SET num = meta::id(id);
+		LET $size = 4;
+		LET $cursor = 0;
+		SELECT VALUE num FROM person ORDER BY num START $cursor * $size LIMIT math::min([$size, 10 - $cursor * $size]);
+		LET $cursor = 2;
+		SELECT VALUE num FROM person ORDER BY num START $cursor * $size LIMIT math::min([$size, 10 - $cursor * $size]);
+		LET $cursor = 3;
+		SELECT VALUE num FROM person ORDER BY num START $cursor * $size LIMIT math::min([$size, 10 - $cursor * $size]);
+		SELECT VALUE num FROM person START 9223372036854775807 LIMIT 9223372036854775807;
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 9);
+	//
+	skip_ok(res, 3)?;
+	// Paging expressions can use params and function calls
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[1, 2, 3, 4]");
+	assert_eq!(tmp, val);
+	//
+	skip_ok(res, 1)?;
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[9, 10]");
+	assert_eq!(tmp, val);
+	//
+	skip_ok(res, 1)?;
+	// A computed value must still be a positive integer
+	let tmp = res.remove(0).result;
+	assert!(matches!(
+		tmp.err(),
+		Some(e) if e.to_string() == "Found -2 but the LIMIT clause must evaluate to a positive integer"
+	));
+	// Values beyond the result set return nothing
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[]");
+	assert_eq!(tmp, val);
+	//
+	Ok(())
+}
+
+#[tokio::test]
+async fn select_explain_prefix() -> Result<(), Error> {
+	let sql = "
+		CREATE person:1 SET name = 'Tobie';
+		CREATE person:2 SET name = 'Jaime';
+		DEFINE INDEX name ON person FIELDS name;
+		EXPLAIN SELECT * FROM person:1, person SAMPLE 1 RECORDS;
+		EXPLAIN FULL SELECT * FROM person WHERE name = 'Tobie';
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 5);
+	//
+	for _ in 0..3 {
+		let tmp = res.remove(0).result;
+		assert!(tmp.is_ok());
+	}
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse(
+		"[
+			{
+				detail: {
+					thing: person:1
+				},
+				operation: 'Iterate Thing'
+			},
+			{
+				detail: {
+					table: 'person'
+				},
+				operation: 'Iterate Table'
+			},
+			{
+				detail: {
+					type: 'Sample'
+				},
+				operation: 'Collector'
+			}
+		]",
+	);
+	assert_eq!(format!("{:#}", tmp), format!("{:#}", val));
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse(
+		"[
+			{
+				detail: {
+					plan: {
+						index: 'name',
+						operator: '=',
+						value: 'Tobie'
+					},
+					table: 'person'
+				},
+				operation: 'Iterate Index'
+			},
+			{
+				detail: {
+					type: 'Memory'
+				},
+				operation: 'Collector'
+			},
+			{
+				detail: {
+					count: 1
+				},
+				operation: 'Fetch'
+			}
+		]",
+	);
+	assert_eq!(format!("{:#}", tmp), format!("{:#}", val));
+	//
+	Ok(())
+}
+
+#[tokio::test]
+async fn select_explain_analyze() -> Result<(), Error> {
+	let sql = "
+		CREATE |person:1..10| SET age = meta::id(id) RETURN NONE;
+		EXPLAIN ANALYZE SELECT * FROM person WHERE age > 6;
+		SELECT * FROM person WHERE age > 6 LIMIT 2 EXPLAIN ANALYZE;
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 3);
+	//
+	skip_ok(res, 1)?;
+	// The plan is followed by the actual runtime statistics, and
+	// a limited query stops scanning once it has enough records
+	for (scanned, returned) in [(10, 4), (8, 2)] {
+		let Value::Array(mut tmp) = res.remove(0).result? else {
+			unreachable!()
+		};
+		let stats = tmp.pop().unwrap();
+		let detail = |field: &str| stats.pick(&["detail".into(), field.into()]);
+		assert_eq!(stats.pick(&["operation".into()]), Value::from("Statistics"));
+		assert_eq!(detail("records_scanned"), Value::from(scanned));
+		assert_eq!(detail("records_filtered"), Value::from(6));
+		assert_eq!(detail("records_returned"), Value::from(returned));
+		assert_eq!(detail("index_hits"), Value::from(0));
+		assert!(matches!(detail("elapsed_us"), Value::Number(_)));
+		let fetch = tmp.pop().unwrap();
+		assert_eq!(fetch.pick(&["detail".into(), "count".into()]), Value::from(returned));
+		let val = Value::parse(
+			"[
+				{
+					detail: {
+						table: 'person'
+					},
+					operation: 'Iterate Table'
+				},
+				{
+					detail: {
+						reason: 'NO INDEX FOUND'
+					},
+					operation: 'Fallback'
+				},
+				{
+					detail: {
+						type: 'Memory'
+					},
+					operation: 'Collector'
+				}
+			]",
+		);
+		assert_eq!(format!("{:#}", Value::from(tmp)), format!("{:#}", val));
+	}
+	//
+	Ok(())
+}
+
+#[tokio::test]
+async fn select_limit_zero() -> Result<(), Error> {
+	let sql = "
+		CREATE |person:1..3|;
+		SELECT VALUE id FROM person LIMIT 0;
+		SELECT VALUE id FROM person START 0;
+		SELECT VALUE id FROM person LIMIT 0 START 1;
+		SELECT VALUE id FROM ONLY person:1 LIMIT 0;
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 5);
+	//
+	let tmp = res.remove(0).result;
+	assert!(tmp.is_ok());
+	// An explicit LIMIT 0 is not the same as no limit
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[]");
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[person:1, person:2, person:3]");
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[]");
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("NONE");
+	assert_eq!(tmp, val);
+	//
+	Ok(())
+}
+
+#[tokio::test]
+async fn select_order_nulls() -> Result<(), Error> {
+	let sql = "
+		CREATE item:1 SET num = 2;
+		CREATE item:2 SET num = NULL;
+		CREATE item:3 SET num = 1;
+		CREATE item:4;
+		CREATE item:5 SET num = 'a';
+		RETURN (SELECT id, num FROM item ORDER BY num ASC NULLS LAST, id).id;
+		RETURN (SELECT id, num FROM item ORDER BY num DESC NULLS FIRST, id).id;
+		RETURN (SELECT id, num FROM item ORDER BY num DESC NULLS LAST).id;
+		RETURN (SELECT id, num FROM item ORDER BY num ASC).id;
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 9);
+	//
+	for _ in 0..5 {
+		let tmp = res.remove(0).result;
+		assert!(tmp.is_ok());
+	}
+	// Missing and null values are ordered together
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[item:3, item:1, item:5, item:2, item:4]");
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[item:2, item:4, item:5, item:1, item:3]");
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[item:5, item:1, item:3, item:2, item:4]");
+	assert_eq!(tmp, val);
+	// By default NONE and NULL come first when ascending
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[item:4, item:2, item:3, item:1, item:5]");
+	assert_eq!(tmp, val);
+	//
+	Ok(())
+}
+
+#[tokio::test]
+async fn select_parallel_multiple_targets() -> Result<(), Error> {
+	let mut sql = String::new();
+	for (i, tb) in ["a", "b", "c"].iter().enumerate() {
+		for n in 1..=50 {
+			sql.push_str(&format!("CREATE {tb}:{n} SET num = {};", i * 50 + n));
+		}
+	}
+	sql.push_str(
+		"
+		SELECT VALUE num FROM a, b, c ORDER BY num;
+		SELECT VALUE num FROM a, b, c ORDER BY num PARALLEL;
+		SELECT VALUE num FROM c, b, a ORDER BY num DESC LIMIT 5 PARALLEL;
+		RETURN count(SELECT * FROM a, b, c PARALLEL);
+	",
+	);
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(&sql, &ses, None).await?;
+	assert_eq!(res.len(), 154);
+	//
+	for _ in 0..150 {
+		let tmp = res.remove(0).result;
+		assert!(tmp.is_ok());
+	}
+	// Parallel iteration returns the same ordered results
+	let sequential = res.remove(0).result?;
+	let parallel = res.remove(0).result?;
+	assert_eq!(sequential, parallel);
+	let val = Value::Array((1..=150).map(Value::from).collect::<Vec<_>>().into());
+	assert_eq!(parallel, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[150, 149, 148, 147, 146]");
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::from(150);
+	assert_eq!(tmp, val);
+	//
+	Ok(())
+}
+
+#[tokio::test]
+async fn select_order_collate() -> Result<(), Error> {
+	let sql = "
+		CREATE fruit:1 SET name = 'banana';
+		CREATE fruit:2 SET name = 'Cherry';
+		CREATE fruit:3 SET name = 'apple';
+		CREATE fruit:4 SET name = 'Äpfel';
+		CREATE fruit:5 SET name = 'Apricot';
+		CREATE fruit:6 SET name = 10;
+		CREATE fruit:7 SET name = NULL;
+		SELECT VALUE name FROM fruit ORDER BY name;
+		SELECT VALUE name FROM fruit ORDER BY name COLLATE;
+		SELECT VALUE name FROM fruit ORDER BY name COLLATE DESC;
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 10);
+	//
+	for _ in 0..7 {
+		let tmp = res.remove(0).result;
+		assert!(tmp.is_ok());
+	}
+	// Uppercase strings are ordered before lowercase strings by default
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[NULL, 10, 'Apricot', 'Cherry', 'apple', 'banana', 'Äpfel']");
+	assert_eq!(tmp, val);
+	// Strings are compared without case, and other values as normal
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[NULL, 10, 'Äpfel', 'apple', 'Apricot', 'banana', 'Cherry']");
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("['Cherry', 'banana', 'Apricot', 'apple', 'Äpfel', 10, NULL]");
+	assert_eq!(tmp, val);
+	//
+	Ok(())
+}
+
+#[tokio::test]
+async fn select_order_collate_locale() -> Result<(), Error> {
+	let sql = r#"
+		CREATE fruit:1 SET name = 'Zucker';
+		CREATE fruit:2 SET name = 'Ärger';
+		CREATE fruit:3 SET name = 'Birne';
+		CREATE fruit:4 SET name = 'Apfel';
+		SELECT VALUE name FROM fruit ORDER BY name;
+		SELECT VALUE name FROM fruit ORDER BY name COLLATE "de-DE";
+		SELECT VALUE name FROM fruit ORDER BY name COLLATE "de-DE" DESC;
+	"#;
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 7);
+	//
+	skip_ok(res, 4)?;
+	// Strings are ordered by their bytes by default
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("['Apfel', 'Birne', 'Zucker', 'Ärger']");
+	assert_eq!(tmp, val);
+	// The German collation sorts `ä` between `a` and `z`
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("['Apfel', 'Ärger', 'Birne', 'Zucker']");
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("['Zucker', 'Birne', 'Ärger', 'Apfel']");
+	assert_eq!(tmp, val);
+	// Locales which need a tailored collation are rejected
+	let sql = r#"SELECT VALUE name FROM fruit ORDER BY name COLLATE "sv-SE""#;
+	assert!(dbs.execute(sql, &ses, None).await.is_err());
+	//
+	Ok(())
+}
+
+#[tokio::test]
+async fn select_order_random() -> Result<(), Error> {
+	const RECORDS: usize = 5;
+	const TRIALS: usize = 1_000;
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	for i in 0..RECORDS {
+		let sql = format!("CREATE item:{i} SET num = {i}");
+		dbs.execute(&sql, &ses, None).await?.remove(0).result?;
+	}
+	// Each record is equally likely to be ordered first
+	let mut buckets = [0usize; RECORDS];
+	for _ in 0..TRIALS {
+		let sql = "SELECT VALUE num FROM item ORDER BY RAND() LIMIT 3";
+		let tmp = dbs.execute(sql, &ses, None).await?.remove(0).result?;
+		let Value::Array(values) = tmp else {
+			panic!("expected an array of values");
+		};
+		// A limited random ordering returns a subset of distinct records
+		let mut nums = values
+			.iter()
+			.map(|v| match v {
+				Value::Number(n) => n.to_usize(),
+				v => panic!("expected a number, found {v}"),
+			})
+			.collect::<Vec<_>>();
+		buckets[nums[0]] += 1;
+		nums.sort();
+		nums.dedup();
+		assert_eq!(nums.len(), 3);
+	}
+	// Chi-squared test with 4 degrees of freedom, where
+	// a value above 18.47 has a probability below 0.1%
+	let expected = TRIALS as f64 / RECORDS as f64;
+	let chi: f64 = buckets.iter().map(|&n| (n as f64 - expected).powi(2) / expected).sum();
+	assert!(chi < 18.47, "records are not ordered uniformly: {buckets:?}");
+	//
+	Ok(())
+}
+
+#[tokio::test]
+async fn select_order_random_seed() -> Result<(), Error> {
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	dbs.execute("CREATE |item:1..20| SET num = 1", &ses, None).await?.remove(0).result?;
+	// A seeded random ordering returns the same order each time
+	let sql = "SELECT VALUE id FROM item ORDER BY RAND(42)";
+	let first = dbs.execute(sql, &ses, None).await?.remove(0).result?;
+	for _ in 0..5 {
+		let tmp = dbs.execute(sql, &ses, None).await?.remove(0).result?;
+		assert_eq!(tmp, first);
+	}
+	assert_ne!(
+		first,
+		dbs.execute("SELECT VALUE id FROM item", &ses, None).await?.remove(0).result?
+	);
+	// Which can be paged through with START and LIMIT
+	let Value::Array(all) = first else {
+		panic!("expected an array of values");
+	};
+	let sql = "SELECT VALUE id FROM item ORDER BY RAND(42) START 5 LIMIT 10";
+	let tmp = dbs.execute(sql, &ses, None).await?.remove(0).result?;
+	assert_eq!(tmp, Value::from(all[5..15].to_vec()));
+	// Different seeds give different orders
+	let sql = "SELECT VALUE id FROM item ORDER BY RAND(7)";
+	let tmp = dbs.execute(sql, &ses, None).await?.remove(0).result?;
+	assert_ne!(tmp, Value::from(all));
+	//
+	Ok(())
+}
+
+#[tokio::test]
+async fn select_order_random_limit_explain() -> Result<(), Error> {
+	let sql = "
+		EXPLAIN SELECT * FROM item ORDER BY RAND() LIMIT 5;
+		EXPLAIN SELECT * FROM item ORDER BY RAND(42) LIMIT 5;
+		EXPLAIN SELECT * FROM item ORDER BY RAND();
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 3);
+	// A limited random ordering only keeps a random sample of the records
+	for collector in ["Sample", "Memory", "Memory"] {
+		let tmp = res.remove(0).result?;
+		let val = Value::parse(&format!(
+			"[
+				{{
+					detail: {{
+						table: 'item'
+					}},
+					operation: 'Iterate Table'
+				}},
+				{{
+					detail: {{
+						type: '{collector}'
+					}},
+					operation: 'Collector'
+				}}
+			]"
+		));
+		assert_eq!(format!("{:#}", tmp), format!("{:#}", val));
+	}
+	//
+	Ok(())
+}
+
+#[tokio::test]
+async fn select_with_timeout() -> Result<(), Error> {
+	let sql = "
+		CREATE |person:1..10|;
+		SELECT id, sleep(20ms) AS slow FROM person TIMEOUT 50ms;
+		SELECT id FROM person:1 TIMEOUT 1s;
+		BEGIN;
+		CREATE log:one;
+		SELECT id, sleep(20ms) AS slow FROM person TIMEOUT 50ms;
+		COMMIT;
+		SELECT * FROM log;
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 6);
+	//
+	let tmp = res.remove(0).result;
+	assert!(tmp.is_ok());
+	// A slow select is stopped once the timeout is reached
+	let tmp = res.remove(0);
+	assert!(matches!(tmp.result, Err(Error::QueryTimedout)));
+	assert!(tmp.time < std::time::Duration::from_millis(200));
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[{ id: person:1 }]");
+	assert_eq!(tmp, val);
+	// A timeout cancels the whole transaction
+	let tmp = res.remove(0).result;
+	assert!(matches!(
+		tmp.err(),
+		Some(e) if e.to_string() == "The query was not executed due to a failed transaction"
+	));
+	//
+	let tmp = res.remove(0).result;
+	assert!(matches!(tmp, Err(Error::QueryTimedout)));
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[]");
+	assert_eq!(tmp, val);
+	//
+	Ok(())
+}
+
+#[tokio::test]
+async fn select_with_timeout_on_large_table() -> Result<(), Error> {
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute("CREATE |person:1..100| RETURN NONE", &ses, None).await?;
+	skip_ok(res, 1)?;
+	// The scan stops once the timeout has elapsed, well before it would finish
+	let sql = "
+		SELECT id, sleep(10ms) AS slow FROM person TIMEOUT 100ms;
+		RETURN (SELECT id, sleep(10ms) AS slow FROM person TIMEOUT 100ms);
+	";
+	let now = std::time::Instant::now();
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	let elapsed = now.elapsed();
+	assert_eq!(res.len(), 2);
+	//
+	let tmp = res.remove(0);
+	assert!(matches!(tmp.result, Err(Error::QueryTimedout)));
+	assert!(tmp.time >= std::time::Duration::from_millis(100));
+	assert!(tmp.time < std::time::Duration::from_millis(300));
+	// The timeout of a subquery is applied to its own iteration
+	let tmp = res.remove(0);
+	assert!(matches!(tmp.result, Err(Error::QueryTimedout)));
+	assert!(tmp.time < std::time::Duration::from_millis(300));
+	assert!(elapsed < std::time::Duration::from_millis(600));
+	//
+	Ok(())
+}
+
+#[tokio::test]
+async fn select_cancelled_with_session() -> Result<(), Error> {
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute("CREATE |person:1..10|", &ses, None).await?;
+	skip_ok(res, 1)?;
+	// Cancel the session part way through a slow scan
+	let sql = "SELECT id, sleep(20ms) AS slow FROM person";
+	let res = tokio::select! {
+		res = dbs.execute(sql, &ses, None) => res?,
+		_ = async {
+			tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+			ses.cancel();
+			std::future::pending::<()>().await
+		} => unreachable!(),
+	};
+	assert_eq!(res.len(), 1);
+	// The records scanned before the cancellation are not returned
+	let tmp = res.into_iter().next().unwrap();
+	assert!(matches!(tmp.result, Err(Error::QueryCancelled)));
+	assert!(tmp.time < std::time::Duration::from_millis(200));
+	// A cancelled session does not run any further queries
+	let res = &mut dbs.execute("SELECT id FROM person:1", &ses, None).await?;
+	assert_eq!(res.len(), 1);
+	let tmp = res.remove(0).result;
+	assert!(matches!(tmp, Err(Error::QueryCancelled)));
+	// Other sessions are not cancelled
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute("SELECT id FROM person:1", &ses, None).await?;
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[{ id: person:1 }]");
+	assert_eq!(tmp, val);
+	//
+	Ok(())
+}
+
+#[tokio::test]
+async fn select_killed_while_running() -> Result<(), Error> {
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute("CREATE |person:1..100| RETURN NONE", &ses, None).await?;
+	skip_ok(res, 1)?;
+	assert!(ses.running_queries().is_empty());
+	// Kill a slow scan part way through, well before it times out
+	let sql = "SELECT id, sleep(10ms) AS slow FROM person TIMEOUT 5s";
+	let (res, kill) = tokio::join!(dbs.execute(sql, &ses, None), async {
+		tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+		let ids = ses.running_queries();
+		assert_eq!(ids.len(), 1);
+		let sql = format!("KILL u'{}'", ids[0]);
+		// Other sessions can not see the running query
+		let other = Session::owner().with_ns("test").with_db("test");
+		let res = &mut dbs.execute(&sql, &other, None).await?;
+		assert!(res.remove(0).result.is_err());
+		// The session which is running the query can kill it
+		dbs.execute(&sql, &ses, None).await
+	});
+	let tmp = kill?.remove(0).result?;
+	assert_eq!(tmp, Value::None);
+	// The scan stops as soon as it is killed
+	let tmp = res?.remove(0);
+	assert!(matches!(tmp.result, Err(Error::QueryCancelled)));
+	assert!(tmp.time < std::time::Duration::from_millis(500));
+	assert!(ses.running_queries().is_empty());
+	// Only the killed query is cancelled, not the session
+	let res = &mut dbs.execute("SELECT id FROM person:1", &ses, None).await?;
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[{ id: person:1 }]");
+	assert_eq!(tmp, val);
+	//
+	Ok(())
+}
+
+#[tokio::test]
+async fn select_after_cursor() -> Result<(), Error> {
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute("CREATE |person:1..10000| RETURN NONE", &ses, None).await?;
+	skip_ok(res, 1)?;
+	// Paging with a cursor returns the same records as paging with an offset
+	let mut after: Option<Thing> = None;
+	for page in 0..11 {
+		let sql =
+			format!("SELECT VALUE id FROM person ORDER BY id LIMIT 1000 START {}", page * 1000);
+		let expected = dbs.execute(&sql, &ses, None).await?.remove(0).result?;
+		let sql = match &after {
+			// A record id can be used as the cursor
+			Some(rid) if page % 2 == 0 => {
+				format!("SELECT VALUE id FROM person LIMIT 1000 AFTER {rid}")
+			}
+			// As can an opaque cursor token
+			Some(rid) => format!(
+				"SELECT VALUE id FROM person ORDER BY id LIMIT 1000 AFTER '{}'",
+				Cursor::encode(rid)
+			),
+			None => "SELECT VALUE id FROM person ORDER BY id LIMIT 1000".to_owned(),
+		};
+		let tmp = dbs.execute(&sql, &ses, None).await?.remove(0).result?;
+		assert_eq!(tmp, expected);
+		after = match tmp {
+			Value::Array(v) => match v.last() {
+				Some(Value::Thing(v)) => Some(v.clone()),
+				_ => None,
+			},
+			_ => unreachable!(),
+		};
+		assert_eq!(after.is_none(), page == 10);
+	}
+	//
+	let sql = "
+		SELECT * FROM person ORDER BY id DESC LIMIT 10 AFTER person:10;
+		SELECT * FROM person LIMIT 10 AFTER user:10;
+		SELECT * FROM person LIMIT 10 AFTER 'invalid';
+	";
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 3);
+	// A cursor requires the records to be ordered by id
+	let tmp = res.remove(0).result;
+	assert!(matches!(
+		tmp.err(),
+		Some(e) if e.to_string() == "The AFTER clause can only be used when ordering the records by id"
+	));
+	// The cursor must point to a record in the selected table
+	let tmp = res.remove(0).result;
+	assert!(matches!(
+		tmp.err(),
+		Some(e) if e.to_string() == "Found user:10 but the AFTER clause must evaluate to a record id or a cursor token"
+	));
+	//
+	let tmp = res.remove(0).result;
+	assert!(matches!(
+		tmp.err(),
+		Some(e) if e.to_string() == "Found 'invalid' but the AFTER clause must evaluate to a record id or a cursor token"
+	));
+	//
+	Ok(())
+}
+
+#[tokio::test]
+async fn select_start_after_ordered_cursor() -> Result<(), Error> {
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let sql = "CREATE |post:1..50| SET rank = 7 - math::floor(meta::id(id) / 7) RETURN NONE";
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	skip_ok(res, 1)?;
+	// Seeking to the cursor with an index returns the same records as scanning the table
+	for index in [false, true] {
+		if index {
+			let sql = "DEFINE INDEX idx_rank ON post FIELDS rank";
+			let res = &mut dbs.execute(sql, &ses, None).await?;
+			skip_ok(res, 1)?;
+		}
+		for dir in ["ASC", "DESC"] {
+			let sql = format!("SELECT * FROM post ORDER BY rank {dir}");
+			let expected = dbs.execute(&sql, &ses, None).await?.remove(0).result?;
+			// Paging with a cursor returns every record once, even when ordered values are equal
+			let mut records = Vec::new();
+			let mut after: Option<(Value, Thing)> = None;
+			for page in 0..8 {
+				let sql = match &after {
+					// The ordered values followed by the record id can be used as the cursor
+					Some((rank, rid)) if page % 2 == 0 => format!(
+						"SELECT * FROM post ORDER BY rank {dir} LIMIT 8 START AFTER [{rank}, {rid}]"
+					),
+					// As can an opaque cursor token
+					Some((rank, rid)) => format!(
+						"SELECT * FROM post ORDER BY rank {dir} START AFTER '{}' LIMIT 8",
+						Cursor::encode_keys(&[rank.clone()], rid)
+					),
+					None => format!("SELECT * FROM post ORDER BY rank {dir} LIMIT 8"),
+				};
+				let tmp = dbs.execute(&sql, &ses, None).await?.remove(0).result?;
+				let Value::Array(tmp) = tmp else {
+					unreachable!()
+				};
+				assert_eq!(tmp.is_empty(), page == 7);
+				after = tmp.last().map(|v| match v.pick(&["id".into()]) {
+					Value::Thing(rid) => (v.pick(&["rank".into()]), rid),
+					_ => unreachable!(),
+				});
+				records.extend(tmp);
+			}
+			assert_eq!(Value::from(records), expected);
+		}
+	}
+	//
+	let sql = "
+		SELECT * FROM post LIMIT 8 START AFTER [1, post:1];
+		SELECT * FROM post ORDER BY rank, id LIMIT 8 START AFTER [1, post:1];
+		SELECT rank FROM post ORDER BY rank LIMIT 8 START AFTER [1, post:1];
+		SELECT * FROM post ORDER BY rank LIMIT 8 START AFTER [1, 2];
+	";
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 4);
+	// The cursor must match the ORDER BY clause
+	let tmp = res.remove(0).result;
+	assert!(matches!(
+		tmp.err(),
+		Some(e) if e.to_string() == "Unable to continue from the AFTER cursor, as it must contain a value for each field in the ORDER BY clause, followed by a record id"
+	));
+	//
+	let tmp = res.remove(0).result;
+	assert!(matches!(
+		tmp.err(),
+		Some(e) if e.to_string() == "Unable to continue from the AFTER cursor, as it must contain a value for each field in the ORDER BY clause, followed by a record id"
+	));
+	// Equally ordered records are ordered by their record ids
+	let tmp = res.remove(0).result;
+	assert!(matches!(
+		tmp.err(),
+		Some(e) if e.to_string() == "Unable to continue from the AFTER cursor, as the record id is not selected"
+	));
+	// The ordered values must be followed by a record id
+	let tmp = res.remove(0).result;
+	assert!(matches!(
+		tmp.err(),
+		Some(e) if e.to_string() == "Found [1, 2] but the AFTER clause must evaluate to a record id or a cursor token"
+	));
+	//
+	Ok(())
+}
+
+#[tokio::test]
+async fn select_with_metrics() -> Result<(), Error> {
+	let sql = "
+		DEFINE INDEX idx_age ON person FIELDS age;
+		CREATE |person:1..20|;
+		UPDATE person SET age = meta::id(id), adult = meta::id(id) >= 18 RETURN NONE;
+		SELECT * FROM person WHERE adult = true;
+		SELECT * FROM person WHERE age = 10;
+		SELECT * FROM person WHERE adult = true;
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test").with_collect_metrics(true);
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 6);
+	skip_ok(res, 3)?;
+	// A table scan filters out the records which do not match
+	let tmp = res.remove(0);
+	let metrics = tmp.metrics.unwrap();
+	assert_eq!(metrics.records_scanned, 20);
+	assert_eq!(metrics.records_returned, 3);
+	assert_eq!(metrics.records_filtered, metrics.records_scanned - metrics.records_returned);
+	assert_eq!(metrics.index_hits, 0);
+	// An index scan only processes the matching records
+	let tmp = res.remove(0);
+	let metrics = tmp.metrics.unwrap();
+	assert_eq!(metrics.records_scanned, 1);
+	assert_eq!(metrics.records_returned, 1);
+	assert_eq!(metrics.records_filtered, 0);
+	assert!(metrics.index_hits > 0);
+	// The result is not changed by collecting metrics
+	let tmp = res.remove(0).result?;
+	assert!(matches!(tmp, Value::Array(v) if v.len() == 3));
+	// Metrics are not collected unless requested
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute("SELECT * FROM person WHERE adult = true", &ses, None).await?;
+	assert!(res.remove(0).metrics.is_none());
+	//
+	Ok(())
+}
+
+#[tokio::test]
+async fn select_for_update_in_read_only_session() -> Result<(), Error> {
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute("CREATE person:1 SET x = 0", &ses, None).await?;
+	skip_ok(res, 1)?;
+	// The record is read without a lock in a read-only session
+	let ses = ses.with_read_only(true);
+	let res = &mut dbs.execute("SELECT * FROM person:1 FOR UPDATE", &ses, None).await?;
+	assert_eq!(res.len(), 1);
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[{ id: person:1, x: 0 }]");
+	assert_eq!(tmp, val);
+	//
+	Ok(())
+}
+
+#[tokio::test]
+async fn select_where_fuzzy_match_ordered_by_similarity() -> Result<(), Error> {
+	let sql = "
+		CREATE product:1 SET name = 'apple';
+		CREATE product:2 SET name = 'Appel';
+		CREATE product:3 SET name = 'pineapple';
+		CREATE product:4 SET name = 'banana';
+		SELECT id, string::similarity(name, 'aple') FROM product WHERE name ?~ 'aple' ORDER BY string::similarity(name, 'aple') DESC;
+		SELECT * FROM product ORDER BY string::similarity(name, 'aple') DESC;
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	// The ordered function call must be one of the selected fields
+	assert!(dbs.execute(sql, &ses, None).await.is_err());
+	let sql = &sql[..sql.rfind("SELECT *").unwrap()];
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 5);
+	skip_ok(res, 4)?;
+	// Strings within the edit distance match, as well as fuzzy matches
+	let tmp = res.remove(0).result?;
+	let val = Value::parse(
+		"[
+			{ id: product:1, 'string::similarity': 0.8f },
+			{ id: product:3, 'string::similarity': 0.4444444444444444f },
+			{ id: product:2, 'string::similarity': 0.4f },
+		]",
+	);
+	assert_eq!(tmp, val);
+	//
+	Ok(())
+}
+
+#[tokio::test]
+async fn select_spilled_to_temporary_files() -> Result<(), Error> {
+	let mut sql = (1..=20)
+		.map(|i| format!("CREATE item:{i} SET n = {}, g = {};", (i * 7) % 20, i % 3))
+		.collect::<String>();
+	sql.push_str(
+		"
+		SELECT VALUE n FROM item ORDER BY n DESC START 2 LIMIT 5;
+		SELECT id, n FROM item ORDER BY n, id LIMIT 3;
+		SELECT g, count() AS c, math::sum(n) AS s, array::group(n) AS ns FROM item GROUP BY g;
+		SELECT count() AS c, math::max(n) AS m FROM item GROUP ALL;
+	",
+	);
+	let ses = Session::owner().with_ns("test").with_db("test");
+	// Results are spilled once more than two have been collected
+	let dbs = new_ds().await?.with_spill_threshold(Some(2));
+	let res = &mut dbs.execute(&sql, &ses, None).await?;
+	assert_eq!(res.len(), 24);
+	skip_ok(res, 20)?;
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[17, 16, 15, 14, 13]");
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse(
+		"[
+			{ id: item:20, n: 0 },
+			{ id: item:3, n: 1 },
+			{ id: item:6, n: 2 },
+		]",
+	);
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let grouped = Value::parse(
+		"[
+			{ g: 0, c: 6, s: 21, ns: [1, 2, 3, 4, 5, 6] },
+			{ g: 1, c: 7, s: 70, ns: [7, 8, 9, 10, 11, 12, 13] },
+			{ g: 2, c: 7, s: 99, ns: [14, 15, 16, 17, 18, 19, 0] },
+		]",
+	);
+	assert_eq!(tmp, grouped);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[{ c: 20, m: 19 }]");
+	assert_eq!(tmp, val);
+	// The output is identical when the results are kept in memory
+	let dbs = new_ds().await?;
+	let res = &mut dbs.execute(&sql, &ses, None).await?;
+	skip_ok(res, 22)?;
+	let tmp = res.remove(0).result?;
+	assert_eq!(tmp, grouped);
+	//
+	Ok(())
+}
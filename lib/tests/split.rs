@@ -0,0 +1,108 @@
+mod parse;
+use parse::Parse;
+mod helpers;
+use helpers::new_ds;
+use helpers::skip_ok;
+use surrealdb::dbs::Session;
+use surrealdb::err::Error;
+use surrealdb::sql::Value;
+
+#[tokio::test]
+async fn select_split_multiple_fields() -> Result<(), Error> {
+	let sql = "
+		CREATE person:1 SET tags = ['a', 'b'], emails = ['x', 'y', 'z'];
+		SELECT tags, emails FROM person SPLIT ON tags, emails;
+		SELECT count() FROM (SELECT tags, emails FROM person SPLIT ON tags, emails) GROUP ALL;
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 3);
+	//
+	skip_ok(res, 1)?;
+	// Each split is applied in sequence, fanning out into every combination
+	let tmp = res.remove(0).result?;
+	let val = Value::parse(
+		"[
+			{ emails: 'x', tags: 'a' },
+			{ emails: 'y', tags: 'a' },
+			{ emails: 'z', tags: 'a' },
+			{ emails: 'x', tags: 'b' },
+			{ emails: 'y', tags: 'b' },
+			{ emails: 'z', tags: 'b' }
+		]",
+	);
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[{ count: 6 }]");
+	assert_eq!(tmp, val);
+	//
+	Ok(())
+}
+
+#[tokio::test]
+async fn select_split_non_array_and_empty() -> Result<(), Error> {
+	let sql = "
+		CREATE person:1 SET name = 'Tobie', tags = 'single';
+		CREATE person:2 SET name = 'Jaime', tags = [];
+		CREATE person:3 SET name = 'Lizzie', tags = ['a', 'b'];
+		SELECT name, tags FROM person SPLIT tags;
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 4);
+	//
+	skip_ok(res, 3)?;
+	// A value which is not an array is kept unchanged,
+	// while a row with an empty array is dropped
+	let tmp = res.remove(0).result?;
+	let val = Value::parse(
+		"[
+			{ name: 'Tobie', tags: 'single' },
+			{ name: 'Lizzie', tags: 'a' },
+			{ name: 'Lizzie', tags: 'b' }
+		]",
+	);
+	assert_eq!(tmp, val);
+	//
+	Ok(())
+}
+
+#[tokio::test]
+async fn select_split_nested_fields() -> Result<(), Error> {
+	let sql = "
+		CREATE person:1 SET contact = { emails: ['x', 'y'], phones: ['1', '2'] };
+		SELECT * FROM person SPLIT contact.emails;
+		SELECT * FROM person SPLIT contact.emails, contact.phones;
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 3);
+	//
+	skip_ok(res, 1)?;
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse(
+		"[
+			{ id: person:1, contact: { emails: 'x', phones: ['1', '2'] } },
+			{ id: person:1, contact: { emails: 'y', phones: ['1', '2'] } }
+		]",
+	);
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse(
+		"[
+			{ id: person:1, contact: { emails: 'x', phones: '1' } },
+			{ id: person:1, contact: { emails: 'x', phones: '2' } },
+			{ id: person:1, contact: { emails: 'y', phones: '1' } },
+			{ id: person:1, contact: { emails: 'y', phones: '2' } }
+		]",
+	);
+	assert_eq!(tmp, val);
+	//
+	Ok(())
+}
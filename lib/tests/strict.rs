@@ -1,7 +1,7 @@
 mod parse;
 use parse::Parse;
 mod helpers;
-use helpers::new_ds;
+use helpers::{new_ds, skip_ok};
 use surrealdb::dbs::Session;
 use surrealdb::err::Error;
 use surrealdb::sql::Value;
@@ -294,3 +294,49 @@ async fn strict_define_in_transaction() -> Result<(), Error> {
 	dbs.execute(sql, &ses, None).await?;
 	Ok(())
 }
+
+#[tokio::test]
+async fn strict_mode_schemafull_undefined_fields() -> Result<(), Error> {
+	let sql = "
+		DEFINE NAMESPACE test;
+		DEFINE DATABASE test;
+		DEFINE TABLE person SCHEMAFULL;
+		DEFINE FIELD name ON person TYPE string;
+		DEFINE TABLE animal SCHEMALESS;
+		CREATE person:one SET name = 'Tobie', age = 30;
+		CREATE person:two SET name = 'Jaime';
+		UPDATE person:two SET tags = ['a'];
+		CREATE animal:one SET name = 'Cat', legs = 4;
+		OPTION IMPORT;
+		CREATE person:three SET name = 'Lizzie', age = 25;
+	";
+	let dbs = new_ds().await?.with_strict_mode(true);
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 10);
+	//
+	skip_ok(res, 5)?;
+	// Undefined fields are rejected on schemafull tables
+	let tmp = res.remove(0).result;
+	assert!(matches!(
+		tmp.err(),
+		Some(e) if e.to_string() == "Found field `age`, with record `person:one`, but the field is not defined on the SCHEMAFULL table"
+	));
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[{ id: person:two, name: 'Jaime' }]");
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result;
+	assert!(matches!(tmp.err(), Some(Error::SchemaViolation { .. })));
+	// But are allowed on schemaless tables
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[{ id: animal:one, legs: 4, name: 'Cat' }]");
+	assert_eq!(tmp, val);
+	// And are kept when importing
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[{ age: 25, id: person:three, name: 'Lizzie' }]");
+	assert_eq!(tmp, val);
+	//
+	Ok(())
+}
@@ -104,6 +104,106 @@ async fn subquery_select() -> Result<(), Error> {
 	Ok(())
 }
 
+#[tokio::test]
+async fn subquery_select_alias() -> Result<(), Error> {
+	let sql = "
+		CREATE user:1 SET name = 'Tobie', age = 30, active = true;
+		CREATE user:2 SET name = 'Jaime', age = 20, active = true;
+		CREATE user:3 SET name = 'Lizzie', age = 40, active = false;
+		-- Select from an aliased subquery
+		SELECT * FROM (SELECT id FROM user WHERE active = true) AS active_users;
+		-- Reference the aliased subquery from within the statement
+		SELECT name FROM (SELECT name, age FROM user WHERE active = true) AS active_users
+			WHERE age > math::mean(SELECT VALUE age FROM active_users);
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 5);
+	//
+	for _ in 0..3 {
+		let tmp = res.remove(0).result;
+		assert!(tmp.is_ok());
+	}
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse(
+		"[
+			{
+				id: user:1
+			},
+			{
+				id: user:2
+			}
+		]",
+	);
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse(
+		"[
+			{
+				name: 'Tobie'
+			}
+		]",
+	);
+	assert_eq!(tmp, val);
+	//
+	Ok(())
+}
+
+#[tokio::test]
+async fn subquery_select_in_where_clause() -> Result<(), Error> {
+	let sql = "
+		CREATE customer:one SET vip = true, max = 50;
+		CREATE customer:two SET vip = false, max = 100;
+		CREATE customer:three SET vip = true, max = 20;
+		CREATE order:1 SET customer = customer:one, total = 30;
+		CREATE order:2 SET customer = customer:two, total = 40;
+		CREATE order:3 SET customer = customer:three, total = 60;
+		SELECT VALUE id FROM order WHERE customer IN (SELECT VALUE id FROM customer WHERE vip = true);
+		SELECT VALUE id FROM order WHERE customer NOTINSIDE (SELECT VALUE id FROM customer WHERE vip = true);
+		SELECT VALUE id FROM order WHERE total <= (SELECT VALUE max FROM ONLY $parent.customer);
+		BEGIN;
+		CREATE customer:four SET vip = true;
+		CREATE order:4 SET customer = customer:four, total = 10;
+		SELECT VALUE id FROM order WHERE customer IN (SELECT VALUE id FROM customer WHERE vip = true);
+		COMMIT;
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 12);
+	//
+	for _ in 0..6 {
+		let tmp = res.remove(0).result;
+		assert!(tmp.is_ok());
+	}
+	// The subquery is computed into an array of record ids
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[order:1, order:3]");
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[order:2]");
+	assert_eq!(tmp, val);
+	// A correlated subquery sees the outer record as $parent
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[order:1, order:2]");
+	assert_eq!(tmp, val);
+	//
+	for _ in 0..2 {
+		let tmp = res.remove(0).result;
+		assert!(tmp.is_ok());
+	}
+	// The subquery sees records written earlier in the same transaction
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[order:1, order:3, order:4]");
+	assert_eq!(tmp, val);
+	//
+	Ok(())
+}
+
 #[tokio::test]
 async fn subquery_ifelse_set() -> Result<(), Error> {
 	let sql = "
@@ -2,7 +2,9 @@ mod parse;
 use parse::Parse;
 mod helpers;
 use helpers::new_ds;
-use surrealdb::dbs::Session;
+use helpers::skip_ok;
+use std::time::Duration;
+use surrealdb::dbs::{Action, Notification, RetryPolicy, Session};
 use surrealdb::err::Error;
 use surrealdb::sql::Value;
 
@@ -185,3 +187,268 @@ async fn transaction_with_throw_and_return() -> Result<(), Error> {
 	//
 	Ok(())
 }
+
+#[tokio::test]
+async fn transaction_retried_after_conflict() -> Result<(), Error> {
+	let dbs = new_ds().await?;
+	let policy = RetryPolicy::new(2, Duration::from_millis(10));
+	let ses = Session::owner().with_ns("test").with_db("test").with_retry_policy(policy);
+	// The first commit conflicts with another transaction
+	dbs.set_conflicts(1);
+	let res = &mut dbs.execute("CREATE person:tobie", &ses, None).await?;
+	assert_eq!(res.len(), 1);
+	// The statement succeeds after exactly one retry
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[{ id: person:tobie }]");
+	assert_eq!(tmp, val);
+	assert_eq!(dbs.conflicts(), 0);
+	//
+	Ok(())
+}
+
+#[tokio::test]
+async fn transaction_explicit_not_retried() -> Result<(), Error> {
+	let sql = "
+		BEGIN;
+		CREATE person:tobie;
+		COMMIT;
+		SELECT * FROM person;
+	";
+	let dbs = new_ds().await?;
+	let policy = RetryPolicy::new(2, Duration::from_millis(10));
+	let ses = Session::owner().with_ns("test").with_db("test").with_retry_policy(policy);
+	// The commit conflicts with another transaction
+	dbs.set_conflicts(1);
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 2);
+	// Statements in an explicit transaction are not retried
+	let tmp = res.remove(0).result;
+	assert!(
+		matches!(tmp, Err(Error::QueryNotExecutedDetail { .. })),
+		"Unexpected result: {:?}",
+		tmp
+	);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[]");
+	assert_eq!(tmp, val);
+	assert_eq!(dbs.conflicts(), 0);
+	//
+	Ok(())
+}
+
+#[tokio::test]
+async fn transaction_with_savepoint() -> Result<(), Error> {
+	let sql = "
+		BEGIN;
+		CREATE person:tobie SET age = 30;
+		SAVEPOINT before_changes;
+		CREATE person:jaime;
+		UPDATE person:tobie SET age = 31;
+		ROLLBACK TO before_changes;
+		CREATE person:other;
+		COMMIT;
+		SELECT * FROM person;
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 7);
+	//
+	skip_ok(res, 6)?;
+	// Only the writes made after the savepoint are undone
+	let tmp = res.remove(0).result?;
+	let val = Value::parse(
+		"[
+			{
+				id: person:other,
+			},
+			{
+				age: 30,
+				id: person:tobie,
+			}
+		]",
+	);
+	assert_eq!(tmp, val);
+	//
+	Ok(())
+}
+
+#[tokio::test]
+async fn transaction_savepoint_discards_changes() -> Result<(), Error> {
+	let sql = "
+		DEFINE TABLE person CHANGEFEED 1h;
+		BEGIN;
+		CREATE person:tobie;
+		SAVEPOINT before_changes;
+		CREATE person:jaime;
+		UPDATE person:tobie SET age = 31;
+		ROLLBACK TO before_changes;
+		COMMIT;
+		SHOW CHANGES FOR TABLE person SINCE 0;
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 7);
+	//
+	skip_ok(res, 6)?;
+	// Only the writes made before the savepoint reach the change feed
+	let tmp = res.remove(0).result?.to_string();
+	assert!(tmp.contains("{ id: person:tobie }"), "{tmp}");
+	assert!(!tmp.contains("person:jaime"), "{tmp}");
+	assert!(!tmp.contains("age: 31"), "{tmp}");
+	//
+	Ok(())
+}
+
+#[tokio::test]
+async fn transaction_savepoint_discards_notifications() -> Result<(), Error> {
+	let dbs = new_ds().await?.with_notifications();
+	let ses = Session::owner().with_ns("test").with_db("test").with_rt(true);
+	let res = &mut dbs.execute("LIVE SELECT * FROM person", &ses, None).await?;
+	let live_id = match res.remove(0).result? {
+		Value::Uuid(id) => id,
+		_ => panic!("expected uuid"),
+	};
+	let notifications = dbs.notifications().expect("expected notifications");
+	let sql = "
+		BEGIN;
+		CREATE person:tobie;
+		SAVEPOINT before_changes;
+		CREATE person:jaime;
+		ROLLBACK TO before_changes;
+		CREATE person:other;
+		COMMIT;
+	";
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 5);
+	skip_ok(res, 5)?;
+	// Only the writes made before or after the savepoint are notified
+	let notification = notifications.recv().await.unwrap();
+	assert_eq!(
+		notification,
+		Notification::new(live_id, Action::Create, Value::parse("{ id: person:tobie }"))
+	);
+	//
+	let notification = notifications.recv().await.unwrap();
+	assert_eq!(
+		notification,
+		Notification::new(live_id, Action::Create, Value::parse("{ id: person:other }"))
+	);
+	assert!(notifications.try_recv().is_err());
+	//
+	Ok(())
+}
+
+#[tokio::test]
+async fn transaction_with_nested_savepoints() -> Result<(), Error> {
+	let sql = "
+		BEGIN;
+		CREATE person:tobie;
+		SAVEPOINT outer;
+		CREATE person:jaime;
+		SAVEPOINT inner;
+		CREATE person:tobie;
+		CREATE person:other;
+		ROLLBACK TO SAVEPOINT inner;
+		RELEASE SAVEPOINT inner;
+		CREATE person:another;
+		SAVEPOINT inner;
+		CREATE person:last;
+		ROLLBACK TO outer;
+		ROLLBACK TO inner;
+		ROLLBACK TO outer;
+		COMMIT;
+		SELECT VALUE id FROM person;
+		SAVEPOINT outer;
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 16);
+	//
+	skip_ok(res, 4)?;
+	// Rolling back to a savepoint recovers from a failed statement
+	let tmp = res.remove(0).result;
+	assert!(matches!(
+		tmp.err(),
+		Some(e) if e.to_string() == r#"Database record `person:tobie` already exists"#
+	));
+	//
+	let tmp = res.remove(0).result;
+	assert!(matches!(
+		tmp.err(),
+		Some(e) if e.to_string() == r#"The query was not executed due to a failed transaction"#
+	));
+	//
+	skip_ok(res, 6)?;
+	// Rolling back the outer savepoint removes the savepoints created after it
+	let tmp = res.remove(0).result;
+	assert!(matches!(
+		tmp.err(),
+		Some(e) if e.to_string() == r#"The savepoint 'inner' does not exist"#
+	));
+	//
+	skip_ok(res, 1)?;
+	// Only the writes made before the outer savepoint are committed
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[person:tobie]");
+	assert_eq!(tmp, val);
+	// Savepoints can only be used within a transaction
+	let tmp = res.remove(0).result;
+	assert!(matches!(
+		tmp.err(),
+		Some(e) if e.to_string() == r#"Savepoints can only be used within a transaction"#
+	));
+	//
+	Ok(())
+}
+
+#[tokio::test]
+async fn transaction_read_only_session() -> Result<(), Error> {
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute("CREATE person:tobie SET name = 'Tobie'", &ses, None).await?;
+	skip_ok(res, 1)?;
+	//
+	let sql = "
+		SELECT * FROM person;
+		UPDATE person:tobie SET name = 'Jaime';
+		BEGIN;
+		SELECT name FROM person;
+		COMMIT;
+		CREATE person:jaime;
+	";
+	let ses = Session::owner().with_ns("test").with_db("test").with_read_only(true);
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 4);
+	// Data can be read within a read-only session
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[{ id: person:tobie, name: 'Tobie' }]");
+	assert_eq!(tmp, val);
+	// But data can not be written
+	let tmp = res.remove(0).result;
+	assert!(matches!(
+		tmp.err(),
+		Some(e) if e.to_string() == "Couldn't write to a read only transaction"
+	));
+	// A read-only transaction can be committed
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[{ name: 'Tobie' }]");
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result;
+	assert!(matches!(
+		tmp.err(),
+		Some(e) if e.to_string() == "Couldn't write to a read only transaction"
+	));
+	// The data was not changed
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute("SELECT * FROM person", &ses, None).await?;
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[{ id: person:tobie, name: 'Tobie' }]");
+	assert_eq!(tmp, val);
+	//
+	Ok(())
+}
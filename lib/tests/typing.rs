@@ -320,3 +320,90 @@ async fn strict_typing_none_null() -> Result<(), Error> {
 	//
 	Ok(())
 }
+
+#[tokio::test]
+async fn strict_typing_defined_kinds() -> Result<(), Error> {
+	let sql = "
+		DEFINE FIELD friend ON person TYPE option<record<person>>;
+		DEFINE FIELD born ON person TYPE option<datetime>;
+		DEFINE FIELD wait ON person TYPE option<duration>;
+		DEFINE FIELD scores ON person TYPE option<array<int>>;
+		DEFINE FIELD meta ON person TYPE option<object>;
+		DEFINE FIELD active ON person TYPE option<bool>;
+		CREATE person:one SET friend = person:two, born = d'2020-01-01', wait = 1h, scores = [1, 2], meta = { a: 1 }, active = true;
+		CREATE person:two;
+		CREATE person:three SET friend = user:one;
+		CREATE person:four SET born = 'yesterday';
+		CREATE person:five SET wait = 10;
+		CREATE person:six SET scores = [1, 'two'];
+		CREATE person:seven SET meta = [1];
+		CREATE person:eight SET active = 'yes';
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 14);
+	//
+	for _ in 0..6 {
+		let tmp = res.remove(0).result;
+		assert!(tmp.is_ok());
+	}
+	// Values which match their field types are stored
+	let tmp = res.remove(0).result?;
+	let val = Value::parse(
+		"[
+			{
+				active: true,
+				born: d'2020-01-01T00:00:00Z',
+				friend: person:two,
+				id: person:one,
+				meta: { a: 1 },
+				scores: [1, 2],
+				wait: 1h,
+			}
+		]",
+	);
+	assert_eq!(tmp, val);
+	// Optional fields can be left empty
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[{ id: person:two }]");
+	assert_eq!(tmp, val);
+	// Values which do not match their field types are rejected
+	let tmp = res.remove(0).result;
+	assert!(matches!(
+		tmp.err(),
+		Some(e) if e.to_string() == "Found user:one for field `friend`, with record `person:three`, but expected a option<record<person>>"
+	));
+	//
+	let tmp = res.remove(0).result;
+	assert!(matches!(
+		tmp.err(),
+		Some(e) if e.to_string() == "Found 'yesterday' for field `born`, with record `person:four`, but expected a option<datetime>"
+	));
+	//
+	let tmp = res.remove(0).result;
+	assert!(matches!(
+		tmp.err(),
+		Some(e) if e.to_string() == "Found 10 for field `wait`, with record `person:five`, but expected a option<duration>"
+	));
+	// The mismatching element of an array is reported
+	let tmp = res.remove(0).result;
+	assert!(matches!(
+		tmp.err(),
+		Some(e) if e.to_string() == "Found 'two' for field `scores`, with record `person:six`, but expected a option<array<int>>"
+	));
+	//
+	let tmp = res.remove(0).result;
+	assert!(matches!(
+		tmp.err(),
+		Some(e) if e.to_string() == "Found [1] for field `meta`, with record `person:seven`, but expected a option<object>"
+	));
+	//
+	let tmp = res.remove(0).result;
+	assert!(matches!(
+		tmp.err(),
+		Some(e) if e.to_string() == "Found 'yes' for field `active`, with record `person:eight`, but expected a option<bool>"
+	));
+	//
+	Ok(())
+}
@@ -0,0 +1,268 @@
+mod parse;
+use parse::Parse;
+mod helpers;
+use helpers::new_ds;
+use helpers::skip_ok;
+use surrealdb::dbs::Session;
+use surrealdb::err::Error;
+use surrealdb::sql::Value;
+
+#[tokio::test]
+async fn select_union_all() -> Result<(), Error> {
+	let sql = "
+		CREATE person:1 SET name = 'Tobie';
+		CREATE person:2 SET name = 'Jaime';
+		CREATE animal:1 SET name = 'Tobie';
+		SELECT name FROM person UNION ALL SELECT name FROM animal;
+		SELECT name FROM person UNION ALL SELECT name FROM animal ORDER BY name LIMIT 2;
+		SELECT name FROM person UNION ALL SELECT name FROM animal ORDER BY name DESC START 1;
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 6);
+	//
+	skip_ok(res, 3)?;
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse(
+		"[
+			{
+				name: 'Tobie'
+			},
+			{
+				name: 'Jaime'
+			},
+			{
+				name: 'Tobie'
+			}
+		]",
+	);
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse(
+		"[
+			{
+				name: 'Jaime'
+			},
+			{
+				name: 'Tobie'
+			}
+		]",
+	);
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse(
+		"[
+			{
+				name: 'Tobie'
+			},
+			{
+				name: 'Jaime'
+			}
+		]",
+	);
+	assert_eq!(tmp, val);
+	//
+	Ok(())
+}
+
+#[tokio::test]
+async fn select_union_distinct() -> Result<(), Error> {
+	let sql = "
+		CREATE person:1 SET name = 'Tobie';
+		CREATE person:2 SET name = 'Jaime';
+		CREATE animal:1 SET name = 'Tobie';
+		SELECT name FROM person UNION SELECT name FROM animal;
+		SELECT name FROM person UNION DISTINCT SELECT name FROM animal UNION DISTINCT SELECT name FROM person;
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 5);
+	//
+	skip_ok(res, 3)?;
+	//
+	for _ in 0..2 {
+		let tmp = res.remove(0).result?;
+		let val = Value::parse(
+			"[
+				{
+					name: 'Tobie'
+				},
+				{
+					name: 'Jaime'
+				}
+			]",
+		);
+		assert_eq!(tmp, val);
+	}
+	//
+	Ok(())
+}
+
+#[tokio::test]
+async fn select_union_mixed_types() -> Result<(), Error> {
+	let sql = "
+		CREATE person:1 SET name = 'Tobie', age = 30;
+		CREATE animal:1 SET name = 1, legs = 4;
+		SELECT VALUE name FROM person UNION ALL SELECT VALUE name FROM animal;
+		SELECT name, age FROM person UNION ALL SELECT name, legs FROM animal;
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 4);
+	//
+	skip_ok(res, 2)?;
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("['Tobie', 1]");
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse(
+		"[
+			{
+				age: 30,
+				name: 'Tobie'
+			},
+			{
+				legs: 4,
+				name: 1
+			}
+		]",
+	);
+	assert_eq!(tmp, val);
+	//
+	Ok(())
+}
+
+#[tokio::test]
+async fn select_union_empty() -> Result<(), Error> {
+	let sql = "
+		CREATE person:1 SET name = 'Tobie';
+		SELECT name FROM person UNION ALL SELECT name FROM animal;
+		SELECT name FROM animal UNION ALL SELECT name FROM animal;
+		SELECT name FROM person WHERE false UNION SELECT name FROM person;
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 4);
+	//
+	skip_ok(res, 1)?;
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse(
+		"[
+			{
+				name: 'Tobie'
+			}
+		]",
+	);
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[]");
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse(
+		"[
+			{
+				name: 'Tobie'
+			}
+		]",
+	);
+	assert_eq!(tmp, val);
+	//
+	Ok(())
+}
+
+#[tokio::test]
+async fn select_union_matches_projected_keys() -> Result<(), Error> {
+	let sql = "
+		CREATE person:1 SET name = 'Tobie', age = 30;
+		CREATE person:2 SET name = 'Jaime', age = 25;
+		SELECT name, age FROM person UNION SELECT age, name FROM person;
+		SELECT name, age FROM person UNION ALL SELECT age, name FROM person WHERE age > 26;
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 4);
+	//
+	skip_ok(res, 2)?;
+	// Rows with the same projected keys and values are duplicates,
+	// whichever order the fields were projected in
+	let tmp = res.remove(0).result?;
+	let val = Value::parse(
+		"[
+			{
+				age: 30,
+				name: 'Tobie'
+			},
+			{
+				age: 25,
+				name: 'Jaime'
+			}
+		]",
+	);
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse(
+		"[
+			{
+				age: 30,
+				name: 'Tobie'
+			},
+			{
+				age: 25,
+				name: 'Jaime'
+			},
+			{
+				age: 30,
+				name: 'Tobie'
+			}
+		]",
+	);
+	assert_eq!(tmp, val);
+	//
+	Ok(())
+}
+
+#[tokio::test]
+async fn select_union_order_nulls() -> Result<(), Error> {
+	let sql = "
+		CREATE player:1 SET score = 10;
+		CREATE player:2 SET score = NULL;
+		CREATE bot:1 SET score = 20;
+		SELECT score FROM player UNION ALL SELECT score FROM bot ORDER BY score DESC;
+		SELECT score FROM player UNION ALL SELECT score FROM bot ORDER BY score DESC NULLS LAST;
+		SELECT score FROM player UNION ALL SELECT score FROM bot ORDER BY score NULLS LAST;
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 6);
+	//
+	skip_ok(res, 3)?;
+	// Without a NULLS clause the existing ordering is kept
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[{ score: 20 }, { score: 10 }, { score: NULL }]");
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[{ score: 20 }, { score: 10 }, { score: NULL }]");
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[{ score: 10 }, { score: 20 }, { score: NULL }]");
+	assert_eq!(tmp, val);
+	//
+	Ok(())
+}
@@ -2,6 +2,7 @@ mod parse;
 use parse::Parse;
 mod helpers;
 use helpers::new_ds;
+use helpers::skip_ok;
 use surrealdb::dbs::Session;
 use surrealdb::err::Error;
 use surrealdb::iam::Role;
@@ -281,6 +282,238 @@ async fn update_with_return_clause() -> Result<(), Error> {
 	Ok(())
 }
 
+#[tokio::test]
+async fn update_with_return_output() -> Result<(), Error> {
+	let sql = "
+		CREATE person:test SET age = 18, name = 'John';
+		UPDATE person:test SET age = 25 RETURN BEFORE;
+		UPDATE person:test SET age = 30 RETURN AFTER;
+		UPDATE person:test SET age = 35, tags = ['a'], name = NONE RETURN DIFF;
+		UPDATE person:test SET age = 40 RETURN NONE;
+		SELECT * FROM person:test;
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 6);
+	//
+	skip_ok(res, 1)?;
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[{ age: 18, id: person:test, name: 'John' }]");
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[{ age: 30, id: person:test, name: 'John' }]");
+	assert_eq!(tmp, val);
+	// The changes are returned as a JSON Patch
+	let tmp = res.remove(0).result?;
+	let val = Value::parse(
+		"[
+			[
+				{ op: 'remove', path: '/name' },
+				{ op: 'replace', path: '/age', value: 35 },
+				{ op: 'add', path: '/tags', value: ['a'] }
+			]
+		]",
+	);
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[]");
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[{ age: 40, id: person:test, tags: ['a'] }]");
+	assert_eq!(tmp, val);
+	//
+	Ok(())
+}
+
+#[tokio::test]
+async fn update_with_return_patch() -> Result<(), Error> {
+	let sql = "
+		CREATE person:test SET name = 'Mary', tags = [1, 2, 3];
+		UPDATE person:test SET name = 'Marie', tags = [1, 2, 4, 3] RETURN PATCH;
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 2);
+	//
+	skip_ok(res, 1)?;
+	// Text changes are replaced, as they are not part of RFC 6902
+	let tmp = res.remove(0).result?;
+	let val = Value::parse(
+		"[
+			[
+				{ op: 'replace', path: '/name', value: 'Marie' },
+				{ op: 'add', path: '/tags/2', value: 4 }
+			]
+		]",
+	);
+	assert_eq!(tmp, val);
+	//
+	Ok(())
+}
+
+#[tokio::test]
+async fn update_with_increment_and_decrement() -> Result<(), Error> {
+	let sql = "
+		CREATE post:1 SET views = 10, tags = ['surrealdb'];
+		UPDATE post:1 SET views += 5, tags += 'rust';
+		UPDATE post:1 SET views -= 2, tags += ['database', 'rust'];
+		UPDATE post:1 SET tags -= 'rust', likes += 1, related += post:2;
+		UPDATE post:1 SET views += 'many';
+		UPDATE post:1 SET name = 'post', name -= 1;
+		SELECT * FROM post:1;
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 7);
+	//
+	skip_ok(res, 1)?;
+	// Numbers are incremented while values are appended to arrays
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[{ id: post:1, tags: ['surrealdb', 'rust'], views: 15 }]");
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse(
+		"[{ id: post:1, tags: ['surrealdb', 'rust', 'database', 'rust'], views: 13 }]",
+	);
+	assert_eq!(tmp, val);
+	// A matching element is removed from arrays, and missing fields are created
+	let tmp = res.remove(0).result?;
+	let val = Value::parse(
+		"[{ id: post:1, likes: 1, related: [post:2], tags: ['surrealdb', 'database', 'rust'], views: 13 }]",
+	);
+	assert_eq!(tmp, val);
+	// Values which can not be added or subtracted are an error
+	let tmp = res.remove(0).result;
+	assert!(matches!(
+		tmp.err(),
+		Some(e) if e.to_string() == "Cannot perform addition with '13' and ''many''"
+	));
+	//
+	let tmp = res.remove(0).result;
+	assert!(matches!(
+		tmp.err(),
+		Some(e) if e.to_string() == "Cannot perform subtraction with ''post'' and '1'"
+	));
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse(
+		"[{ id: post:1, likes: 1, related: [post:2], tags: ['surrealdb', 'database', 'rust'], views: 13 }]",
+	);
+	assert_eq!(tmp, val);
+	//
+	Ok(())
+}
+
+#[tokio::test]
+async fn update_with_patch_operations() -> Result<(), Error> {
+	let sql = "
+		CREATE person:test SET name = { first: 'Tobie', last: 'Morgan Hitchcock' }, tags = ['a'];
+		UPDATE person:test PATCH [
+			{ op: 'test', path: '/name/first', value: 'Tobie' },
+			{ op: 'copy', path: '/nickname', from: '/name/first' },
+			{ op: 'move', path: '/surname', from: '/name/last' }
+		];
+		UPDATE person:test PATCH [
+			{ op: 'remove', path: '/tags' },
+			{ op: 'test', path: '/nickname', value: 'Jaime' }
+		];
+		UPDATE person:test PATCH [{ op: 'move', path: '/name/first/inner', from: '/name' }];
+		UPDATE person:test PATCH [{ op: 'copy', path: '/other', from: '/missing' }];
+		SELECT * FROM person:test;
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 6);
+	//
+	skip_ok(res, 1)?;
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse(
+		"[
+			{
+				id: person:test,
+				name: { first: 'Tobie' },
+				nickname: 'Tobie',
+				surname: 'Morgan Hitchcock',
+				tags: ['a']
+			}
+		]",
+	);
+	assert_eq!(tmp, val);
+	// A failing test operation fails the whole statement
+	let tmp = res.remove(0).result;
+	assert!(matches!(
+		tmp.err(),
+		Some(e) if e.to_string() == "Given test operation failed for JSON Patch. Expected `'Jaime'`, but got `'Tobie'` instead."
+	));
+	//
+	let tmp = res.remove(0).result;
+	assert!(matches!(tmp.err(), Some(Error::InvalidPatch { .. })));
+	//
+	let tmp = res.remove(0).result;
+	assert!(matches!(tmp.err(), Some(Error::InvalidPatch { .. })));
+	// None of the failed patches were applied
+	let tmp = res.remove(0).result?;
+	assert_eq!(tmp, val);
+	//
+	Ok(())
+}
+
+#[tokio::test]
+async fn update_and_delete_only() -> Result<(), Error> {
+	let sql = "
+		CREATE person:tobie SET name = 'Tobie';
+		CREATE person:jaime SET name = 'Jaime';
+		SELECT * FROM ONLY person:tobie;
+		SELECT * FROM ONLY person:unknown;
+		UPDATE ONLY person:tobie SET age = 30;
+		UPDATE ONLY person SET age = 30;
+		DELETE ONLY person RETURN BEFORE;
+		DELETE ONLY person:jaime RETURN BEFORE;
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 8);
+	//
+	skip_ok(res, 2)?;
+	// A single record is returned as an object
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("{ id: person:tobie, name: 'Tobie' }");
+	assert_eq!(tmp, val);
+	// A missing record is returned as NONE
+	let tmp = res.remove(0).result?;
+	assert_eq!(tmp, Value::None);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("{ age: 30, id: person:tobie, name: 'Tobie' }");
+	assert_eq!(tmp, val);
+	// Multiple records can not be returned
+	let tmp = res.remove(0).result;
+	assert!(matches!(
+		tmp.err(),
+		Some(e) if e.to_string() == "Expected a single result output when using the ONLY keyword"
+	));
+	//
+	let tmp = res.remove(0).result;
+	assert!(matches!(tmp.err(), Some(Error::SingleOnlyOutput)));
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("{ id: person:jaime, name: 'Jaime' }");
+	assert_eq!(tmp, val);
+	//
+	Ok(())
+}
+
 //
 // Permissions
 //
@@ -0,0 +1,277 @@
+mod parse;
+use parse::Parse;
+mod helpers;
+use helpers::new_ds;
+use helpers::skip_ok;
+use surrealdb::dbs::Session;
+use surrealdb::err::Error;
+use surrealdb::sql::Value;
+
+#[tokio::test]
+async fn upsert_set_and_content() -> Result<(), Error> {
+	let sql = "
+		UPSERT INTO person:test SET name = 'Tobie', visits = 1;
+		UPSERT INTO person:test SET visits += 1;
+		UPSERT INTO person:other CONTENT { name: 'Jaime' };
+		UPSERT INTO person:other CONTENT { name: 'Jaime', age: 30 };
+		SELECT * FROM person;
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 5);
+	// A missing record is created
+	let tmp = res.remove(0).result?;
+	let val = Value::parse(
+		"[
+			{
+				id: person:test,
+				name: 'Tobie',
+				visits: 1,
+			}
+		]",
+	);
+	assert_eq!(tmp, val);
+	// An existing record is updated
+	let tmp = res.remove(0).result?;
+	let val = Value::parse(
+		"[
+			{
+				id: person:test,
+				name: 'Tobie',
+				visits: 2,
+			}
+		]",
+	);
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse(
+		"[
+			{
+				id: person:other,
+				name: 'Jaime',
+			}
+		]",
+	);
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse(
+		"[
+			{
+				age: 30,
+				id: person:other,
+				name: 'Jaime',
+			}
+		]",
+	);
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse(
+		"[
+			{
+				age: 30,
+				id: person:other,
+				name: 'Jaime',
+			},
+			{
+				id: person:test,
+				name: 'Tobie',
+				visits: 2,
+			}
+		]",
+	);
+	assert_eq!(tmp, val);
+	//
+	Ok(())
+}
+
+#[tokio::test]
+async fn upsert_on_duplicate_key_update() -> Result<(), Error> {
+	let sql = "
+		UPSERT INTO counter:home SET hits = 1 ON DUPLICATE KEY UPDATE hits += 1;
+		UPSERT INTO counter:home SET hits = 1 ON DUPLICATE KEY UPDATE hits += 1;
+		UPSERT INTO counter:home SET hits = 1 ON DUPLICATE KEY UPDATE hits += 1, name = 'home';
+		UPSERT INTO counter:about ON DUPLICATE KEY UPDATE hits += 1;
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 4);
+	// The SET clause creates a missing record
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[{ hits: 1, id: counter:home }]");
+	assert_eq!(tmp, val);
+	// While an existing record is changed by the ON DUPLICATE KEY UPDATE clause
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[{ hits: 2, id: counter:home }]");
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[{ hits: 3, id: counter:home, name: 'home' }]");
+	assert_eq!(tmp, val);
+	// A missing record without a data clause is created empty
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[{ id: counter:about }]");
+	assert_eq!(tmp, val);
+	//
+	Ok(())
+}
+
+#[tokio::test]
+async fn upsert_return_output() -> Result<(), Error> {
+	let sql = "
+		UPSERT ONLY INTO person:test SET name = 'Tobie' RETURN BEFORE;
+		UPSERT ONLY INTO person:test SET name = 'Jaime' RETURN BEFORE;
+		UPSERT ONLY INTO person:test SET name = 'Tobie' RETURN AFTER;
+		UPSERT ONLY INTO person:test SET age = 30 RETURN DIFF;
+		UPSERT ONLY INTO person:new SET name = 'Tobie' RETURN DIFF;
+		UPSERT INTO person:test SET name = 'Tobie' RETURN NONE;
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 6);
+	// A created record had no previous value
+	let tmp = res.remove(0).result?;
+	assert_eq!(tmp, Value::None);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("{ id: person:test, name: 'Tobie' }");
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("{ id: person:test, name: 'Tobie' }");
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[{ op: 'add', path: '/age', value: 30 }]");
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse(
+		"[
+			{ op: 'replace', path: '/', value: { id: person:new, name: 'Tobie' } }
+		]",
+	);
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	assert_eq!(tmp, Value::parse("[]"));
+	//
+	Ok(())
+}
+
+#[tokio::test]
+async fn upsert_invalid_target() -> Result<(), Error> {
+	let sql = "
+		CREATE person:test;
+		UPSERT INTO person SET name = 'Tobie';
+		UPSERT INTO person:1..5 SET name = 'Tobie';
+		UPSERT INTO person:test CONTENT { id: person:other };
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 4);
+	//
+	skip_ok(res, 1)?;
+	// Only specific records can be upserted
+	let tmp = res.remove(0).result;
+	assert!(matches!(
+		tmp.err(),
+		Some(e) if e.to_string() == "Can not execute UPSERT statement using value 'person'"
+	));
+	//
+	let tmp = res.remove(0).result;
+	assert!(matches!(
+		tmp.err(),
+		Some(e) if e.to_string() == "Can not execute UPSERT statement using value 'person:1..5'"
+	));
+	//
+	let tmp = res.remove(0).result;
+	assert!(matches!(
+		tmp.err(),
+		Some(e) if e.to_string() == "Found person:other for the id field, but a specific record has been specified"
+	));
+	//
+	Ok(())
+}
+
+#[tokio::test]
+async fn upsert_with_schema_and_events() -> Result<(), Error> {
+	let sql = "
+		DEFINE TABLE person SCHEMAFULL;
+		DEFINE FIELD name ON person TYPE string;
+		DEFINE FIELD visits ON person TYPE int DEFAULT 0;
+		DEFINE EVENT log ON person WHEN $event = 'CREATE' OR $event = 'UPDATE' THEN (
+			CREATE log SET event = $event, person = $after.id
+		);
+		UPSERT INTO person:test SET name = 'Tobie' ON DUPLICATE KEY UPDATE visits += 1;
+		UPSERT INTO person:test SET name = 'Tobie' ON DUPLICATE KEY UPDATE visits += 1;
+		UPSERT INTO person:test SET name = 10;
+		SELECT VALUE event FROM log ORDER BY event;
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 8);
+	//
+	skip_ok(res, 4)?;
+	// Field defaults and types are applied to created and updated records
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[{ id: person:test, name: 'Tobie', visits: 0 }]");
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[{ id: person:test, name: 'Tobie', visits: 1 }]");
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result;
+	assert!(tmp.is_err());
+	// The events see whether the record was created or updated
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("['CREATE', 'UPDATE']");
+	assert_eq!(tmp, val);
+	//
+	Ok(())
+}
+
+#[tokio::test]
+async fn upsert_without_into() -> Result<(), Error> {
+	let sql = "
+		UPSERT person:test CONTENT { name: 'Tobie', age: 30 } RETURN AFTER;
+		UPSERT person:test SET age = 31 RETURN age;
+		UPSERT person:test MERGE { tags: ['rust'] } RETURN BEFORE;
+		UPSERT person:test, person:other SET visits += 1 RETURN id, visits;
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 4);
+	// The INTO keyword is optional
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[{ age: 30, id: person:test, name: 'Tobie' }]");
+	assert_eq!(tmp, val);
+	// Data is merged into an existing record
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[{ age: 31 }]");
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[{ age: 31, id: person:test, name: 'Tobie' }]");
+	assert_eq!(tmp, val);
+	// Each record is either created or updated
+	let tmp = res.remove(0).result?;
+	let val = Value::parse(
+		"[
+			{ id: person:test, visits: 1 },
+			{ id: person:other, visits: 1 }
+		]",
+	);
+	assert_eq!(tmp, val);
+	//
+	Ok(())
+}
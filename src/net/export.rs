@@ -12,6 +12,7 @@ use surrealdb::dbs::Session;
 use surrealdb::iam::check::check_ns_db;
 use surrealdb::iam::Action::View;
 use surrealdb::iam::ResourceKind::Any;
+use surrealdb::kvs::Compression;
 
 pub(super) fn router<S, B>() -> Router<S, B>
 where
@@ -33,7 +34,7 @@ async fn handler(Extension(session): Extension<Session>) -> Result<impl IntoResp
 	// Create a new bounded channel
 	let (snd, rcv) = surrealdb::channel::bounded(1);
 	// Start the export task
-	let task = db.export(&session, snd).await?;
+	let task = db.export(&session, Compression::None, snd).await?;
 	// Spawn a new database export job
 	tokio::spawn(task);
 	// Process all chunk values
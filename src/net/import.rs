@@ -1,7 +1,6 @@
 use super::headers::Accept;
 use crate::dbs::DB;
 use crate::err::Error;
-use crate::net::input::bytes_to_utf8;
 use crate::net::output;
 use axum::extract::DefaultBodyLimit;
 use axum::response::IntoResponse;
@@ -38,12 +37,11 @@ async fn handler(
 ) -> Result<impl IntoResponse, impl IntoResponse> {
 	// Get the datastore reference
 	let db = DB.get().unwrap();
-	// Convert the body to a byte slice
-	let sql = bytes_to_utf8(&sql)?;
 	// Check the permissions level
 	db.check(&session, Edit, Any.on_level(session.au.level().to_owned()))?;
-	// Execute the sql query in the database
-	match db.import(sql, &session).await {
+	// Execute the sql query in the database, which
+	// is decompressed if it was exported compressed
+	match db.import(&sql, &session).await {
 		Ok(res) => match accept.as_deref() {
 			// Simple serialization
 			Some(Accept::ApplicationJson) => Ok(output::json(&output::simplify(res))),
@@ -192,6 +192,8 @@ impl Connection {
 		let mut tasks = JoinSet::new();
 		// Clone the WebSocket cancellation token
 		let canceller = rpc.read().await.canceller.clone();
+		// Clone the session, so running queries can be cancelled
+		let session = rpc.read().await.session.clone();
 		// Loop, and listen for messages to write
 		loop {
 			tokio::select! {
@@ -248,6 +250,8 @@ impl Connection {
 				}
 			}
 		}
+		// Cancel any queries which are still running
+		session.cancel();
 		// Wait for all tasks to finish
 		while let Some(res) = tasks.join_next().await {
 			if let Err(err) = res {